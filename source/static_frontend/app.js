@@ -0,0 +1,217 @@
+"use strict";
+
+// Every page embeds its resource description as JSON, see `render_static_pages`.
+const config = JSON.parse(document.getElementById("resource-config").textContent);
+const params = new URLSearchParams(window.location.search);
+const content = document.getElementById("content");
+
+function routeWithId(route, id) {
+    return route.replace(/\{[^}]+\}/g, encodeURIComponent(id));
+}
+
+async function apiRequest(method, route, body) {
+    const options = { method, headers: {} };
+    if (body !== undefined) {
+        options.headers["Content-Type"] = "application/json";
+        options.body = JSON.stringify(body);
+    }
+    const response = await fetch(route, options);
+    const text = await response.text();
+    if (!response.ok) {
+        throw new Error(`${method} ${route} failed with ${response.status}: ${text}`);
+    }
+    try {
+        return text ? JSON.parse(text) : null;
+    } catch (_) {
+        return text;
+    }
+}
+
+function showError(error) {
+    const message = document.createElement("p");
+    message.className = "error";
+    message.textContent = error.message || String(error);
+    content.prepend(message);
+}
+
+function link(text, href) {
+    const anchor = document.createElement("a");
+    anchor.textContent = text;
+    anchor.href = href;
+    return anchor;
+}
+
+function displayValue(value) {
+    return typeof value === "object" && value !== null ? JSON.stringify(value) : String(value ?? "");
+}
+
+function asList(data) {
+    if (Array.isArray(data)) {
+        return data;
+    }
+    if (data && typeof data === "object") {
+        return Object.values(data);
+    }
+    return [];
+}
+
+async function loadItem(id) {
+    const routes = config.routes;
+    if (routes.detail) {
+        return apiRequest("GET", routeWithId(routes.detail, id));
+    }
+    const items = asList(await apiRequest("GET", routes.list));
+    return items.find((item) => String(item[config.id_field]) === String(id));
+}
+
+async function deleteItem(id) {
+    if (!window.confirm(`Delete ${config.resource} ${id}?`)) {
+        return false;
+    }
+    await apiRequest("DELETE", routeWithId(config.routes.delete, id));
+    return true;
+}
+
+async function renderList() {
+    if (config.routes.create) {
+        content.append(link(`New ${config.resource}`, `${config.resource}_form.html`));
+    }
+    if (!config.routes.list) {
+        const message = document.createElement("p");
+        message.textContent = "This resource has no list route.";
+        content.append(message);
+        return;
+    }
+
+    const items = asList(await apiRequest("GET", config.routes.list));
+    const table = document.createElement("table");
+    const header = table.createTHead().insertRow();
+    for (const field of config.fields) {
+        header.insertCell().textContent = field.name;
+    }
+    header.insertCell().textContent = "Actions";
+
+    const body = table.createTBody();
+    for (const item of items) {
+        const row = body.insertRow();
+        for (const field of config.fields) {
+            row.insertCell().textContent = displayValue(item[field.name]);
+        }
+        const actions = row.insertCell();
+        const id = item[config.id_field];
+        if (id === undefined) {
+            continue;
+        }
+        actions.append(link("View", `${config.resource}_detail.html?id=${encodeURIComponent(id)}`));
+        if (config.routes.update) {
+            actions.append(" ", link("Edit", `${config.resource}_form.html?id=${encodeURIComponent(id)}`));
+        }
+        if (config.routes.delete) {
+            const button = document.createElement("button");
+            button.textContent = "Delete";
+            button.addEventListener("click", () =>
+                deleteItem(id).then((deleted) => deleted && row.remove()).catch(showError));
+            actions.append(" ", button);
+        }
+    }
+    content.append(table);
+}
+
+async function renderDetail() {
+    const id = params.get("id");
+    const item = await loadItem(id);
+    if (!item) {
+        throw new Error(`${config.resource} ${id} was not found`);
+    }
+
+    const list = document.createElement("dl");
+    for (const field of config.fields) {
+        const term = document.createElement("dt");
+        term.textContent = field.name;
+        const description = document.createElement("dd");
+        description.textContent = displayValue(item[field.name]);
+        list.append(term, description);
+    }
+    content.append(list);
+
+    if (config.routes.update) {
+        content.append(link("Edit", `${config.resource}_form.html?id=${encodeURIComponent(id)}`));
+    }
+    if (config.routes.delete) {
+        const button = document.createElement("button");
+        button.textContent = "Delete";
+        button.addEventListener("click", () =>
+            deleteItem(id)
+                .then((deleted) => deleted && (window.location.href = `${config.resource}.html`))
+                .catch(showError));
+        content.append(" ", button);
+    }
+}
+
+function inputFor(field, value) {
+    const input = document.createElement(field.type === "json" ? "textarea" : "input");
+    input.name = field.name;
+    if (field.type === "bool") {
+        input.type = "checkbox";
+        input.checked = Boolean(value);
+    } else if (field.type === "number") {
+        input.type = "number";
+        input.step = "any";
+        input.value = value ?? "";
+    } else if (field.type === "json") {
+        input.value = value === undefined ? "" : JSON.stringify(value, null, 2);
+    } else {
+        input.value = value ?? "";
+    }
+    return input;
+}
+
+function readInput(field, input) {
+    if (field.type === "bool") {
+        return input.checked;
+    }
+    if (field.type === "number") {
+        return input.value === "" ? null : Number(input.value);
+    }
+    if (field.type === "json") {
+        return input.value === "" ? null : JSON.parse(input.value);
+    }
+    return input.value;
+}
+
+async function renderForm() {
+    const id = params.get("id");
+    const editing = id !== null;
+    const item = editing ? await loadItem(id) : {};
+
+    const form = document.createElement("form");
+    const inputs = new Map();
+    for (const field of config.fields) {
+        const label = document.createElement("label");
+        label.textContent = field.name;
+        const input = inputFor(field, item ? item[field.name] : undefined);
+        inputs.set(field.name, input);
+        label.append(input);
+        form.append(label);
+    }
+    const submit = document.createElement("button");
+    submit.type = "submit";
+    submit.textContent = editing ? "Save" : "Create";
+    form.append(submit);
+
+    form.addEventListener("submit", (event) => {
+        event.preventDefault();
+        const payload = {};
+        for (const field of config.fields) {
+            payload[field.name] = readInput(field, inputs.get(field.name));
+        }
+        const request = editing
+            ? apiRequest("PUT", routeWithId(config.routes.update, id), payload)
+            : apiRequest("POST", config.routes.create, payload);
+        request.then(() => (window.location.href = `${config.resource}.html`)).catch(showError);
+    });
+    content.append(form);
+}
+
+const renderers = { list: renderList, detail: renderDetail, form: renderForm };
+renderers[document.body.dataset.page]().catch(showError);
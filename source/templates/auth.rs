@@ -0,0 +1,220 @@
+use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer, Responder};
+use serde::{Deserialize, Serialize};
+use actix_web::http::header;
+use actix_cors::Cors;
+use actix_files::{Files, NamedFile};
+use argon2::password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use rand::distributions::{Alphanumeric, DistString};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use ::std::sync::Mutex;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct User {
+    id: u64,
+    user_name: String,
+    password_hash: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct Credentials {
+    user_name: String,
+    password: String,
+}
+
+#[derive(Serialize, Debug)]
+struct LoginResponse {
+    token: String,
+}
+
+#[derive(Serialize, Debug)]
+struct UserProfile {
+    id: u64,
+    user_name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct Database {
+    users: HashMap<u64, User>,
+}
+
+impl Database {
+    fn new() -> Self {
+        Self {
+            users: HashMap::new(),
+        }
+    }
+
+    fn next_user_id(&self) -> u64 {
+        self.users.keys().max().map_or(1, |id| id + 1)
+    }
+
+    fn get_user_by_name(&self, user_name: &str) -> Option<&User> {
+        self.users.values().find(|u| u.user_name == user_name)
+    }
+
+    fn save_to_file(&self) -> std::io::Result<()> {
+        let data = serde_json::to_string(&self)?;
+        let mut file = fs::File::create("database.json")?;
+        file.write_all(data.as_bytes())?;
+        Ok(())
+    }
+
+    fn load_from_file() -> std::io::Result<Self> {
+        let file = fs::read_to_string("database.json")?;
+        let db: Database = serde_json::from_str(&file)?;
+        Ok(db)
+    }
+}
+
+struct AppState {
+    db: Mutex<Database>,
+    // Session tokens of the logged in users, kept in memory so a restart logs everyone out
+    sessions: Mutex<HashMap<String, u64>>,
+}
+
+fn hash_password(password: &str) -> Option<String> {
+    let salt: SaltString = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .ok()
+        .map(|hash| hash.to_string())
+}
+
+fn verify_password(password: &str, password_hash: &str) -> bool {
+    PasswordHash::new(password_hash)
+        .map(|hash| Argon2::default().verify_password(password.as_bytes(), &hash).is_ok())
+        .unwrap_or(false)
+}
+
+fn bearer_token(req: &HttpRequest) -> Option<String> {
+    req.headers()
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(|token| token.trim().to_string())
+}
+
+// Returns the id of the logged in user, protected routes answer 401 when it is missing
+fn current_user_id(app_state: &AppState, req: &HttpRequest) -> Option<u64> {
+    let token: String = bearer_token(req)?;
+    app_state.sessions.lock().unwrap().get(&token).copied()
+}
+
+async fn register(app_state: web::Data<AppState>, credentials: web::Json<Credentials>) -> impl Responder {
+    let credentials: Credentials = credentials.into_inner();
+    if credentials.user_name.trim().is_empty() || credentials.password.len() < 8 {
+        return HttpResponse::BadRequest().body("User name is required and passwords need at least 8 characters");
+    }
+    let mut db = app_state.db.lock().unwrap();
+    if db.get_user_by_name(&credentials.user_name).is_some() {
+        return HttpResponse::Conflict().body("User name is taken");
+    }
+    let Some(password_hash) = hash_password(&credentials.password) else {
+        return HttpResponse::InternalServerError().finish();
+    };
+    let user: User = User {
+        id: db.next_user_id(),
+        user_name: credentials.user_name,
+        password_hash,
+    };
+    let profile: UserProfile = UserProfile {
+        id: user.id,
+        user_name: user.user_name.clone(),
+    };
+    db.users.insert(user.id, user);
+    let _ = db.save_to_file();
+    HttpResponse::Created().json(profile)
+}
+
+async fn login(app_state: web::Data<AppState>, credentials: web::Json<Credentials>) -> impl Responder {
+    let user_id: Option<u64> = {
+        let db = app_state.db.lock().unwrap();
+        db.get_user_by_name(&credentials.user_name)
+            .filter(|user| verify_password(&credentials.password, &user.password_hash))
+            .map(|user| user.id)
+    };
+    match user_id {
+        Some(user_id) => {
+            let token: String = Alphanumeric.sample_string(&mut rand::thread_rng(), 48);
+            app_state.sessions.lock().unwrap().insert(token.clone(), user_id);
+            HttpResponse::Ok().json(LoginResponse { token })
+        }
+        None => HttpResponse::Unauthorized().body("Invalid user or password"),
+    }
+}
+
+async fn logout(app_state: web::Data<AppState>, req: HttpRequest) -> impl Responder {
+    match bearer_token(&req) {
+        Some(token) if app_state.sessions.lock().unwrap().remove(&token).is_some() => {
+            HttpResponse::NoContent().finish()
+        }
+        _ => HttpResponse::Unauthorized().finish(),
+    }
+}
+
+async fn read_profile(app_state: web::Data<AppState>, req: HttpRequest) -> impl Responder {
+    let Some(user_id) = current_user_id(&app_state, &req) else {
+        return HttpResponse::Unauthorized().finish();
+    };
+    let db = app_state.db.lock().unwrap();
+    match db.users.get(&user_id) {
+        Some(user) => HttpResponse::Ok().json(UserProfile {
+            id: user.id,
+            user_name: user.user_name.clone(),
+        }),
+        None => HttpResponse::Unauthorized().finish(),
+    }
+}
+
+async fn openapi_document() -> actix_web::Result<NamedFile> {
+    let openapi_path: String = std::env::var("WEB_SERVER_OPENAPI_PATH").unwrap_or_else(|_| "openapi.json".to_string());
+    Ok(NamedFile::open_async(openapi_path).await?)
+}
+
+fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("/register", web::post().to(register))
+        .route("/login", web::post().to(login))
+        .route("/logout", web::post().to(logout))
+        .route("/me", web::get().to(read_profile));
+}
+
+#[actix_web::main]
+async fn main() -> std::io::Result<()> {
+    let port: u16 = std::env::var("WEB_SERVER_PORT")
+        .ok()
+        .and_then(|port| port.parse().ok())
+        .unwrap_or(8080);
+    let host: String = std::env::var("WEB_SERVER_HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
+    let static_dir: String = std::env::var("WEB_SERVER_STATIC_DIR").unwrap_or_else(|_| "static".to_string());
+    let db: Database = Database::load_from_file().unwrap_or_else(|_| Database::new());
+    let data: web::Data<AppState> = web::Data::new(AppState {
+        db: Mutex::new(db),
+        sessions: Mutex::new(HashMap::new()),
+    });
+
+    HttpServer::new(move || {
+        App::new()
+            .wrap(
+                Cors::permissive()
+                    .allowed_origin_fn(|origin, _req_head| {
+                        origin.as_bytes().starts_with(b"http://localhost") || origin == "null"
+                    })
+                    .allowed_methods(vec!["GET", "POST", "PUT", "DELETE"])
+                    .allowed_headers(vec![header::AUTHORIZATION, header::ACCEPT])
+                    .allowed_header(header::CONTENT_TYPE)
+                    .supports_credentials()
+                    .max_age(3600)
+            )
+            .app_data(data.clone())
+            .configure(configure_routes)
+            .route("/openapi.json", web::get().to(openapi_document))
+            .service(Files::new("/static", static_dir.clone()).index_file("index.html"))
+    })
+        .bind((host.as_str(), port))?
+        .run()
+        .await
+}
@@ -0,0 +1,223 @@
+use axum::extract::State;
+use axum::http::{header, HeaderMap, HeaderValue, Method, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use tower_http::services::ServeDir;
+use argon2::password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use rand::distributions::{Alphanumeric, DistString};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::sync::Arc;
+use std::time::Duration;
+use ::std::sync::Mutex;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct User {
+    id: u64,
+    user_name: String,
+    password_hash: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct Credentials {
+    user_name: String,
+    password: String,
+}
+
+#[derive(Serialize, Debug)]
+struct LoginResponse {
+    token: String,
+}
+
+#[derive(Serialize, Debug)]
+struct UserProfile {
+    id: u64,
+    user_name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct Database {
+    users: HashMap<u64, User>,
+}
+
+impl Database {
+    fn new() -> Self {
+        Self {
+            users: HashMap::new(),
+        }
+    }
+
+    fn next_user_id(&self) -> u64 {
+        self.users.keys().max().map_or(1, |id| id + 1)
+    }
+
+    fn get_user_by_name(&self, user_name: &str) -> Option<&User> {
+        self.users.values().find(|u| u.user_name == user_name)
+    }
+
+    fn save_to_file(&self) -> std::io::Result<()> {
+        let data = serde_json::to_string(&self)?;
+        let mut file = fs::File::create("database.json")?;
+        file.write_all(data.as_bytes())?;
+        Ok(())
+    }
+
+    fn load_from_file() -> std::io::Result<Self> {
+        let file = fs::read_to_string("database.json")?;
+        let db: Database = serde_json::from_str(&file)?;
+        Ok(db)
+    }
+}
+
+struct AppState {
+    db: Mutex<Database>,
+    // Session tokens of the logged in users, kept in memory so a restart logs everyone out
+    sessions: Mutex<HashMap<String, u64>>,
+}
+
+fn hash_password(password: &str) -> Option<String> {
+    let salt: SaltString = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .ok()
+        .map(|hash| hash.to_string())
+}
+
+fn verify_password(password: &str, password_hash: &str) -> bool {
+    PasswordHash::new(password_hash)
+        .map(|hash| Argon2::default().verify_password(password.as_bytes(), &hash).is_ok())
+        .unwrap_or(false)
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(|token| token.trim().to_string())
+}
+
+// Returns the id of the logged in user, protected routes answer 401 when it is missing
+fn current_user_id(app_state: &AppState, headers: &HeaderMap) -> Option<u64> {
+    let token: String = bearer_token(headers)?;
+    app_state.sessions.lock().unwrap().get(&token).copied()
+}
+
+async fn register(State(app_state): State<Arc<AppState>>, Json(credentials): Json<Credentials>) -> Response {
+    if credentials.user_name.trim().is_empty() || credentials.password.len() < 8 {
+        return (StatusCode::BAD_REQUEST, "User name is required and passwords need at least 8 characters").into_response();
+    }
+    let mut db = app_state.db.lock().unwrap();
+    if db.get_user_by_name(&credentials.user_name).is_some() {
+        return (StatusCode::CONFLICT, "User name is taken").into_response();
+    }
+    let Some(password_hash) = hash_password(&credentials.password) else {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+    let user: User = User {
+        id: db.next_user_id(),
+        user_name: credentials.user_name,
+        password_hash,
+    };
+    let profile: UserProfile = UserProfile {
+        id: user.id,
+        user_name: user.user_name.clone(),
+    };
+    db.users.insert(user.id, user);
+    let _ = db.save_to_file();
+    (StatusCode::CREATED, Json(profile)).into_response()
+}
+
+async fn login(State(app_state): State<Arc<AppState>>, Json(credentials): Json<Credentials>) -> Response {
+    let user_id: Option<u64> = {
+        let db = app_state.db.lock().unwrap();
+        db.get_user_by_name(&credentials.user_name)
+            .filter(|user| verify_password(&credentials.password, &user.password_hash))
+            .map(|user| user.id)
+    };
+    match user_id {
+        Some(user_id) => {
+            let token: String = Alphanumeric.sample_string(&mut rand::thread_rng(), 48);
+            app_state.sessions.lock().unwrap().insert(token.clone(), user_id);
+            Json(LoginResponse { token }).into_response()
+        }
+        None => (StatusCode::UNAUTHORIZED, "Invalid user or password").into_response(),
+    }
+}
+
+async fn logout(State(app_state): State<Arc<AppState>>, headers: HeaderMap) -> StatusCode {
+    match bearer_token(&headers) {
+        Some(token) if app_state.sessions.lock().unwrap().remove(&token).is_some() => StatusCode::NO_CONTENT,
+        _ => StatusCode::UNAUTHORIZED,
+    }
+}
+
+async fn read_profile(State(app_state): State<Arc<AppState>>, headers: HeaderMap) -> Response {
+    let Some(user_id) = current_user_id(&app_state, &headers) else {
+        return StatusCode::UNAUTHORIZED.into_response();
+    };
+    let db = app_state.db.lock().unwrap();
+    match db.users.get(&user_id) {
+        Some(user) => Json(UserProfile {
+            id: user.id,
+            user_name: user.user_name.clone(),
+        })
+        .into_response(),
+        None => StatusCode::UNAUTHORIZED.into_response(),
+    }
+}
+
+async fn openapi_document() -> Response {
+    let openapi_path: String = std::env::var("WEB_SERVER_OPENAPI_PATH").unwrap_or_else(|_| "openapi.json".to_string());
+    match tokio::fs::read(openapi_path).await {
+        Ok(document) => ([(header::CONTENT_TYPE, "application/json")], document).into_response(),
+        Err(_) => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+fn configure_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/register", post(register))
+        .route("/login", post(login))
+        .route("/logout", post(logout))
+        .route("/me", get(read_profile))
+}
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let port: u16 = std::env::var("WEB_SERVER_PORT")
+        .ok()
+        .and_then(|port| port.parse().ok())
+        .unwrap_or(8080);
+    let host: String = std::env::var("WEB_SERVER_HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
+    let static_dir: String = std::env::var("WEB_SERVER_STATIC_DIR").unwrap_or_else(|_| "static".to_string());
+    let db: Database = Database::load_from_file().unwrap_or_else(|_| Database::new());
+    let state: Arc<AppState> = Arc::new(AppState {
+        db: Mutex::new(db),
+        sessions: Mutex::new(HashMap::new()),
+    });
+
+    let cors: CorsLayer = CorsLayer::new()
+        .allow_origin(AllowOrigin::predicate(|origin: &HeaderValue, _request_parts| {
+            origin.as_bytes().starts_with(b"http://localhost") || origin == "null"
+        }))
+        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
+        .allow_headers([header::AUTHORIZATION, header::ACCEPT, header::CONTENT_TYPE])
+        .allow_credentials(true)
+        .max_age(Duration::from_secs(3600));
+
+    let app: Router = configure_routes()
+        .route("/openapi.json", get(openapi_document))
+        .nest_service("/static", ServeDir::new(static_dir))
+        .layer(cors)
+        .with_state(state);
+
+    let listener: tokio::net::TcpListener = tokio::net::TcpListener::bind((host.as_str(), port)).await?;
+    axum::serve(listener, app).await
+}
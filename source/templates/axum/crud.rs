@@ -0,0 +1,146 @@
+use axum::extract::{Path, State};
+use axum::http::{header, HeaderValue, Method, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use tower_http::services::ServeDir;
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::sync::Arc;
+use std::time::Duration;
+use ::std::sync::Mutex;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct Task {
+    id: u64,
+    name: String,
+    completed: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct Database {
+    tasks: HashMap<u64, Task>,
+}
+
+impl Database {
+    fn new() -> Self {
+        Self {
+            tasks: HashMap::new(),
+        }
+    }
+    fn insert(&mut self, task: Task) {
+        self.tasks.insert(task.id, task);
+    }
+    fn get(&self, id: &u64) -> Option<&Task> {
+        self.tasks.get(id)
+    }
+    fn get_all(&self) -> Vec<&Task> {
+        self.tasks.values().collect()
+    }
+    fn remove(&mut self, id: &u64) -> Option<Task> {
+        self.tasks.remove(id)
+    }
+    fn update(&mut self, task: Task) {
+        self.tasks.insert(task.id, task);
+    }
+    fn save_to_file(&self) -> std::io::Result<()> {
+        let data = serde_json::to_string(&self)?;
+        let mut file = fs::File::create("database.json")?;
+        file.write_all(data.as_bytes())?;
+        Ok(())
+    }
+
+    fn load_from_file() -> std::io::Result<Self> {
+        let file = fs::read_to_string("database.json")?;
+        let db: Database = serde_json::from_str(&file)?;
+        Ok(db)
+    }
+}
+
+struct AppState {
+    db: Mutex<Database>,
+}
+
+async fn create_task(State(app_state): State<Arc<AppState>>, Json(task): Json<Task>) -> impl IntoResponse {
+    let mut db = app_state.db.lock().unwrap();
+    db.insert(task);
+    let _ = db.save_to_file();
+    StatusCode::CREATED
+}
+
+async fn read_task(State(app_state): State<Arc<AppState>>, Path(id): Path<u64>) -> impl IntoResponse {
+    let db = app_state.db.lock().unwrap();
+    match db.get(&id) {
+        Some(task) => Json(task.clone()).into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+async fn update_task(State(app_state): State<Arc<AppState>>, Json(task): Json<Task>) -> impl IntoResponse {
+    let mut db = app_state.db.lock().unwrap();
+    db.update(task);
+    let _ = db.save_to_file();
+    StatusCode::OK
+}
+
+async fn read_all_tasks(State(app_state): State<Arc<AppState>>) -> impl IntoResponse {
+    let db = app_state.db.lock().unwrap();
+    let tasks: Vec<Task> = db.get_all().into_iter().cloned().collect();
+    Json(tasks)
+}
+
+async fn delete_task(State(app_state): State<Arc<AppState>>, Path(id): Path<u64>) -> impl IntoResponse {
+    let mut db = app_state.db.lock().unwrap();
+    db.remove(&id);
+    let _ = db.save_to_file();
+    StatusCode::OK
+}
+
+async fn openapi_document() -> Response {
+    let openapi_path: String = std::env::var("WEB_SERVER_OPENAPI_PATH").unwrap_or_else(|_| "openapi.json".to_string());
+    match tokio::fs::read(openapi_path).await {
+        Ok(document) => ([(header::CONTENT_TYPE, "application/json")], document).into_response(),
+        Err(_) => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+fn configure_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/task", get(read_all_tasks).post(create_task).put(update_task))
+        .route("/task/{id}", get(read_task).delete(delete_task))
+}
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let port: u16 = std::env::var("WEB_SERVER_PORT")
+        .ok()
+        .and_then(|port| port.parse().ok())
+        .unwrap_or(8080);
+    let host: String = std::env::var("WEB_SERVER_HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
+    let static_dir: String = std::env::var("WEB_SERVER_STATIC_DIR").unwrap_or_else(|_| "static".to_string());
+    let db: Database = Database::load_from_file().unwrap_or_else(|_| Database::new());
+    let state: Arc<AppState> = Arc::new(AppState {
+        db: Mutex::new(db)
+    });
+
+    let cors: CorsLayer = CorsLayer::new()
+        .allow_origin(AllowOrigin::predicate(|origin: &HeaderValue, _request_parts| {
+            origin.as_bytes().starts_with(b"http://localhost") || origin == "null"
+        }))
+        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
+        .allow_headers([header::AUTHORIZATION, header::ACCEPT, header::CONTENT_TYPE])
+        .allow_credentials(true)
+        .max_age(Duration::from_secs(3600));
+
+    let app: Router = configure_routes()
+        .route("/openapi.json", get(openapi_document))
+        .nest_service("/static", ServeDir::new(static_dir))
+        .layer(cors)
+        .with_state(state);
+
+    let listener: tokio::net::TcpListener = tokio::net::TcpListener::bind((host.as_str(), port)).await?;
+    axum::serve(listener, app).await
+}
@@ -0,0 +1,126 @@
+use axum::extract::{Path, State};
+use axum::http::{header, HeaderValue, Method, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use tower_http::services::ServeDir;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use ::std::sync::Mutex;
+
+// How long a fetched answer is served from the cache before the provider is asked again
+const CACHE_TTL: Duration = Duration::from_secs(60);
+
+// Only the fields the server needs are read from the provider's response
+#[derive(Deserialize, Debug, Clone)]
+struct ProviderRates {
+    base_code: String,
+    rates: HashMap<String, f64>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+struct ExchangeRate {
+    base: String,
+    target: String,
+    rate: f64,
+}
+
+struct AppState {
+    client: reqwest::Client,
+    cache: Mutex<HashMap<String, (Instant, ProviderRates)>>,
+}
+
+async fn fetch_rates(app_state: &AppState, base: &str) -> Result<ProviderRates, reqwest::Error> {
+    if let Some((fetched_at, rates)) = app_state.cache.lock().unwrap().get(base) {
+        if fetched_at.elapsed() < CACHE_TTL {
+            return Ok(rates.clone());
+        }
+    }
+    let url: String = format!("https://open.er-api.com/v6/latest/{}", base);
+    let rates: ProviderRates = app_state
+        .client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    app_state
+        .cache
+        .lock()
+        .unwrap()
+        .insert(base.to_string(), (Instant::now(), rates.clone()));
+    Ok(rates)
+}
+
+async fn read_rate(State(app_state): State<Arc<AppState>>, Path((base, target)): Path<(String, String)>) -> Response {
+    let base: String = base.to_uppercase();
+    let target: String = target.to_uppercase();
+    if base.len() != 3 || !base.chars().all(|c| c.is_ascii_alphabetic()) {
+        return (StatusCode::BAD_REQUEST, "Currency codes have three letters").into_response();
+    }
+    match fetch_rates(&app_state, &base).await {
+        Ok(rates) => match rates.rates.get(&target) {
+            Some(rate) => Json(ExchangeRate {
+                base: rates.base_code,
+                target,
+                rate: *rate,
+            })
+            .into_response(),
+            None => (StatusCode::NOT_FOUND, "Unknown currency").into_response(),
+        },
+        // The provider's error is not passed on to the client
+        Err(_) => (StatusCode::BAD_GATEWAY, "Exchange rates are unavailable").into_response(),
+    }
+}
+
+async fn openapi_document() -> Response {
+    let openapi_path: String = std::env::var("WEB_SERVER_OPENAPI_PATH").unwrap_or_else(|_| "openapi.json".to_string());
+    match tokio::fs::read(openapi_path).await {
+        Ok(document) => ([(header::CONTENT_TYPE, "application/json")], document).into_response(),
+        Err(_) => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+fn configure_routes() -> Router<Arc<AppState>> {
+    Router::new().route("/rates/{base}/{target}", get(read_rate))
+}
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let port: u16 = std::env::var("WEB_SERVER_PORT")
+        .ok()
+        .and_then(|port| port.parse().ok())
+        .unwrap_or(8080);
+    let host: String = std::env::var("WEB_SERVER_HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
+    let static_dir: String = std::env::var("WEB_SERVER_STATIC_DIR").unwrap_or_else(|_| "static".to_string());
+    let client: reqwest::Client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .expect("Failed to build HTTP client");
+    let state: Arc<AppState> = Arc::new(AppState {
+        client,
+        cache: Mutex::new(HashMap::new()),
+    });
+
+    let cors: CorsLayer = CorsLayer::new()
+        .allow_origin(AllowOrigin::predicate(|origin: &HeaderValue, _request_parts| {
+            origin.as_bytes().starts_with(b"http://localhost") || origin == "null"
+        }))
+        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
+        .allow_headers([header::AUTHORIZATION, header::ACCEPT, header::CONTENT_TYPE])
+        .allow_credentials(true)
+        .max_age(Duration::from_secs(3600));
+
+    let app: Router = configure_routes()
+        .route("/openapi.json", get(openapi_document))
+        .nest_service("/static", ServeDir::new(static_dir))
+        .layer(cors)
+        .with_state(state);
+
+    let listener: tokio::net::TcpListener = tokio::net::TcpListener::bind((host.as_str(), port)).await?;
+    axum::serve(listener, app).await
+}
@@ -0,0 +1,217 @@
+use axum::extract::{Path, State};
+use axum::http::{header, HeaderValue, Method, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use tower_http::services::ServeDir;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::sync::Arc;
+use std::time::Duration;
+use ::std::sync::Mutex;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct Task {
+    id: u64,
+    name: String,
+    completed: bool,
+}
+
+// The request body of a new task, the database assigns the id
+#[derive(Deserialize, Debug)]
+struct NewTask {
+    name: String,
+    completed: bool,
+}
+
+// Applied once each, in this order, when the database is opened; add new files instead of editing applied ones
+const MIGRATIONS: &[(&str, &str)] = &[
+    ("0001_create_tables", include_str!("migrations/0001_create_tables.sql")),
+];
+
+struct Database {
+    connection: Connection,
+}
+
+impl Database {
+    fn open(path: &str) -> rusqlite::Result<Self> {
+        let mut connection: Connection = Connection::open(path)?;
+        connection.execute_batch("PRAGMA journal_mode = WAL; PRAGMA foreign_keys = ON;")?;
+        Self::run_migrations(&mut connection)?;
+        Ok(Self { connection })
+    }
+
+    fn run_migrations(connection: &mut Connection) -> rusqlite::Result<()> {
+        connection.execute_batch(
+            "CREATE TABLE IF NOT EXISTS schema_migrations (
+                 version TEXT PRIMARY KEY NOT NULL,
+                 applied_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+             );",
+        )?;
+        for (version, sql) in MIGRATIONS {
+            let applied: bool = connection.query_row(
+                "SELECT EXISTS(SELECT 1 FROM schema_migrations WHERE version = ?1)",
+                params![version],
+                |row| row.get(0),
+            )?;
+            if applied {
+                continue;
+            }
+            let transaction = connection.transaction()?;
+            transaction.execute_batch(sql)?;
+            transaction.execute("INSERT INTO schema_migrations (version) VALUES (?1)", params![version])?;
+            transaction.commit()?;
+        }
+        Ok(())
+    }
+
+    fn task_from_row(row: &Row) -> rusqlite::Result<Task> {
+        Ok(Task {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            completed: row.get(2)?,
+        })
+    }
+
+    fn insert(&self, task: &NewTask) -> rusqlite::Result<Task> {
+        self.connection.execute(
+            "INSERT INTO task (name, completed) VALUES (?1, ?2)",
+            params![task.name, task.completed],
+        )?;
+        Ok(Task {
+            id: self.connection.last_insert_rowid() as u64,
+            name: task.name.clone(),
+            completed: task.completed,
+        })
+    }
+
+    fn get(&self, id: u64) -> rusqlite::Result<Option<Task>> {
+        self.connection
+            .query_row(
+                "SELECT id, name, completed FROM task WHERE id = ?1",
+                params![id],
+                Self::task_from_row,
+            )
+            .optional()
+    }
+
+    fn get_all(&self) -> rusqlite::Result<Vec<Task>> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT id, name, completed FROM task ORDER BY id")?;
+        let tasks = statement.query_map([], Self::task_from_row)?;
+        tasks.collect()
+    }
+
+    // Returns whether a task with the id existed
+    fn update(&self, task: &Task) -> rusqlite::Result<bool> {
+        let changed: usize = self.connection.execute(
+            "UPDATE task SET name = ?1, completed = ?2 WHERE id = ?3",
+            params![task.name, task.completed, task.id],
+        )?;
+        Ok(changed > 0)
+    }
+
+    // Returns whether a task with the id existed
+    fn remove(&self, id: u64) -> rusqlite::Result<bool> {
+        let changed: usize = self
+            .connection
+            .execute("DELETE FROM task WHERE id = ?1", params![id])?;
+        Ok(changed > 0)
+    }
+}
+
+struct AppState {
+    db: Mutex<Database>,
+}
+
+async fn create_task(State(app_state): State<Arc<AppState>>, Json(task): Json<NewTask>) -> Response {
+    let db = app_state.db.lock().unwrap();
+    match db.insert(&task) {
+        Ok(task) => (StatusCode::CREATED, Json(task)).into_response(),
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+async fn read_task(State(app_state): State<Arc<AppState>>, Path(id): Path<u64>) -> Response {
+    let db = app_state.db.lock().unwrap();
+    match db.get(id) {
+        Ok(Some(task)) => Json(task).into_response(),
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+async fn update_task(State(app_state): State<Arc<AppState>>, Json(task): Json<Task>) -> Response {
+    let db = app_state.db.lock().unwrap();
+    match db.update(&task) {
+        Ok(true) => Json(task).into_response(),
+        Ok(false) => StatusCode::NOT_FOUND.into_response(),
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+async fn read_all_tasks(State(app_state): State<Arc<AppState>>) -> Response {
+    let db = app_state.db.lock().unwrap();
+    match db.get_all() {
+        Ok(tasks) => Json(tasks).into_response(),
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+async fn delete_task(State(app_state): State<Arc<AppState>>, Path(id): Path<u64>) -> StatusCode {
+    let db = app_state.db.lock().unwrap();
+    match db.remove(id) {
+        Ok(true) => StatusCode::OK,
+        Ok(false) => StatusCode::NOT_FOUND,
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+async fn openapi_document() -> Response {
+    let openapi_path: String = std::env::var("WEB_SERVER_OPENAPI_PATH").unwrap_or_else(|_| "openapi.json".to_string());
+    match tokio::fs::read(openapi_path).await {
+        Ok(document) => ([(header::CONTENT_TYPE, "application/json")], document).into_response(),
+        Err(_) => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+fn configure_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/task", get(read_all_tasks).post(create_task).put(update_task))
+        .route("/task/{id}", get(read_task).delete(delete_task))
+}
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let port: u16 = std::env::var("WEB_SERVER_PORT")
+        .ok()
+        .and_then(|port| port.parse().ok())
+        .unwrap_or(8080);
+    let host: String = std::env::var("WEB_SERVER_HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
+    let static_dir: String = std::env::var("WEB_SERVER_STATIC_DIR").unwrap_or_else(|_| "static".to_string());
+    let database_path: String = std::env::var("WEB_SERVER_DATABASE_PATH").unwrap_or_else(|_| "database.sqlite".to_string());
+    let db: Database = Database::open(&database_path).expect("Failed to open the SQLite database");
+    let state: Arc<AppState> = Arc::new(AppState {
+        db: Mutex::new(db)
+    });
+
+    let cors: CorsLayer = CorsLayer::new()
+        .allow_origin(AllowOrigin::predicate(|origin: &HeaderValue, _request_parts| {
+            origin.as_bytes().starts_with(b"http://localhost") || origin == "null"
+        }))
+        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
+        .allow_headers([header::AUTHORIZATION, header::ACCEPT, header::CONTENT_TYPE])
+        .allow_credentials(true)
+        .max_age(Duration::from_secs(3600));
+
+    let app: Router = configure_routes()
+        .route("/openapi.json", get(openapi_document))
+        .nest_service("/static", ServeDir::new(static_dir))
+        .layer(cors)
+        .with_state(state);
+
+    let listener: tokio::net::TcpListener = tokio::net::TcpListener::bind((host.as_str(), port)).await?;
+    axum::serve(listener, app).await
+}
@@ -0,0 +1,111 @@
+// The server is a binary crate, so its code is included to reach the handlers and `configure_routes`
+#![allow(dead_code)]
+include!("../src/main.rs");
+
+use http_body_util::BodyExt;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tower::ServiceExt;
+
+static NEXT_DATABASE: AtomicUsize = AtomicUsize::new(0);
+
+// A database file of its own for every test, deleted with its WAL files when the test ends
+struct TempDatabase {
+    path: PathBuf,
+}
+
+impl TempDatabase {
+    fn new() -> Self {
+        let file_name: String = format!(
+            "web_server_api_tests_{}_{}.sqlite",
+            std::process::id(),
+            NEXT_DATABASE.fetch_add(1, Ordering::SeqCst)
+        );
+        Self {
+            path: std::env::temp_dir().join(file_name),
+        }
+    }
+
+    fn app(&self) -> Router {
+        let db: Database = Database::open(self.path.to_str().unwrap()).expect("Failed to open test database");
+        configure_routes().with_state(Arc::new(AppState {
+            db: Mutex::new(db),
+        }))
+    }
+}
+
+impl Drop for TempDatabase {
+    fn drop(&mut self) {
+        for suffix in ["", "-wal", "-shm"] {
+            let mut path = self.path.clone().into_os_string();
+            path.push(suffix);
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+// Sends one request to the router in-process and returns the status and the JSON body, if any
+async fn send(
+    app: &Router,
+    method: axum::http::Method,
+    uri: &str,
+    body: Option<serde_json::Value>,
+) -> (axum::http::StatusCode, Option<serde_json::Value>) {
+    let request = axum::http::Request::builder().method(method).uri(uri);
+    let request = match body {
+        Some(body) => request
+            .header(axum::http::header::CONTENT_TYPE, "application/json")
+            .body(axum::body::Body::from(body.to_string()))
+            .unwrap(),
+        None => request.body(axum::body::Body::empty()).unwrap(),
+    };
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&bytes).ok())
+}
+
+#[tokio::test]
+async fn test_task_crud() {
+    let database = TempDatabase::new();
+    let app = database.app();
+
+    let new_task = serde_json::json!({"name": "write tests", "completed": false});
+    let (status, created) = send(&app, axum::http::Method::POST, "/task", Some(new_task)).await;
+    assert_eq!(status, axum::http::StatusCode::CREATED);
+    let created = created.expect("The created task is returned");
+    let id = created["id"].as_u64().expect("The database assigns the id");
+    assert_eq!(created["name"], "write tests");
+
+    let (_, body) = send(&app, axum::http::Method::GET, &format!("/task/{}", id), None).await;
+    assert_eq!(body, Some(created));
+
+    let updated = serde_json::json!({"id": id, "name": "write tests", "completed": true});
+    let (status, _) = send(&app, axum::http::Method::PUT, "/task", Some(updated.clone())).await;
+    assert!(status.is_success());
+
+    let (_, body) = send(&app, axum::http::Method::GET, "/task", None).await;
+    assert_eq!(body, Some(serde_json::json!([updated])));
+
+    let (status, _) = send(&app, axum::http::Method::DELETE, &format!("/task/{}", id), None).await;
+    assert!(status.is_success());
+
+    let (status, _) = send(&app, axum::http::Method::GET, &format!("/task/{}", id), None).await;
+    assert_eq!(status, axum::http::StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_data_survives_reopening_the_database() {
+    let database = TempDatabase::new();
+    {
+        let app = database.app();
+        let new_task = serde_json::json!({"name": "persisted", "completed": false});
+        let (status, _) = send(&app, axum::http::Method::POST, "/task", Some(new_task)).await;
+        assert_eq!(status, axum::http::StatusCode::CREATED);
+    }
+
+    // Opening the file again must not apply the migrations twice
+    let app = database.app();
+    let (_, body) = send(&app, axum::http::Method::GET, "/task", None).await;
+    assert_eq!(body.as_ref().and_then(|body| body.as_array()).map(Vec::len), Some(1));
+}
@@ -0,0 +1,152 @@
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Path, State};
+use axum::http::{header, HeaderValue, Method, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{delete, get};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use tower_http::services::ServeDir;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use ::std::sync::Mutex;
+use tokio::sync::broadcast;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct Task {
+    id: u64,
+    name: String,
+    completed: bool,
+}
+
+// What the websocket clients receive on every change
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "event", content = "data", rename_all = "snake_case")]
+enum TaskEvent {
+    Created(Task),
+    Updated(Task),
+    Deleted { id: u64 },
+}
+
+struct AppState {
+    tasks: Mutex<HashMap<u64, Task>>,
+    events: broadcast::Sender<TaskEvent>,
+}
+
+impl AppState {
+    // Sending only fails when no client is connected, which is fine
+    fn publish(&self, event: TaskEvent) {
+        let _ = self.events.send(event);
+    }
+}
+
+async fn create_task(State(app_state): State<Arc<AppState>>, Json(task): Json<Task>) -> impl IntoResponse {
+    app_state.tasks.lock().unwrap().insert(task.id, task.clone());
+    app_state.publish(TaskEvent::Created(task.clone()));
+    (StatusCode::CREATED, Json(task))
+}
+
+async fn read_all_tasks(State(app_state): State<Arc<AppState>>) -> impl IntoResponse {
+    let tasks: Vec<Task> = app_state.tasks.lock().unwrap().values().cloned().collect();
+    Json(tasks)
+}
+
+async fn update_task(State(app_state): State<Arc<AppState>>, Json(task): Json<Task>) -> Response {
+    {
+        let mut tasks = app_state.tasks.lock().unwrap();
+        if !tasks.contains_key(&task.id) {
+            return StatusCode::NOT_FOUND.into_response();
+        }
+        tasks.insert(task.id, task.clone());
+    }
+    app_state.publish(TaskEvent::Updated(task.clone()));
+    Json(task).into_response()
+}
+
+async fn delete_task(State(app_state): State<Arc<AppState>>, Path(id): Path<u64>) -> StatusCode {
+    match app_state.tasks.lock().unwrap().remove(&id) {
+        Some(_) => {
+            app_state.publish(TaskEvent::Deleted { id });
+            StatusCode::OK
+        }
+        None => StatusCode::NOT_FOUND,
+    }
+}
+
+// Upgrades the request to a websocket that receives every task event as JSON
+async fn task_events(State(app_state): State<Arc<AppState>>, upgrade: WebSocketUpgrade) -> Response {
+    let events: broadcast::Receiver<TaskEvent> = app_state.events.subscribe();
+    upgrade.on_upgrade(move |socket| forward_task_events(socket, events))
+}
+
+async fn forward_task_events(mut socket: WebSocket, mut events: broadcast::Receiver<TaskEvent>) {
+    loop {
+        tokio::select! {
+            event = events.recv() => match event {
+                Ok(event) => {
+                    let text: String = serde_json::to_string(&event).unwrap_or_default();
+                    if socket.send(Message::Text(text.into())).await.is_err() {
+                        break;
+                    }
+                }
+                // A slow client missed events, it keeps receiving the newer ones
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            // Pings are answered by axum, the loop only has to notice the client leaving
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+async fn openapi_document() -> Response {
+    let openapi_path: String = std::env::var("WEB_SERVER_OPENAPI_PATH").unwrap_or_else(|_| "openapi.json".to_string());
+    match tokio::fs::read(openapi_path).await {
+        Ok(document) => ([(header::CONTENT_TYPE, "application/json")], document).into_response(),
+        Err(_) => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+fn configure_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/task", get(read_all_tasks).post(create_task).put(update_task))
+        .route("/task/{id}", delete(delete_task))
+        .route("/ws", get(task_events))
+}
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let port: u16 = std::env::var("WEB_SERVER_PORT")
+        .ok()
+        .and_then(|port| port.parse().ok())
+        .unwrap_or(8080);
+    let host: String = std::env::var("WEB_SERVER_HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
+    let static_dir: String = std::env::var("WEB_SERVER_STATIC_DIR").unwrap_or_else(|_| "static".to_string());
+    let (events, _) = broadcast::channel::<TaskEvent>(64);
+    let state: Arc<AppState> = Arc::new(AppState {
+        tasks: Mutex::new(HashMap::new()),
+        events,
+    });
+
+    let cors: CorsLayer = CorsLayer::new()
+        .allow_origin(AllowOrigin::predicate(|origin: &HeaderValue, _request_parts| {
+            origin.as_bytes().starts_with(b"http://localhost") || origin == "null"
+        }))
+        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
+        .allow_headers([header::AUTHORIZATION, header::ACCEPT, header::CONTENT_TYPE])
+        .allow_credentials(true)
+        .max_age(Duration::from_secs(3600));
+
+    let app: Router = configure_routes()
+        .route("/openapi.json", get(openapi_document))
+        .nest_service("/static", ServeDir::new(static_dir))
+        .layer(cors)
+        .with_state(state);
+
+    let listener: tokio::net::TcpListener = tokio::net::TcpListener::bind((host.as_str(), port)).await?;
+    axum::serve(listener, app).await
+}
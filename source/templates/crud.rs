@@ -2,6 +2,7 @@ use actix_web::{web, App, HttpResponse, HttpServer, Responder};
 use serde::{Deserialize, Serialize};
 use actix_web::http::header;
 use actix_cors::Cors;
+use actix_files::{Files, NamedFile};
 use std::collections::HashMap;
 use std::fs;
 use std::io::Write;
@@ -14,24 +15,15 @@ struct Task {
     completed: bool,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-struct User {
-    id: u64,
-    user_name: String,
-    user_password: String,
-}
-
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct Database {
     tasks: HashMap<u64, Task>,
-    users: HashMap<u64, User>,
 }
 
 impl Database {
     fn new() -> Self {
         Self {
             tasks: HashMap::new(),
-            users: HashMap::new(),
         }
     }
     fn insert(&mut self, task: Task) {
@@ -49,14 +41,6 @@ impl Database {
     fn update(&mut self, task: Task) {
         self.tasks.insert(task.id, task);
     }
-    fn insert_user(&mut self, user: User) {
-        self.users.insert(user.id, user);
-    }
-
-    fn get_user_by_name(&self, user_name: &str) -> Option<&User> {
-        self.users.values().find(|u| u.user_name == user_name)
-    }
-
     fn save_to_file(&self) -> std::io::Result<()> {
         let data = serde_json::to_string(&self)?;
         let mut file = fs::File::create("database.json")?;
@@ -92,7 +76,7 @@ async fn read_task(app_state: web::Data<AppState>, id: web::Path<u64>) -> impl R
 
 async fn update_task(app_state: web::Data<AppState>, task: web::Json<Task>) -> impl Responder {
     let mut db = app_state.db.lock().unwrap();
-    db.insert(task.into_inner());
+    db.update(task.into_inner());
     let _ = db.save_to_file();
     HttpResponse::Ok().finish()
 }
@@ -110,25 +94,27 @@ async fn delete_task(app_state: web::Data<AppState>, id: web::Path<u64>) -> impl
     HttpResponse::Ok().finish()
 }
 
-async fn register(app_state: web::Data<AppState>, user: web::Json<User>) -> impl Responder {
-    let mut db = app_state.db.lock().unwrap();
-    db.insert_user(user.into_inner());
-    let _ = db.save_to_file();
-    HttpResponse::Created().finish()
+async fn openapi_document() -> actix_web::Result<NamedFile> {
+    let openapi_path: String = std::env::var("WEB_SERVER_OPENAPI_PATH").unwrap_or_else(|_| "openapi.json".to_string());
+    Ok(NamedFile::open_async(openapi_path).await?)
 }
 
-async fn login(app_state: web::Data<AppState>, user: web::Json<User>) -> impl Responder {
-    let db = app_state.db.lock().unwrap();
-    match db.get_user_by_name(&user.user_name) {
-        Some(stored_user) if stored_user.user_password == user.user_password => {
-            HttpResponse::Ok().body("Logged in successfully")
-        }
-        _ => HttpResponse::BadGateway().body("Invalid user or password"),
-    }
+fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("/task", web::post().to(create_task))
+        .route("/task", web::get().to(read_all_tasks))
+        .route("/task", web::put().to(update_task))
+        .route("/task/{id}", web::get().to(read_task))
+        .route("/task/{id}", web::delete().to(delete_task));
 }
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
+    let port: u16 = std::env::var("WEB_SERVER_PORT")
+        .ok()
+        .and_then(|port| port.parse().ok())
+        .unwrap_or(8080);
+    let host: String = std::env::var("WEB_SERVER_HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
+    let static_dir: String = std::env::var("WEB_SERVER_STATIC_DIR").unwrap_or_else(|_| "static".to_string());
     let db: Database = Database::load_from_file().unwrap_or_else(|_| Database::new());
     let data: web::Data<AppState> = web::Data::new(AppState {
         db: Mutex::new(db)
@@ -148,15 +134,11 @@ async fn main() -> std::io::Result<()> {
                     .max_age(3600)
             )
             .app_data(data.clone())
-            .route("/task", web::post().to(create_task))
-            .route("/task", web::get().to(read_all_tasks))
-            .route("/task", web::put().to(update_task))
-            .route("/task/{id}", web::get().to(read_task))
-            .route("/task/{id}", web::delete().to(delete_task))
-            .route("/register", web::post().to(register))
-            .route("/login", web::post().to(login))
+            .configure(configure_routes)
+            .route("/openapi.json", web::get().to(openapi_document))
+            .service(Files::new("/static", static_dir.clone()).index_file("index.html"))
     })
-        .bind("127.0.0.1:8080")?
+        .bind((host.as_str(), port))?
         .run()
         .await
 }
\ No newline at end of file
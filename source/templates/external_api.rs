@@ -0,0 +1,123 @@
+use actix_web::{web, App, HttpResponse, HttpServer, Responder};
+use serde::{Deserialize, Serialize};
+use actix_web::http::header;
+use actix_cors::Cors;
+use actix_files::{Files, NamedFile};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use ::std::sync::Mutex;
+
+// How long a fetched answer is served from the cache before the provider is asked again
+const CACHE_TTL: Duration = Duration::from_secs(60);
+
+// Only the fields the server needs are read from the provider's response
+#[derive(Deserialize, Debug, Clone)]
+struct ProviderRates {
+    base_code: String,
+    rates: HashMap<String, f64>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+struct ExchangeRate {
+    base: String,
+    target: String,
+    rate: f64,
+}
+
+struct AppState {
+    client: reqwest::Client,
+    cache: Mutex<HashMap<String, (Instant, ProviderRates)>>,
+}
+
+async fn fetch_rates(app_state: &AppState, base: &str) -> Result<ProviderRates, reqwest::Error> {
+    if let Some((fetched_at, rates)) = app_state.cache.lock().unwrap().get(base) {
+        if fetched_at.elapsed() < CACHE_TTL {
+            return Ok(rates.clone());
+        }
+    }
+    let url: String = format!("https://open.er-api.com/v6/latest/{}", base);
+    let rates: ProviderRates = app_state
+        .client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    app_state
+        .cache
+        .lock()
+        .unwrap()
+        .insert(base.to_string(), (Instant::now(), rates.clone()));
+    Ok(rates)
+}
+
+async fn read_rate(app_state: web::Data<AppState>, path: web::Path<(String, String)>) -> impl Responder {
+    let (base, target): (String, String) = path.into_inner();
+    let base: String = base.to_uppercase();
+    let target: String = target.to_uppercase();
+    if base.len() != 3 || !base.chars().all(|c| c.is_ascii_alphabetic()) {
+        return HttpResponse::BadRequest().body("Currency codes have three letters");
+    }
+    match fetch_rates(&app_state, &base).await {
+        Ok(rates) => match rates.rates.get(&target) {
+            Some(rate) => HttpResponse::Ok().json(ExchangeRate {
+                base: rates.base_code,
+                target,
+                rate: *rate,
+            }),
+            None => HttpResponse::NotFound().body("Unknown currency"),
+        },
+        // The provider's error is not passed on to the client
+        Err(_) => HttpResponse::BadGateway().body("Exchange rates are unavailable"),
+    }
+}
+
+async fn openapi_document() -> actix_web::Result<NamedFile> {
+    let openapi_path: String = std::env::var("WEB_SERVER_OPENAPI_PATH").unwrap_or_else(|_| "openapi.json".to_string());
+    Ok(NamedFile::open_async(openapi_path).await?)
+}
+
+fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("/rates/{base}/{target}", web::get().to(read_rate));
+}
+
+#[actix_web::main]
+async fn main() -> std::io::Result<()> {
+    let port: u16 = std::env::var("WEB_SERVER_PORT")
+        .ok()
+        .and_then(|port| port.parse().ok())
+        .unwrap_or(8080);
+    let host: String = std::env::var("WEB_SERVER_HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
+    let static_dir: String = std::env::var("WEB_SERVER_STATIC_DIR").unwrap_or_else(|_| "static".to_string());
+    let client: reqwest::Client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .expect("Failed to build HTTP client");
+    let data: web::Data<AppState> = web::Data::new(AppState {
+        client,
+        cache: Mutex::new(HashMap::new()),
+    });
+
+    HttpServer::new(move || {
+        App::new()
+            .wrap(
+                Cors::permissive()
+                    .allowed_origin_fn(|origin, _req_head| {
+                        origin.as_bytes().starts_with(b"http://localhost") || origin == "null"
+                    })
+                    .allowed_methods(vec!["GET", "POST", "PUT", "DELETE"])
+                    .allowed_headers(vec![header::AUTHORIZATION, header::ACCEPT])
+                    .allowed_header(header::CONTENT_TYPE)
+                    .supports_credentials()
+                    .max_age(3600)
+            )
+            .app_data(data.clone())
+            .configure(configure_routes)
+            .route("/openapi.json", web::get().to(openapi_document))
+            .service(Files::new("/static", static_dir.clone()).index_file("index.html"))
+    })
+        .bind((host.as_str(), port))?
+        .run()
+        .await
+}
@@ -0,0 +1,169 @@
+import hashlib
+import hmac
+import json
+import os
+import secrets
+import threading
+
+import uvicorn
+from fastapi import FastAPI, Header, HTTPException, Response
+from fastapi.middleware.cors import CORSMiddleware
+from fastapi.responses import FileResponse
+from fastapi.staticfiles import StaticFiles
+from pydantic import BaseModel
+
+DATABASE_FILE = "database.json"
+
+
+class User(BaseModel):
+    id: int
+    user_name: str
+    password_hash: str
+
+
+class Credentials(BaseModel):
+    user_name: str
+    password: str
+
+
+class LoginResponse(BaseModel):
+    token: str
+
+
+class UserProfile(BaseModel):
+    id: int
+    user_name: str
+
+
+class Database:
+    def __init__(self) -> None:
+        self.users: dict[int, User] = {}
+        self.lock = threading.Lock()
+
+    def next_user_id(self) -> int:
+        return max(self.users, default=0) + 1
+
+    def get_user_by_name(self, user_name: str) -> User | None:
+        return next((user for user in self.users.values() if user.user_name == user_name), None)
+
+    def save_to_file(self) -> None:
+        data = {"users": {str(user_id): user.model_dump() for user_id, user in self.users.items()}}
+        with open(DATABASE_FILE, "w") as file:
+            json.dump(data, file)
+
+    @classmethod
+    def load_from_file(cls) -> "Database":
+        db = cls()
+        try:
+            with open(DATABASE_FILE) as file:
+                data = json.load(file)
+        except (OSError, ValueError):
+            return db
+        for user in data.get("users", {}).values():
+            user = User.model_validate(user)
+            db.users[user.id] = user
+        return db
+
+
+app = FastAPI(openapi_url=None)
+db = Database.load_from_file()
+# Session tokens of the logged in users, kept in memory so a restart logs everyone out
+sessions: dict[str, int] = {}
+
+
+# scrypt from the standard library, stored as "<salt hex>$<hash hex>"
+def hash_password(password: str) -> str:
+    salt = secrets.token_bytes(16)
+    password_hash = hashlib.scrypt(password.encode(), salt=salt, n=2**14, r=8, p=1)
+    return f"{salt.hex()}${password_hash.hex()}"
+
+
+def verify_password(password: str, password_hash: str) -> bool:
+    salt_hex, _, hash_hex = password_hash.partition("$")
+    try:
+        salt = bytes.fromhex(salt_hex)
+    except ValueError:
+        return False
+    candidate = hashlib.scrypt(password.encode(), salt=salt, n=2**14, r=8, p=1)
+    return hmac.compare_digest(candidate.hex(), hash_hex)
+
+
+def bearer_token(authorization: str | None) -> str | None:
+    if authorization is None or not authorization.startswith("Bearer "):
+        return None
+    return authorization.removeprefix("Bearer ").strip()
+
+
+# Returns the id of the logged in user, protected routes answer 401 when it is missing
+def current_user_id(authorization: str | None) -> int:
+    token = bearer_token(authorization)
+    if token is None or token not in sessions:
+        raise HTTPException(status_code=401)
+    return sessions[token]
+
+
+@app.post("/register", response_model=UserProfile, status_code=201)
+def register(credentials: Credentials) -> UserProfile:
+    if not credentials.user_name.strip() or len(credentials.password) < 8:
+        raise HTTPException(status_code=400, detail="User name is required and passwords need at least 8 characters")
+    with db.lock:
+        if db.get_user_by_name(credentials.user_name) is not None:
+            raise HTTPException(status_code=409, detail="User name is taken")
+        user = User(
+            id=db.next_user_id(),
+            user_name=credentials.user_name,
+            password_hash=hash_password(credentials.password),
+        )
+        db.users[user.id] = user
+        db.save_to_file()
+    return UserProfile(id=user.id, user_name=user.user_name)
+
+
+@app.post("/login", response_model=LoginResponse)
+def login(credentials: Credentials) -> LoginResponse:
+    user = db.get_user_by_name(credentials.user_name)
+    if user is None or not verify_password(credentials.password, user.password_hash):
+        raise HTTPException(status_code=401, detail="Invalid user or password")
+    token = secrets.token_urlsafe(36)
+    sessions[token] = user.id
+    return LoginResponse(token=token)
+
+
+@app.post("/logout", status_code=204)
+def logout(authorization: str | None = Header(default=None)) -> Response:
+    token = bearer_token(authorization)
+    if token is None or sessions.pop(token, None) is None:
+        raise HTTPException(status_code=401)
+    return Response(status_code=204)
+
+
+@app.get("/me", response_model=UserProfile)
+def read_profile(authorization: str | None = Header(default=None)) -> UserProfile:
+    user = db.users.get(current_user_id(authorization))
+    if user is None:
+        raise HTTPException(status_code=401)
+    return UserProfile(id=user.id, user_name=user.user_name)
+
+
+@app.get("/openapi.json", include_in_schema=False)
+def openapi_document() -> FileResponse:
+    return FileResponse(os.environ.get("WEB_SERVER_OPENAPI_PATH", "openapi.json"), media_type="application/json")
+
+
+app.add_middleware(
+    CORSMiddleware,
+    allow_origin_regex=r"http://localhost(:\d+)?|null",
+    allow_methods=["GET", "POST", "PUT", "DELETE"],
+    allow_headers=["Authorization", "Accept", "Content-Type"],
+    allow_credentials=True,
+    max_age=3600,
+)
+app.mount("/static", StaticFiles(directory=os.environ.get("WEB_SERVER_STATIC_DIR", "static"), html=True), name="static")
+
+
+if __name__ == "__main__":
+    uvicorn.run(
+        app,
+        host=os.environ.get("WEB_SERVER_HOST", "127.0.0.1"),
+        port=int(os.environ.get("WEB_SERVER_PORT", "8080")),
+    )
@@ -0,0 +1,123 @@
+import json
+import os
+import threading
+
+import uvicorn
+from fastapi import FastAPI, HTTPException
+from fastapi.middleware.cors import CORSMiddleware
+from fastapi.responses import FileResponse, Response
+from fastapi.staticfiles import StaticFiles
+from pydantic import BaseModel
+
+DATABASE_FILE = "database.json"
+
+
+class Task(BaseModel):
+    id: int
+    name: str
+    completed: bool
+
+
+class Database:
+    def __init__(self) -> None:
+        self.tasks: dict[int, Task] = {}
+        # Handlers run on a thread pool, so changes and saves are serialized
+        self.lock = threading.Lock()
+
+    def insert(self, task: Task) -> None:
+        self.tasks[task.id] = task
+
+    def get(self, task_id: int) -> Task | None:
+        return self.tasks.get(task_id)
+
+    def get_all(self) -> list[Task]:
+        return list(self.tasks.values())
+
+    def remove(self, task_id: int) -> Task | None:
+        return self.tasks.pop(task_id, None)
+
+    def update(self, task: Task) -> None:
+        self.tasks[task.id] = task
+
+    def save_to_file(self) -> None:
+        data = {"tasks": {str(task_id): task.model_dump() for task_id, task in self.tasks.items()}}
+        with open(DATABASE_FILE, "w") as file:
+            json.dump(data, file)
+
+    @classmethod
+    def load_from_file(cls) -> "Database":
+        db = cls()
+        try:
+            with open(DATABASE_FILE) as file:
+                data = json.load(file)
+        except (OSError, ValueError):
+            return db
+        for task in data.get("tasks", {}).values():
+            db.insert(Task.model_validate(task))
+        return db
+
+
+# FastAPI's generated document is disabled, "/openapi.json" serves the file from WEB_SERVER_OPENAPI_PATH
+app = FastAPI(openapi_url=None)
+db = Database.load_from_file()
+
+
+@app.post("/task", status_code=201)
+def create_task(task: Task) -> Response:
+    with db.lock:
+        db.insert(task)
+        db.save_to_file()
+    return Response(status_code=201)
+
+
+@app.get("/task/{id}", response_model=Task)
+def read_task(id: int) -> Task:
+    task = db.get(id)
+    if task is None:
+        raise HTTPException(status_code=404)
+    return task
+
+
+@app.put("/task")
+def update_task(task: Task) -> Response:
+    with db.lock:
+        db.update(task)
+        db.save_to_file()
+    return Response(status_code=200)
+
+
+@app.get("/task", response_model=list[Task])
+def read_all_tasks() -> list[Task]:
+    return db.get_all()
+
+
+@app.delete("/task/{id}")
+def delete_task(id: int) -> Response:
+    with db.lock:
+        db.remove(id)
+        db.save_to_file()
+    return Response(status_code=200)
+
+
+@app.get("/openapi.json", include_in_schema=False)
+def openapi_document() -> FileResponse:
+    return FileResponse(os.environ.get("WEB_SERVER_OPENAPI_PATH", "openapi.json"), media_type="application/json")
+
+
+app.add_middleware(
+    CORSMiddleware,
+    allow_origin_regex=r"http://localhost(:\d+)?|null",
+    allow_methods=["GET", "POST", "PUT", "DELETE"],
+    allow_headers=["Authorization", "Accept", "Content-Type"],
+    allow_credentials=True,
+    max_age=3600,
+)
+app.mount("/static", StaticFiles(directory=os.environ.get("WEB_SERVER_STATIC_DIR", "static"), html=True), name="static")
+
+
+if __name__ == "__main__":
+    uvicorn.run(
+        app,
+        host=os.environ.get("WEB_SERVER_HOST", "127.0.0.1"),
+        port=int(os.environ.get("WEB_SERVER_PORT", "8080")),
+    )
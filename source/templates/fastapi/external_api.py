@@ -0,0 +1,82 @@
+import os
+import threading
+import time
+
+import httpx
+import uvicorn
+from fastapi import FastAPI, HTTPException
+from fastapi.middleware.cors import CORSMiddleware
+from fastapi.responses import FileResponse
+from fastapi.staticfiles import StaticFiles
+from pydantic import BaseModel
+
+# How long a fetched answer is served from the cache before the provider is asked again
+CACHE_TTL_SECONDS = 60
+
+
+class ExchangeRate(BaseModel):
+    base: str
+    target: str
+    rate: float
+
+
+app = FastAPI(openapi_url=None)
+client = httpx.Client(timeout=10.0)
+cache: dict[str, tuple[float, dict]] = {}
+cache_lock = threading.Lock()
+
+
+# Only the fields the server needs are read from the provider's response
+def fetch_rates(base: str) -> dict:
+    with cache_lock:
+        cached = cache.get(base)
+    if cached is not None and time.monotonic() - cached[0] < CACHE_TTL_SECONDS:
+        return cached[1]
+    response = client.get(f"https://open.er-api.com/v6/latest/{base}")
+    response.raise_for_status()
+    body = response.json()
+    rates = {"base_code": body["base_code"], "rates": body["rates"]}
+    with cache_lock:
+        cache[base] = (time.monotonic(), rates)
+    return rates
+
+
+@app.get("/rates/{base}/{target}", response_model=ExchangeRate)
+def read_rate(base: str, target: str) -> ExchangeRate:
+    base = base.upper()
+    target = target.upper()
+    if len(base) != 3 or not base.isalpha():
+        raise HTTPException(status_code=400, detail="Currency codes have three letters")
+    try:
+        rates = fetch_rates(base)
+    except (httpx.HTTPError, KeyError, ValueError):
+        # The provider's error is not passed on to the client
+        raise HTTPException(status_code=502, detail="Exchange rates are unavailable")
+    rate = rates["rates"].get(target)
+    if rate is None:
+        raise HTTPException(status_code=404, detail="Unknown currency")
+    return ExchangeRate(base=rates["base_code"], target=target, rate=rate)
+
+
+@app.get("/openapi.json", include_in_schema=False)
+def openapi_document() -> FileResponse:
+    return FileResponse(os.environ.get("WEB_SERVER_OPENAPI_PATH", "openapi.json"), media_type="application/json")
+
+
+app.add_middleware(
+    CORSMiddleware,
+    allow_origin_regex=r"http://localhost(:\d+)?|null",
+    allow_methods=["GET", "POST", "PUT", "DELETE"],
+    allow_headers=["Authorization", "Accept", "Content-Type"],
+    allow_credentials=True,
+    max_age=3600,
+)
+app.mount("/static", StaticFiles(directory=os.environ.get("WEB_SERVER_STATIC_DIR", "static"), html=True), name="static")
+
+
+if __name__ == "__main__":
+    uvicorn.run(
+        app,
+        host=os.environ.get("WEB_SERVER_HOST", "127.0.0.1"),
+        port=int(os.environ.get("WEB_SERVER_PORT", "8080")),
+    )
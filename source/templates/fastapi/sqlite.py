@@ -0,0 +1,159 @@
+import os
+import sqlite3
+import threading
+from pathlib import Path
+
+import uvicorn
+from fastapi import FastAPI, HTTPException
+from fastapi.middleware.cors import CORSMiddleware
+from fastapi.responses import FileResponse, Response
+from fastapi.staticfiles import StaticFiles
+from pydantic import BaseModel
+
+MIGRATIONS_DIR = Path(__file__).parent / "migrations"
+
+# Applied once each, in this order, when the database is opened; add new files instead of editing applied ones
+MIGRATIONS = [
+    ("0001_create_tables", "0001_create_tables.sql"),
+]
+
+
+class Task(BaseModel):
+    id: int
+    name: str
+    completed: bool
+
+
+# The request body of a new task, the database assigns the id
+class NewTask(BaseModel):
+    name: str
+    completed: bool
+
+
+class Database:
+    def __init__(self, path: str) -> None:
+        # Handlers run on a thread pool, the lock serializes their use of the one connection
+        self.connection = sqlite3.connect(path, check_same_thread=False)
+        self.lock = threading.Lock()
+        self.connection.execute("PRAGMA journal_mode = WAL")
+        self.connection.execute("PRAGMA foreign_keys = ON")
+        self.run_migrations()
+
+    def run_migrations(self) -> None:
+        self.connection.execute(
+            """CREATE TABLE IF NOT EXISTS schema_migrations (
+                   version TEXT PRIMARY KEY NOT NULL,
+                   applied_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+               )"""
+        )
+        for version, file_name in MIGRATIONS:
+            applied = self.connection.execute(
+                "SELECT EXISTS(SELECT 1 FROM schema_migrations WHERE version = ?)", (version,)
+            ).fetchone()[0]
+            if applied:
+                continue
+            sql = (MIGRATIONS_DIR / file_name).read_text()
+            # executescript commits on its own, so the version is recorded in the same script
+            self.connection.executescript(
+                f"BEGIN; {sql}; INSERT INTO schema_migrations (version) VALUES ('{version}'); COMMIT;"
+            )
+
+    @staticmethod
+    def task_from_row(row: tuple) -> Task:
+        return Task(id=row[0], name=row[1], completed=bool(row[2]))
+
+    def insert(self, task: NewTask) -> Task:
+        with self.lock, self.connection:
+            cursor = self.connection.execute(
+                "INSERT INTO task (name, completed) VALUES (?, ?)", (task.name, task.completed)
+            )
+        return Task(id=cursor.lastrowid, name=task.name, completed=task.completed)
+
+    def get(self, task_id: int) -> Task | None:
+        with self.lock:
+            row = self.connection.execute(
+                "SELECT id, name, completed FROM task WHERE id = ?", (task_id,)
+            ).fetchone()
+        return None if row is None else self.task_from_row(row)
+
+    def get_all(self) -> list[Task]:
+        with self.lock:
+            rows = self.connection.execute("SELECT id, name, completed FROM task ORDER BY id").fetchall()
+        return [self.task_from_row(row) for row in rows]
+
+    # Returns whether a task with the id existed
+    def update(self, task: Task) -> bool:
+        with self.lock, self.connection:
+            cursor = self.connection.execute(
+                "UPDATE task SET name = ?, completed = ? WHERE id = ?", (task.name, task.completed, task.id)
+            )
+        return cursor.rowcount > 0
+
+    # Returns whether a task with the id existed
+    def remove(self, task_id: int) -> bool:
+        with self.lock, self.connection:
+            cursor = self.connection.execute("DELETE FROM task WHERE id = ?", (task_id,))
+        return cursor.rowcount > 0
+
+
+app = FastAPI(openapi_url=None)
+db = Database(os.environ.get("WEB_SERVER_DATABASE_PATH", "database.sqlite"))
+
+
+@app.post("/task", response_model=Task, status_code=201)
+def create_task(task: NewTask) -> Task:
+    try:
+        return db.insert(task)
+    except sqlite3.IntegrityError:
+        raise HTTPException(status_code=400)
+
+
+@app.get("/task/{id}", response_model=Task)
+def read_task(id: int) -> Task:
+    task = db.get(id)
+    if task is None:
+        raise HTTPException(status_code=404)
+    return task
+
+
+@app.put("/task", response_model=Task)
+def update_task(task: Task) -> Task:
+    if not db.update(task):
+        raise HTTPException(status_code=404)
+    return task
+
+
+@app.get("/task", response_model=list[Task])
+def read_all_tasks() -> list[Task]:
+    return db.get_all()
+
+
+@app.delete("/task/{id}")
+def delete_task(id: int) -> Response:
+    if not db.remove(id):
+        raise HTTPException(status_code=404)
+    return Response(status_code=200)
+
+
+@app.get("/openapi.json", include_in_schema=False)
+def openapi_document() -> FileResponse:
+    return FileResponse(os.environ.get("WEB_SERVER_OPENAPI_PATH", "openapi.json"), media_type="application/json")
+
+
+app.add_middleware(
+    CORSMiddleware,
+    allow_origin_regex=r"http://localhost(:\d+)?|null",
+    allow_methods=["GET", "POST", "PUT", "DELETE"],
+    allow_headers=["Authorization", "Accept", "Content-Type"],
+    allow_credentials=True,
+    max_age=3600,
+)
+app.mount("/static", StaticFiles(directory=os.environ.get("WEB_SERVER_STATIC_DIR", "static"), html=True), name="static")
+
+
+if __name__ == "__main__":
+    uvicorn.run(
+        app,
+        host=os.environ.get("WEB_SERVER_HOST", "127.0.0.1"),
+        port=int(os.environ.get("WEB_SERVER_PORT", "8080")),
+    )
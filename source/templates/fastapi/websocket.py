@@ -0,0 +1,111 @@
+import asyncio
+import os
+
+import uvicorn
+from fastapi import FastAPI, HTTPException, WebSocket, WebSocketDisconnect
+from fastapi.middleware.cors import CORSMiddleware
+from fastapi.responses import FileResponse, Response
+from fastapi.staticfiles import StaticFiles
+from pydantic import BaseModel
+
+
+class Task(BaseModel):
+    id: int
+    name: str
+    completed: bool
+
+
+app = FastAPI(openapi_url=None)
+tasks: dict[int, Task] = {}
+# One queue per connected websocket client, every change is put on each of them
+subscribers: set[asyncio.Queue] = set()
+
+
+# What the websocket clients receive on every change, e.g. {"event": "created", "data": {...}}
+def publish(event: str, data: dict) -> None:
+    for queue in subscribers:
+        try:
+            queue.put_nowait({"event": event, "data": data})
+        except asyncio.QueueFull:
+            # A slow client misses this event and keeps receiving the newer ones
+            pass
+
+
+# The handlers are async so they run on the event loop the websocket queues belong to
+@app.post("/task", response_model=Task, status_code=201)
+async def create_task(task: Task) -> Task:
+    tasks[task.id] = task
+    publish("created", task.model_dump())
+    return task
+
+
+@app.get("/task", response_model=list[Task])
+async def read_all_tasks() -> list[Task]:
+    return list(tasks.values())
+
+
+@app.put("/task", response_model=Task)
+async def update_task(task: Task) -> Task:
+    if task.id not in tasks:
+        raise HTTPException(status_code=404)
+    tasks[task.id] = task
+    publish("updated", task.model_dump())
+    return task
+
+
+@app.delete("/task/{id}")
+async def delete_task(id: int) -> Response:
+    if tasks.pop(id, None) is None:
+        raise HTTPException(status_code=404)
+    publish("deleted", {"id": id})
+    return Response(status_code=200)
+
+
+# Sends every task event to the client as JSON until it disconnects
+@app.websocket("/ws")
+async def task_events(websocket: WebSocket) -> None:
+    await websocket.accept()
+    queue: asyncio.Queue = asyncio.Queue(maxsize=64)
+    subscribers.add(queue)
+    # Reading notices the client leaving while the other task waits for events
+    receiver = asyncio.create_task(websocket.receive_text())
+    try:
+        while True:
+            sender = asyncio.create_task(queue.get())
+            done, _ = await asyncio.wait({sender, receiver}, return_when=asyncio.FIRST_COMPLETED)
+            if receiver in done:
+                sender.cancel()
+                try:
+                    receiver.result()
+                except WebSocketDisconnect:
+                    break
+                receiver = asyncio.create_task(websocket.receive_text())
+                continue
+            await websocket.send_json(sender.result())
+    finally:
+        receiver.cancel()
+        subscribers.discard(queue)
+
+
+@app.get("/openapi.json", include_in_schema=False)
+async def openapi_document() -> FileResponse:
+    return FileResponse(os.environ.get("WEB_SERVER_OPENAPI_PATH", "openapi.json"), media_type="application/json")
+
+
+app.add_middleware(
+    CORSMiddleware,
+    allow_origin_regex=r"http://localhost(:\d+)?|null",
+    allow_methods=["GET", "POST", "PUT", "DELETE"],
+    allow_headers=["Authorization", "Accept", "Content-Type"],
+    allow_credentials=True,
+    max_age=3600,
+)
+app.mount("/static", StaticFiles(directory=os.environ.get("WEB_SERVER_STATIC_DIR", "static"), html=True), name="static")
+
+
+if __name__ == "__main__":
+    uvicorn.run(
+        app,
+        host=os.environ.get("WEB_SERVER_HOST", "127.0.0.1"),
+        port=int(os.environ.get("WEB_SERVER_PORT", "8080")),
+    )
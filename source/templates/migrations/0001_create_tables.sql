@@ -0,0 +1,7 @@
+-- Generated from the domain model. Add new migrations instead of editing applied ones.
+
+CREATE TABLE "task" (
+    "id" INTEGER PRIMARY KEY,
+    "name" TEXT NOT NULL,
+    "completed" INTEGER NOT NULL CHECK ("completed" IN (0, 1))
+);
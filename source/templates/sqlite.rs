@@ -0,0 +1,215 @@
+use actix_web::{web, App, HttpResponse, HttpServer, Responder};
+use serde::{Deserialize, Serialize};
+use actix_web::http::header;
+use actix_cors::Cors;
+use actix_files::{Files, NamedFile};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use ::std::sync::Mutex;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct Task {
+    id: u64,
+    name: String,
+    completed: bool,
+}
+
+// The request body of a new task, the database assigns the id
+#[derive(Deserialize, Debug)]
+struct NewTask {
+    name: String,
+    completed: bool,
+}
+
+// Applied once each, in this order, when the database is opened; add new files instead of editing applied ones
+const MIGRATIONS: &[(&str, &str)] = &[
+    ("0001_create_tables", include_str!("migrations/0001_create_tables.sql")),
+];
+
+struct Database {
+    connection: Connection,
+}
+
+impl Database {
+    fn open(path: &str) -> rusqlite::Result<Self> {
+        let mut connection: Connection = Connection::open(path)?;
+        connection.execute_batch("PRAGMA journal_mode = WAL; PRAGMA foreign_keys = ON;")?;
+        Self::run_migrations(&mut connection)?;
+        Ok(Self { connection })
+    }
+
+    fn run_migrations(connection: &mut Connection) -> rusqlite::Result<()> {
+        connection.execute_batch(
+            "CREATE TABLE IF NOT EXISTS schema_migrations (
+                 version TEXT PRIMARY KEY NOT NULL,
+                 applied_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+             );",
+        )?;
+        for (version, sql) in MIGRATIONS {
+            let applied: bool = connection.query_row(
+                "SELECT EXISTS(SELECT 1 FROM schema_migrations WHERE version = ?1)",
+                params![version],
+                |row| row.get(0),
+            )?;
+            if applied {
+                continue;
+            }
+            let transaction = connection.transaction()?;
+            transaction.execute_batch(sql)?;
+            transaction.execute("INSERT INTO schema_migrations (version) VALUES (?1)", params![version])?;
+            transaction.commit()?;
+        }
+        Ok(())
+    }
+
+    fn task_from_row(row: &Row) -> rusqlite::Result<Task> {
+        Ok(Task {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            completed: row.get(2)?,
+        })
+    }
+
+    fn insert(&self, task: &NewTask) -> rusqlite::Result<Task> {
+        self.connection.execute(
+            "INSERT INTO task (name, completed) VALUES (?1, ?2)",
+            params![task.name, task.completed],
+        )?;
+        Ok(Task {
+            id: self.connection.last_insert_rowid() as u64,
+            name: task.name.clone(),
+            completed: task.completed,
+        })
+    }
+
+    fn get(&self, id: u64) -> rusqlite::Result<Option<Task>> {
+        self.connection
+            .query_row(
+                "SELECT id, name, completed FROM task WHERE id = ?1",
+                params![id],
+                Self::task_from_row,
+            )
+            .optional()
+    }
+
+    fn get_all(&self) -> rusqlite::Result<Vec<Task>> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT id, name, completed FROM task ORDER BY id")?;
+        let tasks = statement.query_map([], Self::task_from_row)?;
+        tasks.collect()
+    }
+
+    // Returns whether a task with the id existed
+    fn update(&self, task: &Task) -> rusqlite::Result<bool> {
+        let changed: usize = self.connection.execute(
+            "UPDATE task SET name = ?1, completed = ?2 WHERE id = ?3",
+            params![task.name, task.completed, task.id],
+        )?;
+        Ok(changed > 0)
+    }
+
+    // Returns whether a task with the id existed
+    fn remove(&self, id: u64) -> rusqlite::Result<bool> {
+        let changed: usize = self
+            .connection
+            .execute("DELETE FROM task WHERE id = ?1", params![id])?;
+        Ok(changed > 0)
+    }
+}
+
+struct AppState {
+    db: Mutex<Database>,
+}
+
+async fn create_task(app_state: web::Data<AppState>, task: web::Json<NewTask>) -> impl Responder {
+    let db = app_state.db.lock().unwrap();
+    match db.insert(&task) {
+        Ok(task) => HttpResponse::Created().json(task),
+        Err(_) => HttpResponse::InternalServerError().finish(),
+    }
+}
+
+async fn read_task(app_state: web::Data<AppState>, id: web::Path<u64>) -> impl Responder {
+    let db = app_state.db.lock().unwrap();
+    match db.get(id.into_inner()) {
+        Ok(Some(task)) => HttpResponse::Ok().json(task),
+        Ok(None) => HttpResponse::NotFound().finish(),
+        Err(_) => HttpResponse::InternalServerError().finish(),
+    }
+}
+
+async fn update_task(app_state: web::Data<AppState>, task: web::Json<Task>) -> impl Responder {
+    let db = app_state.db.lock().unwrap();
+    match db.update(&task) {
+        Ok(true) => HttpResponse::Ok().json(task.into_inner()),
+        Ok(false) => HttpResponse::NotFound().finish(),
+        Err(_) => HttpResponse::InternalServerError().finish(),
+    }
+}
+
+async fn read_all_tasks(app_state: web::Data<AppState>) -> impl Responder {
+    let db = app_state.db.lock().unwrap();
+    match db.get_all() {
+        Ok(tasks) => HttpResponse::Ok().json(tasks),
+        Err(_) => HttpResponse::InternalServerError().finish(),
+    }
+}
+
+async fn delete_task(app_state: web::Data<AppState>, id: web::Path<u64>) -> impl Responder {
+    let db = app_state.db.lock().unwrap();
+    match db.remove(id.into_inner()) {
+        Ok(true) => HttpResponse::Ok().finish(),
+        Ok(false) => HttpResponse::NotFound().finish(),
+        Err(_) => HttpResponse::InternalServerError().finish(),
+    }
+}
+
+async fn openapi_document() -> actix_web::Result<NamedFile> {
+    let openapi_path: String = std::env::var("WEB_SERVER_OPENAPI_PATH").unwrap_or_else(|_| "openapi.json".to_string());
+    Ok(NamedFile::open_async(openapi_path).await?)
+}
+
+fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("/task", web::post().to(create_task))
+        .route("/task", web::get().to(read_all_tasks))
+        .route("/task", web::put().to(update_task))
+        .route("/task/{id}", web::get().to(read_task))
+        .route("/task/{id}", web::delete().to(delete_task));
+}
+
+#[actix_web::main]
+async fn main() -> std::io::Result<()> {
+    let port: u16 = std::env::var("WEB_SERVER_PORT")
+        .ok()
+        .and_then(|port| port.parse().ok())
+        .unwrap_or(8080);
+    let host: String = std::env::var("WEB_SERVER_HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
+    let static_dir: String = std::env::var("WEB_SERVER_STATIC_DIR").unwrap_or_else(|_| "static".to_string());
+    let database_path: String = std::env::var("WEB_SERVER_DATABASE_PATH").unwrap_or_else(|_| "database.sqlite".to_string());
+    let db: Database = Database::open(&database_path).expect("Failed to open the SQLite database");
+    let data: web::Data<AppState> = web::Data::new(AppState {
+        db: Mutex::new(db)
+    });
+
+    HttpServer::new(move || {
+        App::new()
+            .wrap(
+                Cors::permissive()
+                    .allowed_origin_fn(|origin, _req_head| {
+                        origin.as_bytes().starts_with(b"http://localhost") || origin == "null"
+                    })
+                    .allowed_methods(vec!["GET", "POST", "PUT", "DELETE"])
+                    .allowed_headers(vec![header::AUTHORIZATION, header::ACCEPT])
+                    .allowed_header(header::CONTENT_TYPE)
+                    .supports_credentials()
+                    .max_age(3600)
+            )
+            .app_data(data.clone())
+            .configure(configure_routes)
+            .route("/openapi.json", web::get().to(openapi_document))
+            .service(Files::new("/static", static_dir.clone()).index_file("index.html"))
+    })
+        .bind((host.as_str(), port))?
+        .run()
+        .await
+}
@@ -0,0 +1,115 @@
+// The server is a binary crate, so its code is included to reach the handlers and `configure_routes`
+#![allow(dead_code)]
+include!("../src/main.rs");
+
+use actix_web::http::StatusCode;
+use actix_web::test as actix_test;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static NEXT_DATABASE: AtomicUsize = AtomicUsize::new(0);
+
+// A database file of its own for every test, deleted with its WAL files when the test ends
+struct TempDatabase {
+    path: PathBuf,
+}
+
+impl TempDatabase {
+    fn new() -> Self {
+        let file_name: String = format!(
+            "web_server_api_tests_{}_{}.sqlite",
+            std::process::id(),
+            NEXT_DATABASE.fetch_add(1, Ordering::SeqCst)
+        );
+        Self {
+            path: std::env::temp_dir().join(file_name),
+        }
+    }
+
+    fn app_state(&self) -> web::Data<AppState> {
+        let db: Database = Database::open(self.path.to_str().unwrap()).expect("Failed to open test database");
+        web::Data::new(AppState {
+            db: Mutex::new(db),
+        })
+    }
+}
+
+impl Drop for TempDatabase {
+    fn drop(&mut self) {
+        for suffix in ["", "-wal", "-shm"] {
+            let mut path = self.path.clone().into_os_string();
+            path.push(suffix);
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+#[actix_web::test]
+async fn test_task_crud() {
+    let database = TempDatabase::new();
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(database.app_state())
+            .configure(configure_routes),
+    )
+    .await;
+
+    let req = actix_test::TestRequest::post()
+        .uri("/task")
+        .set_json(serde_json::json!({"name": "write tests", "completed": false}))
+        .to_request();
+    let created: serde_json::Value = actix_test::call_and_read_body_json(&app, req).await;
+    let id = created["id"].as_u64().expect("The database assigns the id");
+    assert_eq!(created["name"], "write tests");
+
+    let req = actix_test::TestRequest::get().uri(&format!("/task/{}", id)).to_request();
+    let body: serde_json::Value = actix_test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body, created);
+
+    let updated = serde_json::json!({"id": id, "name": "write tests", "completed": true});
+    let req = actix_test::TestRequest::put().uri("/task").set_json(&updated).to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+
+    let req = actix_test::TestRequest::get().uri("/task").to_request();
+    let body: serde_json::Value = actix_test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body, serde_json::json!([updated]));
+
+    let req = actix_test::TestRequest::delete().uri(&format!("/task/{}", id)).to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+
+    let req = actix_test::TestRequest::get().uri(&format!("/task/{}", id)).to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn test_data_survives_reopening_the_database() {
+    let database = TempDatabase::new();
+    {
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(database.app_state())
+                .configure(configure_routes),
+        )
+        .await;
+        let req = actix_test::TestRequest::post()
+            .uri("/task")
+            .set_json(serde_json::json!({"name": "persisted", "completed": false}))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CREATED);
+    }
+
+    // Opening the file again must not apply the migrations twice
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(database.app_state())
+            .configure(configure_routes),
+    )
+    .await;
+    let req = actix_test::TestRequest::get().uri("/task").to_request();
+    let body: serde_json::Value = actix_test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body.as_array().map(Vec::len), Some(1));
+}
@@ -0,0 +1,163 @@
+use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer, Responder};
+use serde::{Deserialize, Serialize};
+use actix_web::http::header;
+use actix_cors::Cors;
+use actix_files::{Files, NamedFile};
+use actix_ws::Message;
+use std::collections::HashMap;
+use ::std::sync::Mutex;
+use tokio::sync::broadcast;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct Task {
+    id: u64,
+    name: String,
+    completed: bool,
+}
+
+// What the websocket clients receive on every change
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "event", content = "data", rename_all = "snake_case")]
+enum TaskEvent {
+    Created(Task),
+    Updated(Task),
+    Deleted { id: u64 },
+}
+
+struct AppState {
+    tasks: Mutex<HashMap<u64, Task>>,
+    events: broadcast::Sender<TaskEvent>,
+}
+
+impl AppState {
+    // Sending only fails when no client is connected, which is fine
+    fn publish(&self, event: TaskEvent) {
+        let _ = self.events.send(event);
+    }
+}
+
+async fn create_task(app_state: web::Data<AppState>, task: web::Json<Task>) -> impl Responder {
+    let task: Task = task.into_inner();
+    app_state.tasks.lock().unwrap().insert(task.id, task.clone());
+    app_state.publish(TaskEvent::Created(task.clone()));
+    HttpResponse::Created().json(task)
+}
+
+async fn read_all_tasks(app_state: web::Data<AppState>) -> impl Responder {
+    let tasks: Vec<Task> = app_state.tasks.lock().unwrap().values().cloned().collect();
+    HttpResponse::Ok().json(tasks)
+}
+
+async fn update_task(app_state: web::Data<AppState>, task: web::Json<Task>) -> impl Responder {
+    let task: Task = task.into_inner();
+    {
+        let mut tasks = app_state.tasks.lock().unwrap();
+        if !tasks.contains_key(&task.id) {
+            return HttpResponse::NotFound().finish();
+        }
+        tasks.insert(task.id, task.clone());
+    }
+    app_state.publish(TaskEvent::Updated(task.clone()));
+    HttpResponse::Ok().json(task)
+}
+
+async fn delete_task(app_state: web::Data<AppState>, id: web::Path<u64>) -> impl Responder {
+    let id: u64 = id.into_inner();
+    match app_state.tasks.lock().unwrap().remove(&id) {
+        Some(_) => {
+            app_state.publish(TaskEvent::Deleted { id });
+            HttpResponse::Ok().finish()
+        }
+        None => HttpResponse::NotFound().finish(),
+    }
+}
+
+// Upgrades the request to a websocket that receives every task event as JSON
+async fn task_events(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+    body: web::Payload,
+) -> actix_web::Result<HttpResponse> {
+    let (response, mut session, mut messages) = actix_ws::handle(&req, body)?;
+    let mut events: broadcast::Receiver<TaskEvent> = app_state.events.subscribe();
+
+    actix_web::rt::spawn(async move {
+        loop {
+            tokio::select! {
+                event = events.recv() => match event {
+                    Ok(event) => {
+                        let text: String = serde_json::to_string(&event).unwrap_or_default();
+                        if session.text(text).await.is_err() {
+                            break;
+                        }
+                    }
+                    // A slow client missed events, it keeps receiving the newer ones
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                message = messages.recv() => match message {
+                    Some(Ok(Message::Ping(bytes))) => {
+                        if session.pong(&bytes).await.is_err() {
+                            break;
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => {}
+                },
+            }
+        }
+        let _ = session.close(None).await;
+    });
+
+    Ok(response)
+}
+
+async fn openapi_document() -> actix_web::Result<NamedFile> {
+    let openapi_path: String = std::env::var("WEB_SERVER_OPENAPI_PATH").unwrap_or_else(|_| "openapi.json".to_string());
+    Ok(NamedFile::open_async(openapi_path).await?)
+}
+
+fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("/task", web::post().to(create_task))
+        .route("/task", web::get().to(read_all_tasks))
+        .route("/task", web::put().to(update_task))
+        .route("/task/{id}", web::delete().to(delete_task))
+        .route("/ws", web::get().to(task_events));
+}
+
+#[actix_web::main]
+async fn main() -> std::io::Result<()> {
+    let port: u16 = std::env::var("WEB_SERVER_PORT")
+        .ok()
+        .and_then(|port| port.parse().ok())
+        .unwrap_or(8080);
+    let host: String = std::env::var("WEB_SERVER_HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
+    let static_dir: String = std::env::var("WEB_SERVER_STATIC_DIR").unwrap_or_else(|_| "static".to_string());
+    let (events, _) = broadcast::channel::<TaskEvent>(64);
+    let data: web::Data<AppState> = web::Data::new(AppState {
+        tasks: Mutex::new(HashMap::new()),
+        events,
+    });
+
+    HttpServer::new(move || {
+        App::new()
+            .wrap(
+                Cors::permissive()
+                    .allowed_origin_fn(|origin, _req_head| {
+                        origin.as_bytes().starts_with(b"http://localhost") || origin == "null"
+                    })
+                    .allowed_methods(vec!["GET", "POST", "PUT", "DELETE"])
+                    .allowed_headers(vec![header::AUTHORIZATION, header::ACCEPT])
+                    .allowed_header(header::CONTENT_TYPE)
+                    .supports_credentials()
+                    .max_age(3600)
+            )
+            .app_data(data.clone())
+            .configure(configure_routes)
+            .route("/openapi.json", web::get().to(openapi_document))
+            .service(Files::new("/static", static_dir.clone()).index_file("index.html"))
+    })
+        .bind((host.as_str(), port))?
+        .run()
+        .await
+}
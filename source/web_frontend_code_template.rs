@@ -0,0 +1,141 @@
+use gloo_net::http::Request;
+use serde::{Deserialize, Serialize};
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+const API_BASE_URL: &str = match option_env!("API_BASE_URL") {
+    Some(url) => url,
+    None => "http://127.0.0.1:8080",
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct Task {
+    id: u64,
+    name: String,
+    completed: bool,
+}
+
+async fn fetch_tasks() -> Result<Vec<Task>, gloo_net::Error> {
+    Request::get(&format!("{}/task", API_BASE_URL))
+        .send()
+        .await?
+        .json::<Vec<Task>>()
+        .await
+}
+
+async fn create_task(task: &Task) -> Result<(), gloo_net::Error> {
+    Request::post(&format!("{}/task", API_BASE_URL))
+        .json(task)?
+        .send()
+        .await?;
+    Ok(())
+}
+
+async fn delete_task(id: u64) -> Result<(), gloo_net::Error> {
+    Request::delete(&format!("{}/task/{}", API_BASE_URL, id))
+        .send()
+        .await?;
+    Ok(())
+}
+
+#[function_component(App)]
+fn app() -> Html {
+    let tasks: UseStateHandle<Vec<Task>> = use_state(Vec::new);
+    let error: UseStateHandle<Option<String>> = use_state(|| None);
+    let name_input: NodeRef = use_node_ref();
+
+    let reload: Callback<()> = {
+        let tasks = tasks.clone();
+        let error = error.clone();
+        Callback::from(move |_| {
+            let tasks = tasks.clone();
+            let error = error.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match fetch_tasks().await {
+                    Ok(fetched) => tasks.set(fetched),
+                    Err(e) => error.set(Some(e.to_string())),
+                }
+            });
+        })
+    };
+
+    {
+        let reload = reload.clone();
+        use_effect_with((), move |_| reload.emit(()));
+    }
+
+    let on_create: Callback<SubmitEvent> = {
+        let reload = reload.clone();
+        let error = error.clone();
+        let name_input = name_input.clone();
+        Callback::from(move |event: SubmitEvent| {
+            event.prevent_default();
+            let input: HtmlInputElement = match name_input.cast::<HtmlInputElement>() {
+                Some(input) => input,
+                None => return,
+            };
+            let task: Task = Task {
+                id: now_millis(),
+                name: input.value(),
+                completed: false,
+            };
+            input.set_value("");
+            let reload = reload.clone();
+            let error = error.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match create_task(&task).await {
+                    Ok(()) => reload.emit(()),
+                    Err(e) => error.set(Some(e.to_string())),
+                }
+            });
+        })
+    };
+
+    let on_delete = |id: u64| -> Callback<MouseEvent> {
+        let reload = reload.clone();
+        let error = error.clone();
+        Callback::from(move |_| {
+            let reload = reload.clone();
+            let error = error.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match delete_task(id).await {
+                    Ok(()) => reload.emit(()),
+                    Err(e) => error.set(Some(e.to_string())),
+                }
+            });
+        })
+    };
+
+    html! {
+        <main>
+            <h1>{ "Tasks" }</h1>
+            if let Some(message) = (*error).clone() {
+                <p class="error">{ message }</p>
+            }
+            <form onsubmit={on_create}>
+                <input ref={name_input} placeholder="Task name" required=true />
+                <button type="submit">{ "Add" }</button>
+            </form>
+            <ul>
+                { for tasks.iter().map(|task| html! {
+                    <li key={task.id}>
+                        { format!("{} {}", if task.completed { "[x]" } else { "[ ]" }, task.name) }
+                        <button onclick={on_delete(task.id)}>{ "Delete" }</button>
+                    </li>
+                }) }
+            </ul>
+        </main>
+    }
+}
+
+/// A unique enough id for new records, taken from the current time in milliseconds.
+fn now_millis() -> u64 {
+    web_sys::window()
+        .and_then(|window| window.performance())
+        .map(|performance| (performance.time_origin() + performance.now()) as u64)
+        .unwrap_or_default()
+}
+
+fn main() {
+    yew::Renderer::<App>::new().render();
+}
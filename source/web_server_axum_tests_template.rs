@@ -0,0 +1,73 @@
+// The server is a binary crate, so its code is included to reach the handlers and `configure_routes`
+#![allow(dead_code)]
+include!("../src/main.rs");
+
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+fn test_app() -> Router {
+    // Handlers persist to database.json in the working directory, keep it out of the project.
+    // The working directory is shared by the whole process, so the tests run with `--test-threads=1`
+    let data_dir = std::env::temp_dir().join("web_server_api_tests");
+    std::fs::create_dir_all(&data_dir).unwrap();
+    std::env::set_current_dir(&data_dir).unwrap();
+    configure_routes().with_state(Arc::new(AppState {
+        db: Mutex::new(Database::new()),
+    }))
+}
+
+// Sends one request to the router in-process and returns the status and the JSON body, if any
+async fn send(
+    app: &Router,
+    method: axum::http::Method,
+    uri: &str,
+    body: Option<serde_json::Value>,
+) -> (axum::http::StatusCode, Option<serde_json::Value>) {
+    let request = axum::http::Request::builder().method(method).uri(uri);
+    let request = match body {
+        Some(body) => request
+            .header(axum::http::header::CONTENT_TYPE, "application/json")
+            .body(axum::body::Body::from(body.to_string()))
+            .unwrap(),
+        None => request.body(axum::body::Body::empty()).unwrap(),
+    };
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&bytes).ok())
+}
+
+#[tokio::test]
+async fn test_task_crud() {
+    let app = test_app();
+
+    let task = serde_json::json!({"id": 1, "name": "write tests", "completed": false});
+    let (status, _) = send(&app, axum::http::Method::POST, "/task", Some(task.clone())).await;
+    assert_eq!(status, axum::http::StatusCode::CREATED);
+
+    let (status, body) = send(&app, axum::http::Method::GET, "/task/1", None).await;
+    assert_eq!(status, axum::http::StatusCode::OK);
+    assert_eq!(body, Some(task));
+
+    let updated = serde_json::json!({"id": 1, "name": "write tests", "completed": true});
+    let (status, _) = send(&app, axum::http::Method::PUT, "/task", Some(updated.clone())).await;
+    assert!(status.is_success());
+
+    let (_, body) = send(&app, axum::http::Method::GET, "/task", None).await;
+    assert_eq!(body, Some(serde_json::json!([updated])));
+
+    let (status, _) = send(&app, axum::http::Method::DELETE, "/task/1", None).await;
+    assert!(status.is_success());
+
+    let (status, _) = send(&app, axum::http::Method::GET, "/task/1", None).await;
+    assert_eq!(status, axum::http::StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_invalid_body_is_rejected() {
+    let app = test_app();
+
+    let invalid = serde_json::json!({"id": "not a number"});
+    let (status, _) = send(&app, axum::http::Method::POST, "/task", Some(invalid)).await;
+    assert!(status.is_client_error());
+}
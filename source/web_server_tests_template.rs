@@ -0,0 +1,77 @@
+// The server is a binary crate, so its code is included to reach the handlers and `configure_routes`
+#![allow(dead_code)]
+include!("../src/main.rs");
+
+use actix_web::http::StatusCode;
+use actix_web::test as actix_test;
+
+fn test_app_state() -> web::Data<AppState> {
+    // Handlers persist to database.json in the working directory, keep it out of the project.
+    // The working directory is shared by the whole process, so the tests run with `--test-threads=1`
+    let data_dir = std::env::temp_dir().join("web_server_api_tests");
+    std::fs::create_dir_all(&data_dir).unwrap();
+    std::env::set_current_dir(&data_dir).unwrap();
+    web::Data::new(AppState {
+        db: Mutex::new(Database::new()),
+    })
+}
+
+#[actix_web::test]
+async fn test_task_crud() {
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(test_app_state())
+            .configure(configure_routes),
+    )
+    .await;
+
+    let task = serde_json::json!({"id": 1, "name": "write tests", "completed": false});
+    let req = actix_test::TestRequest::post().uri("/task").set_json(&task).to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let req = actix_test::TestRequest::get().uri("/task/1").to_request();
+    let body: serde_json::Value = actix_test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body, task);
+
+    let updated = serde_json::json!({"id": 1, "name": "write tests", "completed": true});
+    let req = actix_test::TestRequest::put().uri("/task").set_json(&updated).to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+
+    let req = actix_test::TestRequest::get().uri("/task").to_request();
+    let body: serde_json::Value = actix_test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body, serde_json::json!([updated]));
+
+    let req = actix_test::TestRequest::delete().uri("/task/1").to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+
+    let req = actix_test::TestRequest::get().uri("/task/1").to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn test_register_and_login() {
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(test_app_state())
+            .configure(configure_routes),
+    )
+    .await;
+
+    let user = serde_json::json!({"id": 1, "user_name": "alice", "user_password": "secret"});
+    let req = actix_test::TestRequest::post().uri("/register").set_json(&user).to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let req = actix_test::TestRequest::post().uri("/login").set_json(&user).to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let wrong = serde_json::json!({"id": 1, "user_name": "alice", "user_password": "wrong"});
+    let req = actix_test::TestRequest::post().uri("/login").set_json(&wrong).to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert!(!resp.status().is_success());
+}
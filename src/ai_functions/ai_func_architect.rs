@@ -5,27 +5,66 @@ pub fn print_project_scope(_project_description: &str) {
     /// Input: Takes in a user request to build a website project description
     /// Function: Converts user request into JSON response of information items required for a website build.
     /// Important: At least one of the bool results must be true
+    /// Important: Chooses the simplest option that covers the request. Uses "sqlite" only when data must survive concurrent writes or grow large,
+    ///   "in_memory" only when nothing needs to be kept across restarts, and "json_file" otherwise
+    /// Important: auth_style is "none" exactly when is_user_login_and_logout is false, unless clients of the API share an "api_key"
     /// Output: Prints an object response in the following format:
     ///   {
     ///     "is_crud_required": bool, // true if site needs CRUD functionality
-    ///     "is_user_login_and_logout": bool // true if site needs users to be able to log in and log out
-    ///     "is_external_urls_required": bool // true if site needs to fetch data from third part providers
+    ///     "is_user_login_and_logout": bool, // true if site needs users to be able to log in and log out
+    ///     "is_external_urls_required": bool, // true if site needs to fetch data from third part providers
+    ///     "storage_backend": "json_file" | "sqlite" | "in_memory", // where the data is kept
+    ///     "auth_style": "none" | "session" | "jwt" | "api_key", // how users or clients authenticate
+    ///     "is_pagination_required": bool, // true if lists can grow long enough to be returned page by page
+    ///     "is_filtering_required": bool, // true if users search or filter lists by field values
+    ///     "realtime": "none" | "websocket" | "sse", // how updates are pushed to clients, "sse" when they only flow from the server
+    ///     "is_background_jobs_required": bool, // true if the site runs scheduled or periodic work
+    ///     "is_file_upload_required": bool // true if users upload files such as images or documents
     ///   }
     /// Example 1:
     ///   user_request = "I need a full stack website that accepts users and gets stock price data"
     ///   prints:
     ///   {
-    ///     "is_crud_required": true
-    ///     "is_user_login_and_logout": true
-    ///     "is_external_urls_required": bool true
+    ///     "is_crud_required": true,
+    ///     "is_user_login_and_logout": true,
+    ///     "is_external_urls_required": true,
+    ///     "storage_backend": "json_file",
+    ///     "auth_style": "session",
+    ///     "is_pagination_required": false,
+    ///     "is_filtering_required": false,
+    ///     "realtime": "none",
+    ///     "is_background_jobs_required": false,
+    ///     "is_file_upload_required": false
     ///   }
     /// Example 2:
     ///   user_request = "I need a simple TODO app"
     ///   prints:
     ///   {
-    ///     "is_crud_required": true
-    ///     "is_user_login_and_logout": false
-    ///     "is_external_urls_required": bool false
+    ///     "is_crud_required": true,
+    ///     "is_user_login_and_logout": false,
+    ///     "is_external_urls_required": false,
+    ///     "storage_backend": "json_file",
+    ///     "auth_style": "none",
+    ///     "is_pagination_required": false,
+    ///     "is_filtering_required": false,
+    ///     "realtime": "none",
+    ///     "is_background_jobs_required": false,
+    ///     "is_file_upload_required": false
+    ///   }
+    /// Example 3:
+    ///   user_request = "A shared team chat where members post messages with attachments, search old messages and see new ones instantly"
+    ///   prints:
+    ///   {
+    ///     "is_crud_required": true,
+    ///     "is_user_login_and_logout": true,
+    ///     "is_external_urls_required": false,
+    ///     "storage_backend": "sqlite",
+    ///     "auth_style": "jwt",
+    ///     "is_pagination_required": true,
+    ///     "is_filtering_required": true,
+    ///     "realtime": "websocket",
+    ///     "is_background_jobs_required": false,
+    ///     "is_file_upload_required": true
     ///   }
     println!(OUTPUT)
 }
@@ -2,34 +2,74 @@ use ai_functions::ai_function;
 
 #[ai_function]
 pub fn print_backend_webserver_code(_project_description_and_template: &str) {
-    /// INPUT: Takes in a PROJECT_DESCRIPTION and CODE_TEMPLATE for a website backend build
+    /// INPUT: Takes in a PROJECT_DESCRIPTION, CODE_TEMPLATE, SCOPE_REQUIREMENTS and FRAMEWORK_CONVENTIONS for a website backend build
     /// IMPORTANT: The backend code is ONLY an example. If the Project Description requires it, make as many changes as you like.
     /// IMPORTANT: You do not need to follow the backend code exactly. Write functions that make sense for the users request if required.
     /// FUNCTION: Takes an existing set of code marked as CODE_TEMPLATE and updates or re-writes it to work for the purpose in the PROJECT_DESCRIPTION
-    /// IMPORTANT: The following libraries are already installed
-    ///   reqwest, serde, serde_json, tokio, actix-web, async-trait, actix_cors
-    /// No other external libraries should be used. Write functions that fit with the description from the PROJECT_DESCRIPTION
+    /// IMPORTANT: The CODE_TEMPLATE may hold several example servers, each starting with a "TEMPLATE <name>: <description>" comment line.
+    ///   Combine what each of them demonstrates into ONE server with a single app and a single place registering the routes
+    /// IMPORTANT: The libraries FRAMEWORK_CONVENTIONS name are already installed, together with the ones the CODE_TEMPLATE uses
+    /// No other external libraries should be used, except the ones SCOPE_REQUIREMENTS name. Write functions that fit with the description from the PROJECT_DESCRIPTION
+    /// IMPORTANT: SCOPE_REQUIREMENTS are the architect's decisions on storage, authentication and API features. Follow every one of them,
+    ///   even where the CODE_TEMPLATE does something else
+    /// IMPORTANT: The server must bind to the host read from WEB_SERVER_HOST (default 127.0.0.1) on the port read from WEB_SERVER_PORT, exactly as the CODE_TEMPLATE does
+    /// IMPORTANT: Keep the "/static" files service reading its directory from WEB_SERVER_STATIC_DIR, exactly as the CODE_TEMPLATE does
+    /// IMPORTANT: Keep the "/openapi.json" route serving the file read from WEB_SERVER_OPENAPI_PATH, exactly as the CODE_TEMPLATE does
+    /// IMPORTANT: Follow every one of the FRAMEWORK_CONVENTIONS and keep writing the web framework the CODE_TEMPLATE uses.
+    ///   Register every route where they describe, which the integration tests use
+    /// IMPORTANT: If MIGRATIONS are given, they already create the database tables. List every file from the "migrations" directory
+    ///   in the MIGRATIONS list and apply them when the database is opened exactly as the CODE_TEMPLATE does, and use their table and column names in every query.
+    ///   If MIGRATIONS is an empty list, put the CREATE TABLE statements into the MIGRATIONS list as string literals instead
+    /// IMPORTANT: If a DATA_MODEL is given, write one struct or model class per entity named after it, with exactly its field names and matching types,
+    ///   serve each entity under its "resource" route segment, reject payloads breaking a validation rule with 400 and duplicate unique keys with 409
     /// OUTPUT: Print ONLY the code, nothing else. This function ONLY prints code.
     println!(OUTPUT)
 }
 
 #[ai_function]
 pub fn print_improved_webserver_code(_project_description_and_template: &str) {
-    /// INPUT: Takes in a PROJECT_DESCRIPTION and CODE_TEMPLATE for a website backend build
+    /// INPUT: Takes in a PROJECT_DESCRIPTION, CODE_TEMPLATE, SCOPE_REQUIREMENTS and FRAMEWORK_CONVENTIONS for a website backend build
     /// FUNCTION: Performs the following tasks:
     ///   1. Removes any bugs in the code and adds minor additional functionality
     ///   2. Makes sure everything requested in the spec from a backend standpoint was followed. If not, add the feature. No code should be implemented later. Everything should be written now.
     ///   3. ONLY writes the code. No commentary.
-    /// IMPORTANT: The following libraries are already installed. Does not use ANY libraries other than what was provided in the template
-    ///   reqwest, serde, serde_json, tokio, actix-web, async-trait
+    /// IMPORTANT: The libraries FRAMEWORK_CONVENTIONS name are already installed. Does not use ANY libraries other than those,
+    ///   the ones the template uses and the ones SCOPE_REQUIREMENTS name
+    /// IMPORTANT: Makes sure every item of SCOPE_REQUIREMENTS is implemented, they override the free text of the PROJECT_DESCRIPTION
+    /// IMPORTANT: Keeps binding to the host read from WEB_SERVER_HOST (default 127.0.0.1) on the port read from WEB_SERVER_PORT
+    /// IMPORTANT: Keeps the "/static" files service reading its directory from WEB_SERVER_STATIC_DIR
+    /// IMPORTANT: Keeps the "/openapi.json" route serving the file read from WEB_SERVER_OPENAPI_PATH
+    /// IMPORTANT: Keeps every route registered where the FRAMEWORK_CONVENTIONS describe, which the integration tests use, and follows every one of them
+    /// IMPORTANT: If the code has a MIGRATIONS list, keeps it exactly as it is and never creates tables outside of it
+    /// IMPORTANT: If the PROJECT_DESCRIPTION has a domain_model, keeps every struct or model class, field name and route segment exactly as the domain_model defines them
     /// OUTPUT: Print ONLY the code, nothing else. This function ONLY prints code.
     println!(OUTPUT)
 }
 
+#[ai_function]
+pub fn print_revised_webserver_code(_existing_code_and_change_request: &str) {
+    /// INPUT: Takes in the EXISTING_CODE of a generated website backend, a CHANGE_REQUEST, SCOPE_REQUIREMENTS and FRAMEWORK_CONVENTIONS,
+    ///   optionally with the revised DATA_MODEL and the MIGRATIONS
+    /// FUNCTION: Edits the EXISTING_CODE so it does what the CHANGE_REQUEST asks
+    /// IMPORTANT: Makes the smallest edit that implements the CHANGE_REQUEST. Every route, struct and behaviour the CHANGE_REQUEST
+    ///   does not mention stays exactly as it is, existing clients keep working
+    /// IMPORTANT: Keeps the web framework, the bind address, the "/static" files service and the "/openapi.json" route of the EXISTING_CODE
+    /// IMPORTANT: Keeps every route registered where the FRAMEWORK_CONVENTIONS describe and follows every one of them
+    /// IMPORTANT: If MIGRATIONS are given, lists every one of them in the MIGRATIONS list and uses their table and column names in every query
+    /// IMPORTANT: If a DATA_MODEL is given, keeps every struct or model class, field name and route segment exactly as it defines them
+    /// OUTPUT: Print ONLY the complete revised code, nothing else. This function ONLY prints code.
+    println!(OUTPUT)
+}
+
 #[ai_function]
 pub fn print_fixed_code(_broken_code_with_bugs: &str) {
-    /// INPUT: Takes in Rust BROKEN_CODE and the ERROR_BUGS found
+    /// INPUT: Takes in BROKEN_CODE, the ERROR_BUGS found and the FRAMEWORK_CONVENTIONS the code follows
+    /// ERROR_BUGS: Either compiler or linter errors or runtime test failures. Runtime failures list the route, method, request sent,
+    ///   response received, status code and the tail of the server log for every endpoint that misbehaved
     /// FUNCTION: Removes bugs from code
+    /// IMPORTANT: Integration test failures come from tests/api.rs, which includes Rust code and builds the app with `configure_routes`.
+    ///   Fixes the code, never renames `configure_routes` or the state types the tests construct
+    /// IMPORTANT: Keeps the web framework of BROKEN_CODE and follows the FRAMEWORK_CONVENTIONS, e.g. their path parameter syntax
     /// IMPORTANT: Only prints out the new and improved code. No commentary or anything else
     /// OUTPUT: Print ONLY the code, nothing else. This function ONLY prints code.
     println!(OUTPUT)
@@ -37,7 +77,10 @@ pub fn print_fixed_code(_broken_code_with_bugs: &str) {
 
 #[ai_function]
 pub fn print_rest_api_endpoints(_code_input: &str) {
-    /// INPUT: Takes in Rust webserver CODE_INPUT based on actix-web
+    /// INPUT: Takes in webserver CODE_INPUT based on actix-web, axum or FastAPI. axum registers routes as
+    ///   `.route("/item/{id}", get(read_item).put(update_item))`, one method per chained call, and `.nest("/api", ...)` prefixes every nested route.
+    ///   FastAPI registers routes with decorators like `@app.get("/item/{id}", response_model=Item)` on the handler, pydantic `BaseModel`
+    ///   parameters are request bodies and `include_router(router, prefix="/api")` prefixes every route of the router
     /// FUNCTION: Prints out the JSON schema for url endpoints and their respective types
     /// LOGIC: Script analyses all code and can categorize into the following object keys:
     ///   "route": This represents the url path of the endpoint
@@ -0,0 +1,67 @@
+use ai_functions::ai_function;
+
+#[ai_function]
+pub fn print_domain_model(_project_description_and_scope: &str) {
+    /// INPUT: Takes in a PROJECT_DESCRIPTION, optionally with REQUIREMENTS, and the PROJECT_SCOPE of a website build
+    /// FUNCTION: Acts as a data modeller and designs the entities the website backend stores and serves
+    /// IMPORTANT: Every entity has a primary key field, usually "id" of type "integer"
+    /// IMPORTANT: Field names are snake_case and are used verbatim in Rust structs, JSON bodies and routes
+    /// IMPORTANT: "resource" is the lowercase first route segment the entity is served under, e.g. "task" for "/task/{id}"
+    /// IMPORTANT: Field types are one of "string", "integer", "float", "bool", "datetime"
+    /// IMPORTANT: A "belongs_to" relation names the field holding the id of the target entity; that field is part of "fields"
+    /// IMPORTANT: Only model entities the website stores itself, not data fetched from external urls
+    /// OUTPUT: Prints a JSON object in the following format, nothing else:
+    ///   {
+    ///     "entities": [
+    ///       {
+    ///         "name": "string", // PascalCase struct name
+    ///         "resource": "string",
+    ///         "primary_key": "string",
+    ///         "fields": [
+    ///           {
+    ///             "name": "string",
+    ///             "type": "string",
+    ///             "required": bool,
+    ///             "validation": {"min_length": number, "max_length": number, "minimum": number, "maximum": number, "allowed_values": ["string"]} // every rule is optional
+    ///           }
+    ///         ],
+    ///         "unique_keys": [["string"]], // sets of fields whose combined values must be unique
+    ///         "relations": [{"field": "string", "target": "string", "kind": "belongs_to" or "has_many"}]
+    ///       }
+    ///     ]
+    ///   }
+    /// Example:
+    ///   PROJECT_DESCRIPTION = "build a todo app where tasks are grouped into projects"
+    ///   prints:
+    ///   {"entities": [
+    ///     {"name": "Project", "resource": "project", "primary_key": "id",
+    ///      "fields": [{"name": "id", "type": "integer", "required": true},
+    ///                 {"name": "name", "type": "string", "required": true, "validation": {"min_length": 1, "max_length": 100}}],
+    ///      "unique_keys": [["name"]], "relations": [{"field": "tasks", "target": "Task", "kind": "has_many"}]},
+    ///     {"name": "Task", "resource": "task", "primary_key": "id",
+    ///      "fields": [{"name": "id", "type": "integer", "required": true},
+    ///                 {"name": "project_id", "type": "integer", "required": true},
+    ///                 {"name": "title", "type": "string", "required": true, "validation": {"min_length": 1}},
+    ///                 {"name": "status", "type": "string", "required": true, "validation": {"allowed_values": ["open", "done"]}}],
+    ///      "unique_keys": [], "relations": [{"field": "project_id", "target": "Project", "kind": "belongs_to"}]}
+    ///   ]}
+    println!(OUTPUT)
+}
+
+#[ai_function]
+pub fn print_fixed_domain_model(_domain_model_and_issues: &str) {
+    /// INPUT: Takes in a DATA_MODEL in JSON and the ISSUES found in it
+    /// FUNCTION: Fixes every issue while keeping the rest of the DATA_MODEL unchanged
+    /// OUTPUT: Prints ONLY the fixed DATA_MODEL as JSON in the same format, nothing else
+    println!(OUTPUT)
+}
+
+#[ai_function]
+pub fn print_revised_domain_model(_domain_model_and_change_request: &str) {
+    /// INPUT: Takes in the DATA_MODEL of an already generated website in JSON and a CHANGE_REQUEST for it
+    /// FUNCTION: Applies the CHANGE_REQUEST to the DATA_MODEL while keeping every entity, field, resource and relation it does not touch unchanged
+    /// IMPORTANT: Never renames existing entities, fields or resources, the generated code and its database already use them
+    /// IMPORTANT: New fields follow the same rules as the existing ones: snake_case names and one of the types "string", "integer", "float", "bool", "datetime"
+    /// OUTPUT: Prints ONLY the revised DATA_MODEL as JSON in the same format, nothing else
+    println!(OUTPUT)
+}
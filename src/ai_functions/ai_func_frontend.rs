@@ -0,0 +1,24 @@
+use ai_functions::ai_function;
+
+#[ai_function]
+pub fn print_yew_frontend_code(_project_description_template_and_api_schema: &str) {
+    /// INPUT: Takes in a PROJECT_DESCRIPTION, a CODE_TEMPLATE for a Yew frontend and the API_ENDPOINT_SCHEMA of the backend
+    /// FUNCTION: Updates or re-writes the CODE_TEMPLATE into a Yew single page app for the purpose in the PROJECT_DESCRIPTION
+    /// IMPORTANT: The app calls the routes in the API_ENDPOINT_SCHEMA, using their methods, request bodies and responses exactly as described
+    /// IMPORTANT: Every route is prefixed with API_BASE_URL, defined exactly as in the CODE_TEMPLATE
+    /// IMPORTANT: Every resource in the API_ENDPOINT_SCHEMA gets a view to list, create, update and delete it where the routes allow it
+    /// IMPORTANT: The following libraries are already installed
+    ///   yew (csr), gloo-net, wasm-bindgen-futures, web-sys, serde, serde_json
+    /// No other external libraries should be used. The code must compile for the wasm32-unknown-unknown target
+    /// OUTPUT: Print ONLY the code, nothing else. This function ONLY prints code.
+    println!(OUTPUT)
+}
+
+#[ai_function]
+pub fn print_fixed_frontend_code(_broken_code_with_bugs: &str) {
+    /// INPUT: Takes in Rust BROKEN_CODE of a Yew frontend and the ERROR_BUGS found when compiling it for wasm32-unknown-unknown
+    /// FUNCTION: Removes bugs from code, keeping the same libraries and the same API calls
+    /// IMPORTANT: Only prints out the new and improved code. No commentary or anything else
+    /// OUTPUT: Print ONLY the code, nothing else. This function ONLY prints code.
+    println!(OUTPUT)
+}
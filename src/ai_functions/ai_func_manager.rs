@@ -13,3 +13,22 @@ pub fn convert_user_input_to_goal(_user_request: &str) {
     ///   OUTPUT = "build a website that fetches and stores crypto price data within a supabase setup including a frontend UI to fetch the data."
     println!(OUTPUT)
 }
+
+#[ai_function]
+pub fn print_change_plan(_change_request_and_project: &str) {
+    /// INPUT: Takes in a CHANGE_REQUEST for an already generated website together with its PROJECT_DESCRIPTION,
+    ///   its DATA_MODEL and its API_ENDPOINT_SCHEMA
+    /// FUNCTION: Decides which parts of the website the CHANGE_REQUEST touches
+    /// IMPORTANT: "is_data_model_changed" is true if an entity, field, validation rule, unique key or relation is added, removed or changed
+    /// IMPORTANT: "is_backend_changed" is true if a route, query parameter, request body, response or server behaviour changes
+    /// IMPORTANT: "is_frontend_changed" is true if the pages or components users see need to change
+    /// IMPORTANT: "summary" is one sentence describing the edit, written for the developers making it
+    /// OUTPUT: Prints a JSON object in the following format, nothing else:
+    ///   {"is_data_model_changed": bool, "is_backend_changed": bool, "is_frontend_changed": bool, "summary": "string"}
+    /// Example:
+    ///   CHANGE_REQUEST = "add a tags field to tasks and a /tasks?tag= filter"
+    ///   prints:
+    ///   {"is_data_model_changed": true, "is_backend_changed": true, "is_frontend_changed": false,
+    ///    "summary": "Add a tags field to Task and filter the task list by the tag query parameter"}
+    println!(OUTPUT)
+}
@@ -0,0 +1,38 @@
+use ai_functions::ai_function;
+
+#[ai_function]
+pub fn print_follow_up_questions(_project_description_and_interview: &str) {
+    /// INPUT: Takes in a PROJECT_DESCRIPTION of a website build and the INTERVIEW so far, as question and answer pairs
+    /// FUNCTION: Acts as a business analyst onboarding a client and lists the follow-up questions still needed to build the website
+    /// IMPORTANT: Questions cover only what the PROJECT_DESCRIPTION and INTERVIEW leave open, from these areas:
+    ///   1. Entities the website manages and the fields of each entity
+    ///   2. Authentication: whether users log in, user roles and what each role may do
+    ///   3. External data sources the website reads from
+    ///   4. Persistence: what data is stored and for how long
+    ///   5. Non-functional needs: expected load, response times, security and privacy
+    /// IMPORTANT: At most 5 short questions, each answerable in one sentence. Prints an empty list when nothing important is open
+    /// OUTPUT: Prints a JSON list of strings, nothing else
+    /// Example:
+    ///   PROJECT_DESCRIPTION = "build a website that tracks personal fitness workouts"
+    ///   prints:
+    ///   ["Which details should a workout store, e.g. type, duration, calories?", "Do users need accounts, and can they see each other's workouts?"]
+    println!(OUTPUT)
+}
+
+#[ai_function]
+pub fn print_requirements_document(_project_description_and_interview: &str) {
+    /// INPUT: Takes in a PROJECT_DESCRIPTION of a website build and the client INTERVIEW, as question and answer pairs
+    /// FUNCTION: Writes a structured requirements document from the PROJECT_DESCRIPTION and the answers
+    /// IMPORTANT: Only records what the client said or what directly follows from it. Anything undecided goes into "open_questions"
+    /// OUTPUT: Prints a JSON object in the following format, nothing else:
+    ///   {
+    ///     "summary": "string", // one or two sentences describing the website
+    ///     "entities": [{"name": "string", "description": "string", "fields": ["string"]}],
+    ///     "auth": "string", // who logs in and what they may do, or "none"
+    ///     "external_data_sources": ["string"], // third party data, empty if none
+    ///     "persistence": "string", // what is stored and for how long
+    ///     "non_functional": ["string"], // load, latency, security and privacy needs
+    ///     "open_questions": ["string"]
+    ///   }
+    println!(OUTPUT)
+}
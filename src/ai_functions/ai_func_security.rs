@@ -0,0 +1,27 @@
+use ai_functions::ai_function;
+
+#[ai_function]
+pub fn print_security_review(_code_scope_and_findings: &str) {
+    /// INPUT: Takes in Rust webserver BACKEND_CODE, the PROJECT_SCOPE, the CHECKLIST and the STATIC_FINDINGS an automated audit already reported
+    /// FUNCTION: Acts as a security reviewer and audits BACKEND_CODE against every item of the CHECKLIST:
+    ///   password_hashing: passwords are hashed with argon2 and never stored, compared or returned in plaintext
+    ///   mutating_route_authz: when users log in, every POST, PUT, PATCH and DELETE route other than register and login checks a session token
+    ///   input_size_limits: request bodies are size limited and string fields are checked for empty or oversized values
+    ///   cors_origins: CORS only allows the frontend origins, never every origin
+    ///   error_leakage: responses never contain error messages, debug output, file paths or stack details
+    /// IMPORTANT: Only reports problems NOT already in STATIC_FINDINGS. Only reports real problems in BACKEND_CODE, nothing hypothetical
+    /// OUTPUT: Prints a JSON list of findings in the following format, nothing else. Prints [] when there is nothing to add
+    ///   [{"check": "one of the CHECKLIST items", "line": number, "message": "string", "requested_fix": "string"}]
+    println!(OUTPUT)
+}
+
+#[ai_function]
+pub fn print_security_fixed_code(_code_with_findings: &str) {
+    /// INPUT: Takes in Rust webserver BACKEND_CODE, the security FINDINGS of a review, each with a requested fix, and the INSTALLED_LIBRARIES
+    /// FUNCTION: Applies every requested fix to BACKEND_CODE and keeps everything else working as before
+    /// IMPORTANT: The INSTALLED_LIBRARIES are installed, together with argon2 (0.5, for password hashing) and rand (0.8, for session tokens).
+    ///   No other external libraries should be used and the web framework of BACKEND_CODE is kept
+    /// IMPORTANT: Keeps every route, its request and response fields, the WEB_SERVER_HOST, WEB_SERVER_PORT, WEB_SERVER_STATIC_DIR and WEB_SERVER_OPENAPI_PATH handling and `configure_routes` unchanged
+    /// OUTPUT: Print ONLY the code, nothing else. This function ONLY prints code.
+    println!(OUTPUT)
+}
@@ -0,0 +1,27 @@
+use ai_functions::ai_function;
+
+#[ai_function]
+pub fn print_api_integration_tests(_template_code_and_endpoints: &str) {
+    /// INPUT: Takes in a TEST_TEMPLATE, the TEST_CONVENTIONS of the web framework, the webserver BACKEND_CODE, its API_ENDPOINT_SCHEMA and optionally a DATA_MODEL
+    /// FUNCTION: Writes the Rust integration tests file tests/api.rs for the webserver in BACKEND_CODE, following the TEST_TEMPLATE
+    /// IMPORTANT: The file starts exactly like the TEST_TEMPLATE: `#![allow(dead_code)]` then `include!("../src/main.rs");`
+    /// IMPORTANT: Builds the app in-process from `configure_routes` as the TEST_CONVENTIONS describe, exactly as the TEST_TEMPLATE does,
+    ///   with a fresh state per test. Never starts a real server and never binds a port
+    /// IMPORTANT: Follows every one of the TEST_CONVENTIONS and never imports a name BACKEND_CODE already imports
+    /// IMPORTANT: Covers every route in API_ENDPOINT_SCHEMA: a successful call, and for routes taking a body or path id, a
+    ///   not found or invalid request case where BACKEND_CODE handles it. Uses the field names and types from BACKEND_CODE
+    /// IMPORTANT: Tests routes that fetch data from external urls only for their status code being handled, never for the data returned
+    /// IMPORTANT: If the TEST_TEMPLATE gives every test its own temporary database file, keeps doing so and never opens the server's own database file
+    /// IMPORTANT: Only uses the libraries the TEST_CONVENTIONS name. No other libraries
+    /// OUTPUT: Print ONLY the code, nothing else. This function ONLY prints code.
+    println!(OUTPUT)
+}
+
+#[ai_function]
+pub fn print_fixed_api_integration_tests(_broken_tests_with_bugs: &str) {
+    /// INPUT: Takes in Rust BROKEN_TESTS for the webserver in BACKEND_CODE and the compiler ERROR_BUGS
+    /// FUNCTION: Removes the bugs from the tests. BACKEND_CODE is correct and must not be changed
+    /// IMPORTANT: Keeps `#![allow(dead_code)]` and `include!("../src/main.rs");` at the top of the file
+    /// OUTPUT: Print ONLY the code, nothing else. This function ONLY prints code.
+    println!(OUTPUT)
+}
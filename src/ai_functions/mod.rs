@@ -1,3 +1,8 @@
 pub mod ai_func_architect;
 pub mod ai_func_backend;
+pub mod ai_func_data_model;
+pub mod ai_func_frontend;
 pub mod ai_func_manager;
+pub mod ai_func_requirements;
+pub mod ai_func_security;
+pub mod ai_func_test_writer;
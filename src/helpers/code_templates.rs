@@ -0,0 +1,398 @@
+use crate::helpers::target_framework::TargetFramework;
+use crate::models::agents::agent_traits::{
+    AuthStyle, ProjectScope, RealtimeTransport, StorageBackend,
+};
+use serde::{Deserialize, Serialize};
+
+/// A feature a backend code template demonstrates.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateFeature {
+    Crud,
+    JsonFile,
+    Sqlite,
+    Auth,
+    ExternalApi,
+    Websocket,
+}
+
+/// A backend code template of the registry in `source/templates/registry.json`.
+///
+/// # Fields
+/// - `name`: The template name, e.g. `crud`.
+/// - `framework`: The web framework the template is written with, actix-web when the registry leaves it out.
+/// - `file`: The file of the template inside `source/templates`.
+/// - `description`: What the template demonstrates, sent to the model with the code.
+/// - `features`: The features the template demonstrates.
+/// - `dependencies`: `Cargo.toml` dependency lines, or `requirements.txt` lines for Python, the template
+///   needs beyond the manifest template, e.g. `argon2 = "0.5"` or `httpx==0.28.1`.
+/// - `tests_file`: Optional tests template inside `source/templates` showing how to test servers built
+///   from this template, used instead of the framework's default tests template.
+/// - `code`: The template code, read from `file`.
+/// - `tests_code`: The tests template code, read from `tests_file`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CodeTemplate {
+    pub name: String,
+    #[serde(default)]
+    pub framework: TargetFramework,
+    pub file: String,
+    pub description: String,
+    pub features: Vec<TemplateFeature>,
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+    #[serde(default)]
+    pub tests_file: Option<String>,
+    #[serde(skip)]
+    pub code: String,
+    #[serde(skip)]
+    pub tests_code: Option<String>,
+}
+
+/// Lists the template features the backend of a project needs.
+///
+/// Without a scope, or when it asks for nothing a template shows, plain CRUD is used. In-memory storage
+/// has no template of its own, the CRUD template is adapted by the scope requirements.
+///
+/// # Arguments
+/// - `scope`: The project scope recorded by the architect.
+pub fn required_features(scope: Option<&ProjectScope>) -> Vec<TemplateFeature> {
+    let Some(scope) = scope else {
+        return vec![TemplateFeature::Crud];
+    };
+
+    let mut features: Vec<TemplateFeature> = vec![];
+    if scope.is_crud_required {
+        features.push(TemplateFeature::Crud);
+    }
+    match scope.storage_backend {
+        StorageBackend::JsonFile => features.push(TemplateFeature::JsonFile),
+        StorageBackend::Sqlite => features.push(TemplateFeature::Sqlite),
+        StorageBackend::InMemory => {}
+    }
+    if scope.is_user_login_and_logout
+        || matches!(scope.auth_style, AuthStyle::Session | AuthStyle::Jwt)
+    {
+        features.push(TemplateFeature::Auth);
+    }
+    if scope.is_external_urls_required {
+        features.push(TemplateFeature::ExternalApi);
+    }
+    if scope.realtime != RealtimeTransport::None {
+        features.push(TemplateFeature::Websocket);
+    }
+
+    if features.is_empty() {
+        features.push(TemplateFeature::Crud);
+    }
+    features
+}
+
+/// Picks the templates that together demonstrate the required features.
+///
+/// Greedily takes the template covering most of the uncovered features, preferring the more focused
+/// template and then registry order on ties, until every feature a template shows is covered.
+///
+/// # Arguments
+/// - `templates`: Every template of the registry.
+/// - `required`: The features the backend needs, see [`required_features`].
+///
+/// # Returns
+/// The selected templates in the order they were picked, the first template of the registry if none matches.
+pub fn select_templates<'a>(
+    templates: &'a [CodeTemplate],
+    required: &[TemplateFeature],
+) -> Vec<&'a CodeTemplate> {
+    let mut uncovered: Vec<TemplateFeature> = required.to_vec();
+    let mut selected: Vec<&CodeTemplate> = vec![];
+
+    while !uncovered.is_empty() {
+        let best: Option<(usize, &CodeTemplate)> = templates
+            .iter()
+            .filter(|template| !selected.iter().any(|chosen| chosen.name == template.name))
+            .map(|template| {
+                let covered: usize = template
+                    .features
+                    .iter()
+                    .filter(|feature| uncovered.contains(feature))
+                    .count();
+                (covered, template)
+            })
+            .filter(|(covered, _)| *covered > 0)
+            .reduce(|best, candidate| {
+                let better: bool = candidate.0 > best.0
+                    || (candidate.0 == best.0
+                        && candidate.1.features.len() < best.1.features.len());
+                if better {
+                    candidate
+                } else {
+                    best
+                }
+            });
+
+        match best {
+            Some((_, template)) => {
+                uncovered.retain(|feature| !template.features.contains(feature));
+                selected.push(template);
+            }
+            None => break,
+        }
+    }
+
+    if selected.is_empty() {
+        selected.extend(templates.first());
+    }
+    selected
+}
+
+/// Joins the selected templates into the `CODE TEMPLATE` sent to the model.
+///
+/// Each template starts with a `// TEMPLATE <name>: <description>` line, `#` commented for Python, so
+/// the model can tell them apart.
+pub fn compose_code_template(templates: &[&CodeTemplate]) -> String {
+    templates
+        .iter()
+        .map(|template| {
+            format!(
+                "{} TEMPLATE {}: {}\n{}",
+                template.framework.language().line_comment(),
+                template.name,
+                template.description,
+                template.code.trim_end()
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n")
+}
+
+/// Adds the dependencies of the selected templates to a `Cargo.toml`, keeping entries it already has.
+///
+/// # Arguments
+/// - `manifest`: The contents of the `Cargo.toml`.
+/// - `templates`: The templates whose `dependencies` are added.
+///
+/// # Returns
+/// The updated manifest, or the parse error of the manifest or of a dependency line.
+pub fn add_template_dependencies(
+    manifest: &str,
+    templates: &[&CodeTemplate],
+) -> Result<String, toml_edit::TomlError> {
+    let mut manifest_doc: toml_edit::DocumentMut = manifest.parse()?;
+    for line in templates.iter().flat_map(|template| &template.dependencies) {
+        let dependency_doc: toml_edit::DocumentMut = line.parse()?;
+        for (name, item) in dependency_doc.iter() {
+            if manifest_doc["dependencies"].get(name).is_none() {
+                manifest_doc["dependencies"][name] = item.clone();
+            }
+        }
+    }
+    Ok(manifest_doc.to_string())
+}
+
+/// Checks whether a `Cargo.toml` declares a dependency, `false` if it cannot be parsed.
+///
+/// # Arguments
+/// - `manifest`: The contents of the `Cargo.toml`.
+/// - `crate_name`: The dependency as named in `Cargo.toml`, e.g. `axum`.
+pub fn manifest_depends_on(manifest: &str, crate_name: &str) -> bool {
+    manifest
+        .parse::<toml_edit::DocumentMut>()
+        .is_ok_and(|manifest_doc| manifest_doc["dependencies"].get(crate_name).is_some())
+}
+
+/// Adds the dependencies of the selected templates to a `requirements.txt`, keeping packages it already has.
+///
+/// # Arguments
+/// - `requirements`: The contents of the `requirements.txt`.
+/// - `templates`: The templates whose `dependencies` are added.
+///
+/// # Returns
+/// The updated requirements, one line per package.
+pub fn add_template_requirements(requirements: &str, templates: &[&CodeTemplate]) -> String {
+    let mut lines: Vec<String> = requirements
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.to_string())
+        .collect();
+    for line in templates.iter().flat_map(|template| &template.dependencies) {
+        if !requirements_depend_on(&lines.join("\n"), requirement_name(line).as_str()) {
+            lines.push(line.trim().to_string());
+        }
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Checks whether a `requirements.txt` lists a package, comparing normalized names so `PyJWT` matches `pyjwt`.
+///
+/// # Arguments
+/// - `requirements`: The contents of the `requirements.txt`.
+/// - `package_name`: The package as named on PyPI, e.g. `fastapi`.
+pub fn requirements_depend_on(requirements: &str, package_name: &str) -> bool {
+    let package_name: String = normalize_package_name(package_name);
+    requirements
+        .lines()
+        .any(|line| requirement_name(line) == package_name)
+}
+
+/// Returns the normalized package name of a requirement line, empty for comments and options.
+fn requirement_name(line: &str) -> String {
+    let line: &str = line.split('#').next().unwrap_or("").trim();
+    if line.starts_with('-') {
+        return String::new();
+    }
+    let name: &str = line
+        .split(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+        .next()
+        .unwrap_or("");
+    normalize_package_name(name)
+}
+
+/// Normalizes a package name the way pip compares them: lowercase with runs of `-`, `_` and `.` as `-`.
+fn normalize_package_name(name: &str) -> String {
+    let mut normalized: String = String::new();
+    for c in name.trim().chars() {
+        if matches!(c, '-' | '_' | '.') {
+            if !normalized.ends_with('-') {
+                normalized.push('-');
+            }
+        } else {
+            normalized.push(c.to_ascii_lowercase());
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::general::read_code_templates;
+
+    fn scope(storage_backend: StorageBackend, auth_style: AuthStyle) -> ProjectScope {
+        ProjectScope {
+            is_crud_required: true,
+            is_user_login_and_logout: false,
+            is_external_urls_required: false,
+            storage_backend,
+            auth_style,
+            is_pagination_required: false,
+            is_filtering_required: false,
+            realtime: RealtimeTransport::None,
+            is_background_jobs_required: false,
+            is_file_upload_required: false,
+        }
+    }
+
+    fn template(name: &str, dependencies: &[&str]) -> CodeTemplate {
+        CodeTemplate {
+            name: name.to_string(),
+            framework: TargetFramework::FastApi,
+            file: format!("fastapi/{}.py", name),
+            description: format!("The {} template", name),
+            features: vec![TemplateFeature::Crud],
+            dependencies: dependencies.iter().map(|line| line.to_string()).collect(),
+            tests_file: None,
+            code: "app = FastAPI()\n\n".to_string(),
+            tests_code: None,
+        }
+    }
+
+    #[test]
+    fn registry_covers_every_feature_for_every_framework() {
+        let features: [TemplateFeature; 6] = [
+            TemplateFeature::Crud,
+            TemplateFeature::JsonFile,
+            TemplateFeature::Sqlite,
+            TemplateFeature::Auth,
+            TemplateFeature::ExternalApi,
+            TemplateFeature::Websocket,
+        ];
+        for framework in [
+            TargetFramework::ActixWeb,
+            TargetFramework::Axum,
+            TargetFramework::FastApi,
+        ] {
+            let templates: Vec<CodeTemplate> = read_code_templates(framework);
+            for feature in features {
+                assert!(
+                    templates
+                        .iter()
+                        .any(|template| template.features.contains(&feature)),
+                    "{:?} has no template for {:?}",
+                    framework,
+                    feature
+                );
+            }
+            assert!(templates.iter().all(|template| !template.code.is_empty()));
+        }
+    }
+
+    #[test]
+    fn selects_the_fewest_templates_covering_the_scope() {
+        let templates: Vec<CodeTemplate> = read_code_templates(TargetFramework::ActixWeb);
+        let names = |required: &[TemplateFeature]| -> Vec<String> {
+            select_templates(&templates, required)
+                .into_iter()
+                .map(|template| template.name.clone())
+                .collect()
+        };
+
+        let sqlite_with_login: ProjectScope = scope(StorageBackend::Sqlite, AuthStyle::Jwt);
+        assert_eq!(
+            required_features(Some(&sqlite_with_login)),
+            vec![
+                TemplateFeature::Crud,
+                TemplateFeature::Sqlite,
+                TemplateFeature::Auth
+            ]
+        );
+        assert_eq!(
+            names(&required_features(Some(&sqlite_with_login))),
+            vec!["sqlite", "auth"]
+        );
+        assert_eq!(names(&required_features(None)), vec!["crud"]);
+
+        let mut in_memory: ProjectScope = scope(StorageBackend::InMemory, AuthStyle::ApiKey);
+        in_memory.is_crud_required = false;
+        assert_eq!(
+            required_features(Some(&in_memory)),
+            vec![TemplateFeature::Crud]
+        );
+        assert_eq!(names(&[]), vec!["crud"]);
+    }
+
+    #[test]
+    fn composes_templates_with_a_header_per_template() {
+        let crud: CodeTemplate = template("crud", &[]);
+        let auth: CodeTemplate = template("auth", &[]);
+
+        assert_eq!(
+            compose_code_template(&[&crud, &auth]),
+            "# TEMPLATE crud: The crud template\napp = FastAPI()\n\n# TEMPLATE auth: The auth template\napp = FastAPI()"
+        );
+    }
+
+    #[test]
+    fn adds_template_dependencies_once() {
+        let mut auth: CodeTemplate = template("auth", &["argon2 = \"0.5\"", "serde = \"9\""]);
+        auth.framework = TargetFramework::ActixWeb;
+        let manifest: &str =
+            "[package]\nname = \"web_server\"\n\n[dependencies]\nserde = \"1.0\"\n";
+
+        let updated: String = add_template_dependencies(manifest, &[&auth]).unwrap();
+        assert!(manifest_depends_on(&updated, "argon2"));
+        assert!(updated.contains("serde = \"1.0\""));
+        assert!(!manifest_depends_on("not = [toml", "serde"));
+        assert!(add_template_dependencies(manifest, &[&template("bad", &["= 1"])]).is_err());
+
+        let external_api: CodeTemplate =
+            template("external_api", &["httpx==0.28.1", "PyJWT>=2", "Fast_API"]);
+        assert_eq!(
+            add_template_requirements(
+                "fastapi==0.115.0\n# pinned\npyjwt==2.9.0\n\n",
+                &[&external_api]
+            ),
+            "fastapi==0.115.0\n# pinned\npyjwt==2.9.0\nhttpx==0.28.1\nFast_API\n"
+        );
+        assert!(requirements_depend_on("-r base.txt\nPyJWT==2.9.0", "pyjwt"));
+        assert!(!requirements_depend_on("# fastapi\n", "fastapi"));
+    }
+}
@@ -64,7 +64,6 @@ impl PrintCommand {
 /// ```
 /// let response = get_user_response("What is your name?");
 /// ```
-
 pub fn get_user_response(question: &str) -> String {
     let mut stdout: std::io::Stdout = stdout();
 
@@ -81,6 +80,10 @@ pub fn get_user_response(question: &str) -> String {
 
 /// Prompts the user to confirm whether it is safe to run code written by AI.
 ///
+/// # Arguments
+///
+/// - `safety_report`: The static safety scan of the generated code, shown above the prompt.
+///
 /// # Returns
 ///
 /// This function returns a `bool`:
@@ -94,9 +97,12 @@ pub fn get_user_response(question: &str) -> String {
 /// - `[2]` to stop the project.
 ///
 /// If the input is invalid, the prompt will ask again until a valid response is received.
-
-pub fn confirm_safe_code() -> bool {
+pub fn confirm_safe_code(safety_report: &str) -> bool {
     let mut stdout: std::io::Stdout = stdout();
+    stdout.execute(SetForegroundColor(Color::Yellow)).unwrap();
+    println!();
+    println!("{}", safety_report);
+    stdout.execute(ResetColor).unwrap();
     loop {
         stdout.execute(SetForegroundColor(Color::Blue)).unwrap();
         println!();
@@ -0,0 +1,454 @@
+use crate::helpers::general::env_setting;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+use syn::{Item, ItemExternCrate, ItemUse, UseTree};
+
+/// Environment variable overriding the crates generated code may use.
+///
+/// A comma separated list of `name` or `name=version` entries, e.g. `serde,chrono=0.4`.
+/// Entries with a version can be added to the generated project's `Cargo.toml` automatically.
+const CRATE_ALLOWLIST_ENV: &str = "GENERATED_CRATE_ALLOWLIST";
+
+/// Environment variable enabling automatic `Cargo.toml` entries for allowlisted crates with a version.
+const AUTO_ADD_CRATES_ENV: &str = "GENERATED_CRATE_AUTO_ADD";
+
+/// Crates generated code may use unless `GENERATED_CRATE_ALLOWLIST` says otherwise.
+///
+/// `argon2` and `rand` carry versions so security fixes can add them for password hashing and session tokens.
+/// The crates after them back the storage, auth and API features a `ProjectScope` can ask for, and the
+/// last ones are the axum server and the crates its integration tests call it with.
+const DEFAULT_CRATE_ALLOWLIST: &str = "reqwest,serde,serde_json,tokio,actix-web,async-trait,actix-cors,actix-files,argon2=0.5,rand=0.8,\
+     rusqlite=0.32,jsonwebtoken=9,actix-ws=0.3,tokio-stream=0.1,futures-util=0.3,actix-multipart=0.7,\
+     axum=0.8,tower-http=0.6,tower=0.5,http-body-util=0.1";
+
+/// Crates shipped with the compiler, which never need a `Cargo.toml` entry.
+const BUILTIN_CRATES: &[&str] = &["std", "core", "alloc", "proc_macro", "test"];
+
+/// Path prefixes that refer to the generated crate itself.
+const RELATIVE_PATH_ROOTS: &[&str] = &["crate", "self", "super", "Self"];
+
+/// A crate generated code is allowed to depend on.
+///
+/// # Fields
+/// - `name`: The crate name as written in `Cargo.toml`.
+/// - `version`: The version requirement used when the crate is added automatically.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AllowedCrate {
+    pub name: String,
+    pub version: Option<String>,
+}
+
+/// The crates generated code is allowed to depend on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrateAllowlist {
+    crates: Vec<AllowedCrate>,
+    auto_add: bool,
+}
+
+impl CrateAllowlist {
+    /// Reads the allowlist from `GENERATED_CRATE_ALLOWLIST` and `GENERATED_CRATE_AUTO_ADD`.
+    pub fn from_env() -> Self {
+        let entries: String = env_setting(CRATE_ALLOWLIST_ENV, DEFAULT_CRATE_ALLOWLIST.to_string());
+        let crates: Vec<AllowedCrate> = entries
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| match entry.split_once('=') {
+                Some((name, version)) => AllowedCrate {
+                    name: name.trim().to_string(),
+                    version: Some(version.trim().to_string()),
+                },
+                None => AllowedCrate {
+                    name: entry.to_string(),
+                    version: None,
+                },
+            })
+            .collect();
+
+        Self {
+            crates,
+            auto_add: env_setting(AUTO_ADD_CRATES_ENV, false),
+        }
+    }
+
+    /// Returns the allowlist with automatic `Cargo.toml` entries enabled, regardless of `GENERATED_CRATE_AUTO_ADD`.
+    pub fn with_auto_add(mut self) -> Self {
+        self.auto_add = true;
+        self
+    }
+
+    /// Finds the allowlist entry for a crate as it is named in code, e.g. `actix_web`.
+    fn find(&self, code_name: &str) -> Option<&AllowedCrate> {
+        self.crates
+            .iter()
+            .find(|allowed| normalize_crate_name(&allowed.name) == code_name)
+    }
+
+    fn names(&self) -> String {
+        self.crates
+            .iter()
+            .map(|allowed| allowed.name.as_str())
+            .collect::<Vec<&str>>()
+            .join(", ")
+    }
+}
+
+/// Why a crate used by generated code was rejected.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DependencyIssueKind {
+    /// The crate is not on the allowlist.
+    NotAllowed,
+    /// The crate is allowlisted but missing from the generated project's `Cargo.toml`.
+    MissingFromManifest,
+}
+
+/// A crate used by generated code that would not build or is not allowed.
+///
+/// # Fields
+/// - `crate_name`: The crate name as written in code.
+/// - `line`: The line of the `use` or `extern crate` item.
+/// - `kind`: Why the crate was rejected.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DependencyIssue {
+    pub crate_name: String,
+    pub line: usize,
+    pub kind: DependencyIssueKind,
+}
+
+/// The result of checking generated code against the allowlist.
+///
+/// # Fields
+/// - `issues`: Crates the code must stop using.
+/// - `added_crates`: Allowlisted crates that were added to `Cargo.toml` automatically.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DependencyCheck {
+    pub issues: Vec<DependencyIssue>,
+    pub added_crates: Vec<String>,
+}
+
+/// Resolves every `use` path and `extern crate` in the generated code to the crate it comes from.
+///
+/// Paths starting at the crate itself, a builtin crate or an item declared in the code are skipped.
+///
+/// # Arguments
+/// - `code`: The generated Rust source.
+///
+/// # Returns
+/// Each external crate with the line of its first use, or the parse error if the code is not valid Rust.
+pub fn find_external_crates(code: &str) -> Result<Vec<(String, usize)>, syn::Error> {
+    let syntax_tree: syn::File = syn::parse_file(code)?;
+
+    let mut collector: CrateCollector = CrateCollector::default();
+    collector.visit_file(&syntax_tree);
+
+    let mut seen: HashSet<String> = HashSet::new();
+    let external_crates: Vec<(String, usize)> = collector
+        .roots
+        .into_iter()
+        .filter(|(root, _)| {
+            !BUILTIN_CRATES.contains(&root.as_str())
+                && !RELATIVE_PATH_ROOTS.contains(&root.as_str())
+                && !collector.local_names.contains(root)
+        })
+        .filter(|(root, _)| seen.insert(root.clone()))
+        .collect();
+    Ok(external_crates)
+}
+
+/// Checks the crates used by generated code against the allowlist and the project's `Cargo.toml`.
+///
+/// When auto-add is enabled, allowlisted crates with a version that are missing from `Cargo.toml`
+/// are added to it instead of being reported.
+///
+/// # Arguments
+/// - `code`: The generated Rust source.
+/// - `manifest_path`: The generated project's `Cargo.toml`.
+/// - `allowlist`: The crates the code may use.
+///
+/// # Returns
+/// The issues found and the crates added, or the parse error if the code is not valid Rust.
+pub fn check_dependencies(
+    code: &str,
+    manifest_path: &Path,
+    allowlist: &CrateAllowlist,
+) -> Result<DependencyCheck, syn::Error> {
+    let external_crates: Vec<(String, usize)> = find_external_crates(code)?;
+
+    let manifest: String = fs::read_to_string(manifest_path).unwrap_or_default();
+    let mut manifest_doc: toml_edit::DocumentMut = manifest
+        .parse()
+        .expect("Failed to parse web server Cargo.toml");
+    let declared_crates: HashSet<String> = manifest_doc
+        .get("dependencies")
+        .and_then(|dependencies| dependencies.as_table_like())
+        .map(|dependencies| {
+            dependencies
+                .iter()
+                .map(|(name, _)| normalize_crate_name(name))
+                .collect()
+        })
+        .unwrap_or_default();
+    let package_name: Option<String> = manifest_doc
+        .get("package")
+        .and_then(|package| package.get("name"))
+        .and_then(|name| name.as_str())
+        .map(normalize_crate_name);
+
+    let mut check: DependencyCheck = DependencyCheck::default();
+    for (crate_name, line) in external_crates {
+        if declared_crates.contains(&crate_name) && allowlist.find(&crate_name).is_some() {
+            continue;
+        }
+        if package_name.as_ref() == Some(&crate_name) {
+            continue;
+        }
+
+        let kind: DependencyIssueKind = match allowlist.find(&crate_name) {
+            None => DependencyIssueKind::NotAllowed,
+            Some(AllowedCrate {
+                name,
+                version: Some(version),
+            }) if allowlist.auto_add => {
+                manifest_doc["dependencies"][name.as_str()] = toml_edit::value(version.as_str());
+                check
+                    .added_crates
+                    .push(format!("{} = \"{}\"", name, version));
+                continue;
+            }
+            Some(_) => DependencyIssueKind::MissingFromManifest,
+        };
+        check.issues.push(DependencyIssue {
+            crate_name,
+            line,
+            kind,
+        });
+    }
+
+    if !check.added_crates.is_empty() {
+        fs::write(manifest_path, manifest_doc.to_string())
+            .expect("Failed to update web server Cargo.toml");
+    }
+    Ok(check)
+}
+
+/// Formats dependency issues as feedback for the fix loop.
+///
+/// # Arguments
+/// - `issues`: The issues returned by [`check_dependencies`].
+/// - `allowlist`: The crates the code may use, listed in the feedback.
+pub fn format_dependency_feedback(
+    issues: &[DependencyIssue],
+    allowlist: &CrateAllowlist,
+) -> String {
+    let mut feedback: String = String::from("DEPENDENCY ERRORS:");
+    for issue in issues {
+        let reason: &str = match issue.kind {
+            DependencyIssueKind::NotAllowed => "is not an allowed dependency",
+            DependencyIssueKind::MissingFromManifest => {
+                "is allowed but not declared in Cargo.toml, so it cannot be used"
+            }
+        };
+        feedback.push_str(&format!(
+            "\n- line {}: crate `{}` {}",
+            issue.line, issue.crate_name, reason
+        ));
+    }
+    feedback.push_str(&format!(
+        "\nRemove these imports and use only the standard library and these crates: {}",
+        allowlist.names()
+    ));
+    feedback
+}
+
+/// Converts a crate name from `Cargo.toml` to the form used in paths, e.g. `actix-web` to `actix_web`.
+fn normalize_crate_name(name: &str) -> String {
+    name.replace('-', "_")
+}
+
+/// Collects the first segment of every `use` path and `extern crate`, and the names declared in the code.
+#[derive(Default)]
+struct CrateCollector {
+    roots: Vec<(String, usize)>,
+    local_names: HashSet<String>,
+}
+
+impl CrateCollector {
+    fn collect_use_roots(&mut self, tree: &UseTree, line: usize) {
+        match tree {
+            UseTree::Path(use_path) => self.roots.push((use_path.ident.to_string(), line)),
+            UseTree::Name(use_name) => self.roots.push((use_name.ident.to_string(), line)),
+            UseTree::Rename(use_rename) => self.roots.push((use_rename.ident.to_string(), line)),
+            UseTree::Group(use_group) => {
+                for item in &use_group.items {
+                    self.collect_use_roots(item, line);
+                }
+            }
+            UseTree::Glob(_) => {}
+        }
+    }
+
+    /// Records the names a `use` item brings into scope, so later relative paths through them are skipped.
+    fn collect_imported_names(&mut self, tree: &UseTree) {
+        match tree {
+            UseTree::Path(use_path) => self.collect_imported_names(&use_path.tree),
+            UseTree::Name(use_name) => {
+                self.local_names.insert(use_name.ident.to_string());
+            }
+            UseTree::Rename(use_rename) => {
+                self.local_names.insert(use_rename.rename.to_string());
+            }
+            UseTree::Group(use_group) => {
+                for item in &use_group.items {
+                    self.collect_imported_names(item);
+                }
+            }
+            UseTree::Glob(_) => {}
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for CrateCollector {
+    fn visit_item(&mut self, item: &'ast Item) {
+        let declared_name: Option<String> = match item {
+            Item::Const(item) => Some(item.ident.to_string()),
+            Item::Enum(item) => Some(item.ident.to_string()),
+            Item::Fn(item) => Some(item.sig.ident.to_string()),
+            Item::Mod(item) => Some(item.ident.to_string()),
+            Item::Static(item) => Some(item.ident.to_string()),
+            Item::Struct(item) => Some(item.ident.to_string()),
+            Item::Trait(item) => Some(item.ident.to_string()),
+            Item::Type(item) => Some(item.ident.to_string()),
+            Item::Union(item) => Some(item.ident.to_string()),
+            _ => None,
+        };
+        if let Some(declared_name) = declared_name {
+            self.local_names.insert(declared_name);
+        }
+        visit::visit_item(self, item);
+    }
+
+    fn visit_item_use(&mut self, item_use: &'ast ItemUse) {
+        self.collect_use_roots(&item_use.tree, item_use.span().start().line);
+        if let UseTree::Path(use_path) = &item_use.tree {
+            self.collect_imported_names(&use_path.tree);
+        }
+        visit::visit_item_use(self, item_use);
+    }
+
+    fn visit_item_extern_crate(&mut self, extern_crate: &'ast ItemExternCrate) {
+        self.roots.push((
+            extern_crate.ident.to_string(),
+            extern_crate.span().start().line,
+        ));
+        if let Some((_, rename)) = &extern_crate.rename {
+            self.local_names.insert(rename.to_string());
+        }
+        visit::visit_item_extern_crate(self, extern_crate);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    const MANIFEST: &str = "[package]\nname = \"web_server\"\nversion = \"0.1.0\"\n\n[dependencies]\nactix-web = \"4\"\nserde = \"1\"\n";
+
+    fn allowlist(auto_add: bool) -> CrateAllowlist {
+        CrateAllowlist {
+            crates: vec![
+                AllowedCrate {
+                    name: "actix-web".to_string(),
+                    version: None,
+                },
+                AllowedCrate {
+                    name: "serde".to_string(),
+                    version: None,
+                },
+                AllowedCrate {
+                    name: "rand".to_string(),
+                    version: Some("0.8".to_string()),
+                },
+            ],
+            auto_add,
+        }
+    }
+
+    fn write_manifest(name: &str) -> PathBuf {
+        let manifest_path: PathBuf = std::env::temp_dir().join(format!(
+            "dependency_check_{}_{}.toml",
+            name,
+            std::process::id()
+        ));
+        fs::write(&manifest_path, MANIFEST).unwrap();
+        manifest_path
+    }
+
+    #[test]
+    fn skips_builtin_relative_and_local_paths() {
+        let code: &str = r#"
+            use std::collections::HashMap;
+            use crate::models::Item;
+            use self::handlers::index;
+            use actix_web::{web, App};
+            use serde::Deserialize;
+            extern crate rand;
+            mod handlers { pub fn index() {} }
+        "#;
+
+        let crates: Vec<String> = find_external_crates(code)
+            .unwrap()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(crates, vec!["actix_web", "serde", "rand"]);
+    }
+
+    #[test]
+    fn reports_crates_not_allowed_or_not_declared() {
+        let manifest_path: PathBuf = write_manifest("report");
+        let code: &str = "use actix_web::web;\nuse rand::Rng;\nuse diesel::prelude::*;\nuse web_server::routes;\n";
+
+        let check: DependencyCheck =
+            check_dependencies(code, &manifest_path, &allowlist(false)).unwrap();
+        fs::remove_file(&manifest_path).unwrap();
+
+        assert!(check.added_crates.is_empty());
+        assert_eq!(
+            check.issues,
+            vec![
+                DependencyIssue {
+                    crate_name: "rand".to_string(),
+                    line: 2,
+                    kind: DependencyIssueKind::MissingFromManifest,
+                },
+                DependencyIssue {
+                    crate_name: "diesel".to_string(),
+                    line: 3,
+                    kind: DependencyIssueKind::NotAllowed,
+                },
+            ]
+        );
+        let feedback: String = format_dependency_feedback(&check.issues, &allowlist(false));
+        assert!(feedback.contains("line 3: crate `diesel` is not an allowed dependency"));
+        assert!(feedback.contains("actix-web, serde, rand"));
+    }
+
+    #[test]
+    fn adds_allowlisted_crates_with_a_version() {
+        let manifest_path: PathBuf = write_manifest("auto_add");
+        let code: &str = "use rand::Rng;\n";
+
+        let check: DependencyCheck =
+            check_dependencies(code, &manifest_path, &allowlist(true)).unwrap();
+        let manifest: String = fs::read_to_string(&manifest_path).unwrap();
+        fs::remove_file(&manifest_path).unwrap();
+
+        assert!(check.issues.is_empty());
+        assert_eq!(check.added_crates, vec!["rand = \"0.8\""]);
+        assert!(manifest.contains("rand = \"0.8\""));
+    }
+}
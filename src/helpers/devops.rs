@@ -0,0 +1,639 @@
+use serde_yaml::{Mapping, Value};
+use std::collections::HashSet;
+
+/// The port the web server listens on inside its container.
+pub const CONTAINER_PORT: u16 = 8080;
+
+/// Environment variables of the server template with their local default and a description for `.env.example`.
+const KNOWN_ENV_VARS: &[(&str, &str, &str)] = &[
+    (
+        "WEB_SERVER_HOST",
+        "127.0.0.1",
+        "Address the server binds to, use 0.0.0.0 inside containers",
+    ),
+    ("WEB_SERVER_PORT", "8080", "Port the server listens on"),
+    (
+        "WEB_SERVER_STATIC_DIR",
+        "static",
+        "Directory served under /static",
+    ),
+    (
+        "WEB_SERVER_OPENAPI_PATH",
+        "openapi.json",
+        "OpenAPI document served under /openapi.json",
+    ),
+    (
+        "WEB_SERVER_JWT_SECRET",
+        "",
+        "Secret signing the login tokens, set a long random value",
+    ),
+    (
+        "WEB_SERVER_API_KEY",
+        "",
+        "Key clients send in the X-API-Key header, set a long random value",
+    ),
+    (
+        "WEB_SERVER_UPLOAD_DIR",
+        "uploads",
+        "Directory uploaded files are saved to",
+    ),
+    (
+        "WEB_SERVER_DATABASE_PATH",
+        "database.sqlite",
+        "SQLite database file, relative paths are inside the data directory",
+    ),
+];
+
+/// Values the container sets for the environment variables of the server template.
+const CONTAINER_ENV_VARS: &[(&str, &str)] = &[
+    ("WEB_SERVER_HOST", "0.0.0.0"),
+    ("WEB_SERVER_PORT", "8080"),
+    ("WEB_SERVER_STATIC_DIR", "/app/static"),
+    ("WEB_SERVER_OPENAPI_PATH", "/app/openapi.json"),
+];
+
+/// Every instruction a Dockerfile may contain.
+const DOCKERFILE_INSTRUCTIONS: &[&str] = &[
+    "ADD",
+    "ARG",
+    "CMD",
+    "COPY",
+    "ENTRYPOINT",
+    "ENV",
+    "EXPOSE",
+    "HEALTHCHECK",
+    "LABEL",
+    "MAINTAINER",
+    "ONBUILD",
+    "RUN",
+    "SHELL",
+    "STOPSIGNAL",
+    "USER",
+    "VOLUME",
+    "WORKDIR",
+    "FROM",
+];
+
+/// The commands the CI workflow must run.
+const REQUIRED_CI_COMMANDS: &[&str] = &["cargo build", "cargo clippy", "cargo test"];
+
+/// A deployment file written into the generated web server project.
+///
+/// # Fields
+/// - `path`: The path relative to the project directory, e.g. `.github/workflows/ci.yml`.
+/// - `contents`: The file contents.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DevOpsFile {
+    pub path: String,
+    pub contents: String,
+}
+
+/// The templates the deployment files are rendered from.
+///
+/// # Fields
+/// - `dockerfile`: The multi-stage Dockerfile.
+/// - `dockerignore`: The `.dockerignore` file.
+/// - `compose`: The docker compose file, with an `{{ENVIRONMENT}}` placeholder.
+/// - `workflow`: The GitHub Actions workflow.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DevOpsTemplates {
+    pub dockerfile: String,
+    pub dockerignore: String,
+    pub compose: String,
+    pub workflow: String,
+}
+
+/// Renders the Dockerfile, compose file, CI workflow and `.env.example` of the web server.
+///
+/// `{{BINARY_NAME}}` and `{{PORT}}` are replaced in every template. The compose file sets the container
+/// values of the template's variables and passes every other variable through from `.env`.
+///
+/// # Arguments
+/// - `binary_name`: The name of the web server binary.
+/// - `env_vars`: The environment variables the web server reads.
+/// - `templates`: The templates to render.
+///
+/// # Returns
+/// All deployment files.
+pub fn render_devops_files(
+    binary_name: &str,
+    env_vars: &[String],
+    templates: &DevOpsTemplates,
+) -> Vec<DevOpsFile> {
+    let render = |template: &str| -> String {
+        template
+            .replace("{{BINARY_NAME}}", binary_name)
+            .replace("{{PORT}}", &CONTAINER_PORT.to_string())
+    };
+
+    let mut environment: Vec<String> = CONTAINER_ENV_VARS
+        .iter()
+        .map(|(name, value)| format!("      {}: \"{}\"", name, value))
+        .collect();
+    for name in env_vars {
+        if !CONTAINER_ENV_VARS.iter().any(|(known, _)| known == name) {
+            environment.push(format!("      {0}: \"${{{0}:-}}\"", name));
+        }
+    }
+
+    vec![
+        DevOpsFile {
+            path: "Dockerfile".to_string(),
+            contents: render(&templates.dockerfile),
+        },
+        DevOpsFile {
+            path: ".dockerignore".to_string(),
+            contents: render(&templates.dockerignore),
+        },
+        DevOpsFile {
+            path: "docker-compose.yml".to_string(),
+            contents: render(&templates.compose)
+                .replace("{{ENVIRONMENT}}", &environment.join("\n")),
+        },
+        DevOpsFile {
+            path: ".github/workflows/ci.yml".to_string(),
+            contents: render(&templates.workflow),
+        },
+        DevOpsFile {
+            path: ".env.example".to_string(),
+            contents: render_env_example(env_vars),
+        },
+    ]
+}
+
+/// Returns the local default and a description of an environment variable of the web server.
+///
+/// Variables unknown to the server template have an empty default.
+pub fn describe_env_var(name: &str) -> (&'static str, &'static str) {
+    KNOWN_ENV_VARS
+        .iter()
+        .find(|(known, _, _)| *known == name)
+        .map(|(_, default, description)| (*default, *description))
+        .unwrap_or(("", "Read by the web server"))
+}
+
+/// Lists every environment variable with its default, variables unknown to the template are left empty.
+fn render_env_example(env_vars: &[String]) -> String {
+    let mut contents: String =
+        "# Environment variables read by the web server.\n# Copy this file to .env and adjust the values.\n"
+            .to_string();
+    for name in env_vars {
+        let (default, description): (&str, &str) = describe_env_var(name);
+        contents.push_str(&format!("\n# {}\n{}={}\n", description, name, default));
+    }
+    contents
+}
+
+/// Checks the deployment files offline: YAML files are parsed and checked for required keys,
+/// the Dockerfile is checked against common lint rules.
+///
+/// # Arguments
+/// - `files`: The deployment files.
+///
+/// # Returns
+/// One description per problem, prefixed with the file path; empty if every file passed.
+pub fn validate_devops_files(files: &[DevOpsFile]) -> Vec<String> {
+    let mut issues: Vec<String> = vec![];
+    for file in files {
+        let file_issues: Vec<String> = match file.path.as_str() {
+            "Dockerfile" => lint_dockerfile(&file.contents),
+            "docker-compose.yml" => validate_compose_file(&file.contents),
+            path if path.starts_with(".github/workflows/") => {
+                validate_workflow_file(&file.contents)
+            }
+            _ => vec![],
+        };
+        issues.extend(
+            file_issues
+                .into_iter()
+                .map(|issue| format!("{}: {}", file.path, issue)),
+        );
+    }
+    issues
+}
+
+/// A Dockerfile instruction with its continuation lines joined.
+struct Instruction {
+    line: usize,
+    keyword: String,
+    arguments: String,
+}
+
+fn parse_dockerfile(contents: &str) -> Vec<Instruction> {
+    let mut instructions: Vec<Instruction> = vec![];
+    let mut pending: Option<Instruction> = None;
+    for (index, raw_line) in contents.lines().enumerate() {
+        let line: &str = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (text, continues): (&str, bool) = match line.strip_suffix('\\') {
+            Some(text) => (text.trim_end(), true),
+            None => (line, false),
+        };
+
+        let mut instruction: Instruction = match pending.take() {
+            Some(mut instruction) => {
+                instruction.arguments.push(' ');
+                instruction.arguments.push_str(text);
+                instruction
+            }
+            None => {
+                let (keyword, arguments) =
+                    text.split_once(char::is_whitespace).unwrap_or((text, ""));
+                Instruction {
+                    line: index + 1,
+                    keyword: keyword.to_uppercase(),
+                    arguments: arguments.trim().to_string(),
+                }
+            }
+        };
+        if continues {
+            pending = Some(instruction);
+        } else {
+            instruction.arguments = instruction.arguments.trim().to_string();
+            instructions.push(instruction);
+        }
+    }
+    instructions.extend(pending);
+    instructions
+}
+
+/// Checks a Dockerfile against lint rules for small, reproducible and non-root images.
+///
+/// # Arguments
+/// - `contents`: The Dockerfile.
+///
+/// # Returns
+/// One description per broken rule, with the line it was found on.
+pub fn lint_dockerfile(contents: &str) -> Vec<String> {
+    let instructions: Vec<Instruction> = parse_dockerfile(contents);
+    let mut issues: Vec<String> = vec![];
+
+    match instructions
+        .iter()
+        .find(|instruction| instruction.keyword != "ARG")
+    {
+        Some(instruction) if instruction.keyword == "FROM" => {}
+        Some(instruction) => issues.push(format!(
+            "line {}: The first instruction must be FROM, found {}",
+            instruction.line, instruction.keyword
+        )),
+        None => issues.push("The Dockerfile has no instructions".to_string()),
+    }
+
+    let mut stage_names: HashSet<String> = HashSet::new();
+    let mut stage_count: usize = 0;
+    let mut final_user: Option<String> = None;
+    let mut final_command: Option<&Instruction> = None;
+    let mut final_copies_from_stage: bool = false;
+    let mut exposes_port: bool = false;
+
+    for instruction in &instructions {
+        let location: String = format!("line {}", instruction.line);
+        match instruction.keyword.as_str() {
+            "FROM" => {
+                stage_count += 1;
+                final_user = None;
+                final_command = None;
+                final_copies_from_stage = false;
+                let arguments: Vec<&str> = instruction
+                    .arguments
+                    .split_whitespace()
+                    .filter(|argument| !argument.starts_with("--"))
+                    .collect();
+                let image: &str = arguments.first().copied().unwrap_or_default();
+                if let [_, alias, name] = arguments.as_slice() {
+                    if alias.eq_ignore_ascii_case("as") {
+                        stage_names.insert(name.to_lowercase());
+                    }
+                }
+                if image.is_empty() {
+                    issues.push(format!("{}: FROM has no image", location));
+                } else if image != "scratch" && !stage_names.contains(&image.to_lowercase()) {
+                    issues.extend(
+                        check_image_tag(image).map(|issue| format!("{}: {}", location, issue)),
+                    );
+                }
+            }
+            "ADD" => {
+                let is_remote: bool = instruction
+                    .arguments
+                    .split_whitespace()
+                    .find(|argument| !argument.starts_with("--"))
+                    .is_some_and(|source| {
+                        source.starts_with("http://") || source.starts_with("https://")
+                    });
+                if !is_remote {
+                    issues.push(format!(
+                        "{}: Use COPY instead of ADD for local files",
+                        location
+                    ));
+                }
+            }
+            "COPY" if instruction.arguments.starts_with("--from=") => {
+                final_copies_from_stage = true
+            }
+            "USER" => final_user = Some(instruction.arguments.clone()),
+            "CMD" | "ENTRYPOINT" => final_command = Some(instruction),
+            "EXPOSE" => exposes_port = true,
+            "RUN" => {
+                let arguments: &str = instruction.arguments.as_str();
+                if arguments.contains("apt-get install") {
+                    if !arguments.contains("--no-install-recommends") {
+                        issues.push(format!(
+                            "{}: apt-get install must use --no-install-recommends",
+                            location
+                        ));
+                    }
+                    if !arguments.contains(" -y") && !arguments.contains("--yes") {
+                        issues.push(format!("{}: apt-get install must use -y", location));
+                    }
+                    if !arguments.contains("rm -rf /var/lib/apt/lists") {
+                        issues.push(format!(
+                            "{}: Remove /var/lib/apt/lists after apt-get install",
+                            location
+                        ));
+                    }
+                }
+            }
+            keyword if !DOCKERFILE_INSTRUCTIONS.contains(&keyword) => {
+                issues.push(format!("{}: Unknown instruction {}", location, keyword))
+            }
+            _ => {}
+        }
+    }
+
+    if stage_count < 2 {
+        issues.push(
+            "Use a multi-stage build so the final image does not contain the toolchain".to_string(),
+        );
+    } else if !final_copies_from_stage {
+        issues.push("The final stage must copy the build output with COPY --from".to_string());
+    }
+    match final_user.as_deref() {
+        None => issues.push("The final stage must switch to a non-root USER".to_string()),
+        Some(user) if matches!(user.split(':').next(), Some("root" | "0")) => {
+            issues.push("The final stage must not run as root".to_string())
+        }
+        Some(_) => {}
+    }
+    match final_command {
+        None => issues.push("The final stage must define CMD or ENTRYPOINT".to_string()),
+        Some(instruction) if !instruction.arguments.starts_with('[') => issues.push(format!(
+            "line {}: {} must use the exec form, e.g. [\"binary\"]",
+            instruction.line, instruction.keyword
+        )),
+        Some(_) => {}
+    }
+    if !exposes_port {
+        issues.push("EXPOSE the port the server listens on".to_string());
+    }
+    issues
+}
+
+/// Requires an explicit, non-`latest` tag or a digest so builds are reproducible.
+fn check_image_tag(image: &str) -> Option<String> {
+    if image.contains('@') {
+        return None;
+    }
+    let name: &str = image.rsplit('/').next().unwrap_or(image);
+    match name.split_once(':') {
+        None => Some(format!("Image {} has no tag, pin a version", image)),
+        Some((_, "latest")) => Some(format!(
+            "Image {} uses the latest tag, pin a version",
+            image
+        )),
+        Some(_) => None,
+    }
+}
+
+fn parse_yaml_mapping(contents: &str) -> Result<Mapping, String> {
+    match serde_yaml::from_str::<Value>(contents) {
+        Ok(Value::Mapping(mapping)) => Ok(mapping),
+        Ok(_) => Err("The top level must be a mapping".to_string()),
+        Err(e) => Err(format!("Invalid YAML: {}", e)),
+    }
+}
+
+/// Checks a docker compose file parses and every service can be started.
+///
+/// # Arguments
+/// - `contents`: The compose file.
+///
+/// # Returns
+/// One description per problem.
+pub fn validate_compose_file(contents: &str) -> Vec<String> {
+    let compose: Mapping = match parse_yaml_mapping(contents) {
+        Ok(compose) => compose,
+        Err(e) => return vec![e],
+    };
+    let services: &Mapping = match compose.get("services") {
+        Some(Value::Mapping(services)) if !services.is_empty() => services,
+        _ => return vec!["The compose file must define at least one service".to_string()],
+    };
+    let declared_volumes: HashSet<&str> = match compose.get("volumes") {
+        Some(Value::Mapping(volumes)) => volumes.keys().filter_map(Value::as_str).collect(),
+        _ => HashSet::new(),
+    };
+
+    let mut issues: Vec<String> = vec![];
+    for (name, service) in services {
+        let name: &str = name.as_str().unwrap_or("<unnamed>");
+        let Value::Mapping(service) = service else {
+            issues.push(format!("Service {} must be a mapping", name));
+            continue;
+        };
+        if !service.contains_key("build") && !service.contains_key("image") {
+            issues.push(format!("Service {} needs a build or image", name));
+        }
+        if let Some(ports) = service.get("ports") {
+            let valid_ports: bool = ports.as_sequence().is_some_and(|ports| {
+                ports
+                    .iter()
+                    .all(|port| port.is_string() || port.is_u64() || port.is_mapping())
+            });
+            if !valid_ports {
+                issues.push(format!("Service {} has invalid ports", name));
+            }
+        }
+        if let Some(Value::Sequence(volumes)) = service.get("volumes") {
+            for volume in volumes.iter().filter_map(Value::as_str) {
+                let source: &str = volume.split(':').next().unwrap_or_default();
+                let is_named: bool = !source.is_empty()
+                    && !source.starts_with('.')
+                    && !source.starts_with('/')
+                    && !source.starts_with('~')
+                    && volume.contains(':');
+                if is_named && !declared_volumes.contains(source) {
+                    issues.push(format!(
+                        "Service {} uses volume {} which is not declared under volumes",
+                        name, source
+                    ));
+                }
+            }
+        }
+    }
+    issues
+}
+
+/// Checks a GitHub Actions workflow parses and builds, lints and tests the project.
+///
+/// # Arguments
+/// - `contents`: The workflow file.
+///
+/// # Returns
+/// One description per problem.
+pub fn validate_workflow_file(contents: &str) -> Vec<String> {
+    let workflow: Mapping = match parse_yaml_mapping(contents) {
+        Ok(workflow) => workflow,
+        Err(e) => return vec![e],
+    };
+
+    let mut issues: Vec<String> = vec![];
+    // YAML 1.1 parsers read a bare `on` key as `true`.
+    if !workflow.contains_key("on") && !workflow.contains_key(Value::Bool(true)) {
+        issues.push("The workflow has no triggers (on)".to_string());
+    }
+    let jobs: &Mapping = match workflow.get("jobs") {
+        Some(Value::Mapping(jobs)) if !jobs.is_empty() => jobs,
+        _ => {
+            issues.push("The workflow must define at least one job".to_string());
+            return issues;
+        }
+    };
+
+    let mut commands: Vec<&str> = vec![];
+    for (name, job) in jobs {
+        let name: &str = name.as_str().unwrap_or("<unnamed>");
+        if job.get("runs-on").is_none() {
+            issues.push(format!("Job {} has no runs-on", name));
+        }
+        let Some(steps) = job.get("steps").and_then(Value::as_sequence) else {
+            issues.push(format!("Job {} has no steps", name));
+            continue;
+        };
+        for (index, step) in steps.iter().enumerate() {
+            match (step.get("uses"), step.get("run")) {
+                (None, None) => issues.push(format!(
+                    "Step {} of job {} needs uses or run",
+                    index + 1,
+                    name
+                )),
+                (_, Some(run)) => commands.extend(run.as_str()),
+                _ => {}
+            }
+        }
+    }
+    for required in REQUIRED_CI_COMMANDS {
+        if !commands.iter().any(|command| command.contains(required)) {
+            issues.push(format!("No step runs {}", required));
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::general::read_devops_templates;
+
+    #[test]
+    fn renders_shipped_templates_that_pass_validation() {
+        let env_vars: Vec<String> = vec![
+            "WEB_SERVER_PORT".to_string(),
+            "WEB_SERVER_JWT_SECRET".to_string(),
+            "WEATHER_API_KEY".to_string(),
+        ];
+        let files: Vec<DevOpsFile> =
+            render_devops_files("web_server", &env_vars, &read_devops_templates());
+
+        assert_eq!(validate_devops_files(&files), Vec::<String>::new());
+        assert!(files.iter().all(|file| !file.contents.contains("{{")));
+
+        let compose: &DevOpsFile = &files[2];
+        assert!(compose.contents.contains("WEB_SERVER_HOST: \"0.0.0.0\""));
+        assert!(compose
+            .contents
+            .contains("WEATHER_API_KEY: \"${WEATHER_API_KEY:-}\""));
+        assert_eq!(
+            compose.contents.matches("      WEB_SERVER_PORT:").count(),
+            1
+        );
+
+        let env_example: &DevOpsFile = &files[4];
+        assert_eq!(env_example.path, ".env.example");
+        assert!(env_example
+            .contents
+            .contains("\n# Port the server listens on\nWEB_SERVER_PORT=8080\n"));
+        assert!(env_example
+            .contents
+            .contains("\n# Read by the web server\nWEATHER_API_KEY=\n"));
+    }
+
+    #[test]
+    fn lints_dockerfiles() {
+        let dockerfile: &str = "RUN echo hi\n\
+            FROM rust:latest AS builder\n\
+            ADD . /app\n\
+            RUN apt-get install \\\n    curl\n\
+            FROM debian\n\
+            USER root\n\
+            CMD ./web_server\n\
+            FROBNICATE\n";
+
+        assert_eq!(
+            lint_dockerfile(dockerfile),
+            vec![
+                "line 1: The first instruction must be FROM, found RUN",
+                "line 2: Image rust:latest uses the latest tag, pin a version",
+                "line 3: Use COPY instead of ADD for local files",
+                "line 4: apt-get install must use --no-install-recommends",
+                "line 4: apt-get install must use -y",
+                "line 4: Remove /var/lib/apt/lists after apt-get install",
+                "line 6: Image debian has no tag, pin a version",
+                "line 9: Unknown instruction FROBNICATE",
+                "The final stage must copy the build output with COPY --from",
+                "The final stage must not run as root",
+                "line 8: CMD must use the exec form, e.g. [\"binary\"]",
+                "EXPOSE the port the server listens on",
+            ]
+        );
+    }
+
+    #[test]
+    fn validates_compose_and_workflow_files() {
+        assert_eq!(
+            validate_compose_file(
+                "services:\n  api:\n    ports: 8080\n    volumes:\n      - cache:/cache\n      - ./data:/data\n"
+            ),
+            vec![
+                "Service api needs a build or image",
+                "Service api has invalid ports",
+                "Service api uses volume cache which is not declared under volumes",
+            ]
+        );
+        assert_eq!(
+            validate_compose_file("- just a list"),
+            vec!["The top level must be a mapping"]
+        );
+
+        assert_eq!(
+            validate_workflow_file(
+                "on: push\njobs:\n  build:\n    steps:\n      - name: empty\n      - run: cargo build && cargo test\n"
+            ),
+            vec![
+                "Job build has no runs-on",
+                "Step 1 of job build needs uses or run",
+                "No step runs cargo clippy",
+            ]
+        );
+        assert_eq!(
+            validate_workflow_file("name: ci\n"),
+            vec![
+                "The workflow has no triggers (on)",
+                "The workflow must define at least one job",
+            ]
+        );
+    }
+}
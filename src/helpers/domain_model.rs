@@ -0,0 +1,487 @@
+use crate::helpers::endpoint_testing::{resource_key, route_resource};
+use crate::models::agents::agent_traits::{
+    DomainModel, EntitySchema, FieldSchema, FieldType, FieldValidation, RelationKind,
+};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+
+/// The value used for every `datetime` field of synthesized payloads.
+const TEST_DATE_TIME: &str = "2024-01-01T00:00:00Z";
+
+/// Checks a domain model for internal contradictions before anything is generated from it.
+///
+/// # Arguments
+/// - `domain_model`: The domain model to check.
+///
+/// # Returns
+/// One description per problem; empty if the model is consistent.
+pub fn validate_domain_model(domain_model: &DomainModel) -> Vec<String> {
+    let mut issues: Vec<String> = vec![];
+    if domain_model.entities.is_empty() {
+        issues.push("The model has no entities".to_string());
+    }
+
+    let entity_names: HashSet<&str> = domain_model
+        .entities
+        .iter()
+        .map(|entity| entity.name.as_str())
+        .collect();
+    let mut seen_entities: HashSet<&str> = HashSet::new();
+    let mut seen_resources: HashSet<&str> = HashSet::new();
+
+    for entity in &domain_model.entities {
+        if !seen_entities.insert(entity.name.as_str()) {
+            issues.push(format!("Entity '{}' is defined twice", entity.name));
+        }
+        if !seen_resources.insert(entity.resource.as_str()) {
+            issues.push(format!(
+                "Resource '{}' is used by more than one entity",
+                entity.resource
+            ));
+        }
+
+        let mut field_names: HashSet<&str> = HashSet::new();
+        for field in &entity.fields {
+            if !field_names.insert(field.name.as_str()) {
+                issues.push(format!(
+                    "Field '{}.{}' is defined twice",
+                    entity.name, field.name
+                ));
+            }
+            issues.extend(validate_field(entity, field));
+        }
+
+        if !field_names.contains(entity.primary_key.as_str()) {
+            issues.push(format!(
+                "Primary key '{}' of '{}' is not one of its fields",
+                entity.primary_key, entity.name
+            ));
+        }
+        for unique_key in &entity.unique_keys {
+            for field_name in unique_key {
+                if !field_names.contains(field_name.as_str()) {
+                    issues.push(format!(
+                        "Unique key field '{}' of '{}' is not one of its fields",
+                        field_name, entity.name
+                    ));
+                }
+            }
+        }
+        for relation in &entity.relations {
+            if !entity_names.contains(relation.target.as_str()) {
+                issues.push(format!(
+                    "Relation '{}.{}' targets unknown entity '{}'",
+                    entity.name, relation.field, relation.target
+                ));
+            }
+            if relation.kind == RelationKind::BelongsTo
+                && !field_names.contains(relation.field.as_str())
+            {
+                issues.push(format!(
+                    "Relation field '{}' of '{}' is not one of its fields",
+                    relation.field, entity.name
+                ));
+            }
+        }
+    }
+    issues
+}
+
+fn validate_field(entity: &EntitySchema, field: &FieldSchema) -> Vec<String> {
+    let mut issues: Vec<String> = vec![];
+    let validation: &FieldValidation = &field.validation;
+    if let (Some(min_length), Some(max_length)) = (validation.min_length, validation.max_length) {
+        if min_length > max_length {
+            issues.push(format!(
+                "Field '{}.{}' has min_length above max_length",
+                entity.name, field.name
+            ));
+        }
+    }
+    if let (Some(minimum), Some(maximum)) = (validation.minimum, validation.maximum) {
+        if minimum > maximum {
+            issues.push(format!(
+                "Field '{}.{}' has minimum above maximum",
+                entity.name, field.name
+            ));
+        }
+    }
+    if !validation.allowed_values.is_empty() && field.field_type != FieldType::String {
+        issues.push(format!(
+            "Field '{}.{}' has allowed_values but is not a string",
+            entity.name, field.name
+        ));
+    }
+    issues
+}
+
+/// Finds the entity served under the first segment of a route.
+///
+/// # Arguments
+/// - `domain_model`: The domain model.
+/// - `route`: The route, e.g. `/task/{id}`.
+///
+/// # Returns
+/// The entity whose `resource` or name matches the first route segment, if any.
+pub fn entity_for_route<'a>(
+    domain_model: &'a DomainModel,
+    route: &str,
+) -> Option<&'a EntitySchema> {
+    let resource: String = resource_key(route).to_lowercase();
+    domain_model.entities.iter().find(|entity| {
+        entity.resource.trim_matches('/').to_lowercase() == resource
+            || entity.name.to_lowercase() == resource
+    })
+}
+
+/// Returns the resource the ids of every entity are created under, keyed by entity name.
+pub fn entity_resources(domain_model: &DomainModel) -> HashMap<String, String> {
+    domain_model
+        .entities
+        .iter()
+        .map(|entity| (entity.name.clone(), route_resource(&entity.resource)))
+        .collect()
+}
+
+/// Ranks a resource by how many `BelongsTo` relations must be created before it.
+///
+/// Used to create parents before their children when the endpoint tests post resources.
+pub fn creation_rank(domain_model: &DomainModel, route: &str) -> usize {
+    let mut rank: usize = 0;
+    let mut entity: Option<&EntitySchema> = entity_for_route(domain_model, route);
+    while let Some(current) = entity {
+        if rank >= domain_model.entities.len() {
+            break;
+        }
+        entity = current
+            .relations
+            .iter()
+            .find(|relation| relation.kind == RelationKind::BelongsTo)
+            .and_then(|relation| {
+                domain_model
+                    .entities
+                    .iter()
+                    .find(|candidate| candidate.name == relation.target)
+            });
+        if entity.is_some() {
+            rank += 1;
+        }
+    }
+    rank
+}
+
+/// Builds a payload for an entity that satisfies every validation rule of its fields.
+///
+/// # Arguments
+/// - `entity`: The entity to build a payload for.
+/// - `resource_id`: The value used for the primary key.
+/// - `related_ids`: Ids of already created items, keyed by entity name, used for `BelongsTo` fields.
+///
+/// # Returns
+/// A JSON object with a value for every field of the entity.
+pub fn synthesize_entity_payload(
+    entity: &EntitySchema,
+    resource_id: u64,
+    related_ids: &HashMap<String, u64>,
+) -> Value {
+    let mut payload: Map<String, Value> = Map::new();
+    for field in &entity.fields {
+        let related_id: Option<u64> = entity
+            .relations
+            .iter()
+            .find(|relation| {
+                relation.kind == RelationKind::BelongsTo && relation.field == field.name
+            })
+            .map(|relation| *related_ids.get(&relation.target).unwrap_or(&resource_id));
+        let id_value: Option<u64> = if field.name == entity.primary_key {
+            Some(resource_id)
+        } else {
+            related_id
+        };
+        payload.insert(
+            field.name.clone(),
+            synthesize_field_value(field, resource_id, id_value),
+        );
+    }
+    Value::Object(payload)
+}
+
+fn synthesize_field_value(field: &FieldSchema, resource_id: u64, id_value: Option<u64>) -> Value {
+    let validation: &FieldValidation = &field.validation;
+    match field.field_type {
+        FieldType::String => {
+            if let Some(allowed_value) = validation.allowed_values.first() {
+                return Value::String(allowed_value.clone());
+            }
+            let mut text: String = match id_value {
+                Some(id) => id.to_string(),
+                None => format!("test_{}_{}", field.name, resource_id),
+            };
+            let min_length: usize = validation.min_length.unwrap_or(0);
+            while text.chars().count() < min_length {
+                text.push('x');
+            }
+            if let Some(max_length) = validation.max_length {
+                text = text
+                    .chars()
+                    .rev()
+                    .take(max_length)
+                    .collect::<Vec<char>>()
+                    .into_iter()
+                    .rev()
+                    .collect();
+            }
+            Value::String(text)
+        }
+        FieldType::Integer => {
+            let value: f64 = match id_value {
+                Some(id) => id as f64,
+                None => clamp(
+                    1.0,
+                    validation.minimum.map(f64::ceil),
+                    validation.maximum.map(f64::floor),
+                ),
+            };
+            Value::from(value as i64)
+        }
+        FieldType::Float => Value::from(clamp(1.5, validation.minimum, validation.maximum)),
+        FieldType::Bool => Value::Bool(true),
+        FieldType::DateTime => Value::String(TEST_DATE_TIME.to_string()),
+    }
+}
+
+fn clamp(value: f64, minimum: Option<f64>, maximum: Option<f64>) -> f64 {
+    let value: f64 = minimum.map_or(value, |minimum| value.max(minimum));
+    maximum.map_or(value, |maximum| value.min(maximum))
+}
+
+/// Checks that a JSON value returned by the server is an item of the entity.
+///
+/// Missing required fields, wrongly typed values, values breaking a validation rule and
+/// fields the model does not know are all reported, so diverging field names surface as test failures.
+///
+/// # Arguments
+/// - `entity`: The entity the value should be an item of.
+/// - `value`: The value returned by the server.
+///
+/// # Returns
+/// `None` if the value matches, otherwise a description of every mismatch.
+pub fn check_entity_value(entity: &EntitySchema, value: &Value) -> Option<String> {
+    let Value::Object(values) = value else {
+        return Some(format!("Expected a {} object, got {}", entity.name, value));
+    };
+
+    let mut problems: Vec<String> = vec![];
+    for field in &entity.fields {
+        match values.get(&field.name) {
+            None | Some(Value::Null) if field.required => {
+                problems.push(format!("missing field '{}'", field.name))
+            }
+            None | Some(Value::Null) => {}
+            Some(field_value) => problems.extend(check_field_value(field, field_value)),
+        }
+    }
+    for name in values.keys() {
+        if !entity.fields.iter().any(|field| &field.name == name) {
+            problems.push(format!("unexpected field '{}'", name));
+        }
+    }
+
+    if problems.is_empty() {
+        None
+    } else {
+        Some(format!(
+            "Response does not match the {} data model: {}",
+            entity.name,
+            problems.join(", ")
+        ))
+    }
+}
+
+fn check_field_value(field: &FieldSchema, value: &Value) -> Option<String> {
+    let validation: &FieldValidation = &field.validation;
+    let type_matches: bool = match field.field_type {
+        FieldType::String | FieldType::DateTime => value.is_string(),
+        FieldType::Integer => value.is_i64() || value.is_u64(),
+        FieldType::Float => value.is_number(),
+        FieldType::Bool => value.is_boolean(),
+    };
+    if !type_matches {
+        return Some(format!(
+            "field '{}' should be {:?} but is {}",
+            field.name, field.field_type, value
+        ));
+    }
+
+    if let Some(text) = value.as_str() {
+        let length: usize = text.chars().count();
+        if validation
+            .min_length
+            .is_some_and(|min_length| length < min_length)
+            || validation
+                .max_length
+                .is_some_and(|max_length| length > max_length)
+        {
+            return Some(format!("field '{}' has an invalid length", field.name));
+        }
+        if !validation.allowed_values.is_empty()
+            && !validation
+                .allowed_values
+                .iter()
+                .any(|allowed| allowed == text)
+        {
+            return Some(format!(
+                "field '{}' is not one of the allowed values",
+                field.name
+            ));
+        }
+    }
+    if let Some(number) = value.as_f64() {
+        if validation.minimum.is_some_and(|minimum| number < minimum)
+            || validation.maximum.is_some_and(|maximum| number > maximum)
+        {
+            return Some(format!("field '{}' is out of range", field.name));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn project_model() -> DomainModel {
+        serde_json::from_value(json!({
+            "entities": [
+                {
+                    "name": "Project",
+                    "resource": "projects",
+                    "fields": [
+                        {"name": "id", "type": "integer"},
+                        {"name": "name", "type": "string", "validation": {"min_length": 12, "max_length": 20}}
+                    ]
+                },
+                {
+                    "name": "Task",
+                    "resource": "projects/{project_id}/tasks",
+                    "fields": [
+                        {"name": "id", "type": "integer"},
+                        {"name": "project_id", "type": "integer"},
+                        {"name": "status", "type": "string", "validation": {"allowed_values": ["open", "done"]}},
+                        {"name": "estimate", "type": "float", "validation": {"minimum": 2.0, "maximum": 8.0}},
+                        {"name": "priority", "type": "integer", "validation": {"minimum": 3}},
+                        {"name": "note", "type": "string", "required": false}
+                    ],
+                    "relations": [{"field": "project_id", "target": "Project", "kind": "belongs_to"}]
+                }
+            ]
+        }))
+        .expect("valid domain model")
+    }
+
+    #[test]
+    fn accepts_a_consistent_model() {
+        assert_eq!(
+            validate_domain_model(&project_model()),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn reports_contradictions_in_the_model() {
+        let mut domain_model: DomainModel = project_model();
+        let task: &mut EntitySchema = &mut domain_model.entities[1];
+        task.primary_key = "uuid".to_string();
+        task.relations[0].target = "Owner".to_string();
+        task.fields[2].field_type = FieldType::Integer;
+        task.fields[3].validation.minimum = Some(10.0);
+        let mut copy: EntitySchema = domain_model.entities[0].clone();
+        copy.fields.push(copy.fields[1].clone());
+        domain_model.entities.push(copy);
+
+        assert_eq!(
+            validate_domain_model(&domain_model),
+            vec![
+                "Field 'Task.status' has allowed_values but is not a string",
+                "Field 'Task.estimate' has minimum above maximum",
+                "Primary key 'uuid' of 'Task' is not one of its fields",
+                "Relation 'Task.project_id' targets unknown entity 'Owner'",
+                "Entity 'Project' is defined twice",
+                "Resource 'projects' is used by more than one entity",
+                "Field 'Project.name' is defined twice",
+            ]
+        );
+        assert_eq!(
+            validate_domain_model(&DomainModel { entities: vec![] }),
+            vec!["The model has no entities"]
+        );
+    }
+
+    #[test]
+    fn ranks_parents_before_their_children() {
+        let domain_model: DomainModel = project_model();
+
+        assert_eq!(
+            entity_for_route(&domain_model, "/projects/{id}").map(|entity| entity.name.as_str()),
+            Some("Project")
+        );
+        assert_eq!(creation_rank(&domain_model, "/projects"), 0);
+        assert_eq!(creation_rank(&domain_model, "/task"), 1);
+        assert_eq!(creation_rank(&domain_model, "/unknown"), 0);
+        assert_eq!(
+            entity_resources(&domain_model),
+            HashMap::from([
+                ("Project".to_string(), "projects".to_string()),
+                ("Task".to_string(), "tasks".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn synthesizes_payloads_the_model_accepts() {
+        let domain_model: DomainModel = project_model();
+        let related_ids: HashMap<String, u64> = HashMap::from([("Project".to_string(), 7)]);
+
+        for entity in &domain_model.entities {
+            let payload: Value = synthesize_entity_payload(entity, 3, &related_ids);
+            assert_eq!(check_entity_value(entity, &payload), None, "{}", payload);
+        }
+
+        let task_payload: Value =
+            synthesize_entity_payload(&domain_model.entities[1], 3, &related_ids);
+        assert_eq!(
+            task_payload,
+            json!({
+                "id": 3,
+                "project_id": 7,
+                "status": "open",
+                "estimate": 2.0,
+                "priority": 3,
+                "note": "test_note_3"
+            })
+        );
+    }
+
+    #[test]
+    fn reports_every_mismatch_with_the_model() {
+        let project: &EntitySchema = &project_model().entities[0];
+
+        assert_eq!(
+            check_entity_value(project, &json!({"id": "1", "title": "x"})),
+            Some(
+                "Response does not match the Project data model: field 'id' should be Integer but is \"1\", \
+                 missing field 'name', unexpected field 'title'"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            check_entity_value(project, &json!({"id": 1, "name": "short"})),
+            Some(
+                "Response does not match the Project data model: field 'name' has an invalid length"
+                    .to_string()
+            )
+        );
+        assert!(check_entity_value(project, &json!([])).is_some());
+    }
+}
@@ -0,0 +1,137 @@
+use reqwest::Url;
+use std::sync::Arc;
+use tokio::io::{copy_bidirectional, AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+/// Largest request head the proxy accepts before giving up on a connection.
+const MAX_REQUEST_HEAD_BYTES: usize = 16 * 1024;
+
+/// A local HTTP proxy that only lets the sandboxed web server reach allowlisted hosts.
+///
+/// Plain HTTP requests in absolute form and `CONNECT` tunnels (used for HTTPS) are forwarded
+/// when their host is allowlisted and rejected with `403 Forbidden` otherwise.
+/// The proxy stops when it is dropped.
+#[derive(Debug)]
+pub struct EgressProxy {
+    port: u16,
+    task: JoinHandle<()>,
+}
+
+impl EgressProxy {
+    /// Starts the proxy on a free loopback port.
+    ///
+    /// # Arguments
+    /// - `allowed_hosts`: Host names the web server may connect to.
+    pub async fn start(allowed_hosts: Vec<String>) -> std::io::Result<Self> {
+        let listener: TcpListener = TcpListener::bind(("127.0.0.1", 0)).await?;
+        let port: u16 = listener.local_addr()?.port();
+        let allowed_hosts: Arc<Vec<String>> = Arc::new(allowed_hosts);
+
+        let task: JoinHandle<()> = tokio::spawn(async move {
+            while let Ok((client, _)) = listener.accept().await {
+                let allowed_hosts: Arc<Vec<String>> = Arc::clone(&allowed_hosts);
+                tokio::spawn(async move {
+                    let _ = handle_connection(client, &allowed_hosts).await;
+                });
+            }
+        });
+
+        Ok(Self { port, task })
+    }
+
+    /// The loopback port the proxy listens on.
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// The proxy address in the form expected by `HTTP_PROXY` / `HTTPS_PROXY`.
+    pub fn url(&self) -> String {
+        format!("http://127.0.0.1:{}", self.port)
+    }
+}
+
+impl Drop for EgressProxy {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Extracts the host names of external urls, e.g. for the proxy allowlist.
+pub fn hosts_from_urls(urls: &[String]) -> Vec<String> {
+    urls.iter()
+        .filter_map(|url| Url::parse(url).ok())
+        .filter_map(|url| url.host_str().map(str::to_lowercase))
+        .collect()
+}
+
+async fn handle_connection(client: TcpStream, allowed_hosts: &[String]) -> std::io::Result<()> {
+    let mut reader: BufReader<TcpStream> = BufReader::new(client);
+    let mut request_head: String = String::new();
+    loop {
+        let read: usize = reader.read_line(&mut request_head).await?;
+        if read == 0 || request_head.len() > MAX_REQUEST_HEAD_BYTES {
+            return Ok(());
+        }
+        if request_head.ends_with("\r\n\r\n") || request_head == "\r\n" {
+            break;
+        }
+    }
+
+    let request_line: Vec<&str> = request_head
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .split_whitespace()
+        .collect();
+    let (method, target) = match request_line.as_slice() {
+        [method, target, ..] => (method.to_uppercase(), target.to_string()),
+        _ => return Ok(()),
+    };
+
+    let is_tunnel: bool = method == "CONNECT";
+    let destination: Option<(String, u16)> = if is_tunnel {
+        target
+            .rsplit_once(':')
+            .and_then(|(host, port)| Some((host.to_lowercase(), port.parse().ok()?)))
+    } else {
+        Url::parse(&target)
+            .ok()
+            .and_then(|url| Some((url.host_str()?.to_lowercase(), url.port_or_known_default()?)))
+    };
+
+    let (host, port) = match destination {
+        Some((host, port)) if allowed_hosts.contains(&host) => (host, port),
+        _ => {
+            reader
+                .get_mut()
+                .write_all(b"HTTP/1.1 403 Forbidden\r\nContent-Length: 0\r\n\r\n")
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let mut upstream: TcpStream = match TcpStream::connect((host.as_str(), port)).await {
+        Ok(upstream) => upstream,
+        Err(e) => {
+            reader
+                .get_mut()
+                .write_all(b"HTTP/1.1 502 Bad Gateway\r\nContent-Length: 0\r\n\r\n")
+                .await?;
+            return Err(e);
+        }
+    };
+    if is_tunnel {
+        reader
+            .get_mut()
+            .write_all(b"HTTP/1.1 200 Connection Established\r\n\r\n")
+            .await?;
+    } else {
+        upstream.write_all(request_head.as_bytes()).await?;
+    }
+    upstream.write_all(reader.buffer()).await?;
+
+    let mut client: TcpStream = reader.into_inner();
+    copy_bidirectional(&mut client, &mut upstream).await?;
+    Ok(())
+}
@@ -0,0 +1,630 @@
+use crate::helpers::domain_model::{
+    check_entity_value, creation_rank, entity_for_route, entity_resources,
+    synthesize_entity_payload,
+};
+use crate::helpers::general::send_endpoint_request;
+use crate::models::agents::agent_traits::{
+    BodySchema, DomainModel, EntitySchema, FieldType, HttpMethod, RouteObject,
+};
+use reqwest::{Client, Method};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Result of calling a single endpoint of the test server.
+///
+/// # Fields
+/// - `route`: The route as declared in the schema, e.g. `/task/{id}`.
+/// - `method`: The HTTP method used for the call.
+/// - `url`: The resolved url with path parameters substituted.
+/// - `request_body`: The synthesized payload sent with the request, if any.
+/// - `authenticated`: Whether the session token returned by login was sent with the request.
+/// - `status`: The status code returned by the server, if the call completed.
+/// - `response_body`: The raw response body, if the call completed.
+/// - `error`: A description of why the call failed, if it did.
+#[derive(Debug, Clone)]
+pub struct EndpointTestResult {
+    pub route: String,
+    pub method: HttpMethod,
+    pub url: String,
+    pub request_body: Option<Value>,
+    pub authenticated: bool,
+    pub status: Option<u16>,
+    pub response_body: Option<String>,
+    pub error: Option<String>,
+}
+
+impl EndpointTestResult {
+    /// Turns a failed endpoint call into a bug report for the fix loop.
+    ///
+    /// # Arguments
+    /// - `server_log_tail`: The last lines the web server printed.
+    ///
+    /// # Returns
+    /// `None` if the call passed, otherwise a `RuntimeBugReport`.
+    pub fn to_bug_report(&self, server_log_tail: &str) -> Option<RuntimeBugReport> {
+        let error: &String = self.error.as_ref()?;
+        Some(RuntimeBugReport {
+            route: self.route.clone(),
+            method: self.method.to_string(),
+            request: self.request_body.clone(),
+            response: self.response_body.clone(),
+            status: self.status,
+            error: error.clone(),
+            server_log_tail: server_log_tail.to_string(),
+        })
+    }
+}
+
+/// Structured description of an endpoint that misbehaved while the test server was running.
+///
+/// # Fields
+/// - `route`: The route as declared in the schema.
+/// - `method`: The HTTP method used for the call.
+/// - `request`: The payload sent with the request, if any.
+/// - `response`: The raw response body, if the call completed.
+/// - `status`: The status code returned by the server, if the call completed.
+/// - `error`: Why the call was considered a failure.
+/// - `server_log_tail`: The last lines the web server printed.
+#[derive(Debug, Serialize, Clone)]
+pub struct RuntimeBugReport {
+    pub route: String,
+    pub method: String,
+    pub request: Option<Value>,
+    pub response: Option<String>,
+    pub status: Option<u16>,
+    pub error: String,
+    pub server_log_tail: String,
+}
+
+/// Builds a JSON value satisfying a `request_body` or `response` schema from `RouteObject`.
+///
+/// # Arguments
+/// - `schema`: The schema, e.g. `{"id": "integer", "name": "string", "completed": "bool"}`.
+/// - `resource_id`: The value used for any `id` field so created resources can be found again.
+///
+/// # Returns
+/// A payload with placeholder values for each field of the schema.
+pub fn synthesize_payload(schema: &BodySchema, resource_id: u64) -> Value {
+    synthesize_field("", schema, resource_id)
+}
+
+fn synthesize_field(field_name: &str, schema: &BodySchema, resource_id: u64) -> Value {
+    match schema {
+        BodySchema::Object(fields) => {
+            let mut payload: Map<String, Value> = Map::new();
+            for (name, field_schema) in fields {
+                payload.insert(
+                    name.clone(),
+                    synthesize_field(name, field_schema, resource_id),
+                );
+            }
+            Value::Object(payload)
+        }
+        BodySchema::Array(item) => match item.as_ref() {
+            BodySchema::Unknown | BodySchema::None => Value::Array(vec![]),
+            item => Value::Array(vec![synthesize_field(field_name, item, resource_id)]),
+        },
+        BodySchema::Field(FieldType::Integer | FieldType::Float)
+            if field_name == "id" || field_name.ends_with("_id") =>
+        {
+            Value::from(resource_id)
+        }
+        BodySchema::Field(FieldType::Integer | FieldType::Float) => Value::from(1),
+        BodySchema::Field(FieldType::Bool) => Value::Bool(true),
+        BodySchema::Field(FieldType::String) => {
+            Value::String(format!("test_{}_{}", field_name, resource_id))
+        }
+        BodySchema::Field(FieldType::DateTime) => Value::String("2024-01-01T00:00:00Z".to_string()),
+        BodySchema::None | BodySchema::Unknown => Value::Null,
+    }
+}
+
+/// Checks whether a JSON value returned by the server matches a `response` schema.
+///
+/// Missing and unknown schemas accept any value.
+pub fn shape_matches(schema: &BodySchema, value: &Value) -> bool {
+    match schema {
+        BodySchema::Object(fields) => match value {
+            Value::Object(values) => fields.iter().all(|(name, field_schema)| {
+                values
+                    .get(name)
+                    .is_some_and(|field_value| shape_matches(field_schema, field_value))
+            }),
+            _ => false,
+        },
+        BodySchema::Array(item) => match value {
+            Value::Array(values) => values.iter().all(|value| shape_matches(item, value)),
+            _ => false,
+        },
+        BodySchema::Field(FieldType::Integer | FieldType::Float) => value.is_number(),
+        BodySchema::Field(FieldType::Bool) => value.is_boolean(),
+        BodySchema::Field(FieldType::String | FieldType::DateTime) => value.is_string(),
+        BodySchema::None | BodySchema::Unknown => true,
+    }
+}
+
+/// Returns the first static segment of a route, used to group routes by resource.
+pub fn resource_key(route: &str) -> String {
+    route
+        .trim_start_matches('/')
+        .split('/')
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Returns the last static segment of a route: the resource a `POST` to it creates, and the resource a
+/// trailing `{param}` identifies, e.g. `tasks` for `/users/{user_id}/tasks/{task_id}`.
+pub fn route_resource(route: &str) -> String {
+    route
+        .split('/')
+        .rfind(|segment| !segment.is_empty() && !is_path_param(segment))
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn is_path_param(segment: &str) -> bool {
+    segment.starts_with('{') && segment.ends_with('}')
+}
+
+/// Replaces every `{param}` segment of a route with the id created for the resource named by the segment
+/// before it, e.g. `/users/{user_id}/tasks/{task_id}` takes the ids created for `users` and `tasks`.
+///
+/// # Arguments
+/// - `route`: The route as declared in the schema.
+/// - `created_ids`: The ids of the resources created so far, keyed by [`route_resource`].
+/// - `default_id`: The id used for a resource that was not created.
+pub fn substitute_path_params(
+    route: &str,
+    created_ids: &HashMap<String, u64>,
+    default_id: u64,
+) -> String {
+    let mut resource: &str = "";
+    route
+        .split('/')
+        .map(|segment| {
+            if is_path_param(segment) {
+                created_ids.get(resource).unwrap_or(&default_id).to_string()
+            } else {
+                if !segment.is_empty() {
+                    resource = segment;
+                }
+                segment.to_string()
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("/")
+}
+
+/// Orders sign-up routes first and login routes second, so later calls can send the session token,
+/// and logout routes last, so the token they revoke is not needed anymore.
+fn auth_order(route_object: &RouteObject) -> u8 {
+    let route: String = route_object.route.to_lowercase();
+    if ["logout", "log_out", "signout", "sign_out"]
+        .iter()
+        .any(|name| route.contains(name))
+    {
+        return 3;
+    }
+    if route_object.method != HttpMethod::Post {
+        return 2;
+    }
+    if route.contains("register") || route.contains("signup") || route.contains("sign_up") {
+        0
+    } else if route.contains("login") || route.contains("signin") {
+        1
+    } else {
+        2
+    }
+}
+
+/// Reads a session token from a login response: a JSON field named like `token`, or a JSON string.
+fn extract_session_token(body: &str) -> Option<String> {
+    match serde_json::from_str::<Value>(body).ok()? {
+        Value::Object(fields) => fields
+            .iter()
+            .find(|(name, value)| name.to_lowercase().contains("token") && value.is_string())
+            .and_then(|(_, value)| value.as_str().map(str::to_string)),
+        Value::String(token) if !token.contains(' ') => Some(token),
+        _ => None,
+    }
+}
+
+/// Orders routes in the order they are called: by [`auth_order`], then by [`method_order`], then parents
+/// of the domain model before their children.
+fn order_routes<'a>(
+    routes: &'a [RouteObject],
+    domain_model: Option<&DomainModel>,
+) -> Vec<&'a RouteObject> {
+    let mut ordered_routes: Vec<&RouteObject> = routes.iter().collect();
+    ordered_routes.sort_by_key(|route_object| {
+        (
+            auth_order(route_object),
+            method_order(route_object.method),
+            domain_model.map_or(0, |model| creation_rank(model, &route_object.route)),
+        )
+    });
+    ordered_routes
+}
+
+/// Orders routes so resources are created before they are read, updated and deleted.
+fn method_order(method: HttpMethod) -> u8 {
+    match method {
+        HttpMethod::Post => 0,
+        HttpMethod::Get => 1,
+        HttpMethod::Put | HttpMethod::Patch => 2,
+        HttpMethod::Delete => 3,
+        HttpMethod::Head | HttpMethod::Options => 4,
+    }
+}
+
+/// Converts a route method into the method sent by `reqwest`.
+fn request_method(method: HttpMethod) -> Method {
+    match method {
+        HttpMethod::Get => Method::GET,
+        HttpMethod::Post => Method::POST,
+        HttpMethod::Put => Method::PUT,
+        HttpMethod::Patch => Method::PATCH,
+        HttpMethod::Delete => Method::DELETE,
+        HttpMethod::Head => Method::HEAD,
+        HttpMethod::Options => Method::OPTIONS,
+    }
+}
+
+/// Picks an id for test resources that is unlikely to collide with data left by earlier runs.
+fn fresh_resource_id() -> u64 {
+    let nanos: u128 = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or_default();
+    100_000 + (nanos % 900_000) as u64
+}
+
+/// Exercises every route of the test server, including dynamic and mutating ones.
+///
+/// Resources are created first via POST using payloads synthesized from `request_body`,
+/// the created ids are substituted into the path parameters naming their resource and the GET,
+/// PUT and DELETE routes are called afterwards. Each response is checked for a successful status code
+/// and, where a `response` shape is described, for a body matching that shape.
+///
+/// Sign-up and login routes are called first; a session token returned by login is sent as a
+/// bearer token with every later request. Logout routes are called last.
+///
+/// When a domain model is given, routes of its entities send payloads built from the entity
+/// fields and validation rules, parents are created before their children and GET responses
+/// are checked against the entity fields instead of the extracted shape.
+///
+/// # Arguments
+/// - `client`: The `reqwest::Client` used to make the requests.
+/// - `base_url`: The address of the test server, e.g. `http://127.0.0.1:8080`.
+/// - `routes`: The routes extracted from the backend code.
+/// - `domain_model`: The domain model the server should implement, if one was designed.
+///
+/// # Returns
+/// One `EndpointTestResult` per route, in the order they were called.
+pub async fn run_endpoint_tests(
+    client: &Client,
+    base_url: &str,
+    routes: &[RouteObject],
+    domain_model: Option<&DomainModel>,
+) -> Vec<EndpointTestResult> {
+    let default_id: u64 = fresh_resource_id();
+    let mut created_ids: HashMap<String, u64> = HashMap::new();
+    let mut session_token: Option<String> = None;
+
+    let mut results: Vec<EndpointTestResult> = vec![];
+    for route_object in order_routes(routes, domain_model) {
+        let resource: String = route_resource(&route_object.route);
+        let resource_id: u64 = *created_ids.get(&resource).unwrap_or(&default_id);
+
+        let url: String = format!(
+            "{}{}",
+            base_url,
+            substitute_path_params(&route_object.route, &created_ids, default_id)
+        );
+        let entity: Option<&EntitySchema> =
+            domain_model.and_then(|model| entity_for_route(model, &route_object.route));
+        let sends_entity: bool = matches!(
+            route_object.method,
+            HttpMethod::Post | HttpMethod::Put | HttpMethod::Patch
+        );
+        let request_body: Option<Value> = match (entity, domain_model) {
+            (Some(entity), Some(model)) if sends_entity => {
+                let related_ids: HashMap<String, u64> = entity_resources(model)
+                    .into_iter()
+                    .filter_map(|(name, resource)| {
+                        created_ids
+                            .get(&resource)
+                            .map(|created_id| (name, *created_id))
+                    })
+                    .collect();
+                Some(synthesize_entity_payload(entity, resource_id, &related_ids))
+            }
+            _ => route_object
+                .request_body
+                .is_described()
+                .then(|| synthesize_payload(&route_object.request_body, resource_id)),
+        };
+
+        let mut result: EndpointTestResult = EndpointTestResult {
+            route: route_object.route.clone(),
+            method: route_object.method,
+            url: url.clone(),
+            request_body: request_body.clone(),
+            authenticated: session_token.is_some(),
+            status: None,
+            response_body: None,
+            error: None,
+        };
+
+        let method: Method = request_method(route_object.method);
+
+        match send_endpoint_request(
+            client,
+            method.clone(),
+            &url,
+            request_body.as_ref(),
+            session_token.as_deref(),
+        )
+        .await
+        {
+            Ok((status, body)) => {
+                result.status = Some(status);
+                result.error = check_response(route_object, entity, status, &body);
+
+                if auth_order(route_object) == 1 && result.error.is_none() {
+                    session_token = extract_session_token(&body).or(session_token);
+                }
+
+                if method == Method::POST && result.error.is_none() {
+                    let created_id: u64 = serde_json::from_str::<Value>(&body)
+                        .ok()
+                        .and_then(|value| value.get("id").and_then(Value::as_u64))
+                        .unwrap_or(resource_id);
+                    created_ids.entry(resource).or_insert(created_id);
+                }
+                result.response_body = Some(body);
+            }
+            Err(e) => result.error = Some(format!("Request failed: {}", e)),
+        }
+        results.push(result);
+    }
+
+    results
+}
+
+/// Validates the status code and body of a response against the route description.
+///
+/// GET responses of routes serving an entity of the domain model are checked against the entity.
+///
+/// # Returns
+/// `None` if the response is acceptable, otherwise a description of the problem.
+fn check_response(
+    route_object: &RouteObject,
+    entity: Option<&EntitySchema>,
+    status: u16,
+    body: &str,
+) -> Option<String> {
+    if !(200..300).contains(&status) {
+        return Some(format!("Unexpected status code {}", status));
+    }
+
+    if let Some(entity) = entity.filter(|_| route_object.method == HttpMethod::Get) {
+        return match serde_json::from_str::<Value>(body) {
+            Ok(value) => check_entity_response(entity, &value),
+            Err(_) => Some("Response body is not valid JSON".to_string()),
+        };
+    }
+
+    if !route_object.response.is_described() {
+        return None;
+    }
+
+    match serde_json::from_str::<Value>(body) {
+        Ok(value) if shape_matches(&route_object.response, &value) => None,
+        Ok(_) => Some(format!(
+            "Response does not match expected shape {}",
+            route_object.response
+        )),
+        Err(_) => Some("Response body is not valid JSON".to_string()),
+    }
+}
+
+/// Checks a GET response holding a single item, a list of items or a map of items of an entity.
+fn check_entity_response(entity: &EntitySchema, value: &Value) -> Option<String> {
+    match value {
+        Value::Array(items) => items
+            .iter()
+            .find_map(|item| check_entity_value(entity, item)),
+        Value::Object(fields) if !fields.contains_key(&entity.primary_key) => fields
+            .values()
+            .find_map(|item| check_entity_value(entity, item)),
+        _ => check_entity_value(entity, value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::route_extraction::extract_route_objects;
+    use serde_json::json;
+
+    #[test]
+    fn calls_logout_after_the_protected_routes_of_the_auth_template() {
+        let code: String = std::fs::read_to_string("source/templates/auth.rs").unwrap();
+        let routes: Vec<RouteObject> = extract_route_objects(&code).unwrap();
+
+        let order: Vec<(HttpMethod, &str)> = order_routes(&routes, None)
+            .into_iter()
+            .map(|route_object| (route_object.method, route_object.route.as_str()))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                (HttpMethod::Post, "/register"),
+                (HttpMethod::Post, "/login"),
+                (HttpMethod::Get, "/me"),
+                (HttpMethod::Get, "/openapi.json"),
+                (HttpMethod::Post, "/logout"),
+            ]
+        );
+    }
+
+    #[test]
+    fn ranks_auth_routes() {
+        let route = |method: HttpMethod, route: &str| {
+            RouteObject::new(
+                method,
+                route.to_string(),
+                BodySchema::None,
+                BodySchema::None,
+            )
+        };
+
+        assert_eq!(auth_order(&route(HttpMethod::Post, "/auth/sign_up")), 0);
+        assert_eq!(auth_order(&route(HttpMethod::Post, "/users/login")), 1);
+        assert_eq!(auth_order(&route(HttpMethod::Get, "/login/help")), 2);
+        assert_eq!(auth_order(&route(HttpMethod::Post, "/task")), 2);
+        assert_eq!(
+            auth_order(&route(HttpMethod::Delete, "/session/signout")),
+            3
+        );
+        assert_eq!(auth_order(&route(HttpMethod::Get, "/logout")), 3);
+    }
+
+    #[test]
+    fn substitutes_the_id_of_each_path_param_resource() {
+        let created_ids: HashMap<String, u64> =
+            HashMap::from([("users".to_string(), 7), ("tasks".to_string(), 42)]);
+
+        assert_eq!(
+            substitute_path_params("/users/{user_id}/tasks/{task_id}", &created_ids, 1),
+            "/users/7/tasks/42"
+        );
+        assert_eq!(
+            substitute_path_params("/tasks/{id}/complete", &created_ids, 1),
+            "/tasks/42/complete"
+        );
+        assert_eq!(
+            substitute_path_params("/projects/{id:\\d+}", &created_ids, 1),
+            "/projects/1"
+        );
+        assert_eq!(substitute_path_params("/{id}", &created_ids, 1), "/1");
+    }
+
+    #[test]
+    fn names_the_resource_a_route_creates() {
+        assert_eq!(route_resource("/users"), "users");
+        assert_eq!(route_resource("/users/{user_id}/tasks"), "tasks");
+        assert_eq!(route_resource("/users/{user_id}/tasks/{task_id}"), "tasks");
+        assert_eq!(route_resource("/"), "");
+    }
+
+    #[test]
+    fn synthesizes_payloads_from_schemas() {
+        let schema: BodySchema = BodySchema::from(json!({
+            "id": "integer",
+            "user_id": "integer",
+            "title": "string",
+            "done": "bool",
+            "score": "float",
+            "due": "datetime",
+            "tags": ["string"],
+            "notes": []
+        }));
+
+        assert_eq!(
+            synthesize_payload(&schema, 42),
+            json!({
+                "id": 42,
+                "user_id": 42,
+                "title": "test_title_42",
+                "done": true,
+                "score": 1,
+                "due": "2024-01-01T00:00:00Z",
+                "tags": ["test_tags_42"],
+                "notes": []
+            })
+        );
+        assert_eq!(synthesize_payload(&BodySchema::None, 42), Value::Null);
+    }
+
+    #[test]
+    fn matches_response_shapes() {
+        let schema: BodySchema = BodySchema::from(json!([{"id": "integer", "title": "string"}]));
+
+        assert!(shape_matches(
+            &schema,
+            &json!([{"id": 1, "title": "a", "extra": true}])
+        ));
+        assert!(shape_matches(&schema, &json!([])));
+        assert!(!shape_matches(&schema, &json!([{"id": "1", "title": "a"}])));
+        assert!(!shape_matches(&schema, &json!([{"id": 1}])));
+        assert!(!shape_matches(&schema, &json!({"id": 1, "title": "a"})));
+        assert!(shape_matches(&BodySchema::Unknown, &json!("anything")));
+    }
+
+    #[test]
+    fn reports_failed_calls_as_runtime_bugs() {
+        let route_object: RouteObject = RouteObject::new(
+            HttpMethod::Get,
+            "/task/{id}".to_string(),
+            BodySchema::None,
+            BodySchema::from(json!({"id": "integer", "title": "string"})),
+        );
+        let mut result: EndpointTestResult = EndpointTestResult {
+            route: route_object.route.clone(),
+            method: route_object.method,
+            url: "http://127.0.0.1:8080/task/7".to_string(),
+            request_body: None,
+            authenticated: false,
+            status: Some(200),
+            response_body: Some(r#"{"id": 7}"#.to_string()),
+            error: None,
+        };
+        assert!(result.to_bug_report("").is_none());
+
+        result.error = check_response(&route_object, None, 200, r#"{"id": 7}"#);
+        let bug_report: RuntimeBugReport = result.to_bug_report("thread panicked").unwrap();
+        assert_eq!(bug_report.route, "/task/{id}");
+        assert_eq!(bug_report.method, "GET");
+        assert_eq!(bug_report.status, Some(200));
+        assert!(bug_report
+            .error
+            .starts_with("Response does not match expected shape"));
+        assert_eq!(bug_report.server_log_tail, "thread panicked");
+    }
+
+    #[test]
+    fn checks_status_codes_and_bodies() {
+        let route_object: RouteObject = RouteObject::new(
+            HttpMethod::Get,
+            "/task".to_string(),
+            BodySchema::None,
+            BodySchema::from(json!([{"id": "integer"}])),
+        );
+
+        assert_eq!(
+            check_response(&route_object, None, 500, ""),
+            Some("Unexpected status code 500".to_string())
+        );
+        assert_eq!(
+            check_response(&route_object, None, 200, "<html>"),
+            Some("Response body is not valid JSON".to_string())
+        );
+        assert_eq!(
+            check_response(&route_object, None, 200, r#"[{"id": 1}]"#),
+            None
+        );
+
+        let untyped_route: RouteObject = RouteObject::new(
+            HttpMethod::Delete,
+            "/task/{id}".to_string(),
+            BodySchema::None,
+            BodySchema::None,
+        );
+        assert_eq!(check_response(&untyped_route, None, 204, ""), None);
+    }
+}
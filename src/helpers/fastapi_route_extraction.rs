@@ -0,0 +1,1065 @@
+use crate::models::agents::agent_traits::{BodySchema, FieldType, HttpMethod, RouteObject};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Route decorators besides the method shortcuts like `@app.get(...)`.
+const API_ROUTE_DECORATOR: &str = "api_route";
+
+/// Annotations serialized as JSON integers.
+const INTEGER_TYPES: &[&str] = &["int", "conint", "PositiveInt", "NonNegativeInt"];
+
+/// Annotations serialized as JSON floats.
+const FLOAT_TYPES: &[&str] = &["float", "Decimal", "confloat", "PositiveFloat"];
+
+/// Annotations serialized as RFC 3339 timestamps.
+const DATETIME_TYPES: &[&str] = &["datetime", "AwareDatetime", "NaiveDatetime"];
+
+/// Annotations serialized as JSON strings.
+const STRING_TYPES: &[&str] = &[
+    "str",
+    "constr",
+    "UUID",
+    "date",
+    "time",
+    "EmailStr",
+    "HttpUrl",
+    "AnyUrl",
+    "SecretStr",
+    "Path",
+];
+
+/// Annotations whose value is their first type argument.
+const TRANSPARENT_WRAPPERS: &[&str] = &["Optional", "Annotated", "Required", "NotRequired"];
+
+/// Annotations serialized as JSON arrays.
+const SEQUENCE_TYPES: &[&str] = &[
+    "list",
+    "List",
+    "set",
+    "Set",
+    "frozenset",
+    "tuple",
+    "Tuple",
+    "Sequence",
+];
+
+/// Annotations serialized as JSON objects with arbitrary keys.
+const MAP_TYPES: &[&str] = &["dict", "Dict", "Mapping"];
+
+/// Return annotations of handlers answering without a JSON body of a known shape.
+const RESPONSE_CLASSES: &[&str] = &[
+    "Response",
+    "FileResponse",
+    "HTMLResponse",
+    "PlainTextResponse",
+    "RedirectResponse",
+    "StreamingResponse",
+];
+
+/// Parameter defaults marking a parameter as something other than the request body.
+const NON_BODY_DEFAULTS: &[&str] = &[
+    "Depends", "Header", "Query", "Path", "Cookie", "Form", "File", "Security",
+];
+
+/// Nesting depth after which schemas of recursive models are cut.
+const MAX_SCHEMA_DEPTH: usize = 8;
+
+/// Nesting depth after which `include_router(...)` mounts are no longer followed.
+const MAX_MOUNT_DEPTH: usize = 8;
+
+/// A statement of the Python source, with the lines it continues over joined and comments removed.
+///
+/// # Fields
+/// - `indent`: The indentation of its first line.
+/// - `text`: The statement without leading indentation.
+struct LogicalLine {
+    indent: usize,
+    text: String,
+}
+
+/// A route decorator waiting for the handler it decorates.
+///
+/// # Fields
+/// - `router`: The `FastAPI` or `APIRouter` variable the route is registered on.
+/// - `path`: The path given to the decorator.
+/// - `methods`: The HTTP methods of the route.
+/// - `response_model`: The `response_model=` annotation, if any.
+/// - `no_content`: Whether the decorator sets status 204 or 304, which send no body.
+struct RouteDecorator {
+    router: String,
+    path: String,
+    methods: Vec<HttpMethod>,
+    response_model: Option<String>,
+    no_content: bool,
+}
+
+/// A class declared in the code, before its bases are resolved.
+///
+/// # Fields
+/// - `name`: The class name.
+/// - `bases`: The names of its base classes, without module prefixes.
+/// - `fields`: The `(name, annotation)` of the annotated attributes of its body.
+struct ClassDefinition {
+    name: String,
+    bases: Vec<String>,
+    fields: Vec<(String, String)>,
+}
+
+/// The pydantic models, enums and routers of a FastAPI app.
+///
+/// # Fields
+/// - `models`: The fields of every `BaseModel` subclass, in declaration order.
+/// - `enums`: The `Enum` subclasses, serialized as strings.
+/// - `router_prefixes`: The `prefix=` each `APIRouter(...)` variable was created with; apps have none.
+/// - `mounts`: `(parent, child, prefix)` for every `parent.include_router(child, prefix=...)`.
+#[derive(Default)]
+struct FastApiIndex {
+    models: HashMap<String, Vec<(String, String)>>,
+    enums: HashSet<String>,
+    router_prefixes: HashMap<String, String>,
+    mounts: Vec<(String, String, String)>,
+}
+
+/// Builds the endpoint schema of FastAPI code without asking the model.
+///
+/// Reads `@app.get("/path")` style decorators, `@router.api_route("/path", methods=[...])` and the
+/// prefixes of `APIRouter(prefix=...)` and `include_router(router, prefix=...)`. The request body of
+/// a route is the handler parameter annotated with a pydantic model. The response is the
+/// `response_model=` of the decorator, otherwise the return annotation of the handler; `Response`
+/// classes and `None` give `BodySchema::None`, annotations that cannot be resolved `BodySchema::Unknown`.
+/// WebSocket routes are skipped. Path converters like `{file:path}` are written as `{file}`.
+///
+/// # Arguments
+/// - `code`: The generated Python source.
+///
+/// # Returns
+/// One `RouteObject` per route, in the order they are declared, without duplicates.
+pub fn extract_fastapi_route_objects(code: &str) -> Vec<RouteObject> {
+    let lines: Vec<LogicalLine> = logical_lines(code);
+    let index: FastApiIndex = FastApiIndex::read(&lines);
+
+    let mut route_objects: Vec<RouteObject> = vec![];
+    let mut decorators: Vec<RouteDecorator> = vec![];
+    for line in &lines {
+        if let Some(decorator) = line.text.strip_prefix('@') {
+            decorators.extend(route_decorator(decorator.trim()));
+            continue;
+        }
+        let Some(signature) = handler_signature(&line.text) else {
+            decorators.clear();
+            continue;
+        };
+
+        for decorator in decorators.drain(..) {
+            let request_body: BodySchema = signature
+                .parameters
+                .iter()
+                .find_map(|parameter| index.body_parameter_schema(parameter))
+                .unwrap_or_default();
+            let response: BodySchema = if decorator.no_content {
+                BodySchema::None
+            } else {
+                match decorator
+                    .response_model
+                    .as_deref()
+                    .or(signature.return_annotation.as_deref())
+                {
+                    Some(annotation) => index.response_schema(annotation),
+                    None => BodySchema::Unknown,
+                }
+            };
+
+            for prefix in index.mount_prefixes(&decorator.router, 0) {
+                let mut route: String =
+                    strip_path_converters(&format!("{}{}", prefix, decorator.path));
+                if !route.starts_with('/') {
+                    route.insert(0, '/');
+                }
+                for method in &decorator.methods {
+                    let route_object: RouteObject = RouteObject::new(
+                        *method,
+                        route.clone(),
+                        request_body.clone(),
+                        response.clone(),
+                    );
+                    if !route_objects.contains(&route_object) {
+                        route_objects.push(route_object);
+                    }
+                }
+            }
+        }
+    }
+    route_objects
+}
+
+impl FastApiIndex {
+    /// Reads the models, enums, routers and router mounts of the code.
+    fn read(lines: &[LogicalLine]) -> Self {
+        let mut index: FastApiIndex = FastApiIndex::default();
+        let mut classes: Vec<ClassDefinition> = vec![];
+        let mut open_class: Option<(usize, Option<usize>)> = None;
+
+        for line in lines {
+            if let Some((class_indent, body_indent)) = open_class {
+                let in_body: bool = line.indent > class_indent;
+                match (in_body, body_indent) {
+                    (true, None) => open_class = Some((class_indent, Some(line.indent))),
+                    (true, Some(body_indent)) if line.indent > body_indent => continue,
+                    (true, Some(_)) => {}
+                    (false, _) => open_class = None,
+                }
+                if in_body {
+                    if let Some(field) = model_field(&line.text) {
+                        if let Some(class) = classes.last_mut() {
+                            class.fields.push(field);
+                        }
+                    }
+                    continue;
+                }
+            }
+
+            if let Some((name, bases)) = class_header(&line.text) {
+                classes.push(ClassDefinition {
+                    name,
+                    bases,
+                    fields: vec![],
+                });
+                open_class = Some((line.indent, None));
+            } else if let Some((target, value)) = line
+                .text
+                .split_once('=')
+                .filter(|(target, _)| is_identifier(target.trim()))
+            {
+                // Calls with keyword arguments like `app.include_router(router, prefix="/api")` are not assignments
+                let target: &str = target.trim();
+                if let Some((callee, args)) = call_parts(value.trim()) {
+                    match last_segment(callee) {
+                        "APIRouter" => {
+                            let prefix: String = keyword_argument(args, "prefix")
+                                .and_then(string_literal)
+                                .unwrap_or_default();
+                            index.router_prefixes.insert(target.to_string(), prefix);
+                        }
+                        "FastAPI" => {
+                            index.router_prefixes.remove(target);
+                        }
+                        _ => {}
+                    }
+                }
+            } else if let Some((callee, args)) = call_parts(&line.text) {
+                if let Some(parent) = callee.strip_suffix(".include_router") {
+                    let Some(child) = positional_arguments(args)
+                        .first()
+                        .map(|child| child.trim().to_string())
+                        .or_else(|| keyword_argument(args, "router").map(str::to_string))
+                    else {
+                        continue;
+                    };
+                    let prefix: String = keyword_argument(args, "prefix")
+                        .and_then(string_literal)
+                        .unwrap_or_default();
+                    index
+                        .mounts
+                        .push((parent.trim().to_string(), child, prefix));
+                }
+            }
+        }
+
+        // Enums and models may subclass classes declared later, so bases are resolved once all are read
+        let mut changed: bool = true;
+        while changed {
+            changed = false;
+            for class in &classes {
+                if index.models.contains_key(&class.name) || index.enums.contains(&class.name) {
+                    continue;
+                }
+                if class.bases.iter().any(|base| base.ends_with("Enum")) {
+                    index.enums.insert(class.name.clone());
+                    changed = true;
+                    continue;
+                }
+                let is_model: bool = class
+                    .bases
+                    .iter()
+                    .any(|base| base == "BaseModel" || index.models.contains_key(base));
+                if !is_model {
+                    continue;
+                }
+                let mut model_fields: Vec<(String, String)> = vec![];
+                for base in &class.bases {
+                    for field in index.models.get(base).into_iter().flatten() {
+                        model_fields.retain(|(field_name, _)| *field_name != field.0);
+                        model_fields.push(field.clone());
+                    }
+                }
+                for field in &class.fields {
+                    model_fields.retain(|(field_name, _)| *field_name != field.0);
+                    model_fields.push(field.clone());
+                }
+                index.models.insert(class.name.clone(), model_fields);
+                changed = true;
+            }
+        }
+        index
+    }
+
+    /// Lists the path prefixes a router's routes are served under.
+    ///
+    /// Apps and routers that are never included are served under their own prefix only.
+    fn mount_prefixes(&self, router: &str, depth: usize) -> Vec<String> {
+        let own_prefix: String = self
+            .router_prefixes
+            .get(router)
+            .cloned()
+            .unwrap_or_default();
+        let parents: Vec<&(String, String, String)> = self
+            .mounts
+            .iter()
+            .filter(|(_, child, _)| child == router)
+            .collect();
+        if parents.is_empty() || depth > MAX_MOUNT_DEPTH {
+            return vec![own_prefix];
+        }
+
+        let mut prefixes: Vec<String> = vec![];
+        for (parent, _, include_prefix) in parents {
+            for parent_prefix in self.mount_prefixes(parent, depth + 1) {
+                let prefix: String = format!("{}{}{}", parent_prefix, include_prefix, own_prefix);
+                if !prefixes.contains(&prefix) {
+                    prefixes.push(prefix);
+                }
+            }
+        }
+        prefixes
+    }
+
+    /// Describes the request body of a handler parameter, `None` if the parameter is not the body.
+    fn body_parameter_schema(&self, parameter: &str) -> Option<BodySchema> {
+        let (_, annotation) = parameter.split_once(':')?;
+        let (annotation, default): (&str, &str) = split_top_level(annotation, '=')
+            .map_or((annotation, ""), |(annotation, default)| {
+                (annotation, default)
+            });
+        let default_callee: &str =
+            call_parts(default.trim()).map_or("", |(callee, _)| last_segment(callee));
+        if NON_BODY_DEFAULTS.contains(&default_callee) {
+            return None;
+        }
+        let is_body: bool =
+            default_callee == "Body" || self.referenced_model(annotation.trim(), 0).is_some();
+        is_body.then(|| self.annotation_schema(annotation.trim(), 0))
+    }
+
+    /// Describes the response of a route from its response model or return annotation.
+    fn response_schema(&self, annotation: &str) -> BodySchema {
+        let annotation: &str = unquote(annotation.trim());
+        if annotation == "None" || RESPONSE_CLASSES.contains(&last_segment(annotation)) {
+            return BodySchema::None;
+        }
+        self.annotation_schema(annotation, 0)
+    }
+
+    /// Returns the model an annotation is or holds, e.g. `Task` for `list[Task] | None`.
+    fn referenced_model(&self, annotation: &str, depth: usize) -> Option<String> {
+        if depth > MAX_SCHEMA_DEPTH {
+            return None;
+        }
+        let annotation: &str = unquote(annotation.trim());
+        if self.models.contains_key(annotation) {
+            return Some(annotation.to_string());
+        }
+        let (name, type_args) = annotation_parts(annotation);
+        if !TRANSPARENT_WRAPPERS.contains(&name)
+            && !SEQUENCE_TYPES.contains(&name)
+            && name != "Union"
+        {
+            return None;
+        }
+        type_args
+            .iter()
+            .find_map(|type_arg| self.referenced_model(type_arg, depth + 1))
+    }
+
+    /// Describes how an annotation is serialized, e.g. `{"id": "integer", "name": "string"}`.
+    fn annotation_schema(&self, annotation: &str, depth: usize) -> BodySchema {
+        if depth > MAX_SCHEMA_DEPTH {
+            return BodySchema::Unknown;
+        }
+        let annotation: &str = unquote(annotation.trim());
+        let (name, type_args) = annotation_parts(annotation);
+        let first_arg_schema = || {
+            type_args.first().map_or(BodySchema::Unknown, |type_arg| {
+                self.annotation_schema(type_arg, depth + 1)
+            })
+        };
+
+        if name.is_empty() {
+            BodySchema::Unknown
+        } else if name == "None" {
+            BodySchema::None
+        } else if name == "Union" {
+            let members: Vec<&str> = type_args
+                .iter()
+                .copied()
+                .filter(|member| member.trim() != "None")
+                .collect();
+            match members.as_slice() {
+                [member] => self.annotation_schema(member, depth + 1),
+                _ => BodySchema::Unknown,
+            }
+        } else if name == "bool" || name == "StrictBool" {
+            BodySchema::Field(FieldType::Bool)
+        } else if INTEGER_TYPES.contains(&name) || name == "StrictInt" {
+            BodySchema::Field(FieldType::Integer)
+        } else if FLOAT_TYPES.contains(&name) || name == "StrictFloat" {
+            BodySchema::Field(FieldType::Float)
+        } else if DATETIME_TYPES.contains(&name) {
+            BodySchema::Field(FieldType::DateTime)
+        } else if STRING_TYPES.contains(&name) || name == "Literal" || self.enums.contains(name) {
+            BodySchema::Field(FieldType::String)
+        } else if TRANSPARENT_WRAPPERS.contains(&name) {
+            first_arg_schema()
+        } else if SEQUENCE_TYPES.contains(&name) {
+            BodySchema::Array(Box::new(first_arg_schema()))
+        } else if MAP_TYPES.contains(&name) {
+            BodySchema::Object(BTreeMap::new())
+        } else if let Some(fields) = self.models.get(name) {
+            BodySchema::Object(
+                fields
+                    .iter()
+                    .map(|(field_name, field_annotation)| {
+                        (
+                            field_name.clone(),
+                            self.annotation_schema(field_annotation, depth + 1),
+                        )
+                    })
+                    .collect(),
+            )
+        } else {
+            BodySchema::Unknown
+        }
+    }
+}
+
+/// The parameters and return annotation of a `def` or `async def` statement.
+///
+/// # Fields
+/// - `parameters`: The parameters as written, e.g. `task: Task`.
+/// - `return_annotation`: The annotation after `->`, if any.
+struct HandlerSignature {
+    parameters: Vec<String>,
+    return_annotation: Option<String>,
+}
+
+/// Splits Python source into statements, joining lines continued inside brackets or after `\`.
+///
+/// Comments are removed and strings are kept as written, so a `#` inside a string is not a comment.
+fn logical_lines(code: &str) -> Vec<LogicalLine> {
+    let mut lines: Vec<LogicalLine> = vec![];
+    let mut text: String = String::new();
+    let mut indent: Option<usize> = None;
+    let mut depth: usize = 0;
+    let mut quote: Option<(char, bool)> = None;
+    let mut closing_quotes: usize = 0;
+    let mut chars = code.chars().peekable();
+    let mut column: usize = 0;
+
+    while let Some(c) = chars.next() {
+        if let Some((quote_char, triple)) = quote {
+            if c == '\n' && !triple {
+                // An unterminated string ends with its line
+                quote = None;
+            } else {
+                text.push(if c == '\n' { ' ' } else { c });
+                if c == '\\' {
+                    closing_quotes = 0;
+                    if let Some(escaped) = chars.next() {
+                        text.push(if escaped == '\n' { ' ' } else { escaped });
+                    }
+                } else if c == quote_char {
+                    closing_quotes += 1;
+                    if !triple || closing_quotes == 3 {
+                        quote = None;
+                    }
+                } else {
+                    closing_quotes = 0;
+                }
+                continue;
+            }
+        }
+
+        match c {
+            '\n' => {
+                column = 0;
+                if depth == 0 {
+                    push_logical_line(&mut lines, &mut text, &mut indent);
+                } else {
+                    text.push(' ');
+                }
+                continue;
+            }
+            '\\' if chars.peek() == Some(&'\n') => {
+                chars.next();
+                text.push(' ');
+                continue;
+            }
+            '#' => {
+                while chars.peek().is_some_and(|next| *next != '\n') {
+                    chars.next();
+                }
+                continue;
+            }
+            ' ' | '\t' if indent.is_none() => {
+                column += 1;
+                continue;
+            }
+            '\r' => continue,
+            _ => {}
+        }
+
+        if indent.is_none() {
+            indent = Some(column);
+        }
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            '"' | '\'' => {
+                let triple: bool = chars.peek() == Some(&c) && {
+                    let mut lookahead = chars.clone();
+                    lookahead.next();
+                    lookahead.peek() == Some(&c)
+                };
+                if triple {
+                    text.push(c);
+                    text.push(chars.next().unwrap_or(c));
+                    text.push(chars.next().unwrap_or(c));
+                    quote = Some((c, true));
+                    closing_quotes = 0;
+                    continue;
+                }
+                quote = Some((c, false));
+            }
+            _ => {}
+        }
+        text.push(c);
+    }
+    push_logical_line(&mut lines, &mut text, &mut indent);
+    lines
+}
+
+/// Ends the statement being read, dropping it if it is blank.
+fn push_logical_line(lines: &mut Vec<LogicalLine>, text: &mut String, indent: &mut Option<usize>) {
+    let statement: String = std::mem::take(text).trim().to_string();
+    if let (Some(indent), false) = (indent.take(), statement.is_empty()) {
+        lines.push(LogicalLine {
+            indent,
+            text: statement,
+        });
+    }
+}
+
+/// Reads a route decorator like `app.get("/task/{id}", response_model=Task)`, without its `@`.
+fn route_decorator(decorator: &str) -> Option<RouteDecorator> {
+    let (callee, args) = call_parts(decorator)?;
+    let (router, method_name) = callee.rsplit_once('.')?;
+    let methods: Vec<HttpMethod> = if method_name == API_ROUTE_DECORATOR {
+        let methods: Vec<HttpMethod> = keyword_argument(args, "methods")
+            .map(|methods| {
+                split_arguments(
+                    methods
+                        .trim()
+                        .trim_start_matches(['[', '('])
+                        .trim_end_matches([']', ')']),
+                )
+                .iter()
+                .filter_map(|method| string_literal(method)?.parse().ok())
+                .collect()
+            })
+            .unwrap_or_default();
+        if methods.is_empty() {
+            vec![HttpMethod::Get]
+        } else {
+            methods
+        }
+    } else {
+        vec![method_name.parse().ok()?]
+    };
+
+    let path: String = positional_arguments(args)
+        .first()
+        .and_then(|path| string_literal(path))
+        .or_else(|| keyword_argument(args, "path").and_then(string_literal))?;
+    let no_content: bool = keyword_argument(args, "status_code").is_some_and(|status| {
+        let status: &str = status.trim();
+        status == "204" || status == "304" || status.ends_with("HTTP_204_NO_CONTENT")
+    });
+
+    Some(RouteDecorator {
+        router: router.trim().to_string(),
+        path,
+        methods,
+        response_model: keyword_argument(args, "response_model").map(str::to_string),
+        no_content,
+    })
+}
+
+/// Reads the signature of a `def` or `async def` statement.
+fn handler_signature(statement: &str) -> Option<HandlerSignature> {
+    let definition: &str = statement
+        .strip_prefix("async ")
+        .map(str::trim_start)
+        .unwrap_or(statement)
+        .strip_prefix("def ")?;
+    let open: usize = definition.find('(')?;
+    let close: usize = matching_bracket(definition, open)?;
+    let parameters: Vec<String> = split_arguments(&definition[open + 1..close])
+        .iter()
+        .map(|parameter| parameter.trim().to_string())
+        .filter(|parameter| !parameter.is_empty() && parameter != "self" && parameter != "*")
+        .collect();
+    let return_annotation: Option<String> = definition[close + 1..]
+        .trim()
+        .strip_prefix("->")
+        // A body written on the same line follows the `:`
+        .map(|annotation| {
+            split_top_level(annotation, ':')
+                .map_or(annotation, |(annotation, _)| annotation)
+                .trim()
+                .to_string()
+        });
+    Some(HandlerSignature {
+        parameters,
+        return_annotation,
+    })
+}
+
+/// Reads `class Name(Base, Other):` into the class name and its base names.
+fn class_header(statement: &str) -> Option<(String, Vec<String>)> {
+    let header: &str = statement
+        .strip_prefix("class ")?
+        .trim_end()
+        .strip_suffix(':')?;
+    let (name, bases): (&str, Vec<String>) = match header.find('(') {
+        Some(open) => (
+            &header[..open],
+            split_arguments(&header[open + 1..matching_bracket(header, open)?])
+                .iter()
+                .filter(|base| !base.contains('='))
+                .map(|base| last_segment(base.trim()).to_string())
+                .collect(),
+        ),
+        None => (header, vec![]),
+    };
+    Some((name.trim().to_string(), bases))
+}
+
+/// Reads a model field declaration like `name: str = Field(min_length=1)` into its name and annotation.
+///
+/// `alias=` and `serialization_alias=` of a `Field(...)` default rename the field.
+fn model_field(statement: &str) -> Option<(String, String)> {
+    let (name, rest) = statement.split_once(':')?;
+    let name: &str = name.trim();
+    if !is_identifier(name) || name == "model_config" || name.starts_with('_') {
+        return None;
+    }
+    let (annotation, default): (&str, &str) = split_top_level(rest, '=').unwrap_or((rest, ""));
+    if annotation.trim().starts_with("ClassVar") {
+        return None;
+    }
+    let alias: Option<String> = call_parts(default.trim())
+        .filter(|(callee, _)| last_segment(callee) == "Field")
+        .and_then(|(_, args)| {
+            keyword_argument(args, "serialization_alias")
+                .or_else(|| keyword_argument(args, "alias"))
+                .and_then(string_literal)
+        });
+    Some((
+        alias.unwrap_or_else(|| name.to_string()),
+        annotation.trim().to_string(),
+    ))
+}
+
+/// Splits an annotation like `dict[str, list[int]]` into its name and type arguments.
+///
+/// `X | None` is read as `Union[X, None]`.
+fn annotation_parts(annotation: &str) -> (&str, Vec<&str>) {
+    let members: Vec<&str> = split_top_level_all(annotation, '|');
+    if members.len() > 1 {
+        return ("Union", members);
+    }
+    let annotation: &str = annotation.trim();
+    match annotation.find('[') {
+        Some(open) if annotation.ends_with(']') => (
+            last_segment(annotation[..open].trim()),
+            split_arguments(&annotation[open + 1..annotation.len() - 1])
+                .into_iter()
+                .filter(|type_arg| type_arg.trim() != "...")
+                .collect(),
+        ),
+        _ => (last_segment(annotation), vec![]),
+    }
+}
+
+/// Splits a call like `app.get("/task", status_code=201)` into its callee and its argument list.
+fn call_parts(expression: &str) -> Option<(&str, &str)> {
+    let open: usize = expression.find('(')?;
+    let close: usize = matching_bracket(expression, open)?;
+    if !expression[close + 1..].trim().is_empty() {
+        return None;
+    }
+    let callee: &str = expression[..open].trim();
+    let is_callee: bool = !callee.is_empty() && callee.split('.').all(is_identifier);
+    is_callee.then(|| (callee, &expression[open + 1..close]))
+}
+
+/// Returns the value of a keyword argument, e.g. `Task` for `response_model` in `"/task", response_model=Task`.
+fn keyword_argument<'a>(args: &'a str, name: &str) -> Option<&'a str> {
+    split_arguments(args).into_iter().find_map(|argument| {
+        let (key, value) = split_top_level(argument, '=')?;
+        (key.trim() == name).then(|| value.trim())
+    })
+}
+
+/// Returns the arguments that are not keyword arguments.
+fn positional_arguments(args: &str) -> Vec<&str> {
+    split_arguments(args)
+        .into_iter()
+        .filter(|argument| {
+            split_top_level(argument, '=').is_none_or(|(key, _)| !is_identifier(key.trim()))
+        })
+        .collect()
+}
+
+/// Splits an argument list on its top level commas, leaving out a trailing empty argument.
+fn split_arguments(args: &str) -> Vec<&str> {
+    let mut arguments: Vec<&str> = split_top_level_all(args, ',');
+    if arguments
+        .last()
+        .is_some_and(|argument| argument.trim().is_empty())
+    {
+        arguments.pop();
+    }
+    arguments
+}
+
+/// Splits on the first top level occurrence of a separator, outside brackets and strings.
+///
+/// `=` is only matched on its own, not as part of `==`, `<=`, `>=` or `!=`.
+fn split_top_level(text: &str, separator: char) -> Option<(&str, &str)> {
+    let position: usize = top_level_positions(text, separator).into_iter().next()?;
+    Some((&text[..position], &text[position + separator.len_utf8()..]))
+}
+
+/// Splits on every top level occurrence of a separator, outside brackets and strings.
+fn split_top_level_all(text: &str, separator: char) -> Vec<&str> {
+    let mut parts: Vec<&str> = vec![];
+    let mut start: usize = 0;
+    for position in top_level_positions(text, separator) {
+        parts.push(&text[start..position]);
+        start = position + separator.len_utf8();
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+/// Lists the byte positions of a separator outside brackets and strings.
+fn top_level_positions(text: &str, separator: char) -> Vec<usize> {
+    let bytes: &[u8] = text.as_bytes();
+    let mut positions: Vec<usize> = vec![];
+    let mut depth: usize = 0;
+    let mut quote: Option<char> = None;
+    let mut escaped: bool = false;
+    for (position, c) in text.char_indices() {
+        if let Some(quote_char) = quote {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == quote_char {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' => quote = Some(c),
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            _ if c == separator && depth == 0 => {
+                let is_comparison: bool = separator == '='
+                    && (bytes.get(position + 1) == Some(&b'=')
+                        || position
+                            .checked_sub(1)
+                            .is_some_and(|before| b"=<>!:".contains(&bytes[before])));
+                if !is_comparison {
+                    positions.push(position);
+                }
+            }
+            _ => {}
+        }
+    }
+    positions
+}
+
+/// Returns the position of the bracket closing the one at `open`, skipping strings.
+fn matching_bracket(text: &str, open: usize) -> Option<usize> {
+    let mut depth: usize = 0;
+    let mut quote: Option<char> = None;
+    let mut escaped: bool = false;
+    for (position, c) in text[open..].char_indices() {
+        if let Some(quote_char) = quote {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == quote_char {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' => quote = Some(c),
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + position);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Reads a plain or raw string literal, `None` for f-strings and anything else.
+fn string_literal(expression: &str) -> Option<String> {
+    let expression: &str = expression.trim();
+    let (is_raw, literal): (bool, &str) = match expression.strip_prefix(['r', 'R']) {
+        Some(literal) => (true, literal),
+        None => (false, expression),
+    };
+    let quote: char = literal.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let body: &str = literal.strip_prefix(quote)?.strip_suffix(quote)?;
+    if is_raw {
+        Some(body.to_string())
+    } else {
+        Some(
+            body.replace("\\\\", "\\")
+                .replace(&format!("\\{}", quote), &quote.to_string()),
+        )
+    }
+}
+
+/// Strips the quotes of a forward reference annotation like `"Task"`.
+fn unquote(annotation: &str) -> &str {
+    annotation
+        .strip_prefix('"')
+        .and_then(|inner| inner.strip_suffix('"'))
+        .or_else(|| {
+            annotation
+                .strip_prefix('\'')
+                .and_then(|inner| inner.strip_suffix('\''))
+        })
+        .unwrap_or(annotation)
+}
+
+/// Returns the name after the last dot, e.g. `Response` for `fastapi.Response`.
+fn last_segment(name: &str) -> &str {
+    name.rsplit('.').next().unwrap_or(name).trim()
+}
+
+/// Whether a name is a plain Python identifier.
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_alphabetic() || first == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Rewrites Starlette path converters like `{file:path}` to `{file}`.
+fn strip_path_converters(path: &str) -> String {
+    let mut stripped: String = String::new();
+    let mut in_converter: bool = false;
+    for c in path.chars() {
+        match c {
+            ':' if stripped.rfind('{') > stripped.rfind('}') => in_converter = true,
+            '}' => {
+                in_converter = false;
+                stripped.push(c);
+            }
+            _ if in_converter => {}
+            _ => stripped.push(c),
+        }
+    }
+    stripped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn route<'a>(
+        route_objects: &'a [RouteObject],
+        method: HttpMethod,
+        path: &str,
+    ) -> &'a RouteObject {
+        route_objects
+            .iter()
+            .find(|route_object| route_object.method == method && route_object.route == path)
+            .unwrap_or_else(|| panic!("{:?} {} not extracted", method, path))
+    }
+
+    #[test]
+    fn reads_multi_line_decorators_and_router_prefixes() {
+        let code: &str = r#"
+from fastapi import APIRouter, FastAPI, Response
+
+app = FastAPI()
+router = APIRouter(prefix="/tasks", tags=["tasks"])
+admin = APIRouter()
+
+
+@router.get(
+    "/{task_id}",
+    response_model=Task,  # the stored task
+)
+def read_task(task_id: int):
+    return db[task_id]
+
+
+@router.api_route(
+    "/{task_id}/archive",
+    methods=["POST", "PUT"],
+)
+def archive_task(task_id: int) -> Response:
+    return Response(status_code=204)
+
+
+@admin.delete("/stats/{file:path}", status_code=204)
+def reset_stats(file: str) -> None:
+    pass
+
+
+app.include_router(router, prefix="/api")
+app.include_router(
+    admin,
+    prefix="/admin",
+)
+"#;
+        let route_objects: Vec<RouteObject> = extract_fastapi_route_objects(code);
+
+        let routes: Vec<(HttpMethod, &str)> = route_objects
+            .iter()
+            .map(|route_object| (route_object.method, route_object.route.as_str()))
+            .collect();
+        assert_eq!(
+            routes,
+            vec![
+                (HttpMethod::Get, "/api/tasks/{task_id}"),
+                (HttpMethod::Post, "/api/tasks/{task_id}/archive"),
+                (HttpMethod::Put, "/api/tasks/{task_id}/archive"),
+                (HttpMethod::Delete, "/admin/stats/{file}"),
+            ]
+        );
+        let reset_stats: &RouteObject =
+            route(&route_objects, HttpMethod::Delete, "/admin/stats/{file}");
+        assert_eq!(reset_stats.path_params, vec!["file"]);
+        assert_eq!(reset_stats.response, BodySchema::None);
+    }
+
+    #[test]
+    fn reads_request_and_response_models() {
+        let code: &str = r#"
+from datetime import datetime
+from enum import Enum
+from typing import List, Optional
+
+from pydantic import BaseModel
+
+
+class Status(str, Enum):
+    OPEN = "open"
+    DONE = "done"
+
+
+class TaskCreate(BaseModel):
+    name: str
+    status: Status
+    estimate: Optional[float] = None
+    tags: List[str] = []
+
+
+class Task(TaskCreate):
+    id: int
+    created_at: datetime
+
+
+app = FastAPI()
+
+
+@app.post("/task", status_code=201)
+async def create_task(task: TaskCreate, user: User = Depends(current_user)) -> Task:
+    return save(task)
+
+
+@app.get("/task", response_model=list[Task])
+async def list_tasks(limit: int = 10):
+    return tasks[:limit]
+"#;
+        let route_objects: Vec<RouteObject> = extract_fastapi_route_objects(code);
+        let task_create = json!({
+            "name": "string",
+            "status": "string",
+            "estimate": "float",
+            "tags": ["string"]
+        });
+        let mut task = task_create.clone();
+        task["id"] = json!("integer");
+        task["created_at"] = json!("datetime");
+
+        let create_task: &RouteObject = route(&route_objects, HttpMethod::Post, "/task");
+        assert_eq!(create_task.request_body, BodySchema::from(task_create));
+        assert_eq!(create_task.response, BodySchema::from(task.clone()));
+        let list_tasks: &RouteObject = route(&route_objects, HttpMethod::Get, "/task");
+        assert_eq!(list_tasks.request_body, BodySchema::None);
+        assert_eq!(list_tasks.response, BodySchema::from(json!([task])));
+    }
+
+    #[test]
+    fn skips_websocket_routes() {
+        let code: &str = r#"
+app = FastAPI()
+
+
+@app.websocket("/ws")
+async def events(websocket: WebSocket):
+    await websocket.accept()
+
+
+@app.get("/health")
+def health() -> dict:
+    return {"status": "ok"}
+"#;
+        let route_objects: Vec<RouteObject> = extract_fastapi_route_objects(code);
+
+        assert_eq!(route_objects.len(), 1);
+        assert_eq!(route_objects[0].route, "/health");
+    }
+
+    #[test]
+    fn extracts_the_routes_of_the_code_templates() {
+        for (template, expected) in [
+            ("source/templates/fastapi/crud.py", 6),
+            ("source/templates/fastapi/auth.py", 5),
+        ] {
+            let code: String = std::fs::read_to_string(template).unwrap();
+            assert_eq!(
+                extract_fastapi_route_objects(&code).len(),
+                expected,
+                "{}",
+                template
+            );
+        }
+    }
+}
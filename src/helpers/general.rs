@@ -1,36 +1,311 @@
 use crate::api_handler::call_request::call_gpt;
+use crate::helpers::code_templates::{
+    add_template_dependencies, add_template_requirements, manifest_depends_on,
+    requirements_depend_on, CodeTemplate,
+};
 use crate::helpers::command_lines::PrintCommand;
+use crate::helpers::devops::{DevOpsFile, DevOpsTemplates};
+use crate::helpers::sql_migrations::MigrationFile;
+use crate::helpers::static_pages::StaticPage;
+use crate::helpers::target_framework::{TargetFramework, TargetLanguage};
+use crate::models::agents::agent_traits::FactSheet;
 use crate::models::general::llm::Message;
-use reqwest::Client;
+use dotenv::dotenv;
+use reqwest::{Client, Method};
 use serde::de::DeserializeOwned;
-use std::path::PathBuf;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::{env, fs};
 
 fn get_project_root() -> PathBuf {
     env::current_dir().expect("Failed to get current directory")
 }
-const EXEC_MAIN_PATH: &str = r#"src\main2.rs"#;
-const API_SCHEMA_PATH: &str = r#"source\schemas\api_schema.json"#;
-const CODE_TEMPLATE_PATH: &str = r#"source\web_server_code_template.rs"#;
+const WEB_SERVER_PROJECT_PATH_ENV: &str = "WEB_SERVER_PROJECT_PATH";
+const WEB_SERVER_PROJECT_PATH: &str = "web_server";
+const WEB_SERVER_BINARY_NAME: &str = "web_server";
+const EXEC_MAIN_PATH: &str = "src/main.rs";
+const PYTHON_MAIN_PATH: &str = "main.py";
+const REQUIREMENTS_PATH: &str = "requirements.txt";
+const PYTHON_VENV_PATH: &str = ".venv";
+const API_TESTS_PATH: &str = "tests/api.rs";
+const MIGRATIONS_PATH: &str = "src/migrations";
+const PYTHON_MIGRATIONS_PATH: &str = "migrations";
+const SQLITE_DATABASE_FILE: &str = "database.sqlite";
+const WEB_SERVER_DATA_PATH: &str = "data";
+const WEB_SERVER_STATIC_PATH: &str = "static";
+const API_SCHEMA_PATH: &str = "api_schema.json";
+const OPENAPI_SCHEMA_PATH: &str = "source/schemas/openapi_3_1_schema.json";
+const OPENAPI_DOCUMENT_PATH: &str = "openapi.json";
+const FACT_SHEET_PATH: &str = "fact_sheet.json";
+const CODE_TEMPLATES_PATH: &str = "source/templates";
+const CODE_TEMPLATE_REGISTRY_FILE: &str = "registry.json";
+const CARGO_TEMPLATE_PATH: &str = "source/web_server_cargo_template.toml";
+const TESTS_TEMPLATE_PATH: &str = "source/web_server_tests_template.rs";
+const AXUM_CARGO_TEMPLATE_PATH: &str = "source/web_server_axum_cargo_template.toml";
+const AXUM_TESTS_TEMPLATE_PATH: &str = "source/web_server_axum_tests_template.rs";
+const FASTAPI_REQUIREMENTS_TEMPLATE_PATH: &str =
+    "source/web_server_fastapi_requirements_template.txt";
+const RUN_REPORT_PATH: &str = "source/reports/run_report.json";
+const WEB_FRONTEND_PROJECT_PATH_ENV: &str = "WEB_FRONTEND_PROJECT_PATH";
+const WEB_FRONTEND_PROJECT_PATH: &str = "web_frontend";
+const FRONTEND_CODE_TEMPLATE_PATH: &str = "source/web_frontend_code_template.rs";
+const FRONTEND_CARGO_TEMPLATE_PATH: &str = "source/web_frontend_cargo_template.toml";
+const FRONTEND_INDEX_TEMPLATE_PATH: &str = "source/web_frontend_index_template.html";
+const STATIC_FRONTEND_TEMPLATE_PATH: &str = "source/static_frontend";
+const DEVOPS_TEMPLATE_PATH: &str = "source/devops";
 
+/// Returns the directory of the generated web server project.
+///
+/// Defaults to `web_server` next to this project and can be moved with `WEB_SERVER_PROJECT_PATH`.
 pub fn get_web_server_project_path() -> String {
-    // let project_root = get_project_root();
-    get_project_root().to_str().unwrap().to_string()
+    let project_root = get_project_root();
+    let project_path: String = env_setting(
+        WEB_SERVER_PROJECT_PATH_ENV,
+        WEB_SERVER_PROJECT_PATH.to_string(),
+    );
+    project_root
+        .join(project_path)
+        .to_str()
+        .unwrap()
+        .to_string()
 }
 
+/// Returns the file holding the generated backend code, `src/main.rs` or `main.py` for the language
+/// of the framework read from `BACKEND_FRAMEWORK`.
 pub fn get_exec_main_path() -> String {
-    let project_root = get_project_root();
-    project_root.join(EXEC_MAIN_PATH).to_str().unwrap().to_string()
+    let project_path = PathBuf::from(get_web_server_project_path());
+    let main_path: &str = match TargetFramework::from_env().language() {
+        TargetLanguage::Rust => EXEC_MAIN_PATH,
+        TargetLanguage::Python => PYTHON_MAIN_PATH,
+    };
+    project_path.join(main_path).to_str().unwrap().to_string()
+}
+
+pub fn get_api_tests_path() -> String {
+    let project_path = PathBuf::from(get_web_server_project_path());
+    project_path
+        .join(API_TESTS_PATH)
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+/// Returns the file listing the dependencies of the generated web server, `Cargo.toml` or `requirements.txt`
+/// for the language of the framework read from `BACKEND_FRAMEWORK`.
+pub fn get_web_server_manifest_path() -> String {
+    let project_path = PathBuf::from(get_web_server_project_path());
+    let manifest_path: &str = match TargetFramework::from_env().language() {
+        TargetLanguage::Rust => "Cargo.toml",
+        TargetLanguage::Python => REQUIREMENTS_PATH,
+    };
+    project_path
+        .join(manifest_path)
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+/// Returns the name of the generated web server binary.
+pub fn get_web_server_binary_name() -> String {
+    WEB_SERVER_BINARY_NAME.to_string()
 }
 
+pub fn get_web_server_binary_path() -> String {
+    let project_path = PathBuf::from(get_web_server_project_path());
+    let binary_path = project_path
+        .join("target")
+        .join("debug")
+        .join(WEB_SERVER_BINARY_NAME);
+    binary_path.to_str().unwrap().to_string()
+}
+
+/// Returns the virtualenv the generated Python web server's requirements are installed into.
+pub fn get_python_venv_path() -> String {
+    let project_path = PathBuf::from(get_web_server_project_path());
+    project_path
+        .join(PYTHON_VENV_PATH)
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+/// Returns the program starting the generated web server: the built binary, or the virtualenv's
+/// python for FastAPI, which runs uvicorn with `TargetFramework::run_args`.
+///
+/// # Arguments
+/// - `framework`: The framework the web server is written with.
+pub fn get_web_server_program_path(framework: TargetFramework) -> String {
+    match framework.language() {
+        TargetLanguage::Rust => get_web_server_binary_path(),
+        TargetLanguage::Python => PathBuf::from(get_python_venv_path())
+            .join("bin")
+            .join("python")
+            .to_str()
+            .unwrap()
+            .to_string(),
+    }
+}
+
+/// Returns the migrations directory of the generated web server: inside `src` for Rust so `include_str!`
+/// and the Dockerfile's `COPY src` both reach it, next to `main.py` for Python.
+pub fn get_migrations_path() -> String {
+    let project_path = PathBuf::from(get_web_server_project_path());
+    let migrations_path: &str = match TargetFramework::from_env().language() {
+        TargetLanguage::Rust => MIGRATIONS_PATH,
+        TargetLanguage::Python => PYTHON_MIGRATIONS_PATH,
+    };
+    project_path
+        .join(migrations_path)
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+pub fn get_web_server_data_path() -> String {
+    let project_path = PathBuf::from(get_web_server_project_path());
+    project_path
+        .join(WEB_SERVER_DATA_PATH)
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+pub fn get_web_server_static_path() -> String {
+    let project_path = PathBuf::from(get_web_server_project_path());
+    project_path
+        .join(WEB_SERVER_STATIC_PATH)
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+/// Returns the path of the endpoint schema saved inside the generated web server project, so each stage
+/// of its history holds the schema matching its code.
 pub fn get_api_schema_path() -> String {
+    let project_path = PathBuf::from(get_web_server_project_path());
+    project_path
+        .join(API_SCHEMA_PATH)
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+/// Returns the path of the OpenAPI document inside the generated web server project.
+pub fn get_openapi_document_path() -> String {
+    let project_path = PathBuf::from(get_web_server_project_path());
+    project_path
+        .join(OPENAPI_DOCUMENT_PATH)
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+/// Returns the path of the fact sheet saved inside the generated web server project.
+pub fn get_fact_sheet_path() -> String {
+    let project_path = PathBuf::from(get_web_server_project_path());
+    project_path
+        .join(FACT_SHEET_PATH)
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+pub fn get_run_report_path() -> String {
+    let project_root = get_project_root();
+    project_root
+        .join(RUN_REPORT_PATH)
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+pub fn get_code_templates_path() -> String {
+    let project_root = get_project_root();
+    project_root
+        .join(CODE_TEMPLATES_PATH)
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+/// Returns the path of the generated web server's `Cargo.toml` or `requirements.txt` template for a framework.
+pub fn get_manifest_template_path(framework: TargetFramework) -> String {
+    let project_root = get_project_root();
+    let template_path: &str = match framework {
+        TargetFramework::ActixWeb => CARGO_TEMPLATE_PATH,
+        TargetFramework::Axum => AXUM_CARGO_TEMPLATE_PATH,
+        TargetFramework::FastApi => FASTAPI_REQUIREMENTS_TEMPLATE_PATH,
+    };
+    project_root
+        .join(template_path)
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+/// Returns the path of the default integration tests template for a framework, if it has one.
+pub fn get_tests_template_path(framework: TargetFramework) -> Option<String> {
+    let project_root = get_project_root();
+    let template_path: &str = match framework {
+        TargetFramework::ActixWeb => TESTS_TEMPLATE_PATH,
+        TargetFramework::Axum => AXUM_TESTS_TEMPLATE_PATH,
+        TargetFramework::FastApi => return None,
+    };
+    Some(
+        project_root
+            .join(template_path)
+            .to_str()
+            .unwrap()
+            .to_string(),
+    )
+}
+
+/// Returns the directory of the generated Yew frontend project.
+///
+/// Defaults to `web_frontend` next to this project and can be moved with `WEB_FRONTEND_PROJECT_PATH`.
+pub fn get_web_frontend_project_path() -> String {
     let project_root = get_project_root();
-    project_root.join(API_SCHEMA_PATH).to_str().unwrap().to_string()
+    let project_path: String = env_setting(
+        WEB_FRONTEND_PROJECT_PATH_ENV,
+        WEB_FRONTEND_PROJECT_PATH.to_string(),
+    );
+    project_root
+        .join(project_path)
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+pub fn get_frontend_main_path() -> String {
+    let project_path = PathBuf::from(get_web_frontend_project_path());
+    project_path
+        .join(EXEC_MAIN_PATH)
+        .to_str()
+        .unwrap()
+        .to_string()
 }
 
-pub fn get_code_template_path() -> String {
+pub fn get_frontend_code_template_path() -> String {
     let project_root = get_project_root();
-    project_root.join(CODE_TEMPLATE_PATH).to_str().unwrap().to_string()
+    project_root
+        .join(FRONTEND_CODE_TEMPLATE_PATH)
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+/// Reads a setting from the environment (or `.env`), falling back to a default value.
+///
+/// # Arguments
+/// - `key`: The name of the environment variable.
+/// - `default`: The value used when the variable is missing or cannot be parsed.
+pub fn env_setting<T: FromStr>(key: &str, default: T) -> T {
+    dotenv().ok();
+    env::var(key)
+        .ok()
+        .and_then(|value| value.trim().parse::<T>().ok())
+        .unwrap_or(default)
 }
 
 /// Extends an AI function by formatting the input and creating a system message.
@@ -89,7 +364,7 @@ pub async fn ai_task_request_decoded<T: DeserializeOwned>(
         ai_task_request(msg_context, agent_position, agent_operation, function_pass).await;
     let decoded_response: T = serde_json::from_str(llm_response.as_str())
         .expect("Failed to decode ai response from serde_json");
-    return decoded_response;
+    decoded_response
 }
 
 /// Checks the HTTP status code of a URL using the provided client.
@@ -105,55 +380,367 @@ pub async fn check_status_code(client: &Client, url: &str) -> Result<u16, reqwes
     Ok(response.status().as_u16())
 }
 
-/// Reads the contents of the web server code template from the specified path.
+/// Sends a request with an optional JSON body to a URL using the provided client.
+///
+/// # Arguments
+/// - `client`: The `reqwest::Client` used to make the request.
+/// - `method`: The HTTP method of the request.
+/// - `url`: The URL to send the request to.
+/// - `body`: An optional JSON payload sent as the request body.
+/// - `bearer_token`: An optional session token sent as `Authorization: Bearer <token>`.
 ///
 /// # Returns
-/// The contents of the code template as a `String`.
-pub fn read_code_template_contents() -> String {
-    let path: String = String::from(get_code_template_path());
-    fs::read_to_string(path).expect("Failed to read code template")
+/// The HTTP status code and the text body of the response.
+pub async fn send_endpoint_request(
+    client: &Client,
+    method: Method,
+    url: &str,
+    body: Option<&Value>,
+    bearer_token: Option<&str>,
+) -> Result<(u16, String), reqwest::Error> {
+    let mut request: reqwest::RequestBuilder = client.request(method, url);
+    if let Some(body) = body {
+        request = request.json(body);
+    }
+    if let Some(bearer_token) = bearer_token {
+        request = request.bearer_auth(bearer_token);
+    }
+    let response: reqwest::Response = request.send().await?;
+    let status: u16 = response.status().as_u16();
+    let text: String = response.text().await?;
+    Ok((status, text))
+}
+
+/// Reads the registry of web server code templates from `source/templates`, with the code of every template.
+///
+/// # Arguments
+/// - `framework`: Only the templates written with this framework are read.
+///
+/// # Returns
+/// The templates in registry order.
+pub fn read_code_templates(framework: TargetFramework) -> Vec<CodeTemplate> {
+    let templates_path = PathBuf::from(get_code_templates_path());
+    let registry: String = fs::read_to_string(templates_path.join(CODE_TEMPLATE_REGISTRY_FILE))
+        .expect("Failed to read code template registry");
+    let mut templates: Vec<CodeTemplate> =
+        serde_json::from_str(&registry).expect("Failed to parse code template registry");
+    templates.retain(|template| template.framework == framework);
+    for template in &mut templates {
+        template.code = fs::read_to_string(templates_path.join(&template.file))
+            .expect("Failed to read code template");
+        template.tests_code = template.tests_file.as_ref().map(|tests_file| {
+            fs::read_to_string(templates_path.join(tests_file))
+                .expect("Failed to read code template tests")
+        });
+    }
+    templates
 }
 
 /// Reads the contents of the main server file from the specified path.
 ///
 /// # Returns
-/// The contents of the `main2.rs` file as a `String`.
+/// The contents of the generated `src/main.rs` or `main.py` file as a `String`.
 pub fn read_exec_main_contents() -> String {
-    let path: String = String::from(get_exec_main_path());
+    let path: String = get_exec_main_path();
     fs::read_to_string(path).expect("Failed to read code template")
 }
 
-/// Saves the backend code to the `main2.rs` file.
+/// Reads the contents of the integration tests template for the generated web server.
+///
+/// # Arguments
+/// - `framework`: The framework the web server is written with.
+///
+/// # Returns
+/// The contents of the tests template as a `String`, `None` if the framework has no tests template.
+pub fn read_tests_template_contents(framework: TargetFramework) -> Option<String> {
+    let path: String = get_tests_template_path(framework)?;
+    Some(fs::read_to_string(path).expect("Failed to read tests template"))
+}
+
+/// Creates the generated web server project with its manifest and data directory if missing.
+///
+/// A missing `Cargo.toml` or `requirements.txt` is written from the template of the framework read
+/// from `BACKEND_FRAMEWORK`.
+pub fn ensure_web_server_project() {
+    let main_path = PathBuf::from(get_exec_main_path());
+    if let Some(source_path) = main_path.parent() {
+        fs::create_dir_all(source_path).expect("Failed to create web server project");
+    }
+    fs::create_dir_all(get_web_server_data_path()).expect("Failed to create web server data dir");
+    fs::create_dir_all(get_web_server_static_path())
+        .expect("Failed to create web server static dir");
+
+    let manifest_path: PathBuf = PathBuf::from(get_web_server_manifest_path());
+    if !manifest_path.exists() {
+        let manifest_template: String =
+            fs::read_to_string(get_manifest_template_path(TargetFramework::from_env()))
+                .expect("Failed to read manifest template");
+        fs::write(manifest_path, manifest_template).expect("Failed to write manifest");
+    }
+}
+
+/// Reads the contents of the Yew frontend code template.
+///
+/// # Returns
+/// The contents of the frontend code template as a `String`.
+pub fn read_frontend_code_template_contents() -> String {
+    let path: String = get_frontend_code_template_path();
+    fs::read_to_string(path).expect("Failed to read frontend code template")
+}
+
+/// Creates the generated Yew frontend project with its `Cargo.toml` and `index.html` if missing.
+pub fn ensure_web_frontend_project() {
+    let project_path = PathBuf::from(get_web_frontend_project_path());
+    fs::create_dir_all(project_path.join("src")).expect("Failed to create web frontend project");
+
+    let project_root = get_project_root();
+    let templates: [(&str, &str); 2] = [
+        (FRONTEND_CARGO_TEMPLATE_PATH, "Cargo.toml"),
+        (FRONTEND_INDEX_TEMPLATE_PATH, "index.html"),
+    ];
+    for (template_path, file_name) in templates {
+        let file_path: PathBuf = project_path.join(file_name);
+        if !file_path.exists() {
+            let template: String = fs::read_to_string(project_root.join(template_path))
+                .expect("Failed to read web frontend template");
+            fs::write(file_path, template).expect("Failed to write web frontend project file");
+        }
+    }
+}
+
+/// Saves the frontend code to the generated frontend project's `src/main.rs` file.
+///
+/// # Arguments
+/// - `contents`: The code to write into the file.
+pub fn save_frontend_code(contents: &String) {
+    ensure_web_frontend_project();
+    let path: String = get_frontend_main_path();
+    fs::write(path, contents).expect("Failed to write frontend main.rs file");
+}
+
+/// Reads a shared asset of the static frontend, e.g. `app.js`.
+///
+/// # Arguments
+/// - `file_name`: The asset file name inside `source/static_frontend`.
+pub fn read_static_frontend_asset(file_name: &str) -> String {
+    let project_root = get_project_root();
+    let path: PathBuf = project_root
+        .join(STATIC_FRONTEND_TEMPLATE_PATH)
+        .join(file_name);
+    fs::read_to_string(path).expect("Failed to read static frontend asset")
+}
+
+/// Replaces the contents of the generated web server's static files directory.
+///
+/// # Arguments
+/// - `pages`: The files of the static frontend.
+pub fn save_static_pages(pages: &[StaticPage]) {
+    ensure_web_server_project();
+    let static_path: String = get_web_server_static_path();
+    fs::remove_dir_all(&static_path).expect("Failed to clear web server static dir");
+    fs::create_dir_all(&static_path).expect("Failed to create web server static dir");
+    for page in pages {
+        let path: PathBuf = Path::new(&static_path).join(&page.file_name);
+        fs::write(path, &page.contents).expect("Failed to write static page");
+    }
+}
+
+/// Reads the templates of the deployment files from `source/devops`.
+pub fn read_devops_templates() -> DevOpsTemplates {
+    let template_path: PathBuf = get_project_root().join(DEVOPS_TEMPLATE_PATH);
+    let read_template = |file_name: &str| -> String {
+        fs::read_to_string(template_path.join(file_name)).expect("Failed to read devops template")
+    };
+    DevOpsTemplates {
+        dockerfile: read_template("Dockerfile"),
+        dockerignore: read_template("dockerignore"),
+        compose: read_template("docker-compose.yml"),
+        workflow: read_template("ci.yml"),
+    }
+}
+
+/// Writes the deployment files into the generated web server project.
+///
+/// # Arguments
+/// - `files`: The deployment files, with paths relative to the project directory.
+pub fn save_devops_files(files: &[DevOpsFile]) {
+    ensure_web_server_project();
+    let project_path = PathBuf::from(get_web_server_project_path());
+    for file in files {
+        let path: PathBuf = project_path.join(&file.path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).expect("Failed to create devops file directory");
+        }
+        fs::write(path, &file.contents).expect("Failed to write devops file");
+    }
+}
+
+/// Returns `true` if the generated web server project contains a file.
+///
+/// # Arguments
+/// - `relative_path`: The path relative to the project directory, e.g. `Dockerfile`.
+pub fn web_server_project_file_exists(relative_path: &str) -> bool {
+    PathBuf::from(get_web_server_project_path())
+        .join(relative_path)
+        .exists()
+}
+
+/// Saves the README of the generated web server project.
+///
+/// # Arguments
+/// - `contents`: The README markdown.
+pub fn save_project_readme(contents: &String) {
+    ensure_web_server_project();
+    let path: PathBuf = PathBuf::from(get_web_server_project_path()).join("README.md");
+    fs::write(path, contents).expect("Failed to write project README");
+}
+
+/// Saves the backend code to the generated project's `src/main.rs` or `main.py` file.
 ///
 /// # Arguments
 /// - `contents`: The code to write into the file.
 pub fn save_backend_code(contents: &String) {
-    let path: String = String::from(get_exec_main_path());
-    fs::write(path, contents).expect("Failed to write main2.rs file");
+    ensure_web_server_project();
+    let path: String = get_exec_main_path();
+    fs::write(path, contents).expect("Failed to write backend code file");
+}
+
+/// Adds the dependencies of the selected code templates to the generated project's `Cargo.toml` or
+/// `requirements.txt`.
+///
+/// A manifest an earlier run wrote for another framework is replaced by the framework's template first.
+///
+/// # Arguments
+/// - `templates`: The templates the backend code was generated from.
+/// - `framework`: The framework the backend code is written with.
+pub fn save_template_dependencies(templates: &[&CodeTemplate], framework: TargetFramework) {
+    ensure_web_server_project();
+    let manifest_path: String = get_web_server_manifest_path();
+    let mut manifest: String =
+        fs::read_to_string(&manifest_path).expect("Failed to read web server manifest");
+    let depends_on_framework: bool = match framework.language() {
+        TargetLanguage::Rust => manifest_depends_on(&manifest, framework.package_name()),
+        TargetLanguage::Python => requirements_depend_on(&manifest, framework.package_name()),
+    };
+    if !depends_on_framework {
+        manifest = fs::read_to_string(get_manifest_template_path(framework))
+            .expect("Failed to read manifest template");
+    }
+    let updated: String = match framework.language() {
+        TargetLanguage::Rust => add_template_dependencies(&manifest, templates)
+            .expect("Failed to add code template dependencies"),
+        TargetLanguage::Python => add_template_requirements(&manifest, templates),
+    };
+    fs::write(manifest_path, updated).expect("Failed to update web server manifest");
+}
+
+/// Replaces the SQL migrations of the generated web server.
+///
+/// The SQLite database the test servers ran against is deleted as well, so the next run applies the new migrations.
+///
+/// # Arguments
+/// - `migrations`: The migrations, written under `src/migrations`, or `migrations` for Python.
+pub fn save_migrations(migrations: &[MigrationFile]) {
+    ensure_web_server_project();
+    let migrations_path = PathBuf::from(get_migrations_path());
+    if migrations_path.exists() {
+        fs::remove_dir_all(&migrations_path).expect("Failed to clear web server migrations dir");
+    }
+    fs::create_dir_all(&migrations_path).expect("Failed to create web server migrations dir");
+    for migration in migrations {
+        fs::write(migrations_path.join(&migration.file_name), &migration.sql)
+            .expect("Failed to write migration");
+    }
+
+    let data_path = PathBuf::from(get_web_server_data_path());
+    for suffix in ["", "-wal", "-shm"] {
+        let _ = fs::remove_file(data_path.join(format!("{}{}", SQLITE_DATABASE_FILE, suffix)));
+    }
+}
+
+/// Saves the integration tests to the generated project's `tests/api.rs` file.
+///
+/// # Arguments
+/// - `contents`: The test code to write into the file.
+pub fn save_api_tests(contents: &String) {
+    ensure_web_server_project();
+    let path: String = get_api_tests_path();
+    if let Some(parent) = Path::new(&path).parent() {
+        fs::create_dir_all(parent).expect("Failed to create web server tests dir");
+    }
+    fs::write(path, contents).expect("Failed to write api.rs file");
 }
 
-/// Saves the API endpoints to a JSON file.
+/// Saves the API endpoints to a JSON file in the generated project.
 ///
 /// # Arguments
 /// - `api_endpoints`: The API endpoints to save as a JSON string.
 pub fn save_api_endpoints(api_endpoints: &String) {
-    let path: String = String::from(get_api_schema_path());
+    ensure_web_server_project();
+    let path: String = get_api_schema_path();
     fs::write(path, api_endpoints).expect("Failed to write API Endpoints to file");
 }
 
+/// Reads the JSON schema OpenAPI 3.1 documents are validated against.
+pub fn read_openapi_schema() -> Value {
+    let path: PathBuf = get_project_root().join(OPENAPI_SCHEMA_PATH);
+    let schema: String = fs::read_to_string(path).expect("Failed to read OpenAPI schema");
+    serde_json::from_str(&schema).expect("Failed to parse OpenAPI schema")
+}
+
+/// Saves the OpenAPI document to the generated project, where the web server serves it from.
+///
+/// # Arguments
+/// - `openapi_document`: The OpenAPI document as a JSON string.
+pub fn save_openapi_document(openapi_document: &String) {
+    ensure_web_server_project();
+    let path: String = get_openapi_document_path();
+    fs::write(path, openapi_document).expect("Failed to write OpenAPI document");
+}
+
+/// Saves the run report, including test server logs, to a JSON file.
+///
+/// # Arguments
+/// - `run_report`: The run report to save as a JSON string.
+pub fn save_run_report(run_report: &String) {
+    let path: String = get_run_report_path();
+    if let Some(parent) = Path::new(&path).parent() {
+        fs::create_dir_all(parent).expect("Failed to create run report directory");
+    }
+    fs::write(path, run_report).expect("Failed to write run report to file");
+}
+
+/// Saves the fact sheet of a finished run to the generated project.
+///
+/// # Arguments
+/// - `fact_sheet`: The fact sheet as a JSON string.
+pub fn save_fact_sheet(fact_sheet: &String) {
+    ensure_web_server_project();
+    let path: String = get_fact_sheet_path();
+    fs::write(path, fact_sheet).expect("Failed to write fact sheet");
+}
+
+/// Reads the fact sheet an earlier run saved to the generated project.
+///
+/// # Returns
+/// The fact sheet, `None` if no run saved one yet.
+pub fn read_fact_sheet() -> Option<FactSheet> {
+    let contents: String = fs::read_to_string(get_fact_sheet_path()).ok()?;
+    Some(serde_json::from_str(&contents).expect("Failed to parse fact sheet"))
+}
+
 // #[cfg(test)]
 // mod tests {
 //     use super::*;
 //     #[test]
 //     fn tests_paths() {
 //         println!("{:?}", get_api_schema_path());
-//         println!("{:?}", get_code_template_path());
+//         println!("{:?}", get_code_templates_path());
 //         println!("{:?}", get_exec_main_path());
 //         println!("{:?}", get_web_server_project_path());
 //     }
 // }
 
-
 // #[cfg(test)]
 // mod tests {
 //     use super::*;
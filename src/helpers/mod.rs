@@ -1,2 +1,21 @@
+pub mod code_templates;
 pub mod command_lines;
+pub mod dependency_check;
+pub mod devops;
+pub mod domain_model;
+pub mod egress_proxy;
+pub mod endpoint_testing;
+pub mod fastapi_route_extraction;
 pub mod general;
+pub mod openapi;
+pub mod project_docs;
+pub mod project_history;
+pub mod route_extraction;
+pub mod safety_scanner;
+pub mod sandbox;
+pub mod security_review;
+pub mod server_process;
+pub mod socket_bridge;
+pub mod sql_migrations;
+pub mod static_pages;
+pub mod target_framework;
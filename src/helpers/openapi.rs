@@ -0,0 +1,533 @@
+use crate::helpers::domain_model::entity_for_route;
+use crate::helpers::endpoint_testing::{resource_key, EndpointTestResult};
+use crate::helpers::general::send_endpoint_request;
+use crate::models::agents::agent_traits::{
+    BodySchema, DomainModel, EntitySchema, FieldSchema, FieldType, HttpMethod, RouteObject,
+};
+use reqwest::{Client, Method};
+use serde_json::{json, Map, Value};
+
+/// The route the generated web server serves its OpenAPI document under.
+pub const OPENAPI_ROUTE: &str = "/openapi.json";
+
+/// The OpenAPI version of the generated documents.
+const OPENAPI_VERSION: &str = "3.1.0";
+
+/// Builds an OpenAPI 3.1 document from the routes extracted from the backend code.
+///
+/// Request and response shapes become JSON schemas under `components/schemas`, named after their
+/// resource and shared by every operation using the same shape. Routes serving an entity of the
+/// domain model use the entity schema instead, including its validation rules.
+///
+/// # Arguments
+/// - `title`: The API title.
+/// - `description`: The project description.
+/// - `routes`: The API endpoint schema.
+/// - `domain_model`: The domain model the server implements, if one was designed.
+///
+/// # Returns
+/// The OpenAPI document.
+pub fn build_openapi_document(
+    title: &str,
+    description: &str,
+    routes: &[RouteObject],
+    domain_model: Option<&DomainModel>,
+) -> Value {
+    let mut components: Map<String, Value> = Map::new();
+    let mut paths: Map<String, Value> = Map::new();
+
+    for route_object in routes {
+        let method: &str = route_object.method.as_str();
+        let resource: String = resource_key(&route_object.route);
+        let entity: Option<&EntitySchema> =
+            domain_model.and_then(|model| entity_for_route(model, &route_object.route));
+
+        let mut operation: Map<String, Value> = Map::new();
+        operation.insert(
+            "operationId".to_string(),
+            Value::String(operation_id(method, &route_object.route)),
+        );
+        if !resource.is_empty() {
+            operation.insert("tags".to_string(), json!([resource]));
+        }
+
+        let parameters: Vec<Value> = path_parameters(&route_object.path_params);
+        if !parameters.is_empty() {
+            operation.insert("parameters".to_string(), Value::Array(parameters));
+        }
+
+        let request_schema: Option<Value> = match entity {
+            Some(entity)
+                if matches!(
+                    route_object.method,
+                    HttpMethod::Post | HttpMethod::Put | HttpMethod::Patch
+                ) =>
+            {
+                Some(entity_component(&mut components, entity))
+            }
+            _ => shape_schema(&route_object.request_body)
+                .map(|schema| component_ref(&mut components, &resource, method, "Request", schema)),
+        };
+        if let Some(schema) = request_schema {
+            operation.insert(
+                "requestBody".to_string(),
+                json!({
+                    "required": true,
+                    "content": {"application/json": {"schema": schema}}
+                }),
+            );
+        }
+
+        let response_schema: Option<Value> = match entity {
+            Some(entity) if route_object.method == HttpMethod::Get => {
+                let item: Value = entity_component(&mut components, entity);
+                Some(match &route_object.response {
+                    BodySchema::Array(_) => json!({"type": "array", "items": item}),
+                    _ => item,
+                })
+            }
+            _ => shape_schema(&route_object.response).map(|schema| match schema {
+                Value::Object(ref fields) if fields.get("type") == Some(&json!("array")) => {
+                    let items: Value = fields.get("items").cloned().unwrap_or(json!({}));
+                    let item: Value =
+                        component_ref(&mut components, &resource, method, "Response", items);
+                    json!({"type": "array", "items": item})
+                }
+                schema => component_ref(&mut components, &resource, method, "Response", schema),
+            }),
+        };
+        let mut success: Map<String, Value> = Map::new();
+        success.insert(
+            "description".to_string(),
+            Value::String("Successful response".to_string()),
+        );
+        if let Some(schema) = response_schema {
+            success.insert(
+                "content".to_string(),
+                json!({"application/json": {"schema": schema}}),
+            );
+        }
+        operation.insert(
+            "responses".to_string(),
+            json!({"2XX": success, "default": {"description": "Error response"}}),
+        );
+
+        let path_item: &mut Value = paths
+            .entry(route_object.route.clone())
+            .or_insert_with(|| json!({}));
+        path_item[method] = Value::Object(operation);
+    }
+
+    let mut document: Value = json!({
+        "openapi": OPENAPI_VERSION,
+        "info": {
+            "title": title,
+            "version": "0.1.0",
+            "description": description,
+        },
+        "paths": paths,
+    });
+    if !components.is_empty() {
+        document["components"] = json!({ "schemas": components });
+    }
+    document
+}
+
+/// Checks an OpenAPI document against the OpenAPI 3.1 JSON schema and resolves its `$ref`s.
+///
+/// # Arguments
+/// - `document`: The OpenAPI document.
+/// - `openapi_schema`: The JSON schema of OpenAPI 3.1 documents.
+///
+/// # Returns
+/// One description per problem; empty if the document is valid.
+pub fn validate_openapi_document(document: &Value, openapi_schema: &Value) -> Vec<String> {
+    let validator: jsonschema::Validator = match jsonschema::draft202012::new(openapi_schema) {
+        Ok(validator) => validator,
+        Err(e) => return vec![format!("Unable to compile the OpenAPI schema: {}", e)],
+    };
+    let mut issues: Vec<String> = validator
+        .iter_errors(document)
+        .map(|error| {
+            let instance_path: String = error.instance_path.to_string();
+            match instance_path.as_str() {
+                "" => format!("/: {}", error),
+                _ => format!("{}: {}", instance_path, error),
+            }
+        })
+        .collect();
+
+    let mut references: Vec<String> = vec![];
+    collect_references(document, &mut references);
+    for reference in references {
+        let resolved: Option<&Value> = reference
+            .strip_prefix('#')
+            .and_then(|pointer| document.pointer(pointer));
+        if resolved.is_none() {
+            issues.push(format!("Unresolved reference {}", reference));
+        }
+    }
+    issues
+}
+
+fn collect_references(value: &Value, references: &mut Vec<String>) {
+    match value {
+        Value::Object(fields) => {
+            for (name, field) in fields {
+                match (name.as_str(), field) {
+                    ("$ref", Value::String(reference)) => references.push(reference.clone()),
+                    _ => collect_references(field, references),
+                }
+            }
+        }
+        Value::Array(items) => items
+            .iter()
+            .for_each(|item| collect_references(item, references)),
+        _ => {}
+    }
+}
+
+/// Fetches the OpenAPI document from the running web server and checks it matches the generated one.
+///
+/// # Arguments
+/// - `client`: The `reqwest::Client` used to make the request.
+/// - `base_url`: The web server address, e.g. `http://127.0.0.1:8080`.
+/// - `document`: The generated OpenAPI document.
+///
+/// # Returns
+/// The result of the call, with an error if the document is missing or differs.
+pub async fn check_openapi_served(
+    client: &Client,
+    base_url: &str,
+    document: &Value,
+) -> EndpointTestResult {
+    let url: String = format!("{}{}", base_url, OPENAPI_ROUTE);
+    let mut result: EndpointTestResult = EndpointTestResult {
+        route: OPENAPI_ROUTE.to_string(),
+        method: HttpMethod::Get,
+        url: url.clone(),
+        request_body: None,
+        authenticated: false,
+        status: None,
+        response_body: None,
+        error: None,
+    };
+
+    match send_endpoint_request(client, Method::GET, &url, None, None).await {
+        Ok((status, body)) => {
+            result.status = Some(status);
+            result.error = match (status, serde_json::from_str::<Value>(&body)) {
+                (200, Ok(served)) if &served == document => None,
+                (200, Ok(_)) => Some("Serves a different OpenAPI document".to_string()),
+                (200, Err(_)) => Some("Response body is not valid JSON".to_string()),
+                (status, _) => Some(format!(
+                    "Unexpected status code {}, the route must serve the file read from WEB_SERVER_OPENAPI_PATH",
+                    status
+                )),
+            };
+            result.response_body = Some(body);
+        }
+        Err(e) => result.error = Some(format!("Request failed: {}", e)),
+    }
+    result
+}
+
+/// Builds an operation id like `get_task_by_id` from the method and route.
+fn operation_id(method: &str, route: &str) -> String {
+    let mut parts: Vec<String> = vec![method.to_string()];
+    for segment in route.split('/').filter(|segment| !segment.is_empty()) {
+        match segment
+            .strip_prefix('{')
+            .and_then(|segment| segment.strip_suffix('}'))
+        {
+            Some(param) => parts.push(format!("by_{}", param)),
+            None => parts.push(segment.to_string()),
+        }
+    }
+    parts
+        .join("_")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Describes every path parameter of a route as a required path parameter.
+fn path_parameters(path_params: &[String]) -> Vec<Value> {
+    path_params
+        .iter()
+        .map(|name| {
+            let schema_type: &str = if name == "id" || name.ends_with("_id") {
+                "integer"
+            } else {
+                "string"
+            };
+            json!({
+                "name": name,
+                "in": "path",
+                "required": true,
+                "schema": {"type": schema_type},
+            })
+        })
+        .collect()
+}
+
+/// Converts a schema from `RouteObject` to a JSON schema; missing and unknown schemas have none.
+fn shape_schema(schema: &BodySchema) -> Option<Value> {
+    match schema {
+        BodySchema::Object(fields) if !fields.is_empty() => {
+            let properties: Map<String, Value> = fields
+                .iter()
+                .map(|(name, field_schema)| {
+                    (
+                        name.clone(),
+                        shape_schema(field_schema).unwrap_or_else(|| json!({})),
+                    )
+                })
+                .collect();
+            let required: Vec<&String> = fields.keys().collect();
+            Some(json!({"type": "object", "properties": properties, "required": required}))
+        }
+        BodySchema::Array(item) => {
+            let items: Value = shape_schema(item).unwrap_or_else(|| json!({}));
+            Some(json!({"type": "array", "items": items}))
+        }
+        BodySchema::Field(field_type) => Some(field_type_schema(*field_type)),
+        _ => None,
+    }
+}
+
+/// Adds an object schema to the components and returns a reference to it.
+///
+/// Schemas equal to an existing component reuse it. Other schemas are named after their resource,
+/// with the method and role appended when that name is taken. Non-object schemas are inlined.
+fn component_ref(
+    components: &mut Map<String, Value>,
+    resource: &str,
+    method: &str,
+    role: &str,
+    schema: Value,
+) -> Value {
+    if schema.get("type") != Some(&json!("object")) || resource.is_empty() {
+        return schema;
+    }
+    if let Some((name, _)) = components.iter().find(|(_, existing)| **existing == schema) {
+        return schema_ref(name);
+    }
+
+    let base_name: String = pascal_case(resource);
+    let name: String = if components.contains_key(&base_name) {
+        format!("{}{}{}", base_name, pascal_case(method), role)
+    } else {
+        base_name
+    };
+    let mut unique_name: String = name.clone();
+    let mut suffix: usize = 2;
+    while components.contains_key(&unique_name) {
+        unique_name = format!("{}{}", name, suffix);
+        suffix += 1;
+    }
+    components.insert(unique_name.clone(), schema);
+    schema_ref(&unique_name)
+}
+
+/// Adds the schema of a domain model entity to the components and returns a reference to it.
+fn entity_component(components: &mut Map<String, Value>, entity: &EntitySchema) -> Value {
+    let name: String = pascal_case(&entity.name);
+    if !components.contains_key(&name) {
+        let properties: Map<String, Value> = entity
+            .fields
+            .iter()
+            .map(|field| (field.name.clone(), field_schema(field)))
+            .collect();
+        let required: Vec<&String> = entity
+            .fields
+            .iter()
+            .filter(|field| field.required)
+            .map(|field| &field.name)
+            .collect();
+        components.insert(
+            name.clone(),
+            json!({"type": "object", "properties": properties, "required": required}),
+        );
+    }
+    schema_ref(&name)
+}
+
+fn field_schema(field: &FieldSchema) -> Value {
+    let mut schema: Value = field_type_schema(field.field_type);
+    let validation = &field.validation;
+    if let Some(min_length) = validation.min_length {
+        schema["minLength"] = json!(min_length);
+    }
+    if let Some(max_length) = validation.max_length {
+        schema["maxLength"] = json!(max_length);
+    }
+    if let Some(minimum) = validation.minimum {
+        schema["minimum"] = json!(minimum);
+    }
+    if let Some(maximum) = validation.maximum {
+        schema["maximum"] = json!(maximum);
+    }
+    if !validation.allowed_values.is_empty() {
+        schema["enum"] = json!(validation.allowed_values);
+    }
+    schema
+}
+
+fn field_type_schema(field_type: FieldType) -> Value {
+    match field_type {
+        FieldType::String => json!({"type": "string"}),
+        FieldType::Integer => json!({"type": "integer"}),
+        FieldType::Float => json!({"type": "number"}),
+        FieldType::Bool => json!({"type": "boolean"}),
+        FieldType::DateTime => json!({"type": "string", "format": "date-time"}),
+    }
+}
+
+fn schema_ref(name: &str) -> Value {
+    json!({ "$ref": format!("#/components/schemas/{}", name) })
+}
+
+/// Turns `user_profile` or `user-profile` into `UserProfile`.
+fn pascal_case(text: &str) -> String {
+    text.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::general::read_openapi_schema;
+    use std::collections::BTreeMap;
+
+    fn object(fields: &[(&str, FieldType)]) -> BodySchema {
+        BodySchema::Object(
+            fields
+                .iter()
+                .map(|(name, field_type)| (name.to_string(), BodySchema::Field(*field_type)))
+                .collect::<BTreeMap<String, BodySchema>>(),
+        )
+    }
+
+    fn routes() -> Vec<RouteObject> {
+        let note: BodySchema = object(&[("id", FieldType::Integer), ("text", FieldType::String)]);
+        vec![
+            RouteObject::new(
+                HttpMethod::Get,
+                "/notes".to_string(),
+                BodySchema::None,
+                BodySchema::Array(Box::new(note.clone())),
+            ),
+            RouteObject::new(
+                HttpMethod::Get,
+                "/notes/{id}".to_string(),
+                BodySchema::None,
+                note,
+            ),
+            RouteObject::new(
+                HttpMethod::Post,
+                "/notes".to_string(),
+                object(&[("text", FieldType::String)]),
+                BodySchema::Unknown,
+            ),
+            RouteObject::new(
+                HttpMethod::Post,
+                "/task".to_string(),
+                BodySchema::Unknown,
+                BodySchema::Unknown,
+            ),
+        ]
+    }
+
+    fn domain_model() -> DomainModel {
+        serde_json::from_value(json!({
+            "entities": [{
+                "name": "Task",
+                "resource": "task",
+                "fields": [
+                    {"name": "id", "type": "integer"},
+                    {"name": "title", "type": "string", "validation": {"max_length": 80}},
+                    {"name": "due", "type": "datetime", "required": false}
+                ]
+            }]
+        }))
+        .expect("valid domain model")
+    }
+
+    #[test]
+    fn builds_documents_that_pass_the_openapi_schema() {
+        let document: Value =
+            build_openapi_document("notes", "Notes API", &routes(), Some(&domain_model()));
+
+        assert_eq!(
+            validate_openapi_document(&document, &read_openapi_schema()),
+            Vec::<String>::new()
+        );
+        assert_eq!(document["openapi"], "3.1.0");
+        assert_eq!(
+            document["paths"]["/notes/{id}"]["get"]["operationId"],
+            "get_notes_by_id"
+        );
+        assert_eq!(
+            document["paths"]["/notes/{id}"]["get"]["parameters"][0]["schema"]["type"],
+            "integer"
+        );
+    }
+
+    #[test]
+    fn shares_component_schemas_between_operations() {
+        let document: Value =
+            build_openapi_document("notes", "Notes API", &routes(), Some(&domain_model()));
+        let schemas: &Value = &document["components"]["schemas"];
+
+        let list_items: &Value = &document["paths"]["/notes"]["get"]["responses"]["2XX"]["content"]
+            ["application/json"]["schema"]["items"];
+        let detail: &Value = &document["paths"]["/notes/{id}"]["get"]["responses"]["2XX"]
+            ["content"]["application/json"]["schema"];
+        let create: &Value = &document["paths"]["/notes"]["post"]["requestBody"]["content"]
+            ["application/json"]["schema"];
+        assert_eq!(list_items, &schema_ref("Notes"));
+        assert_eq!(detail, &schema_ref("Notes"));
+        assert_eq!(create, &schema_ref("NotesPostRequest"));
+
+        let task_request: &Value = &document["paths"]["/task"]["post"]["requestBody"]["content"]
+            ["application/json"]["schema"];
+        assert_eq!(task_request, &schema_ref("Task"));
+        assert_eq!(schemas["Task"]["required"], json!(["id", "title"]));
+        assert_eq!(
+            schemas["Task"]["properties"]["title"],
+            json!({"type": "string", "maxLength": 80})
+        );
+        assert_eq!(schemas["Task"]["properties"]["due"]["format"], "date-time");
+    }
+
+    #[test]
+    fn reports_unresolved_references() {
+        let mut document: Value = build_openapi_document("notes", "Notes API", &routes(), None);
+        document["paths"]["/notes"]["get"]["responses"]["2XX"]["content"]["application/json"]
+            ["schema"] = schema_ref("Missing");
+
+        assert_eq!(
+            validate_openapi_document(&document, &read_openapi_schema()),
+            vec!["Unresolved reference #/components/schemas/Missing"]
+        );
+    }
+
+    #[test]
+    fn names_operations_and_components() {
+        assert_eq!(
+            operation_id("delete", "/users/{user_id}/tasks/{id}"),
+            "delete_users_by_user_id_tasks_by_id"
+        );
+        assert_eq!(pascal_case("user_profile"), "UserProfile");
+        assert_eq!(pascal_case("user-profile"), "UserProfile");
+    }
+}
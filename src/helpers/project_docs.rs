@@ -0,0 +1,342 @@
+use crate::helpers::devops::describe_env_var;
+use crate::helpers::endpoint_testing::EndpointTestResult;
+use crate::helpers::openapi::OPENAPI_ROUTE;
+use crate::helpers::sandbox::TEST_THREADS_ARG;
+use crate::helpers::target_framework::TargetFramework;
+use crate::models::agents::agent_traits::{BodySchema, FactSheet, HttpMethod, RouteObject};
+use serde_json::Value;
+
+/// The address the documented curl examples call.
+pub const DOCS_BASE_URL: &str = "http://127.0.0.1:8080";
+
+/// Response bodies longer than this are cut in the documented examples.
+const MAX_EXAMPLE_RESPONSE_CHARS: usize = 1200;
+
+/// An endpoint call that succeeded against the live test server, documented as a curl example.
+///
+/// # Fields
+/// - `method`: The HTTP method.
+/// - `route`: The route as declared in the schema, e.g. `/task/{id}`.
+/// - `path`: The path that was called, with path parameters substituted.
+/// - `request_body`: The payload that was sent, if any.
+/// - `authenticated`: Whether the session token returned by login was sent.
+/// - `status`: The status code the server returned.
+/// - `response_body`: The body the server returned.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EndpointExample {
+    pub method: HttpMethod,
+    pub route: String,
+    pub path: String,
+    pub request_body: Option<Value>,
+    pub authenticated: bool,
+    pub status: u16,
+    pub response_body: String,
+}
+
+impl EndpointExample {
+    /// Builds the curl command reproducing the call against [`DOCS_BASE_URL`].
+    pub fn curl_command(&self) -> String {
+        let mut command: String = format!("curl -X {} {}{}", self.method, DOCS_BASE_URL, self.path);
+        if self.authenticated {
+            command.push_str(" \\\n  -H \"Authorization: Bearer $TOKEN\"");
+        }
+        if let Some(request_body) = &self.request_body {
+            command.push_str(&format!(
+                " \\\n  -H 'Content-Type: application/json' \\\n  -d '{}'",
+                request_body.to_string().replace('\'', "'\\''")
+            ));
+        }
+        command
+    }
+}
+
+/// Keeps the endpoint calls that passed against the live test server as documentation examples.
+///
+/// # Arguments
+/// - `test_results`: The results of the endpoint tests.
+/// - `base_url`: The address of the test server the calls were made against.
+///
+/// # Returns
+/// One example per passing call, in the order they were made.
+pub fn examples_from_results(
+    test_results: &[EndpointTestResult],
+    base_url: &str,
+) -> Vec<EndpointExample> {
+    test_results
+        .iter()
+        .filter(|test_result| test_result.error.is_none())
+        .filter_map(|test_result| {
+            Some(EndpointExample {
+                method: test_result.method,
+                route: test_result.route.clone(),
+                path: test_result.url.strip_prefix(base_url)?.to_string(),
+                request_body: test_result.request_body.clone(),
+                authenticated: test_result.authenticated,
+                status: test_result.status?,
+                response_body: test_result.response_body.clone().unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+/// Renders the README of the generated project.
+///
+/// The README holds the project description, how to run and test the server, its environment variables
+/// and an API reference: a table of every route of the endpoint schema and a curl example for each
+/// route that passed against the live test server.
+///
+/// # Arguments
+/// - `project_name`: The name of the generated project.
+/// - `framework`: The framework the server is written with, picking how it is run.
+/// - `fact_sheet`: The fact sheet with the project description, environment variables and endpoint schema.
+/// - `examples`: The verified endpoint examples.
+/// - `has_docker`: Whether the project ships a Dockerfile and compose file.
+///
+/// # Returns
+/// The README as markdown.
+pub fn render_project_readme(
+    project_name: &str,
+    framework: TargetFramework,
+    fact_sheet: &FactSheet,
+    examples: &[EndpointExample],
+    has_docker: bool,
+) -> String {
+    let mut readme: String = format!(
+        "# {}\n\n{}\n",
+        project_name,
+        fact_sheet.project_description.trim()
+    );
+
+    readme.push_str(&format!(
+        "\n## Running\n\n```sh\n{}\n```\n",
+        framework.run_instructions()
+    ));
+    readme.push_str(
+        "\nThe server listens on http://127.0.0.1:8080 and stores its data in `database.json` in the working directory.\nFiles in `static/` are served under `/static/`.\n",
+    );
+    if has_docker {
+        readme.push_str(
+            "\nTo run it in a container, copy `.env.example` to `.env` and start it with Docker Compose:\n\n```sh\ndocker compose up --build\n```\n",
+        );
+    }
+    if fact_sheet.api_tests_code.is_some() {
+        readme.push_str(&format!(
+            "\n## Testing\n\nThe tests save `database.json` in a temporary working directory they share, so they run one at a time:\n\n```sh\ncargo test -- {}\n```\n",
+            TEST_THREADS_ARG
+        ));
+    }
+
+    let env_vars: Vec<String> = fact_sheet.env_vars.clone().unwrap_or_default();
+    if !env_vars.is_empty() {
+        readme.push_str("\n## Environment variables\n\n| Variable | Default | Description |\n| --- | --- | --- |\n");
+        for name in &env_vars {
+            let (default, description): (&str, &str) = describe_env_var(name);
+            let default: String = if default.is_empty() {
+                "-".to_string()
+            } else {
+                format!("`{}`", default)
+            };
+            readme.push_str(&format!("| `{}` | {} | {} |\n", name, default, description));
+        }
+    }
+
+    let routes: Vec<RouteObject> = fact_sheet.api_endpoint_schema.clone().unwrap_or_default();
+    if routes.is_empty() {
+        return readme;
+    }
+    readme.push_str(&format!(
+        "\n## API reference\n\nThe OpenAPI 3.1 document is served under `{}`.\n\n| Method | Route | Request body | Response |\n| --- | --- | --- | --- |\n",
+        OPENAPI_ROUTE
+    ));
+    for route_object in &routes {
+        readme.push_str(&format!(
+            "| {} | `{}` | {} | {} |\n",
+            route_object.method,
+            route_object.route,
+            table_shape(&route_object.request_body),
+            table_shape(&route_object.response)
+        ));
+    }
+
+    if examples.is_empty() {
+        return readme;
+    }
+    readme.push_str(
+        "\n### Examples\n\nEvery example was run against the server while it was generated.\n",
+    );
+    if examples.iter().any(|example| example.authenticated) {
+        readme.push_str(
+            "Examples sending `$TOKEN` need the token returned by the login route: `export TOKEN=<token>`.\n",
+        );
+    }
+    for example in examples {
+        readme.push_str(&format!(
+            "\n#### {} {}\n\n```sh\n{}\n```\n",
+            example.method,
+            example.route,
+            example.curl_command()
+        ));
+        if example.response_body.trim().is_empty() {
+            readme.push_str(&format!(
+                "\nResponse `{}` with an empty body.\n",
+                example.status
+            ));
+            continue;
+        }
+        let (language, response): (&str, String) = example_response(&example.response_body);
+        readme.push_str(&format!(
+            "\nResponse `{}`:\n\n```{}\n{}\n```\n",
+            example.status, language, response
+        ));
+    }
+    readme
+}
+
+/// Formats a schema for a table cell; missing and unknown schemas become `-`.
+fn table_shape(schema: &BodySchema) -> String {
+    match schema {
+        BodySchema::None | BodySchema::Unknown => "-".to_string(),
+        _ => format!("`{}`", schema.to_string().replace('|', "\\|")),
+    }
+}
+
+/// Pretty prints a JSON response body and cuts it to a readable length.
+///
+/// # Returns
+/// The code block language, `json` or `text`, and the formatted body.
+fn example_response(body: &str) -> (&'static str, String) {
+    let (language, formatted): (&str, String) = match serde_json::from_str::<Value>(body) {
+        Ok(value) => (
+            "json",
+            serde_json::to_string_pretty(&value).expect("Failed to format response body"),
+        ),
+        Err(_) => ("text", body.to_string()),
+    };
+    if formatted.chars().count() <= MAX_EXAMPLE_RESPONSE_CHARS {
+        return (language, formatted);
+    }
+    let mut cut: String = formatted.chars().take(MAX_EXAMPLE_RESPONSE_CHARS).collect();
+    cut.push_str("\n...");
+    (language, cut)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn test_result(url: &str, status: Option<u16>, error: Option<&str>) -> EndpointTestResult {
+        EndpointTestResult {
+            route: "/todos/{id}".to_string(),
+            method: HttpMethod::Get,
+            url: url.to_string(),
+            request_body: None,
+            authenticated: false,
+            status,
+            response_body: Some("{\"id\":1}".to_string()),
+            error: error.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn keeps_the_passing_calls_as_examples() {
+        let base_url: &str = "http://127.0.0.1:41000";
+        let results: Vec<EndpointTestResult> = vec![
+            test_result("http://127.0.0.1:41000/todos/1", Some(200), None),
+            test_result(
+                "http://127.0.0.1:41000/todos/2",
+                Some(500),
+                Some("Expected 200"),
+            ),
+            test_result("http://127.0.0.1:41000/todos/3", None, None),
+            test_result("http://example.com/todos/4", Some(200), None),
+        ];
+
+        let examples: Vec<EndpointExample> = examples_from_results(&results, base_url);
+
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].path, "/todos/1");
+        assert_eq!(examples[0].status, 200);
+        assert_eq!(examples[0].response_body, "{\"id\":1}");
+    }
+
+    #[test]
+    fn builds_curl_commands_with_token_and_quoted_body() {
+        let example: EndpointExample = EndpointExample {
+            method: HttpMethod::Post,
+            route: "/todos".to_string(),
+            path: "/todos".to_string(),
+            request_body: Some(json!({"title": "don't forget"})),
+            authenticated: true,
+            status: 201,
+            response_body: String::new(),
+        };
+
+        assert_eq!(
+            example.curl_command(),
+            "curl -X POST http://127.0.0.1:8080/todos \\\n  -H \"Authorization: Bearer $TOKEN\" \\\n  -H 'Content-Type: application/json' \\\n  -d '{\"title\":\"don'\\''t forget\"}'"
+        );
+    }
+
+    #[test]
+    fn renders_the_readme_sections() {
+        let mut fact_sheet: FactSheet = serde_json::from_value(json!({
+            "project_description": "  A todo list server.  ",
+            "env_vars": ["WEB_SERVER_PORT", "WEATHER_API_KEY"],
+            "api_tests_code": "// tests"
+        }))
+        .expect("valid fact sheet");
+        fact_sheet.api_endpoint_schema = Some(vec![RouteObject::new(
+            HttpMethod::Get,
+            "/todos/{id}".to_string(),
+            BodySchema::None,
+            BodySchema::Unknown,
+        )]);
+        let example: EndpointExample = EndpointExample {
+            method: HttpMethod::Get,
+            route: "/todos/{id}".to_string(),
+            path: "/todos/1".to_string(),
+            request_body: None,
+            authenticated: true,
+            status: 200,
+            response_body: "{\"id\":1}".to_string(),
+        };
+
+        let readme: String = render_project_readme(
+            "todos",
+            TargetFramework::Axum,
+            &fact_sheet,
+            &[example],
+            true,
+        );
+
+        assert!(readme.starts_with("# todos\n\nA todo list server.\n"));
+        assert!(readme.contains("```sh\ncargo run --release\n```"));
+        assert!(readme.contains("docker compose up --build"));
+        assert!(readme.contains(&format!("cargo test -- {}", TEST_THREADS_ARG)));
+        assert!(readme.contains("| `WEB_SERVER_PORT` | `8080` | Port the server listens on |\n"));
+        assert!(readme.contains("| `WEATHER_API_KEY` | - | Read by the web server |\n"));
+        assert!(readme.contains("| GET | `/todos/{id}` | - | - |\n"));
+        assert!(readme.contains("export TOKEN=<token>"));
+        assert!(readme.contains("Response `200`:\n\n```json\n{\n  \"id\": 1\n}\n```\n"));
+
+        let bare_readme: String =
+            render_project_readme("todos", TargetFramework::Axum, &fact_sheet, &[], false);
+        assert!(!bare_readme.contains("### Examples"));
+        assert!(!bare_readme.contains("docker compose"));
+    }
+
+    #[test]
+    fn formats_and_cuts_example_responses() {
+        assert_eq!(
+            example_response("not json"),
+            ("text", "not json".to_string())
+        );
+
+        let long_body: String = format!("\"{}\"", "a".repeat(MAX_EXAMPLE_RESPONSE_CHARS * 2));
+        let (language, response): (&str, String) = example_response(&long_body);
+        assert_eq!(language, "json");
+        assert_eq!(response.chars().count(), MAX_EXAMPLE_RESPONSE_CHARS + 4);
+        assert!(response.ends_with("\n..."));
+    }
+}
@@ -0,0 +1,518 @@
+use crate::helpers::command_lines::PrintCommand;
+use crate::helpers::general::{env_setting, get_web_server_project_path, save_fact_sheet};
+use crate::models::agents::agent_traits::FactSheet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+/// Environment variable turning the git history of the generated project off with `false`.
+const PROJECT_HISTORY_ENV: &str = "PROJECT_GIT_HISTORY";
+/// The identity stages are committed with, so commits work without a global git config.
+const HISTORY_AUTHOR_NAME: &str = "RestOn";
+const HISTORY_AUTHOR_EMAIL: &str = "reston@localhost";
+/// Build output, virtualenvs, runtime data and secrets kept out of the history.
+const HISTORY_GITIGNORE: &str = "/target/\n/.venv/\n__pycache__/\n/data/\n.env\n";
+/// Longest subject line of a stage commit, in characters.
+const MAX_SUBJECT_LENGTH: usize = 72;
+/// How many lines of the errors a fix addressed are kept in its commit message.
+const MAX_ERROR_LINES: usize = 40;
+/// Shortest commit hash prefix accepted by `rollback`.
+const MIN_HASH_PREFIX_LENGTH: usize = 4;
+/// Start of the subject of a [`ProjectStage::RolledBack`] commit.
+const ROLLED_BACK_SUBJECT_PREFIX: &str = "Roll back to ";
+
+/// A point in the generation of a project that is committed to its git history.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProjectStage {
+    /// The backend code written from the code templates.
+    Initial,
+    /// The backend code after the improvement pass.
+    Improved,
+    /// The backend code after a change request of a revise run was applied.
+    Revised { change_request: String },
+    /// The backend code after a fix iteration, with the errors it was asked to fix.
+    Fix { iteration: u8, errors: String },
+    /// The backend code after the security reviewer applied its fixes.
+    SecurityFixes,
+    /// The backend code once it builds and passes the endpoint and integration tests, with the checks that
+    /// could not be run for its framework, e.g. the integration tests of a FastAPI server.
+    TestsPassing { skipped_checks: Vec<String> },
+    /// The frontend, DevOps files, docs and fact sheet written at the end of a run.
+    Completed,
+    /// The project as it was when an agent stopped the run, with its error.
+    Stopped { error: String },
+    /// Uncommitted changes found in the project before rolling it back.
+    ManualEdits,
+    /// The project restored to the tree of an earlier stage.
+    RolledBack { stage: StageCommit },
+}
+
+impl ProjectStage {
+    /// Returns the commit message describing the stage: a subject line, then details such as the
+    /// errors a fix addressed.
+    pub fn commit_message(&self) -> String {
+        match self {
+            ProjectStage::Initial => "Initial backend code from the code templates".to_string(),
+            ProjectStage::Improved => "Improved backend code".to_string(),
+            ProjectStage::Revised { change_request } => format!(
+                "{}\n\nChange request:\n{}",
+                truncate_subject(&format!("Revise backend: {}", change_request)),
+                change_request
+            ),
+            ProjectStage::Fix { iteration, errors } => {
+                let error_lines: Vec<&str> = errors.lines().collect();
+                let mut message: String = format!(
+                    "{}\n\nErrors addressed:\n{}",
+                    truncate_subject(&format!("Fix #{}: {}", iteration, error_summary(errors))),
+                    error_lines[..error_lines.len().min(MAX_ERROR_LINES)].join("\n")
+                );
+                if error_lines.len() > MAX_ERROR_LINES {
+                    message.push_str(&format!(
+                        "\n... {} more lines",
+                        error_lines.len() - MAX_ERROR_LINES
+                    ));
+                }
+                message
+            }
+            ProjectStage::SecurityFixes => "Security review fixes".to_string(),
+            ProjectStage::TestsPassing { skipped_checks } if skipped_checks.is_empty() => {
+                "Tests passing: build, endpoint and integration tests".to_string()
+            }
+            ProjectStage::TestsPassing { skipped_checks } => format!(
+                "{}\n\nNot run for this framework: {}",
+                truncate_subject(&format!(
+                    "Tests passing without {}",
+                    skipped_checks.join(" and ")
+                )),
+                skipped_checks.join(", ")
+            ),
+            ProjectStage::Completed => {
+                "Run completed: frontend, DevOps files, docs and fact sheet".to_string()
+            }
+            ProjectStage::Stopped { error } => format!(
+                "{}\n\n{}",
+                truncate_subject(&format!("Run stopped: {}", error)),
+                error
+            ),
+            ProjectStage::ManualEdits => "Manual edits before rollback".to_string(),
+            ProjectStage::RolledBack { stage } => truncate_subject(&format!(
+                "{}{}: {}",
+                ROLLED_BACK_SUBJECT_PREFIX, stage.hash, stage.subject
+            )),
+        }
+    }
+}
+
+/// A committed stage of the generated project.
+///
+/// # Fields
+/// - `hash`: The abbreviated commit hash.
+/// - `subject`: The subject line of the commit message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StageCommit {
+    pub hash: String,
+    pub subject: String,
+}
+
+impl StageCommit {
+    /// Whether the commit is a stage of a run, as opposed to the commits a rollback adds.
+    pub fn is_generation_stage(&self) -> bool {
+        self.subject != ProjectStage::ManualEdits.commit_message()
+            && !self.subject.starts_with(ROLLED_BACK_SUBJECT_PREFIX)
+    }
+}
+
+/// Numbers the generation stages from 1, leaving out the commits of rollbacks so the numbers shown by
+/// `history` stay the same after rolling back.
+///
+/// # Arguments
+/// - `stages`: The stage commits, oldest first, as returned by [`list_project_stages`].
+///
+/// # Returns
+/// The number of each commit, `None` for the commits of rollbacks.
+pub fn stage_numbers(stages: &[StageCommit]) -> Vec<Option<usize>> {
+    let mut number: usize = 0;
+    stages
+        .iter()
+        .map(|stage| {
+            stage.is_generation_stage().then(|| {
+                number += 1;
+                number
+            })
+        })
+        .collect()
+}
+
+/// Returns the line of the errors that best names them: the first compiler `error`, else the first non-empty line.
+fn error_summary(errors: &str) -> &str {
+    errors
+        .lines()
+        .map(str::trim)
+        .find(|line| line.starts_with("error"))
+        .or_else(|| errors.lines().map(str::trim).find(|line| !line.is_empty()))
+        .unwrap_or("unknown errors")
+}
+
+/// Shortens a subject line to [`MAX_SUBJECT_LENGTH`] characters.
+fn truncate_subject(subject: &str) -> String {
+    let first_line: &str = subject.lines().next().unwrap_or_default();
+    if first_line.chars().count() <= MAX_SUBJECT_LENGTH {
+        return first_line.to_string();
+    }
+    let shortened: String = first_line.chars().take(MAX_SUBJECT_LENGTH - 3).collect();
+    format!("{}...", shortened.trim_end())
+}
+
+/// Runs a git command in the generated project.
+///
+/// # Arguments
+/// - `project_dir`: The generated project directory.
+/// - `args`: The git arguments, e.g. `["add", "-A"]`.
+///
+/// # Returns
+/// The standard output, or the standard error if git failed or could not be started.
+fn run_git(project_dir: &Path, args: &[&str]) -> Result<String, String> {
+    let output: Output = Command::new("git")
+        .arg("-c")
+        .arg(format!("user.name={}", HISTORY_AUTHOR_NAME))
+        .arg("-c")
+        .arg(format!("user.email={}", HISTORY_AUTHOR_EMAIL))
+        .args(args)
+        .current_dir(project_dir)
+        .output()
+        .map_err(|e| format!("Unable to run git: {}", e))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+/// Initialises a git repository in the generated project, with a `.gitignore` for build output and runtime data.
+///
+/// # Arguments
+/// - `project_dir`: The generated project directory.
+fn ensure_history(project_dir: &Path) -> Result<(), String> {
+    if !project_dir.join(".git").exists() {
+        fs::create_dir_all(project_dir).map_err(|e| e.to_string())?;
+        run_git(project_dir, &["init", "--quiet"])?;
+    }
+    let gitignore_path: PathBuf = project_dir.join(".gitignore");
+    if !gitignore_path.exists() {
+        fs::write(gitignore_path, HISTORY_GITIGNORE).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Commits every change of the generated project as a stage.
+///
+/// # Arguments
+/// - `project_dir`: The generated project directory.
+/// - `stage`: The stage the project reached.
+///
+/// # Returns
+/// The stage commit, `None` if nothing changed since the last stage.
+pub fn commit_project_stage(
+    project_dir: &Path,
+    stage: &ProjectStage,
+) -> Result<Option<StageCommit>, String> {
+    ensure_history(project_dir)?;
+    run_git(project_dir, &["add", "--all"])?;
+    if run_git(project_dir, &["status", "--porcelain"])?
+        .trim()
+        .is_empty()
+    {
+        return Ok(None);
+    }
+
+    let message: String = stage.commit_message();
+    run_git(project_dir, &["commit", "--quiet", "--message", &message])?;
+    let hash: String = run_git(project_dir, &["rev-parse", "--short", "HEAD"])?
+        .trim()
+        .to_string();
+    Ok(Some(StageCommit {
+        hash,
+        subject: message.lines().next().unwrap_or_default().to_string(),
+    }))
+}
+
+/// Lists the committed stages of the generated project, oldest first.
+///
+/// # Arguments
+/// - `project_dir`: The generated project directory.
+///
+/// # Returns
+/// The stage commits, empty if the project has no history yet.
+pub fn list_project_stages(project_dir: &Path) -> Result<Vec<StageCommit>, String> {
+    if !project_dir.join(".git").exists() {
+        return Ok(vec![]);
+    }
+    // A fresh repository has no HEAD to list yet
+    if run_git(project_dir, &["rev-parse", "--verify", "--quiet", "HEAD"]).is_err() {
+        return Ok(vec![]);
+    }
+
+    let log: String = run_git(project_dir, &["log", "--reverse", "--format=%h%x09%s"])?;
+    Ok(log
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(hash, subject)| StageCommit {
+            hash: hash.to_string(),
+            subject: subject.to_string(),
+        })
+        .collect())
+}
+
+/// Finds the stage a `rollback` argument names.
+///
+/// # Arguments
+/// - `project_dir`: The generated project directory.
+/// - `stage`: Either a stage number shown by `history`, see [`stage_numbers`], or at least
+///   [`MIN_HASH_PREFIX_LENGTH`] characters of a commit hash.
+///
+/// # Returns
+/// The stage commit, or why the argument names no stage, with a hint on what to pass instead.
+pub fn resolve_project_stage(project_dir: &Path, stage: &str) -> Result<StageCommit, String> {
+    let stages: Vec<StageCommit> = list_project_stages(project_dir)?;
+    let numbers: Vec<Option<usize>> = stage_numbers(&stages);
+    let stage_count: usize = numbers.iter().flatten().count();
+
+    let by_hash = || {
+        stages.iter().find(|stage_commit| {
+            stage.len() >= MIN_HASH_PREFIX_LENGTH
+                && (stage_commit.hash.starts_with(stage) || stage.starts_with(&stage_commit.hash))
+        })
+    };
+    // Hashes made only of digits are rare, numbers are tried first
+    let by_number = stage.parse::<usize>().ok().and_then(|number| {
+        numbers
+            .iter()
+            .position(|stage_number| *stage_number == Some(number))
+            .map(|index| &stages[index])
+    });
+    if let Some(stage_commit) = by_number.or_else(by_hash) {
+        return Ok(stage_commit.clone());
+    }
+
+    Err(if stage_count == 0 {
+        "The project has no stages yet".to_string()
+    } else if stage.parse::<usize>().is_ok() && stage.len() < MIN_HASH_PREFIX_LENGTH {
+        format!(
+            "There is no stage {}, `history` numbers the stages 1 to {}",
+            stage, stage_count
+        )
+    } else if stage.len() < MIN_HASH_PREFIX_LENGTH {
+        format!(
+            "'{}' is too short for a commit hash, pass at least {} characters of a hash or a stage number from `history`",
+            stage, MIN_HASH_PREFIX_LENGTH
+        )
+    } else {
+        format!(
+            "Unknown stage '{}', pass a stage number or a commit hash from `history`",
+            stage
+        )
+    })
+}
+
+/// Rolls the generated project back to an earlier stage.
+///
+/// The rollback is committed as a new stage, so the stages after it stay in the history and can be restored
+/// the same way. Uncommitted changes are committed first so they are not lost. The fact sheet and endpoint
+/// schema are part of each stage, so they are restored along with the code.
+///
+/// # Arguments
+/// - `project_dir`: The generated project directory.
+/// - `target`: The stage to restore, see [`resolve_project_stage`].
+pub fn roll_back_project(project_dir: &Path, target: &StageCommit) -> Result<(), String> {
+    commit_project_stage(project_dir, &ProjectStage::ManualEdits)?;
+    run_git(project_dir, &["read-tree", "--reset", "-u", &target.hash])?;
+    commit_project_stage(
+        project_dir,
+        &ProjectStage::RolledBack {
+            stage: target.clone(),
+        },
+    )?;
+    Ok(())
+}
+
+/// Commits the generated web server project as a stage, unless `PROJECT_GIT_HISTORY` is `false`.
+///
+/// The fact sheet is saved into the project first, so every stage holds the fact sheet matching its code.
+/// Failures are reported but never stop the run, the history is only a record of it.
+///
+/// # Arguments
+/// - `stage`: The stage the project reached.
+/// - `fact_sheet`: The fact sheet of the run at that stage.
+/// - `agent_position`: The position of the agent reporting the commit.
+pub fn record_project_stage(stage: &ProjectStage, fact_sheet: &FactSheet, agent_position: &str) {
+    if !env_setting(PROJECT_HISTORY_ENV, true) {
+        return;
+    }
+
+    let fact_sheet: String =
+        serde_json::to_string_pretty(fact_sheet).expect("Failed to serialize fact sheet");
+    save_fact_sheet(&fact_sheet);
+
+    let project_path: String = get_web_server_project_path();
+    match commit_project_stage(Path::new(&project_path), stage) {
+        Ok(Some(stage_commit)) => PrintCommand::UnitTest.print_agent_message(
+            agent_position,
+            format!("Committed {}: {}", stage_commit.hash, stage_commit.subject).as_str(),
+        ),
+        Ok(None) => {}
+        Err(e) => PrintCommand::Issue.print_agent_message(
+            agent_position,
+            format!("Unable to commit the project history: {}", e).as_str(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stage_commit(hash: &str, stage: &ProjectStage) -> StageCommit {
+        StageCommit {
+            hash: hash.to_string(),
+            subject: stage.commit_message().lines().next().unwrap().to_string(),
+        }
+    }
+
+    #[test]
+    fn commit_messages_keep_the_subject_short_and_the_errors_in_the_body() {
+        let errors: String = format!(
+            "warning: unused import\nerror[E0308]: mismatched types{}\n  --> src/main.rs:3:5",
+            " in the handler".repeat(10)
+        );
+        let message: String = ProjectStage::Fix {
+            iteration: 2,
+            errors: errors.clone(),
+        }
+        .commit_message();
+
+        let subject: &str = message.lines().next().unwrap();
+        assert!(subject.starts_with("Fix #2: error[E0308]: mismatched types"));
+        assert!(subject.ends_with("..."));
+        assert!(subject.chars().count() <= MAX_SUBJECT_LENGTH);
+        assert!(message.ends_with(&format!("Errors addressed:\n{}", errors)));
+    }
+
+    #[test]
+    fn commit_messages_cut_long_error_output() {
+        let errors: String = (1..=50)
+            .map(|line| format!("error: line {}", line))
+            .collect::<Vec<String>>()
+            .join("\n");
+        let message: String = ProjectStage::Fix {
+            iteration: 1,
+            errors,
+        }
+        .commit_message();
+
+        assert!(message.contains("error: line 40\n... 10 more lines"));
+        assert!(!message.contains("error: line 41"));
+    }
+
+    #[test]
+    fn commit_messages_list_the_checks_not_run() {
+        let all_run: String = ProjectStage::TestsPassing {
+            skipped_checks: vec![],
+        }
+        .commit_message();
+        assert_eq!(
+            all_run,
+            "Tests passing: build, endpoint and integration tests"
+        );
+
+        let skipped: String = ProjectStage::TestsPassing {
+            skipped_checks: vec![
+                "the security review".to_string(),
+                "the integration tests".to_string(),
+            ],
+        }
+        .commit_message();
+        assert_eq!(
+            skipped,
+            "Tests passing without the security review and the integration tests\n\n\
+             Not run for this framework: the security review, the integration tests"
+        );
+    }
+
+    #[test]
+    fn commit_messages_name_the_change_request_and_the_restored_stage() {
+        let revised: String = ProjectStage::Revised {
+            change_request: "Add due dates to tasks\nand sort by them".to_string(),
+        }
+        .commit_message();
+        assert_eq!(
+            revised,
+            "Revise backend: Add due dates to tasks\n\nChange request:\nAdd due dates to tasks\nand sort by them"
+        );
+
+        let rolled_back: String = ProjectStage::RolledBack {
+            stage: stage_commit("3f9a2c1", &ProjectStage::Improved),
+        }
+        .commit_message();
+        assert_eq!(rolled_back, "Roll back to 3f9a2c1: Improved backend code");
+    }
+
+    #[test]
+    fn numbers_only_generation_stages() {
+        let initial: StageCommit = stage_commit("aaaa111", &ProjectStage::Initial);
+        let stages: Vec<StageCommit> = vec![
+            initial.clone(),
+            stage_commit("bbbb222", &ProjectStage::Improved),
+            stage_commit("cccc333", &ProjectStage::ManualEdits),
+            stage_commit("dddd444", &ProjectStage::RolledBack { stage: initial }),
+            stage_commit("eeee555", &ProjectStage::Completed),
+        ];
+
+        assert_eq!(
+            stage_numbers(&stages),
+            vec![Some(1), Some(2), None, None, Some(3)]
+        );
+    }
+
+    #[test]
+    fn rollback_keeps_stage_numbers_and_explains_unknown_stages() {
+        let project_dir: PathBuf =
+            std::env::temp_dir().join(format!("project_history_test_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&project_dir);
+        fs::create_dir_all(&project_dir).unwrap();
+
+        fs::write(project_dir.join("main.rs"), "fn main() {}").unwrap();
+        commit_project_stage(&project_dir, &ProjectStage::Initial).unwrap();
+        fs::write(project_dir.join("main.rs"), "fn main() { println!(); }").unwrap();
+        commit_project_stage(&project_dir, &ProjectStage::Improved).unwrap();
+        fs::write(project_dir.join("main.rs"), "edited by hand").unwrap();
+
+        let initial: StageCommit = resolve_project_stage(&project_dir, "1").unwrap();
+        roll_back_project(&project_dir, &initial).unwrap();
+        assert_eq!(
+            fs::read_to_string(project_dir.join("main.rs")).unwrap(),
+            "fn main() {}"
+        );
+        assert_eq!(list_project_stages(&project_dir).unwrap().len(), 4);
+        assert_eq!(resolve_project_stage(&project_dir, "1").unwrap(), initial);
+        assert_eq!(
+            resolve_project_stage(&project_dir, "2").unwrap().subject,
+            "Improved backend code"
+        );
+        assert_eq!(
+            resolve_project_stage(&project_dir, &initial.hash[..4]).unwrap(),
+            initial
+        );
+
+        assert!(resolve_project_stage(&project_dir, "3")
+            .unwrap_err()
+            .contains("stages 1 to 2"));
+        assert!(resolve_project_stage(&project_dir, "ab")
+            .unwrap_err()
+            .contains("too short"));
+        assert!(resolve_project_stage(&project_dir, "zzzzzz")
+            .unwrap_err()
+            .contains("Unknown stage"));
+
+        fs::remove_dir_all(&project_dir).unwrap();
+    }
+}
@@ -0,0 +1,1272 @@
+use crate::models::agents::agent_traits::{BodySchema, FieldType, HttpMethod, RouteObject};
+use quote::ToTokens;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use syn::parse::ParseStream;
+use syn::visit::{self, Visit};
+use syn::{
+    parse_quote, Arm, Attribute, Expr, ExprCall, ExprForLoop, ExprLet, ExprMatch, ExprMethodCall,
+    ExprPath, Fields, FnArg, GenericArgument, Ident, ImplItemFn, ItemEnum, ItemFn, ItemImpl,
+    ItemStruct, ItemType, Lit, LitStr, Local, Member, Pat, PathArguments, ReturnType, Signature,
+    Token, Type, UnOp,
+};
+
+/// Extractors whose type parameter is the request body.
+const REQUEST_BODY_EXTRACTORS: &[&str] = &["Json", "Form"];
+
+/// Types dereferencing to their first type parameter.
+const TRANSPARENT_WRAPPERS: &[&str] = &[
+    "Box",
+    "Rc",
+    "Arc",
+    "Cow",
+    "Json",
+    "Form",
+    "Path",
+    "Query",
+    "Data",
+    "State",
+    "Extension",
+    "Mutex",
+    "MutexGuard",
+    "RwLock",
+    "RwLockReadGuard",
+    "RwLockWriteGuard",
+    "RefCell",
+    "Ref",
+    "RefMut",
+];
+
+/// Types whose success value is their first type parameter.
+const FALLIBLE_WRAPPERS: &[&str] = &["Option", "Result", "LockResult"];
+
+/// Types serialized as JSON arrays.
+const SEQUENCE_TYPES: &[&str] = &["Vec", "VecDeque", "HashSet", "BTreeSet", "LinkedList"];
+
+/// Types serialized as JSON objects with arbitrary keys.
+const MAP_TYPES: &[&str] = &["HashMap", "BTreeMap", "IndexMap"];
+
+/// Types serialized as JSON integers.
+const INTEGER_TYPES: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+/// Types serialized as JSON floats.
+const FLOAT_TYPES: &[&str] = &["f32", "f64"];
+
+/// Types serialized as RFC 3339 timestamps.
+const DATETIME_TYPES: &[&str] = &["DateTime", "NaiveDateTime"];
+
+/// Types serialized as JSON strings.
+const STRING_TYPES: &[&str] = &[
+    "String",
+    "str",
+    "char",
+    "Uuid",
+    "NaiveDate",
+    "NaiveTime",
+    "PathBuf",
+];
+
+/// Nesting depth after which schemas of recursive types are cut.
+const MAX_SCHEMA_DEPTH: usize = 8;
+
+/// Nesting depth after which `.configure(...)`, `.service(...)` and `.nest(...)` mounts are no longer followed.
+const MAX_MOUNT_DEPTH: usize = 8;
+
+/// A route registered with the web server.
+///
+/// # Fields
+/// - `path`: The full path, including the prefixes of enclosing `web::scope(...)`s and `.nest(...)`s.
+/// - `method`: The HTTP method.
+/// - `handler`: The name of the handler function, empty if the handler is a closure.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegisteredRoute {
+    pub path: String,
+    pub method: HttpMethod,
+    pub handler: String,
+}
+
+/// Collects the routes registered in actix-web or axum code.
+///
+/// Reads `.route(path, web::method().to(handler))` calls, `web::scope(...)` and `web::resource(...)`
+/// services and handlers annotated with `#[get("/path")]` style macros. Prefixes of scopes are
+/// followed through `.service(...)` and `.configure(...)`. For axum, reads
+/// `.route(path, get(handler).post(handler))` calls and follows routers passed to `.nest(...)` and
+/// `.merge(...)`; axum 0.7 parameters like `:id` are written as `{id}`.
+///
+/// # Arguments
+/// - `syntax_tree`: The parsed code.
+///
+/// # Returns
+/// The routes in the order they are registered, without duplicates.
+pub fn collect_routes(syntax_tree: &syn::File) -> Vec<RegisteredRoute> {
+    let mut collector: RouteCollector = RouteCollector::default();
+    collector.visit_file(syntax_tree);
+
+    let mut routes: Vec<RegisteredRoute> = vec![];
+    for (owner, route) in &collector.routes {
+        for (prefix, nested) in collector.mount_prefixes(owner.as_deref(), 0) {
+            let mut path: String =
+                brace_path_params(&join_route_path(&prefix, &route.path, nested));
+            if !path.starts_with('/') {
+                path.insert(0, '/');
+            }
+            let registered_route: RegisteredRoute = RegisteredRoute {
+                path,
+                method: route.method,
+                handler: route.handler.clone(),
+            };
+            if !routes.contains(&registered_route) {
+                routes.push(registered_route);
+            }
+        }
+    }
+    routes
+}
+
+/// Builds the endpoint schema of actix-web or axum code without asking the model.
+///
+/// The request body of a route is the type of its `Json<T>` or `Form<T>` extractor. The response is
+/// the type the handler returns as `Json<T>`, wraps in `Json(...)` or passes to `HttpResponse::...().json(...)`,
+/// followed through typed bindings, struct fields and the return types of functions and methods.
+/// Routes without a body or JSON response get `BodySchema::None`, types that cannot be resolved
+/// `BodySchema::Unknown`.
+///
+/// # Arguments
+/// - `code`: The generated Rust source.
+///
+/// # Returns
+/// One `RouteObject` per registered route, or the parse error if the code is not valid Rust.
+pub fn extract_route_objects(code: &str) -> Result<Vec<RouteObject>, syn::Error> {
+    let syntax_tree: syn::File = syn::parse_file(code)?;
+
+    let mut type_index: TypeIndex = TypeIndex::default();
+    type_index.visit_file(&syntax_tree);
+
+    Ok(collect_routes(&syntax_tree)
+        .iter()
+        .map(|route| type_index.route_object(route))
+        .collect())
+}
+
+/// Joins a prefix and the path of a route. axum serves the `/` route of a router nested with `.nest(...)`
+/// at the nest path itself, while actix-web serves `/` in a scope with a trailing slash.
+fn join_route_path(prefix: &str, path: &str, nested: bool) -> String {
+    if nested && path == "/" && !prefix.is_empty() {
+        prefix.to_string()
+    } else {
+        join_paths(prefix, path)
+    }
+}
+
+/// Joins a scope prefix and a path the way actix-web does.
+fn join_paths(prefix: &str, path: &str) -> String {
+    match (prefix.ends_with('/'), path.starts_with('/')) {
+        (true, true) => format!("{}{}", prefix, &path[1..]),
+        (false, false) if !prefix.is_empty() && !path.is_empty() => {
+            format!("{}/{}", prefix, path)
+        }
+        _ => format!("{}{}", prefix, path),
+    }
+}
+
+/// Rewrites axum 0.7 path parameters like `:id` to the `{id}` form actix-web and axum 0.8 use.
+fn brace_path_params(path: &str) -> String {
+    path.split('/')
+        .map(|segment| match segment.strip_prefix(':') {
+            Some(name) if !name.is_empty() => format!("{{{}}}", name),
+            _ => segment.to_string(),
+        })
+        .collect::<Vec<String>>()
+        .join("/")
+}
+
+/// Reads a string literal expression.
+fn string_literal(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(expr_lit) => match &expr_lit.lit {
+            Lit::Str(text) => Some(text.value()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Reads the name of a function given by path, e.g. `handlers::create_task`.
+fn function_name(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Path(ExprPath { path, .. }) => Some(path.segments.last()?.ident.to_string()),
+        _ => None,
+    }
+}
+
+/// Reads `web::post().to(handler)` into its method and handler name.
+fn route_target(expr: &Expr) -> Option<(HttpMethod, String)> {
+    let Expr::MethodCall(to_call) = expr else {
+        return None;
+    };
+    if to_call.method != "to" {
+        return None;
+    }
+    // Guards may sit between the method and the handler: `web::get().guard(...).to(handler)`
+    let mut receiver: &Expr = &to_call.receiver;
+    while let Expr::MethodCall(inner_call) = receiver {
+        receiver = &inner_call.receiver;
+    }
+    let Expr::Call(method_call) = receiver else {
+        return None;
+    };
+    let method: HttpMethod = function_name(&method_call.func)?.parse().ok()?;
+    let handler: String = function_name(to_call.args.first()?).unwrap_or_default();
+    Some((method, handler))
+}
+
+/// Reads axum's `get(handler).post(other_handler)` into its methods and handler names.
+///
+/// Calls that are not methods, e.g. `.layer(...)`, are skipped.
+fn method_router_targets(expr: &Expr) -> Vec<(HttpMethod, String)> {
+    let mut targets: Vec<(HttpMethod, String)> = vec![];
+    let mut current: &Expr = expr;
+    loop {
+        match current {
+            Expr::MethodCall(method_call) => {
+                let method: Option<HttpMethod> = method_call.method.to_string().parse().ok();
+                if let (Some(method), 1) = (method, method_call.args.len()) {
+                    let handler: String = function_name(&method_call.args[0]).unwrap_or_default();
+                    targets.push((method, handler));
+                }
+                current = &method_call.receiver;
+            }
+            Expr::Call(call) if call.args.len() == 1 => {
+                if let Some(method) = function_name(&call.func).and_then(|name| name.parse().ok()) {
+                    targets.push((method, function_name(&call.args[0]).unwrap_or_default()));
+                }
+                break;
+            }
+            _ => break,
+        }
+    }
+    targets.reverse();
+    targets
+}
+
+/// Reads the paths and methods of a route attribute such as `#[get("/task")]` or
+/// `#[route("/task", method = "GET", method = "HEAD")]`.
+fn attribute_routes(attribute: &Attribute) -> Vec<(String, HttpMethod)> {
+    let Some(name) = attribute.path().segments.last() else {
+        return vec![];
+    };
+    let name: String = name.ident.to_string();
+    let attribute_method: Option<HttpMethod> = name.parse().ok();
+    if name != "route" && attribute_method.is_none() {
+        return vec![];
+    }
+
+    let parsed: syn::Result<(String, Vec<HttpMethod>)> =
+        attribute.parse_args_with(|input: ParseStream| {
+            let path: LitStr = input.parse()?;
+            let mut methods: Vec<HttpMethod> = vec![];
+            while !input.is_empty() {
+                input.parse::<Token![,]>()?;
+                if input.is_empty() {
+                    break;
+                }
+                let key: Ident = input.parse()?;
+                input.parse::<Token![=]>()?;
+                let value: Expr = input.parse()?;
+                if key == "method" {
+                    if let Some(method) = string_literal(&value).and_then(|m| m.parse().ok()) {
+                        methods.push(method);
+                    }
+                }
+            }
+            Ok((path.value(), methods))
+        });
+    let Ok((path, methods)) = parsed else {
+        return vec![];
+    };
+    match attribute_method {
+        Some(method) => vec![(path, method)],
+        None => methods
+            .into_iter()
+            .map(|method| (path.clone(), method))
+            .collect(),
+    }
+}
+
+/// Collects routes together with the function registering them, and where functions are mounted.
+#[derive(Default)]
+struct RouteCollector {
+    current_fn: Option<String>,
+    routes: Vec<(Option<String>, RegisteredRoute)>,
+    /// The functions mounting each function, with the prefix and whether it is an axum `.nest(...)`.
+    mounts: HashMap<String, Vec<(Option<String>, String, bool)>>,
+}
+
+impl RouteCollector {
+    /// Returns every prefix the routes registered by a function are served under, and whether the
+    /// function is mounted with an axum `.nest(...)` there.
+    fn mount_prefixes(&self, owner: Option<&str>, depth: usize) -> Vec<(String, bool)> {
+        match owner.and_then(|owner| self.mounts.get(owner)) {
+            Some(mounts) if depth < MAX_MOUNT_DEPTH => mounts
+                .iter()
+                .flat_map(|(mount_owner, prefix, nested)| {
+                    self.mount_prefixes(mount_owner.as_deref(), depth + 1)
+                        .into_iter()
+                        .map(move |(outer_prefix, outer_nested)| {
+                            let prefix: String =
+                                join_route_path(&outer_prefix, prefix, outer_nested);
+                            (prefix, *nested)
+                        })
+                })
+                .collect(),
+            _ => vec![(String::new(), false)],
+        }
+    }
+
+    fn push_route(&mut self, path: String, method: HttpMethod, handler: String) {
+        self.routes.push((
+            self.current_fn.clone(),
+            RegisteredRoute {
+                path,
+                method,
+                handler,
+            },
+        ));
+    }
+
+    fn mount(&mut self, function: String, prefix: &str, nested: bool) {
+        self.mounts.entry(function).or_default().push((
+            self.current_fn.clone(),
+            prefix.to_string(),
+            nested,
+        ));
+    }
+
+    /// Reads a chain of builder calls, e.g. `web::scope("/api").route(...).service(...)` or
+    /// `Router::new().route(...).nest(...)`. `nested` tells whether the chain is a router passed to `.nest(...)`.
+    fn collect_chain(&mut self, method_call: &ExprMethodCall, prefix: &str, nested: bool) {
+        let mut calls: Vec<&ExprMethodCall> = vec![method_call];
+        let mut base: &Expr = &method_call.receiver;
+        while let Expr::MethodCall(inner_call) = base {
+            calls.push(inner_call);
+            base = &inner_call.receiver;
+        }
+        calls.reverse();
+
+        let base_builder: Option<(String, String)> = match base {
+            Expr::Call(call) if call.args.len() == 1 => function_name(&call.func)
+                .zip(string_literal(&call.args[0]))
+                .filter(|(builder, _)| builder == "scope" || builder == "resource"),
+            _ => None,
+        };
+        let (chain_prefix, resource_path): (String, Option<String>) = match base_builder {
+            Some((builder, path)) if builder == "scope" => (join_paths(prefix, &path), None),
+            Some((_, path)) => (prefix.to_string(), Some(join_paths(prefix, &path))),
+            None => {
+                self.visit_expr(base);
+                (prefix.to_string(), None)
+            }
+        };
+
+        for call in calls {
+            let args: Vec<&Expr> = call.args.iter().collect();
+            let handled: bool = match (call.method.to_string().as_str(), args.as_slice()) {
+                ("route", [path, target]) => {
+                    let targets: Vec<(HttpMethod, String)> = match route_target(target) {
+                        Some(target) => vec![target],
+                        None => method_router_targets(target),
+                    };
+                    match string_literal(path) {
+                        Some(path) if !targets.is_empty() => {
+                            for (method, handler) in targets {
+                                self.push_route(
+                                    join_route_path(&chain_prefix, &path, nested),
+                                    method,
+                                    handler,
+                                );
+                            }
+                            true
+                        }
+                        _ => false,
+                    }
+                }
+                ("route", [target]) => match (&resource_path, route_target(target)) {
+                    (Some(resource_path), Some((method, handler))) => {
+                        self.push_route(resource_path.clone(), method, handler);
+                        true
+                    }
+                    _ => false,
+                },
+                ("service", [service]) => {
+                    self.collect_service(service, &chain_prefix);
+                    true
+                }
+                ("configure", [config_fn]) => match function_name(config_fn) {
+                    Some(config_fn) => {
+                        self.mount(config_fn, &chain_prefix, false);
+                        true
+                    }
+                    None => false,
+                },
+                ("nest", [path, router]) => match string_literal(path) {
+                    Some(path) => {
+                        self.collect_router(
+                            router,
+                            &join_route_path(&chain_prefix, &path, nested),
+                            true,
+                        );
+                        true
+                    }
+                    None => false,
+                },
+                ("merge", [router]) => {
+                    self.collect_router(router, &chain_prefix, nested);
+                    true
+                }
+                _ => false,
+            };
+            if !handled {
+                for arg in &call.args {
+                    self.visit_expr(arg);
+                }
+            }
+        }
+    }
+
+    /// Reads the argument of `.service(...)`: a nested builder chain or a handler with a route attribute.
+    fn collect_service(&mut self, service: &Expr, prefix: &str) {
+        match service {
+            Expr::MethodCall(method_call) => self.collect_chain(method_call, prefix, false),
+            Expr::Path(_) => {
+                if let Some(handler) = function_name(service) {
+                    self.mount(handler, prefix, false);
+                }
+            }
+            _ => self.visit_expr(service),
+        }
+    }
+
+    /// Reads the router passed to axum's `.nest(...)` or `.merge(...)`: a builder chain or a call of a
+    /// function returning a router.
+    fn collect_router(&mut self, router: &Expr, prefix: &str, nested: bool) {
+        match router {
+            Expr::MethodCall(method_call) => self.collect_chain(method_call, prefix, nested),
+            Expr::Call(call) => {
+                if let Some(router_fn) = function_name(&call.func) {
+                    self.mount(router_fn, prefix, nested);
+                }
+                for arg in &call.args {
+                    self.visit_expr(arg);
+                }
+            }
+            _ => self.visit_expr(router),
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for RouteCollector {
+    fn visit_item_fn(&mut self, item_fn: &'ast ItemFn) {
+        let handler: String = item_fn.sig.ident.to_string();
+        for attribute in &item_fn.attrs {
+            for (path, method) in attribute_routes(attribute) {
+                self.routes.push((
+                    Some(handler.clone()),
+                    RegisteredRoute {
+                        path,
+                        method,
+                        handler: handler.clone(),
+                    },
+                ));
+            }
+        }
+
+        let outer_fn: Option<String> = self.current_fn.replace(handler);
+        visit::visit_item_fn(self, item_fn);
+        self.current_fn = outer_fn;
+    }
+
+    fn visit_impl_item_fn(&mut self, impl_item_fn: &'ast ImplItemFn) {
+        let outer_fn: Option<String> = self.current_fn.replace(impl_item_fn.sig.ident.to_string());
+        visit::visit_impl_item_fn(self, impl_item_fn);
+        self.current_fn = outer_fn;
+    }
+
+    fn visit_expr_method_call(&mut self, method_call: &'ast ExprMethodCall) {
+        self.collect_chain(method_call, "", false);
+    }
+}
+
+/// Removes references, parentheses and groups around a type.
+fn peel_reference(ty: &Type) -> &Type {
+    match ty {
+        Type::Reference(reference) => peel_reference(&reference.elem),
+        Type::Paren(paren) => peel_reference(&paren.elem),
+        Type::Group(group) => peel_reference(&group.elem),
+        _ => ty,
+    }
+}
+
+/// Splits a path type into the name of its last segment and its type parameters.
+fn path_parts(ty: &Type) -> Option<(String, Vec<&Type>)> {
+    let Type::Path(type_path) = peel_reference(ty) else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    let type_args: Vec<&Type> = match &segment.arguments {
+        PathArguments::AngleBracketed(arguments) => arguments
+            .args
+            .iter()
+            .filter_map(|argument| match argument {
+                GenericArgument::Type(type_arg) => Some(type_arg),
+                _ => None,
+            })
+            .collect(),
+        _ => vec![],
+    };
+    Some((segment.ident.to_string(), type_args))
+}
+
+/// Returns the first type parameter of a type whose name is one of `wrappers`.
+fn unwrap_type(ty: &Type, wrappers: &[&str]) -> Option<Type> {
+    let (name, type_args) = path_parts(ty)?;
+    if !wrappers.contains(&name.as_str()) {
+        return None;
+    }
+    type_args.first().map(|type_arg| (*type_arg).clone())
+}
+
+/// Returns the element type of a sequence, slice or array.
+fn element_type(ty: &Type) -> Option<Type> {
+    match peel_reference(ty) {
+        Type::Slice(slice) => Some((*slice.elem).clone()),
+        Type::Array(array) => Some((*array.elem).clone()),
+        ty => unwrap_type(ty, SEQUENCE_TYPES).or_else(|| unwrap_type(ty, &["Option"])),
+    }
+}
+
+/// Returns the type `get`-like methods of the standard collections return.
+fn collection_method(ty: &Type, method: &str) -> Option<Type> {
+    let (name, type_args) = path_parts(ty)?;
+    let element: &Type = if MAP_TYPES.contains(&name.as_str()) {
+        type_args.get(1)?
+    } else if SEQUENCE_TYPES.contains(&name.as_str()) {
+        type_args.first()?
+    } else {
+        return None;
+    };
+    match method {
+        "get" | "get_mut" | "remove" | "first" | "last" | "pop" => {
+            Some(parse_quote!(Option<#element>))
+        }
+        _ => None,
+    }
+}
+
+/// Replaces `Self` in a type taken from an `impl` block with the implementing type.
+fn replace_self(ty: &Type, self_name: &str) -> Type {
+    let tokens: String = ty.to_token_stream().to_string();
+    if !tokens.split_whitespace().any(|token| token == "Self") {
+        return ty.clone();
+    }
+    let replaced: String = tokens
+        .split_whitespace()
+        .map(|token| if token == "Self" { self_name } else { token })
+        .collect::<Vec<&str>>()
+        .join(" ");
+    syn::parse_str::<Type>(&replaced).unwrap_or_else(|_| ty.clone())
+}
+
+/// Reads `#[serde(rename = "...")]` and `#[serde(skip)]` off a struct field.
+///
+/// # Returns
+/// The serialized name of the field, or `None` if it is skipped.
+fn serialized_field_name(field: &syn::Field) -> Option<String> {
+    let mut name: String = field.ident.as_ref()?.to_string();
+    if let Some(raw_name) = name.strip_prefix("r#") {
+        name = raw_name.to_string();
+    }
+    let mut skipped: bool = false;
+    for attribute in field
+        .attrs
+        .iter()
+        .filter(|attribute| attribute.path().is_ident("serde"))
+    {
+        let _ = attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                let rename: LitStr = meta.value()?.parse()?;
+                name = rename.value();
+            } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_serializing") {
+                skipped = true;
+            } else if meta.input.peek(Token![=]) {
+                meta.value()?.parse::<Expr>()?;
+            }
+            Ok(())
+        });
+    }
+    (!skipped).then_some(name)
+}
+
+/// The types and functions of the code, used to resolve handler signatures into schemas.
+#[derive(Default)]
+struct TypeIndex<'ast> {
+    structs: HashMap<String, &'ast Fields>,
+    enums: HashSet<String>,
+    aliases: HashMap<String, &'ast Type>,
+    functions: HashMap<String, &'ast ItemFn>,
+    methods: HashMap<String, Vec<(String, &'ast Signature)>>,
+}
+
+impl<'ast> TypeIndex<'ast> {
+    /// Builds the `RouteObject` of a registered route from its handler.
+    fn route_object(&self, route: &RegisteredRoute) -> RouteObject {
+        let (request_body, response): (BodySchema, BodySchema) =
+            match self.functions.get(&route.handler) {
+                Some(handler) => (self.request_body(handler), self.response(handler)),
+                None => (BodySchema::None, BodySchema::Unknown),
+            };
+        RouteObject::new(route.method, route.path.clone(), request_body, response)
+    }
+
+    /// Returns the schema of the body extractor of a handler.
+    fn request_body(&self, handler: &ItemFn) -> BodySchema {
+        handler
+            .sig
+            .inputs
+            .iter()
+            .find_map(|input| match input {
+                FnArg::Typed(typed) => unwrap_type(&typed.ty, REQUEST_BODY_EXTRACTORS),
+                FnArg::Receiver(_) => None,
+            })
+            .map_or(BodySchema::None, |body_type| {
+                self.type_schema(&body_type, 0)
+            })
+    }
+
+    /// Returns the schema of the JSON a handler responds with.
+    fn response(&self, handler: &ItemFn) -> BodySchema {
+        if let ReturnType::Type(_, output) = &handler.sig.output {
+            let output: Type = unwrap_type(output, FALLIBLE_WRAPPERS).unwrap_or((**output).clone());
+            if let Some(response_type) = unwrap_type(&output, &["Json"]) {
+                return self.type_schema(&response_type, 0);
+            }
+        }
+
+        let mut response_finder: ResponseFinder = ResponseFinder {
+            type_index: self,
+            locals: HashMap::new(),
+            responses: vec![],
+        };
+        for input in &handler.sig.inputs {
+            if let FnArg::Typed(typed) = input {
+                response_finder.bind_pattern(&typed.pat, Some((*typed.ty).clone()));
+            }
+        }
+        response_finder.visit_block(&handler.block);
+
+        if response_finder.responses.is_empty() {
+            return BodySchema::None;
+        }
+        match response_finder.responses.iter().flatten().next() {
+            Some(response_type) => self.type_schema(response_type, 0),
+            None => BodySchema::Unknown,
+        }
+    }
+
+    /// Describes how a type is serialized, e.g. `{"id": "integer", "name": "string"}`.
+    fn type_schema(&self, ty: &Type, depth: usize) -> BodySchema {
+        if depth > MAX_SCHEMA_DEPTH {
+            return BodySchema::Unknown;
+        }
+        let ty: &Type = peel_reference(ty);
+        if let Type::Slice(_) | Type::Array(_) = ty {
+            return match element_type(ty) {
+                Some(element) => BodySchema::Array(Box::new(self.type_schema(&element, depth + 1))),
+                None => BodySchema::Unknown,
+            };
+        }
+        let Some((name, type_args)) = path_parts(ty) else {
+            return BodySchema::Unknown;
+        };
+        let first_arg_schema: BodySchema =
+            type_args.first().map_or(BodySchema::Unknown, |type_arg| {
+                self.type_schema(type_arg, depth + 1)
+            });
+
+        let name: &str = name.as_str();
+        if INTEGER_TYPES.contains(&name) {
+            BodySchema::Field(FieldType::Integer)
+        } else if FLOAT_TYPES.contains(&name) {
+            BodySchema::Field(FieldType::Float)
+        } else if name == "bool" {
+            BodySchema::Field(FieldType::Bool)
+        } else if DATETIME_TYPES.contains(&name) {
+            BodySchema::Field(FieldType::DateTime)
+        } else if STRING_TYPES.contains(&name) {
+            BodySchema::Field(FieldType::String)
+        } else if TRANSPARENT_WRAPPERS.contains(&name) || FALLIBLE_WRAPPERS.contains(&name) {
+            first_arg_schema
+        } else if SEQUENCE_TYPES.contains(&name) {
+            BodySchema::Array(Box::new(first_arg_schema))
+        } else if MAP_TYPES.contains(&name) {
+            BodySchema::Object(BTreeMap::new())
+        } else if let Some(fields) = self.structs.get(name) {
+            self.fields_schema(fields, depth)
+        } else if self.enums.contains(name) {
+            BodySchema::Field(FieldType::String)
+        } else if let Some(alias) = self.aliases.get(name) {
+            self.type_schema(alias, depth + 1)
+        } else {
+            BodySchema::Unknown
+        }
+    }
+
+    fn fields_schema(&self, fields: &Fields, depth: usize) -> BodySchema {
+        match fields {
+            Fields::Named(named_fields) => BodySchema::Object(
+                named_fields
+                    .named
+                    .iter()
+                    .filter_map(|field| {
+                        let name: String = serialized_field_name(field)?;
+                        Some((name, self.type_schema(&field.ty, depth + 1)))
+                    })
+                    .collect(),
+            ),
+            // Newtypes serialize as the type they wrap
+            Fields::Unnamed(unnamed_fields) if unnamed_fields.unnamed.len() == 1 => {
+                self.type_schema(&unnamed_fields.unnamed[0].ty, depth + 1)
+            }
+            _ => BodySchema::Unknown,
+        }
+    }
+
+    /// Returns the name of the type in the code a type dereferences to, if any.
+    fn local_type_name(&self, ty: &Type) -> Option<String> {
+        let mut ty: Type = ty.clone();
+        while let Some(inner) = unwrap_type(&ty, TRANSPARENT_WRAPPERS) {
+            ty = inner;
+        }
+        let (name, _) = path_parts(&ty)?;
+        (self.structs.contains_key(&name)
+            || self
+                .methods
+                .values()
+                .flatten()
+                .any(|(owner, _)| *owner == name))
+        .then_some(name)
+    }
+
+    /// Returns the type of a field of a struct, looking through wrappers like `web::Data<T>`.
+    fn field_type(&self, ty: &Type, member: &Member) -> Option<Type> {
+        let fields: &Fields = self.structs.get(&self.local_type_name(ty)?)?;
+        match (fields, member) {
+            (Fields::Named(named_fields), Member::Named(ident)) => named_fields
+                .named
+                .iter()
+                .find(|field| field.ident.as_ref() == Some(ident))
+                .map(|field| field.ty.clone()),
+            (Fields::Unnamed(unnamed_fields), Member::Unnamed(index)) => unnamed_fields
+                .unnamed
+                .iter()
+                .nth(index.index as usize)
+                .map(|field| field.ty.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns the return type of a method of a type in the code.
+    ///
+    /// If the receiver type is unknown, the method is only resolved if one type has a method of that name.
+    fn method_return(&self, receiver: Option<&Type>, method: &str) -> Option<Type> {
+        let candidates: &Vec<(String, &Signature)> = self.methods.get(method)?;
+        let (owner, signature): &(String, &Signature) = match receiver {
+            Some(receiver) => {
+                let owner: String = self.local_type_name(receiver)?;
+                candidates
+                    .iter()
+                    .find(|(candidate, _)| *candidate == owner)?
+            }
+            None if candidates.len() == 1 => &candidates[0],
+            None => return None,
+        };
+        match &signature.output {
+            ReturnType::Type(_, output) => Some(replace_self(output, owner)),
+            ReturnType::Default => None,
+        }
+    }
+
+    /// Returns the return type of a function or associated function called by path.
+    fn function_return(&self, path: &syn::Path) -> Option<Type> {
+        let segments: Vec<String> = path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect();
+        match segments.as_slice() {
+            [.., owner, function] if owner.chars().next()?.is_uppercase() => {
+                let owner_type: Type = syn::parse_str(owner).ok()?;
+                self.method_return(Some(&owner_type), function)
+            }
+            [.., function] => match &self.functions.get(function)?.sig.output {
+                ReturnType::Type(_, output) => Some((**output).clone()),
+                ReturnType::Default => None,
+            },
+            [] => None,
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for TypeIndex<'ast> {
+    fn visit_item_struct(&mut self, item_struct: &'ast ItemStruct) {
+        self.structs
+            .insert(item_struct.ident.to_string(), &item_struct.fields);
+        visit::visit_item_struct(self, item_struct);
+    }
+
+    fn visit_item_enum(&mut self, item_enum: &'ast ItemEnum) {
+        self.enums.insert(item_enum.ident.to_string());
+        visit::visit_item_enum(self, item_enum);
+    }
+
+    fn visit_item_type(&mut self, item_type: &'ast ItemType) {
+        self.aliases
+            .insert(item_type.ident.to_string(), &item_type.ty);
+        visit::visit_item_type(self, item_type);
+    }
+
+    fn visit_item_fn(&mut self, item_fn: &'ast ItemFn) {
+        self.functions
+            .insert(item_fn.sig.ident.to_string(), item_fn);
+        visit::visit_item_fn(self, item_fn);
+    }
+
+    fn visit_item_impl(&mut self, item_impl: &'ast ItemImpl) {
+        if item_impl.trait_.is_none() {
+            if let Some((owner, _)) = path_parts(&item_impl.self_ty) {
+                for item in &item_impl.items {
+                    if let syn::ImplItem::Fn(impl_item_fn) = item {
+                        self.methods
+                            .entry(impl_item_fn.sig.ident.to_string())
+                            .or_default()
+                            .push((owner.clone(), &impl_item_fn.sig));
+                    }
+                }
+            }
+        }
+        visit::visit_item_impl(self, item_impl);
+    }
+}
+
+/// Walks a handler body, tracking the types of local bindings, and records what it responds with.
+///
+/// Every `HttpResponse::...().json(value)` and `web::Json(value)` adds the type of `value`, or `None`
+/// if it cannot be resolved.
+struct ResponseFinder<'a, 'ast> {
+    type_index: &'a TypeIndex<'ast>,
+    locals: HashMap<String, Type>,
+    responses: Vec<Option<Type>>,
+}
+
+impl ResponseFinder<'_, '_> {
+    /// Binds the names of a pattern matched against a value of the given type.
+    fn bind_pattern(&mut self, pat: &Pat, ty: Option<Type>) {
+        match pat {
+            Pat::Ident(pat_ident) => {
+                let name: String = pat_ident.ident.to_string();
+                match ty {
+                    Some(ty) => self.locals.insert(name, ty),
+                    None => self.locals.remove(&name),
+                };
+            }
+            Pat::Type(pat_type) => self.bind_pattern(&pat_type.pat, Some((*pat_type.ty).clone())),
+            Pat::Reference(pat_reference) => self.bind_pattern(&pat_reference.pat, ty),
+            Pat::Paren(pat_paren) => self.bind_pattern(&pat_paren.pat, ty),
+            // `Some(task)`, `Ok(task)` and `web::Path(id)`
+            Pat::TupleStruct(pat_tuple_struct) if pat_tuple_struct.elems.len() == 1 => {
+                let inner: Option<Type> = ty.and_then(|ty| {
+                    unwrap_type(&ty, FALLIBLE_WRAPPERS)
+                        .or_else(|| unwrap_type(&ty, TRANSPARENT_WRAPPERS))
+                });
+                self.bind_pattern(&pat_tuple_struct.elems[0], inner);
+            }
+            _ => {}
+        }
+    }
+
+    /// Infers the type of an expression from the bindings seen so far.
+    fn type_of(&self, expr: &Expr) -> Option<Type> {
+        match expr {
+            Expr::Path(expr_path) => self
+                .locals
+                .get(&expr_path.path.get_ident()?.to_string())
+                .cloned(),
+            Expr::Reference(reference) => self.type_of(&reference.expr),
+            Expr::Paren(paren) => self.type_of(&paren.expr),
+            Expr::Group(group) => self.type_of(&group.expr),
+            Expr::Await(expr_await) => self.type_of(&expr_await.base),
+            Expr::Unary(unary) if matches!(unary.op, UnOp::Deref(_)) => {
+                let ty: Type = self.type_of(&unary.expr)?;
+                Some(unwrap_type(&ty, TRANSPARENT_WRAPPERS).unwrap_or(ty))
+            }
+            Expr::Try(expr_try) => unwrap_type(&self.type_of(&expr_try.expr)?, FALLIBLE_WRAPPERS),
+            Expr::Cast(cast) => Some((*cast.ty).clone()),
+            Expr::Lit(expr_lit) if matches!(expr_lit.lit, Lit::Str(_)) => {
+                Some(parse_quote!(String))
+            }
+            Expr::Struct(expr_struct) => Some(Type::Path(syn::TypePath {
+                qself: None,
+                path: expr_struct.path.clone(),
+            })),
+            Expr::Field(field) => self
+                .type_index
+                .field_type(&self.type_of(&field.base)?, &field.member),
+            Expr::Call(call) => self.call_type(call),
+            Expr::MethodCall(method_call) => self.method_call_type(method_call),
+            Expr::Macro(expr_macro) if expr_macro.mac.path.is_ident("format") => {
+                Some(parse_quote!(String))
+            }
+            _ => None,
+        }
+    }
+
+    fn call_type(&self, call: &ExprCall) -> Option<Type> {
+        let Expr::Path(ExprPath { path, .. }) = call.func.as_ref() else {
+            return None;
+        };
+        match function_name(&call.func)?.as_str() {
+            // The schema of `Some(task)` or `web::Json(task)` is the schema of `task`
+            "Some" | "Ok" | "Json" | "Box" | "Arc" | "Rc" if call.args.len() == 1 => {
+                self.type_of(&call.args[0])
+            }
+            _ => self.type_index.function_return(path),
+        }
+    }
+
+    fn method_call_type(&self, method_call: &ExprMethodCall) -> Option<Type> {
+        let receiver: Option<Type> = self.type_of(&method_call.receiver);
+        match method_call.method.to_string().as_str() {
+            "clone" | "to_owned" | "cloned" | "copied" | "as_ref" | "as_mut" | "borrow"
+            | "borrow_mut" | "as_deref" => receiver,
+            "unwrap" | "expect" | "unwrap_or" | "unwrap_or_else" | "unwrap_or_default" => {
+                receiver.map(|ty| unwrap_type(&ty, FALLIBLE_WRAPPERS).unwrap_or(ty))
+            }
+            "into_inner" | "lock" | "read" | "write" | "get_ref" => {
+                receiver.map(|ty| unwrap_type(&ty, TRANSPARENT_WRAPPERS).unwrap_or(ty))
+            }
+            "to_string" | "to_lowercase" | "to_uppercase" => Some(parse_quote!(String)),
+            "collect" => method_call
+                .turbofish
+                .as_ref()?
+                .args
+                .iter()
+                .find_map(|argument| match argument {
+                    GenericArgument::Type(collected) => Some(collected.clone()),
+                    _ => None,
+                }),
+            method => match &receiver {
+                Some(receiver_type) => collection_method(receiver_type, method)
+                    .or_else(|| self.type_index.method_return(Some(receiver_type), method)),
+                None => self.type_index.method_return(None, method),
+            },
+        }
+    }
+
+    /// Checks whether a `.json(...)` call builds an `HttpResponse`, not e.g. an outgoing request.
+    fn is_response_builder(method_call: &ExprMethodCall) -> bool {
+        let mut receiver: &Expr = &method_call.receiver;
+        while let Expr::MethodCall(inner_call) = receiver {
+            receiver = &inner_call.receiver;
+        }
+        match receiver {
+            Expr::Call(call) => match call.func.as_ref() {
+                Expr::Path(ExprPath { path, .. }) => path
+                    .segments
+                    .iter()
+                    .any(|segment| segment.ident.to_string().starts_with("HttpResponse")),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+impl<'v> Visit<'v> for ResponseFinder<'_, '_> {
+    fn visit_local(&mut self, local: &'v Local) {
+        let mut init_type: Option<Type> = None;
+        if let Some(init) = &local.init {
+            self.visit_expr(&init.expr);
+            if let Some((_, diverge)) = &init.diverge {
+                self.visit_expr(diverge);
+            }
+            init_type = self.type_of(&init.expr);
+        }
+        self.bind_pattern(&local.pat, init_type);
+    }
+
+    fn visit_expr_let(&mut self, expr_let: &'v ExprLet) {
+        self.visit_expr(&expr_let.expr);
+        let scrutinee_type: Option<Type> = self.type_of(&expr_let.expr);
+        self.bind_pattern(&expr_let.pat, scrutinee_type);
+    }
+
+    fn visit_expr_match(&mut self, expr_match: &'v ExprMatch) {
+        self.visit_expr(&expr_match.expr);
+        let scrutinee_type: Option<Type> = self.type_of(&expr_match.expr);
+        for arm in &expr_match.arms {
+            let Arm {
+                pat, guard, body, ..
+            } = arm;
+            self.bind_pattern(pat, scrutinee_type.clone());
+            if let Some((_, guard)) = guard {
+                self.visit_expr(guard);
+            }
+            self.visit_expr(body);
+        }
+    }
+
+    fn visit_expr_for_loop(&mut self, for_loop: &'v ExprForLoop) {
+        self.visit_expr(&for_loop.expr);
+        let item_type: Option<Type> = self
+            .type_of(&for_loop.expr)
+            .and_then(|ty| element_type(&ty));
+        self.bind_pattern(&for_loop.pat, item_type);
+        self.visit_block(&for_loop.body);
+    }
+
+    fn visit_expr_method_call(&mut self, method_call: &'v ExprMethodCall) {
+        visit::visit_expr_method_call(self, method_call);
+        if method_call.method == "json"
+            && method_call.args.len() == 1
+            && Self::is_response_builder(method_call)
+        {
+            let response_type: Option<Type> = self.type_of(&method_call.args[0]);
+            self.responses.push(response_type);
+        }
+    }
+
+    fn visit_expr_call(&mut self, call: &'v ExprCall) {
+        visit::visit_expr_call(self, call);
+        if call.args.len() == 1 && function_name(&call.func).as_deref() == Some("Json") {
+            let response_type: Option<Type> = self.type_of(&call.args[0]);
+            self.responses.push(response_type);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn routes(code: &str) -> Vec<(HttpMethod, String)> {
+        extract_route_objects(code)
+            .unwrap()
+            .into_iter()
+            .map(|route_object| (route_object.method, route_object.route))
+            .collect()
+    }
+
+    fn route<'a>(
+        route_objects: &'a [RouteObject],
+        method: HttpMethod,
+        path: &str,
+    ) -> &'a RouteObject {
+        route_objects
+            .iter()
+            .find(|route_object| route_object.method == method && route_object.route == path)
+            .unwrap_or_else(|| panic!("{:?} {} not extracted", method, path))
+    }
+
+    #[test]
+    fn follows_nested_actix_scopes_and_configure_calls() {
+        let code: &str = r#"
+async fn list_tasks() -> impl Responder { HttpResponse::Ok().finish() }
+async fn create_task() -> impl Responder { HttpResponse::Created().finish() }
+async fn read_task() -> impl Responder { HttpResponse::Ok().finish() }
+async fn delete_task() -> impl Responder { HttpResponse::Ok().finish() }
+async fn health() -> impl Responder { HttpResponse::Ok().finish() }
+
+fn task_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("/{id}")
+            .route(web::get().to(read_task))
+            .route(web::delete().to(delete_task)),
+    )
+    .route("", web::get().to(list_tasks))
+    .route("", web::post().to(create_task));
+}
+
+fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::scope("/api").service(web::scope("/tasks").configure(task_routes)))
+        .route("/health", web::get().to(health));
+}
+"#;
+
+        assert_eq!(
+            routes(code),
+            vec![
+                (HttpMethod::Get, "/api/tasks/{id}".to_string()),
+                (HttpMethod::Delete, "/api/tasks/{id}".to_string()),
+                (HttpMethod::Get, "/api/tasks".to_string()),
+                (HttpMethod::Post, "/api/tasks".to_string()),
+                (HttpMethod::Get, "/health".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_actix_handler_macros_with_their_scope() {
+        let code: &str = r#"
+#[get("/users/{user_id}/posts/{post_id}")]
+async fn read_post(path: web::Path<(u64, u64)>) -> impl Responder { HttpResponse::Ok().finish() }
+
+#[put("/users/{user_id}")]
+async fn update_user() -> impl Responder { HttpResponse::Ok().finish() }
+
+fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::scope("/v1").service(read_post).service(update_user));
+}
+"#;
+        let route_objects: Vec<RouteObject> = extract_route_objects(code).unwrap();
+
+        let read_post: &RouteObject = route(
+            &route_objects,
+            HttpMethod::Get,
+            "/v1/users/{user_id}/posts/{post_id}",
+        );
+        assert!(read_post.is_route_dynamic);
+        assert_eq!(read_post.path_params, vec!["user_id", "post_id"]);
+        route(&route_objects, HttpMethod::Put, "/v1/users/{user_id}");
+    }
+
+    #[test]
+    fn reads_request_and_response_schemas_of_actix_handlers() {
+        let code: &str = r#"
+#[derive(Serialize, Deserialize)]
+struct Tag { label: String }
+
+#[derive(Serialize, Deserialize)]
+struct Task {
+    id: u64,
+    name: String,
+    done: bool,
+    score: Option<f32>,
+    tags: Vec<Tag>,
+}
+
+struct AppState { db: Mutex<HashMap<u64, Task>> }
+
+async fn create_task(state: web::Data<AppState>, task: web::Json<Task>) -> impl Responder {
+    HttpResponse::Created().finish()
+}
+
+async fn list_tasks(state: web::Data<AppState>) -> impl Responder {
+    let db = state.db.lock().unwrap();
+    let tasks: Vec<&Task> = db.values().collect();
+    HttpResponse::Ok().json(tasks)
+}
+
+fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("/task", web::post().to(create_task))
+        .route("/task", web::get().to(list_tasks));
+}
+"#;
+        let route_objects: Vec<RouteObject> = extract_route_objects(code).unwrap();
+        let task: BodySchema = BodySchema::from(json!({
+            "id": "integer",
+            "name": "string",
+            "done": "bool",
+            "score": "float",
+            "tags": [{"label": "string"}]
+        }));
+
+        let create_task: &RouteObject = route(&route_objects, HttpMethod::Post, "/task");
+        assert_eq!(create_task.request_body, task);
+        assert_eq!(create_task.response, BodySchema::None);
+        let list_tasks: &RouteObject = route(&route_objects, HttpMethod::Get, "/task");
+        assert_eq!(list_tasks.request_body, BodySchema::None);
+        assert_eq!(list_tasks.response, BodySchema::Array(Box::new(task)));
+    }
+
+    #[test]
+    fn follows_axum_nest_and_merge() {
+        let code: &str = r#"
+async fn list_tasks() -> StatusCode { StatusCode::OK }
+async fn create_task() -> StatusCode { StatusCode::CREATED }
+async fn read_task() -> StatusCode { StatusCode::OK }
+async fn delete_task() -> StatusCode { StatusCode::OK }
+async fn health() -> StatusCode { StatusCode::OK }
+
+fn task_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/", get(list_tasks).post(create_task))
+        .route("/:id", get(read_task).delete(delete_task))
+}
+
+fn configure_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .nest("/api/tasks", task_routes())
+        .merge(Router::new().route("/health", get(health)))
+}
+"#;
+
+        assert_eq!(
+            routes(code),
+            vec![
+                (HttpMethod::Get, "/api/tasks".to_string()),
+                (HttpMethod::Post, "/api/tasks".to_string()),
+                (HttpMethod::Get, "/api/tasks/{id}".to_string()),
+                (HttpMethod::Delete, "/api/tasks/{id}".to_string()),
+                (HttpMethod::Get, "/health".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_request_and_response_schemas_of_axum_handlers() {
+        let code: &str = r#"
+#[derive(Serialize, Deserialize, Clone)]
+struct Task { id: u64, name: String }
+
+async fn create_task(
+    State(state): State<Arc<AppState>>,
+    Json(task): Json<Task>,
+) -> (StatusCode, Json<Task>) {
+    (StatusCode::CREATED, Json(task))
+}
+
+async fn read_task(Path(id): Path<u64>) -> Result<Json<Task>, StatusCode> {
+    Err(StatusCode::NOT_FOUND)
+}
+
+fn configure_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/task", post(create_task))
+        .route("/task/{id}", get(read_task))
+}
+"#;
+        let route_objects: Vec<RouteObject> = extract_route_objects(code).unwrap();
+        let task: BodySchema = BodySchema::from(json!({"id": "integer", "name": "string"}));
+
+        let create_task: &RouteObject = route(&route_objects, HttpMethod::Post, "/task");
+        assert_eq!(create_task.request_body, task);
+        assert_eq!(create_task.response, task);
+        let read_task: &RouteObject = route(&route_objects, HttpMethod::Get, "/task/{id}");
+        assert_eq!(read_task.path_params, vec!["id"]);
+        assert_eq!(read_task.request_body, BodySchema::None);
+        assert_eq!(read_task.response, task);
+    }
+
+    #[test]
+    fn extracts_the_routes_of_the_code_templates() {
+        for (template, expected) in [
+            ("source/templates/crud.rs", 6),
+            ("source/templates/axum/crud.rs", 6),
+        ] {
+            let code: String = std::fs::read_to_string(template).unwrap();
+            let route_objects: Vec<RouteObject> = extract_route_objects(&code).unwrap();
+            assert_eq!(route_objects.len(), expected, "{}", template);
+        }
+    }
+}
@@ -0,0 +1,1339 @@
+use reqwest::Url;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::{Component, Path};
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+use syn::{
+    Expr, ExprCall, ExprLit, ExprMethodCall, ExprPath, Item, Lit, LitStr, Macro, Pat, Stmt, Token,
+};
+
+/// Files the generated web server is expected to read and write.
+const ALLOWED_DATA_FILES: &[&str] = &["database.json", "database.sqlite"];
+
+/// The directory the generated web server keeps its data in, relative to its working directory.
+const ALLOWED_DATA_DIR: &str = "data";
+
+/// Environment variables the operator sets to paths the generated web server may access.
+const PATH_SETTING_ENV_VARS: &[&str] = &[
+    "WEB_SERVER_DATABASE_PATH",
+    "WEB_SERVER_STATIC_DIR",
+    "WEB_SERVER_OPENAPI_PATH",
+    "WEB_SERVER_UPLOAD_DIR",
+];
+
+/// Environment variables the generated web server is expected to read.
+const ALLOWED_ENV_VARS: &[&str] = &[
+    "WEB_SERVER_HOST",
+    "WEB_SERVER_PORT",
+    "WEB_SERVER_STATIC_DIR",
+    "WEB_SERVER_OPENAPI_PATH",
+    "WEB_SERVER_JWT_SECRET",
+    "WEB_SERVER_API_KEY",
+    "WEB_SERVER_UPLOAD_DIR",
+    "WEB_SERVER_DATABASE_PATH",
+];
+
+/// Hosts the generated web server may always talk to.
+const LOCAL_HOSTS: &[&str] = &["127.0.0.1", "localhost", "0.0.0.0"];
+
+/// Well known prefixes of API keys and private keys.
+const SECRET_PREFIXES: &[&str] = &[
+    "sk-",
+    "ghp_",
+    "gho_",
+    "github_pat_",
+    "xoxb-",
+    "xoxp-",
+    "AKIA",
+    "AIza",
+    "-----BEGIN",
+];
+
+/// Variable name fragments suggesting a value is a credential.
+const SECRET_NAME_HINTS: &[&str] = &["api_key", "apikey", "secret", "token", "password"];
+
+/// Python calls and modules spawning processes.
+const PYTHON_PROCESS_CALLS: &[&str] = &[
+    "import subprocess",
+    "from subprocess",
+    "subprocess.",
+    "os.system(",
+    "os.popen(",
+    "os.exec",
+    "os.spawn",
+    "os.fork(",
+    "asyncio.create_subprocess_",
+    "pty.spawn(",
+];
+
+/// Python builtins running code given as data.
+const PYTHON_DYNAMIC_CODE_CALLS: &[&str] = &["eval(", "exec(", "compile(", "__import__("];
+
+/// Python calls changing files besides writing through `open`.
+const PYTHON_FILESYSTEM_CALLS: &[&str] = &[
+    "shutil.",
+    "os.remove(",
+    "os.unlink(",
+    "os.rmdir(",
+    "os.rename(",
+    "os.replace(",
+    "os.chmod(",
+];
+
+/// Python calls opening raw sockets.
+const PYTHON_SOCKET_CALLS: &[&str] = &["socket.socket(", "socket.create_connection("];
+
+/// How dangerous a finding is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    High,
+    Medium,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::High => write!(f, "HIGH"),
+            Severity::Medium => write!(f, "MEDIUM"),
+        }
+    }
+}
+
+/// A risky construct found in generated code.
+///
+/// # Fields
+/// - `severity`: How dangerous the construct is.
+/// - `line`: The line of the generated code the construct starts on.
+/// - `category`: The kind of risk, e.g. `process`, `filesystem`, `network`.
+/// - `message`: What was found.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SafetyFinding {
+    pub severity: Severity,
+    pub line: usize,
+    pub category: &'static str,
+    pub message: String,
+}
+
+/// Parses generated code with `syn` and scans it for risky constructs.
+///
+/// Flags spawning processes, filesystem access outside the data file and the configured paths, `unsafe` code,
+/// network calls to hosts outside `allowed_hosts`, environment variable reads and hard-coded secrets.
+/// The arguments of macros like `format!` are scanned as expressions too.
+///
+/// # Arguments
+/// - `code`: The generated Rust source.
+/// - `allowed_hosts`: Host names the code may call, e.g. from `FactSheet.external_urls`.
+///
+/// # Returns
+/// The findings ordered by severity and line, or the parse error if the code is not valid Rust.
+pub fn scan_generated_code(
+    code: &str,
+    allowed_hosts: &[String],
+) -> Result<Vec<SafetyFinding>, syn::Error> {
+    let syntax_tree: syn::File = syn::parse_file(code)?;
+
+    let mut scanner: SafetyScanner = SafetyScanner {
+        allowed_hosts,
+        string_constants: collect_string_constants(&syntax_tree),
+        path_setting_functions: collect_path_setting_functions(&syntax_tree),
+        path_setting_locals: HashSet::new(),
+        filesystem_locals: HashMap::new(),
+        findings: vec![],
+        env_vars: vec![],
+    };
+    scanner.visit_file(&syntax_tree);
+
+    let mut findings: Vec<SafetyFinding> = scanner.findings;
+    findings.sort_by_key(|finding| (finding.severity, finding.line));
+    findings.dedup();
+    Ok(findings)
+}
+
+/// Lists the environment variables generated code reads by name.
+///
+/// # Arguments
+/// - `code`: The generated Rust source.
+///
+/// # Returns
+/// The sorted variable names, or the parse error if the code is not valid Rust.
+pub fn find_env_vars(code: &str) -> Result<Vec<String>, syn::Error> {
+    let syntax_tree: syn::File = syn::parse_file(code)?;
+
+    let mut scanner: SafetyScanner = SafetyScanner {
+        allowed_hosts: &[],
+        string_constants: collect_string_constants(&syntax_tree),
+        path_setting_functions: collect_path_setting_functions(&syntax_tree),
+        path_setting_locals: HashSet::new(),
+        filesystem_locals: HashMap::new(),
+        findings: vec![],
+        env_vars: vec![],
+    };
+    scanner.visit_file(&syntax_tree);
+
+    let mut env_vars: Vec<String> = scanner.env_vars;
+    env_vars.sort();
+    env_vars.dedup();
+    Ok(env_vars)
+}
+
+/// Scans generated Python code line by line for risky constructs.
+///
+/// Flags the same risks as [`scan_generated_code`]: spawning processes, `eval` and `exec`, `ctypes`,
+/// `open` outside the data file and the configured paths, network calls to hosts outside `allowed_hosts`, environment variable
+/// reads and hard-coded secrets. Paths and variable names given by module level string constants are
+/// resolved.
+///
+/// # Arguments
+/// - `code`: The generated Python source.
+/// - `allowed_hosts`: Host names the code may call, e.g. from `FactSheet.external_urls`.
+///
+/// # Returns
+/// The findings ordered by severity and line.
+pub fn scan_python_code(code: &str, allowed_hosts: &[String]) -> Vec<SafetyFinding> {
+    let string_constants: HashMap<String, String> = code
+        .lines()
+        .filter(|line| !line.starts_with([' ', '\t']))
+        .filter_map(|line| {
+            let (name, value) = line.split_once('=')?;
+            let value: String = python_string_literal(value)?;
+            Some((name.trim().to_string(), value))
+        })
+        .collect();
+    let path_setting_constants: HashSet<&str> = code
+        .lines()
+        .filter(|line| !line.starts_with([' ', '\t']))
+        .filter_map(|line| line.split_once('='))
+        .filter(|(_, value)| is_python_path_setting_read(value))
+        .map(|(name, _)| name.trim())
+        .collect();
+    let resolve = |argument: &str| -> Option<String> {
+        let argument: &str = argument.trim();
+        python_string_literals(argument)
+            .into_iter()
+            .next()
+            .filter(|_| argument.starts_with(['"', '\'']))
+            .or_else(|| string_constants.get(argument).cloned())
+    };
+    let is_host_allowed = |host: &str| -> bool {
+        let host: String = host.to_lowercase();
+        LOCAL_HOSTS.contains(&host.as_str()) || allowed_hosts.contains(&host)
+    };
+
+    let mut findings: Vec<SafetyFinding> = vec![];
+    for (index, line) in code.lines().enumerate() {
+        let line_number: usize = index + 1;
+        let line: &str = strip_python_comment(line).trim();
+        let mut report = |severity: Severity, category: &'static str, message: String| {
+            findings.push(SafetyFinding {
+                severity,
+                line: line_number,
+                category,
+                message,
+            });
+        };
+
+        if let Some(call) = PYTHON_PROCESS_CALLS
+            .iter()
+            .find(|call| line.contains(*call))
+        {
+            report(
+                Severity::High,
+                "process",
+                format!(
+                    "Spawns an external process via `{}`",
+                    call.trim_end_matches(['(', '.'])
+                ),
+            );
+        }
+        for call in PYTHON_DYNAMIC_CODE_CALLS {
+            if python_calls(line, call).next().is_some() {
+                report(
+                    Severity::High,
+                    "process",
+                    format!(
+                        "Runs code given as data via `{}`",
+                        call.trim_end_matches('(')
+                    ),
+                );
+            }
+        }
+        if line.starts_with("import ctypes") || line.starts_with("from ctypes") {
+            report(
+                Severity::High,
+                "unsafe",
+                "Imports `ctypes`, which bypasses memory safety".to_string(),
+            );
+        }
+
+        for arguments in python_calls(line, "open(") {
+            let path_argument: &str = python_first_argument(arguments);
+            if path_setting_constants.contains(path_argument)
+                || is_python_path_setting_read(path_argument)
+            {
+                continue;
+            }
+            match resolve(path_argument) {
+                Some(path) if is_data_file_path(&path) => {}
+                Some(path) => report(
+                    Severity::High,
+                    "filesystem",
+                    format!("Accesses `{}` via open, outside the data file", path),
+                ),
+                None => report(
+                    Severity::Medium,
+                    "filesystem",
+                    "Accesses a path computed at runtime via open".to_string(),
+                ),
+            }
+        }
+        if let Some(call) = PYTHON_FILESYSTEM_CALLS
+            .iter()
+            .find(|call| line.contains(*call))
+        {
+            report(
+                Severity::High,
+                "filesystem",
+                format!("Changes files via `{}`", call.trim_end_matches(['(', '.'])),
+            );
+        }
+
+        let env_reads = ["os.environ.get(", "os.getenv(", "getenv(", "os.environ["]
+            .iter()
+            .flat_map(|call| {
+                line.match_indices(*call)
+                    .map(move |(position, _)| &line[position + call.len()..])
+            });
+        for arguments in env_reads {
+            let name_argument: &str = arguments.split([',', ')', ']']).next().unwrap_or_default();
+            match resolve(name_argument) {
+                Some(name) if ALLOWED_ENV_VARS.contains(&name.as_str()) => {}
+                Some(name) => report(
+                    Severity::Medium,
+                    "environment",
+                    format!("Reads environment variable `{}`", name),
+                ),
+                None => report(
+                    Severity::Medium,
+                    "environment",
+                    "Reads environment variables via os.environ".to_string(),
+                ),
+            }
+        }
+
+        if let Some(call) = PYTHON_SOCKET_CALLS.iter().find(|call| line.contains(*call)) {
+            report(
+                Severity::Medium,
+                "network",
+                format!("Opens a raw socket via `{}`", call.trim_end_matches('(')),
+            );
+        }
+
+        for value in python_string_literals(line) {
+            if value.starts_with("http://") || value.starts_with("https://") {
+                if let Some(host) = Url::parse(&value)
+                    .ok()
+                    .and_then(|url| url.host_str().map(str::to_string))
+                {
+                    if !is_host_allowed(&host) {
+                        report(
+                            Severity::High,
+                            "network",
+                            format!("Calls host `{}`, which is not in the external urls", host),
+                        );
+                    }
+                }
+            } else if SECRET_PREFIXES
+                .iter()
+                .any(|prefix| value.starts_with(prefix))
+            {
+                report(
+                    Severity::High,
+                    "secret",
+                    "Contains what looks like a hard-coded API key or private key".to_string(),
+                );
+            }
+        }
+
+        if let Some((name, value)) = line.split_once('=') {
+            let name: String = name.trim().to_lowercase();
+            let is_plain_name: bool = !name.is_empty()
+                && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+                && !value.starts_with('=');
+            let has_hint: bool = SECRET_NAME_HINTS.iter().any(|hint| name.contains(hint));
+            if is_plain_name && has_hint && resolve(value).is_some_and(|value| !value.is_empty()) {
+                report(
+                    Severity::Medium,
+                    "secret",
+                    format!("Assigns a hard-coded value to `{}`", name),
+                );
+            }
+        }
+    }
+
+    findings.sort_by_key(|finding| (finding.severity, finding.line));
+    findings.dedup();
+    findings
+}
+
+/// Formats scan results as a report shown next to the code review prompt.
+pub fn format_safety_report(scan_result: &Result<Vec<SafetyFinding>, syn::Error>) -> String {
+    match scan_result {
+        Err(e) => format!(
+            "SAFETY SCAN: Unable to parse generated code ({}), review it manually",
+            e
+        ),
+        Ok(findings) if findings.is_empty() => "SAFETY SCAN: No risky constructs found".to_string(),
+        Ok(findings) => {
+            let mut report: String = format!("SAFETY SCAN: {} finding(s)", findings.len());
+            for finding in findings {
+                report.push_str(&format!(
+                    "\n  [{}] line {} ({}): {}",
+                    finding.severity, finding.line, finding.category, finding.message
+                ));
+            }
+            report
+        }
+    }
+}
+
+/// Collects `const` and `static` string items so paths passed by name can be checked too.
+fn collect_string_constants(syntax_tree: &syn::File) -> HashMap<String, String> {
+    let mut constants: HashMap<String, String> = HashMap::new();
+    for item in &syntax_tree.items {
+        let (ident, expr) = match item {
+            Item::Const(item_const) => (&item_const.ident, item_const.expr.as_ref()),
+            Item::Static(item_static) => (&item_static.ident, item_static.expr.as_ref()),
+            _ => continue,
+        };
+        if let Expr::Lit(ExprLit {
+            lit: Lit::Str(value),
+            ..
+        }) = expr
+        {
+            constants.insert(ident.to_string(), value.value());
+        }
+    }
+    constants
+}
+
+/// Cuts a `#` comment off a line of Python, leaving `#` inside strings alone.
+fn strip_python_comment(line: &str) -> &str {
+    let mut quote: Option<char> = None;
+    for (position, c) in line.char_indices() {
+        match (quote, c) {
+            (Some(quote_char), _) if c == quote_char => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '#') => return &line[..position],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// Lists the contents of the single line string literals in a line of Python.
+fn python_string_literals(line: &str) -> Vec<String> {
+    let mut literals: Vec<String> = vec![];
+    let mut current: Option<(char, String)> = None;
+    let mut escaped: bool = false;
+    for c in line.chars() {
+        match &mut current {
+            Some((_, literal)) if escaped => {
+                literal.push(c);
+                escaped = false;
+            }
+            Some(_) if c == '\\' => escaped = true,
+            Some((quote_char, literal)) => {
+                if c == *quote_char {
+                    literals.push(std::mem::take(literal));
+                    current = None;
+                } else {
+                    literal.push(c);
+                }
+            }
+            None if c == '"' || c == '\'' => current = Some((c, String::new())),
+            None => {}
+        }
+    }
+    literals
+}
+
+/// The first argument of a Python call given the text following its `(`, e.g. `os.environ.get("A", "b")` for
+/// `os.environ.get("A", "b"), "w")`.
+fn python_first_argument(arguments: &str) -> &str {
+    let mut quote: Option<char> = None;
+    let mut depth: usize = 0;
+    for (position, c) in arguments.char_indices() {
+        match (quote, c) {
+            (Some(quote_char), _) if c == quote_char => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(' | '[' | '{') => depth += 1,
+            (None, ')' | ']' | '}' | ',') if depth == 0 => return arguments[..position].trim(),
+            (None, ')' | ']' | '}') => depth -= 1,
+            _ => {}
+        }
+    }
+    arguments.trim()
+}
+
+/// The contents of Python code that is a single string literal, e.g. `database.json` for `"database.json"`.
+fn python_string_literal(code: &str) -> Option<String> {
+    let code: &str = code.trim();
+    let literals: Vec<String> = python_string_literals(code);
+    let is_single_literal: bool =
+        literals.len() == 1 && code.starts_with(['"', '\'']) && code.ends_with(['"', '\'']);
+    is_single_literal.then(|| literals[0].clone())
+}
+
+/// Whether Python code only reads one of the [`PATH_SETTING_ENV_VARS`], optionally falling back to the data file,
+/// e.g. `os.environ.get("WEB_SERVER_DATABASE_PATH", "database.json")`, and builds no other path from it.
+fn is_python_path_setting_read(code: &str) -> bool {
+    let code: &str = code.trim();
+    let Some(arguments) = ["os.environ.get(", "os.getenv(", "os.environ["]
+        .iter()
+        .find_map(|prefix| code.strip_prefix(prefix))
+        .and_then(|arguments| arguments.strip_suffix([')', ']']))
+    else {
+        return false;
+    };
+    let arguments: Vec<&str> = arguments.split(',').collect();
+    let (name, fallback) = match arguments.as_slice() {
+        [name] => (*name, None),
+        [name, fallback] => (*name, Some(*fallback)),
+        _ => return false,
+    };
+    python_string_literal(name).is_some_and(|name| PATH_SETTING_ENV_VARS.contains(&name.as_str()))
+        && fallback.is_none_or(|fallback| {
+            python_string_literal(fallback).is_some_and(|path| is_data_file_path(&path))
+        })
+}
+
+/// Lists the argument text following each call of a builtin like `open(`, skipping methods like `re.compile(`.
+fn python_calls<'a>(line: &'a str, call: &'a str) -> impl Iterator<Item = &'a str> {
+    line.match_indices(call).filter_map(move |(position, _)| {
+        let is_builtin: bool = line[..position]
+            .chars()
+            .next_back()
+            .is_none_or(|before| !(before.is_alphanumeric() || before == '_' || before == '.'));
+        is_builtin.then(|| &line[position + call.len()..])
+    })
+}
+
+struct SafetyScanner<'a> {
+    allowed_hosts: &'a [String],
+    string_constants: HashMap<String, String>,
+    /// Functions returning a path read from one of the [`PATH_SETTING_ENV_VARS`].
+    path_setting_functions: HashSet<String>,
+    /// Local variables of the current function holding such a path.
+    path_setting_locals: HashSet<String>,
+    /// Local variables of the current function holding an `OpenOptions` or `DirBuilder`, with the call creating it.
+    filesystem_locals: HashMap<String, String>,
+    findings: Vec<SafetyFinding>,
+    env_vars: Vec<String>,
+}
+
+impl SafetyScanner<'_> {
+    fn report(
+        &mut self,
+        severity: Severity,
+        span: proc_macro2::Span,
+        category: &'static str,
+        message: String,
+    ) {
+        self.findings.push(SafetyFinding {
+            severity,
+            line: span.start().line,
+            category,
+            message,
+        });
+    }
+
+    /// Resolves an argument to a string if it is a literal or a named string constant.
+    fn resolve_string_argument(&self, argument: Option<&Expr>) -> Option<String> {
+        match argument? {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(value),
+                ..
+            }) => Some(value.value()),
+            Expr::Path(ExprPath { path, .. }) => path
+                .get_ident()
+                .and_then(|ident| self.string_constants.get(&ident.to_string()))
+                .cloned(),
+            Expr::Reference(reference) => {
+                self.resolve_string_argument(Some(reference.expr.as_ref()))
+            }
+            _ => None,
+        }
+    }
+
+    fn is_host_allowed(&self, host: &str) -> bool {
+        let host: String = host.to_lowercase();
+        LOCAL_HOSTS.contains(&host.as_str()) || self.allowed_hosts.contains(&host)
+    }
+
+    fn check_call(&mut self, call: &ExprCall, segments: &[String]) {
+        let segment = |index_from_end: usize| -> &str {
+            segments
+                .len()
+                .checked_sub(index_from_end + 1)
+                .map(|index| segments[index].as_str())
+                .unwrap_or_default()
+        };
+        let (owner, function) = (segment(1), segment(0));
+        let callee: String = segments.join("::");
+
+        if owner == "Command" && function == "new" {
+            let program: String = self
+                .resolve_string_argument(call.args.first())
+                .unwrap_or_else(|| "<dynamic>".to_string());
+            self.report(
+                Severity::High,
+                call.span(),
+                "process",
+                format!("Spawns an external process `{}` via {}", program, callee),
+            );
+        }
+
+        if is_filesystem_path(segments) && !matches!(function, "new" | "options") {
+            self.check_path_argument(call.span(), call.args.first(), &callee);
+        }
+
+        if owner == "env" && matches!(function, "var" | "var_os" | "vars" | "vars_os") {
+            let name: Option<String> = self.resolve_string_argument(call.args.first());
+            self.check_env_read(call.span(), name, &callee);
+        }
+
+        if matches!(owner, "TcpStream" | "UdpSocket") && function == "connect" {
+            self.report(
+                Severity::Medium,
+                call.span(),
+                "network",
+                format!("Opens a raw socket via {}", callee),
+            );
+        }
+    }
+
+    /// Reports a filesystem access unless its path is the data file or one of the path settings.
+    fn check_path_argument(
+        &mut self,
+        span: proc_macro2::Span,
+        argument: Option<&Expr>,
+        callee: &str,
+    ) {
+        if argument.is_some_and(|argument| self.is_path_setting_expr(argument)) {
+            return;
+        }
+        match self.resolve_string_argument(argument) {
+            Some(path) if is_data_file_path(&path) => {}
+            Some(path) => self.report(
+                Severity::High,
+                span,
+                "filesystem",
+                format!("Accesses `{}` via {}, outside the data file", path, callee),
+            ),
+            None => self.report(
+                Severity::Medium,
+                span,
+                "filesystem",
+                format!("Accesses a path computed at runtime via {}", callee),
+            ),
+        }
+    }
+
+    /// Names the call creating the filesystem builder a method is called on, e.g. `OpenOptions::new()` for
+    /// `OpenOptions::new().write(true).open(..)` or for a local variable holding that builder.
+    fn filesystem_receiver(&self, receiver: &Expr) -> Option<String> {
+        match receiver {
+            Expr::MethodCall(call) => self.filesystem_receiver(&call.receiver),
+            Expr::Reference(reference) => self.filesystem_receiver(&reference.expr),
+            Expr::Paren(paren) => self.filesystem_receiver(&paren.expr),
+            Expr::Call(call) => match call.func.as_ref() {
+                Expr::Path(ExprPath { path, .. }) => {
+                    let segments: Vec<String> = path_segments(path);
+                    is_filesystem_path(&segments).then(|| format!("{}()", segments.join("::")))
+                }
+                _ => None,
+            },
+            Expr::Path(ExprPath { path, .. }) => path
+                .get_ident()
+                .and_then(|ident| self.filesystem_locals.get(&ident.to_string()))
+                .cloned(),
+            _ => None,
+        }
+    }
+
+    /// Records an environment variable read and reports it unless the variable is expected.
+    fn check_env_read(&mut self, span: proc_macro2::Span, name: Option<String>, callee: &str) {
+        self.env_vars.extend(name.clone());
+        match name {
+            Some(name) if ALLOWED_ENV_VARS.contains(&name.as_str()) => {}
+            Some(name) => self.report(
+                Severity::Medium,
+                span,
+                "environment",
+                format!("Reads environment variable `{}`", name),
+            ),
+            None => self.report(
+                Severity::Medium,
+                span,
+                "environment",
+                format!("Reads environment variables via {}", callee),
+            ),
+        }
+    }
+
+    /// Whether an expression is the path read from one of the [`PATH_SETTING_ENV_VARS`], directly,
+    /// through a local variable or through a function returning it.
+    fn is_path_setting_expr(&self, expr: &Expr) -> bool {
+        is_path_setting_value(expr, &|ident: &str| {
+            self.path_setting_locals.contains(ident) || self.path_setting_functions.contains(ident)
+        })
+    }
+
+    fn check_string_literal(&mut self, literal: &LitStr) {
+        let value: String = literal.value();
+
+        if value.starts_with("http://") || value.starts_with("https://") {
+            if let Some(host) = Url::parse(&value)
+                .ok()
+                .and_then(|url| url.host_str().map(str::to_string))
+            {
+                if !self.is_host_allowed(&host) {
+                    self.report(
+                        Severity::High,
+                        literal.span(),
+                        "network",
+                        format!("Calls host `{}`, which is not in the external urls", host),
+                    );
+                }
+            }
+            return;
+        }
+
+        if SECRET_PREFIXES
+            .iter()
+            .any(|prefix| value.starts_with(prefix))
+        {
+            self.report(
+                Severity::High,
+                literal.span(),
+                "secret",
+                "Contains what looks like a hard-coded API key or private key".to_string(),
+            );
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for SafetyScanner<'_> {
+    fn visit_expr_call(&mut self, call: &'ast ExprCall) {
+        if let Expr::Path(ExprPath { path, .. }) = call.func.as_ref() {
+            let segments: Vec<String> = path_segments(path);
+            self.check_call(call, &segments);
+        }
+        visit::visit_expr_call(self, call);
+    }
+
+    fn visit_expr_method_call(&mut self, call: &'ast ExprMethodCall) {
+        let method: String = call.method.to_string();
+        if let Some(receiver) = self.filesystem_receiver(&call.receiver) {
+            // `OpenOptions::create` takes a flag, only `DirBuilder::create` takes a path.
+            let takes_path: bool = match method.as_str() {
+                "open" => true,
+                "create" => receiver.contains("DirBuilder"),
+                method => method.starts_with("remove"),
+            };
+            if takes_path {
+                let callee: String = format!("{}.{}", receiver, method);
+                self.check_path_argument(call.span(), call.args.first(), &callee);
+            }
+        }
+        visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_expr_unsafe(&mut self, unsafe_block: &'ast syn::ExprUnsafe) {
+        self.report(
+            Severity::High,
+            unsafe_block.unsafe_token.span,
+            "unsafe",
+            "Uses an `unsafe` block".to_string(),
+        );
+        visit::visit_expr_unsafe(self, unsafe_block);
+    }
+
+    fn visit_signature(&mut self, signature: &'ast syn::Signature) {
+        if let Some(unsafety) = &signature.unsafety {
+            self.report(
+                Severity::High,
+                unsafety.span,
+                "unsafe",
+                format!("Declares `unsafe fn {}`", signature.ident),
+            );
+        }
+        visit::visit_signature(self, signature);
+    }
+
+    fn visit_item_impl(&mut self, item_impl: &'ast syn::ItemImpl) {
+        if let Some(unsafety) = &item_impl.unsafety {
+            self.report(
+                Severity::High,
+                unsafety.span,
+                "unsafe",
+                "Declares an `unsafe impl`".to_string(),
+            );
+        }
+        visit::visit_item_impl(self, item_impl);
+    }
+
+    fn visit_item_fn(&mut self, item_fn: &'ast syn::ItemFn) {
+        let outer_locals: HashSet<String> = std::mem::take(&mut self.path_setting_locals);
+        let outer_filesystem_locals: HashMap<String, String> =
+            std::mem::take(&mut self.filesystem_locals);
+        visit::visit_item_fn(self, item_fn);
+        self.path_setting_locals = outer_locals;
+        self.filesystem_locals = outer_filesystem_locals;
+    }
+
+    fn visit_impl_item_fn(&mut self, impl_item_fn: &'ast syn::ImplItemFn) {
+        let outer_locals: HashSet<String> = std::mem::take(&mut self.path_setting_locals);
+        let outer_filesystem_locals: HashMap<String, String> =
+            std::mem::take(&mut self.filesystem_locals);
+        visit::visit_impl_item_fn(self, impl_item_fn);
+        self.path_setting_locals = outer_locals;
+        self.filesystem_locals = outer_filesystem_locals;
+    }
+
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        let ident: Option<String> = local_ident(&local.pat);
+        if let Some(ident) = &ident {
+            let holds_path_setting: bool = local
+                .init
+                .as_ref()
+                .is_some_and(|init| self.is_path_setting_expr(&init.expr));
+            if holds_path_setting {
+                self.path_setting_locals.insert(ident.clone());
+            } else {
+                self.path_setting_locals.remove(ident);
+            }
+            match local
+                .init
+                .as_ref()
+                .and_then(|init| self.filesystem_receiver(&init.expr))
+            {
+                Some(builder) => self.filesystem_locals.insert(ident.clone(), builder),
+                None => self.filesystem_locals.remove(ident),
+            };
+        }
+        let name: Option<String> = ident.map(|ident| ident.to_lowercase());
+        let literal_value: Option<String> = local
+            .init
+            .as_ref()
+            .and_then(|init| self.resolve_string_argument(Some(init.expr.as_ref())));
+
+        if let (Some(name), Some(value)) = (name, literal_value) {
+            if !value.is_empty() && SECRET_NAME_HINTS.iter().any(|hint| name.contains(hint)) {
+                self.report(
+                    Severity::Medium,
+                    local.span(),
+                    "secret",
+                    format!("Assigns a hard-coded value to `{}`", name),
+                );
+            }
+        }
+        visit::visit_local(self, local);
+    }
+
+    fn visit_lit_str(&mut self, literal: &'ast LitStr) {
+        self.check_string_literal(literal);
+        visit::visit_lit_str(self, literal);
+    }
+
+    /// `syn` keeps macro arguments as tokens, they are parsed as comma separated expressions where possible,
+    /// so `format!`, `println!` or `vec!` arguments are checked like any other code.
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        let name: String = mac
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string())
+            .unwrap_or_default();
+        let arguments: Option<Punctuated<Expr, Token![,]>> =
+            Punctuated::<Expr, Token![,]>::parse_terminated
+                .parse2(mac.tokens.clone())
+                .ok();
+
+        if matches!(name.as_str(), "env" | "option_env") {
+            let variable: Option<String> = arguments
+                .as_ref()
+                .and_then(|arguments| self.resolve_string_argument(arguments.first()));
+            self.check_env_read(mac.span(), variable, &format!("{}!", name));
+        }
+        for argument in arguments.iter().flatten() {
+            self.visit_expr(argument);
+        }
+        visit::visit_macro(self, mac);
+    }
+}
+
+/// Whether a path is the data file or lies in the data directory, relative to the web server's working
+/// directory and without leaving it, e.g. `database.json` or `data/database.sqlite`.
+fn is_data_file_path(path: &str) -> bool {
+    let path: &Path = Path::new(path);
+    let stays_inside: bool = path
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+    let file_name: &str = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+    let in_data_dir: bool = path
+        .components()
+        .find(|component| *component != Component::CurDir)
+        .is_some_and(|component| component.as_os_str() == ALLOWED_DATA_DIR);
+    stays_inside && (ALLOWED_DATA_FILES.contains(&file_name) || in_data_dir)
+}
+
+fn path_segments(path: &syn::Path) -> Vec<String> {
+    path.segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect()
+}
+
+/// Whether a called path belongs to the filesystem API, e.g. `fs::write`, `File::open` or `OpenOptions::new`.
+fn is_filesystem_path(segments: &[String]) -> bool {
+    let owner: &str = segments
+        .len()
+        .checked_sub(2)
+        .map(|index| segments[index].as_str())
+        .unwrap_or_default();
+    segments.iter().any(|segment| segment == "fs")
+        || matches!(owner, "File" | "OpenOptions" | "DirBuilder")
+}
+
+/// The variable a `let` binds, if it binds a single one.
+fn local_ident(pat: &Pat) -> Option<String> {
+    match pat {
+        Pat::Ident(pat_ident) => Some(pat_ident.ident.to_string()),
+        Pat::Type(pat_type) => local_ident(&pat_type.pat),
+        _ => None,
+    }
+}
+
+/// Whether an expression is the value of one of the [`PATH_SETTING_ENV_VARS`] itself, not a path built from it.
+///
+/// Accepts reading the variable with a data file as fallback, identifiers holding its value, conversions like
+/// `PathBuf::from` or `.as_str()` and joining a relative file name, so `format!("{}/../etc", database_path())`
+/// is not a path setting.
+fn is_path_setting_value(expr: &Expr, is_path_setting_ident: &dyn Fn(&str) -> bool) -> bool {
+    let is_setting = |expr: &Expr| is_path_setting_value(expr, is_path_setting_ident);
+    match expr {
+        Expr::Path(ExprPath { path, .. }) => path
+            .get_ident()
+            .is_some_and(|ident| is_path_setting_ident(&ident.to_string())),
+        Expr::Reference(reference) => is_setting(&reference.expr),
+        Expr::Paren(paren) => is_setting(&paren.expr),
+        Expr::Try(expr_try) => is_setting(&expr_try.expr),
+        Expr::Call(call) => {
+            let Expr::Path(ExprPath { path, .. }) = call.func.as_ref() else {
+                return false;
+            };
+            let segments: Vec<String> = path_segments(path);
+            let owner: &str = segments
+                .len()
+                .checked_sub(2)
+                .map(|index| segments[index].as_str())
+                .unwrap_or_default();
+            let function: &str = segments.last().map(String::as_str).unwrap_or_default();
+            let arguments: Vec<&Expr> = call.args.iter().collect();
+            match (owner, function, arguments.as_slice()) {
+                ("env", "var" | "var_os", [name]) => literal_value(name)
+                    .is_some_and(|name| PATH_SETTING_ENV_VARS.contains(&name.as_str())),
+                ("Path" | "PathBuf" | "String" | "OsString" | "OsStr", "new" | "from", [value]) => {
+                    is_setting(value)
+                }
+                (_, _, []) => is_path_setting_ident(function),
+                _ => false,
+            }
+        }
+        Expr::MethodCall(call) => {
+            let method: String = call.method.to_string();
+            let arguments: Vec<&Expr> = call.args.iter().collect();
+            match (method.as_str(), arguments.as_slice()) {
+                (
+                    "clone" | "to_string" | "to_owned" | "to_path_buf" | "as_str" | "as_path"
+                    | "as_ref" | "as_os_str" | "into" | "ok" | "unwrap",
+                    [],
+                )
+                | ("expect", [_]) => is_setting(&call.receiver),
+                ("unwrap_or" | "unwrap_or_else", [fallback]) => {
+                    is_setting(&call.receiver)
+                        && literal_value(fallback).is_some_and(|path| is_data_file_path(&path))
+                }
+                ("join", [file_name]) => {
+                    is_setting(&call.receiver)
+                        && literal_value(file_name).is_some_and(|file_name| {
+                            Path::new(&file_name)
+                                .components()
+                                .all(|component| matches!(component, Component::Normal(_)))
+                        })
+                }
+                (_, []) => is_path_setting_ident(&method),
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+/// The string literal an expression evaluates to, looking through closures returning it and conversions like
+/// `.to_string()`, e.g. `|_| "database.json".into()`.
+fn literal_value(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(value),
+            ..
+        }) => Some(value.value()),
+        Expr::Closure(closure) => literal_value(&closure.body),
+        Expr::Reference(reference) => literal_value(&reference.expr),
+        Expr::Paren(paren) => literal_value(&paren.expr),
+        Expr::MethodCall(call)
+            if call.args.is_empty()
+                && matches!(
+                    call.method.to_string().as_str(),
+                    "to_string" | "to_owned" | "into"
+                ) =>
+        {
+            literal_value(&call.receiver)
+        }
+        Expr::Call(call) if call.args.len() == 1 => match call.func.as_ref() {
+            Expr::Path(ExprPath { path, .. })
+                if matches!(
+                    path_segments(path).last().map(String::as_str),
+                    Some("from" | "new")
+                ) =>
+            {
+                call.args.first().and_then(literal_value)
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Whether a function body evaluates to the value of one of the [`PATH_SETTING_ENV_VARS`], directly or through
+/// its own local variables.
+fn returns_path_setting(block: &syn::Block, path_setting_functions: &HashSet<String>) -> bool {
+    let mut locals: HashSet<String> = HashSet::new();
+    for (index, statement) in block.stmts.iter().enumerate() {
+        let is_path_setting_ident =
+            |ident: &str| locals.contains(ident) || path_setting_functions.contains(ident);
+        match statement {
+            Stmt::Local(local) => {
+                let Some(ident) = local_ident(&local.pat) else {
+                    continue;
+                };
+                let holds_path_setting: bool = local
+                    .init
+                    .as_ref()
+                    .is_some_and(|init| is_path_setting_value(&init.expr, &is_path_setting_ident));
+                if holds_path_setting {
+                    locals.insert(ident);
+                } else {
+                    locals.remove(&ident);
+                }
+            }
+            Stmt::Expr(expr, None) if index + 1 == block.stmts.len() => {
+                return is_path_setting_value(expr, &is_path_setting_ident);
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Collects the functions and methods returning the value of one of the [`PATH_SETTING_ENV_VARS`], as such a
+/// path is usually looked up by a small helper, possibly calling another one.
+fn collect_path_setting_functions(syntax_tree: &syn::File) -> HashSet<String> {
+    struct FunctionBodies<'ast>(Vec<(String, &'ast syn::Block)>);
+
+    impl<'ast> Visit<'ast> for FunctionBodies<'ast> {
+        fn visit_item_fn(&mut self, item_fn: &'ast syn::ItemFn) {
+            self.0.push((item_fn.sig.ident.to_string(), &item_fn.block));
+            visit::visit_item_fn(self, item_fn);
+        }
+
+        fn visit_impl_item_fn(&mut self, impl_item_fn: &'ast syn::ImplItemFn) {
+            self.0
+                .push((impl_item_fn.sig.ident.to_string(), &impl_item_fn.block));
+            visit::visit_impl_item_fn(self, impl_item_fn);
+        }
+    }
+
+    let mut bodies: FunctionBodies = FunctionBodies(vec![]);
+    bodies.visit_file(syntax_tree);
+
+    let mut functions: HashSet<String> = HashSet::new();
+    loop {
+        let found: Vec<String> = bodies
+            .0
+            .iter()
+            .filter(|(name, block)| {
+                !functions.contains(name) && returns_path_setting(block, &functions)
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        if found.is_empty() {
+            return functions;
+        }
+        functions.extend(found);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(code: &str) -> Vec<SafetyFinding> {
+        scan_generated_code(code, &["api.example.com".to_string()]).expect("valid Rust")
+    }
+
+    fn categories(findings: &[SafetyFinding]) -> Vec<&'static str> {
+        findings.iter().map(|finding| finding.category).collect()
+    }
+
+    #[test]
+    fn flags_calls_inside_macro_arguments() {
+        let findings: Vec<SafetyFinding> = scan(
+            r#"
+fn main() {
+    println!("{}", std::fs::read_to_string("/etc/passwd").unwrap());
+    let url = format!("https://evil.example.org/{}", 1);
+}
+"#,
+        );
+
+        assert_eq!(categories(&findings), vec!["filesystem", "network"]);
+        assert_eq!(findings[0].line, 3);
+        assert!(findings[1].message.contains("evil.example.org"));
+    }
+
+    #[test]
+    fn flags_compile_time_env_reads() {
+        let code: &str = r#"
+const KEY: &str = env!("AWS_SECRET_ACCESS_KEY");
+fn main() {
+    let port = option_env!("WEB_SERVER_PORT");
+    let home = std::env::var("HOME");
+}
+"#;
+        let findings: Vec<SafetyFinding> = scan(code);
+
+        let messages: Vec<&str> = findings
+            .iter()
+            .map(|finding| finding.message.as_str())
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Reads environment variable `AWS_SECRET_ACCESS_KEY`",
+                "Reads environment variable `HOME`"
+            ]
+        );
+        assert_eq!(
+            find_env_vars(code).unwrap(),
+            vec!["AWS_SECRET_ACCESS_KEY", "HOME", "WEB_SERVER_PORT"]
+        );
+    }
+
+    #[test]
+    fn allows_paths_read_from_path_settings() {
+        let findings: Vec<SafetyFinding> = scan(
+            r#"
+fn database_path() -> String {
+    std::env::var("WEB_SERVER_DATABASE_PATH").unwrap_or_else(|_| "database.json".to_string())
+}
+fn save(json: &str) {
+    std::fs::write(database_path(), json).unwrap();
+    let path = std::env::var("WEB_SERVER_DATABASE_PATH").unwrap();
+    std::fs::read_to_string(&path).unwrap();
+}
+fn other(json: &str) {
+    std::fs::write(path, json).unwrap();
+}
+"#,
+        );
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].line, 11);
+        assert_eq!(findings[0].severity, Severity::Medium);
+    }
+
+    #[test]
+    fn flags_paths_built_from_path_settings() {
+        let findings: Vec<SafetyFinding> = scan(
+            r#"
+fn database_path() -> String {
+    let path = std::env::var("WEB_SERVER_DATABASE_PATH").unwrap_or_else(|_| "database.json".into());
+    path
+}
+fn uploads_dir() -> std::path::PathBuf {
+    std::path::PathBuf::from(std::env::var("WEB_SERVER_UPLOAD_DIR").unwrap())
+}
+fn escape_path() -> String {
+    format!("{}/../../etc", database_path())
+}
+fn save(json: &str) {
+    std::fs::write(database_path(), json).unwrap();
+    std::fs::write(uploads_dir().join("avatar.png"), json).unwrap();
+    std::fs::write(format!("{}/../../etc/passwd", database_path()), json).unwrap();
+    std::fs::write(uploads_dir().join("../../.ssh/id_rsa"), json).unwrap();
+    std::fs::remove_dir_all(escape_path()).unwrap();
+}
+"#,
+        );
+
+        let lines: Vec<usize> = findings.iter().map(|finding| finding.line).collect();
+        assert_eq!(lines, vec![15, 16, 17]);
+        assert!(findings
+            .iter()
+            .all(|finding| finding.category == "filesystem"));
+    }
+
+    #[test]
+    fn flags_paths_opened_through_builders() {
+        let findings: Vec<SafetyFinding> = scan(
+            r#"
+use std::fs::{DirBuilder, File, OpenOptions};
+fn main() {
+    OpenOptions::new().write(true).open("/etc/passwd").unwrap();
+    File::options().append(true).open("/root/.bashrc").unwrap();
+    let mut options = OpenOptions::new();
+    options.create(true);
+    options.open(std::env::args().nth(1).unwrap()).unwrap();
+    DirBuilder::new().recursive(true).create("data/uploads").unwrap();
+    OpenOptions::new().read(true).open("database.json").unwrap();
+}
+"#,
+        );
+
+        let summary: Vec<(usize, Severity)> = findings
+            .iter()
+            .map(|finding| (finding.line, finding.severity))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (4, Severity::High),
+                (5, Severity::High),
+                (8, Severity::Medium)
+            ]
+        );
+        assert!(findings[0].message.contains("OpenOptions::new().open"));
+    }
+
+    #[test]
+    fn matches_data_files_by_path_under_the_data_dir() {
+        assert!(is_data_file_path("database.json"));
+        assert!(is_data_file_path("./database.sqlite"));
+        assert!(is_data_file_path("data/database.json"));
+        assert!(is_data_file_path("data/uploads/avatar.png"));
+        assert!(!is_data_file_path("/etc/passwd"));
+        assert!(!is_data_file_path("/home/user/database.json"));
+        assert!(!is_data_file_path("../database.json"));
+        assert!(!is_data_file_path("data/../../.env"));
+        assert!(!is_data_file_path(".env"));
+    }
+
+    #[test]
+    fn python_scan_allows_paths_read_from_path_settings() {
+        let findings: Vec<SafetyFinding> = scan_python_code(
+            r#"
+DATABASE_FILE = os.environ.get("WEB_SERVER_DATABASE_PATH", "database.json")
+ESCAPE_DIR = os.environ["WEB_SERVER_UPLOAD_DIR"] + "/../.."
+with open(DATABASE_FILE) as file:
+    pass
+with open(os.getenv("WEB_SERVER_STATIC_DIR"), "rb") as file:
+    pass
+with open("data/database.json") as file:
+    pass
+with open("/etc/passwd") as file:
+    pass
+with open(os.environ.get("WEB_SERVER_DATABASE_PATH") + "/../../.env") as file:
+    pass
+with open(ESCAPE_DIR) as file:
+    pass
+"#,
+            &[],
+        );
+
+        let summary: Vec<(usize, Severity)> = findings
+            .iter()
+            .map(|finding| (finding.line, finding.severity))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (10, Severity::High),
+                (12, Severity::Medium),
+                (14, Severity::Medium)
+            ]
+        );
+        assert!(findings
+            .iter()
+            .all(|finding| finding.category == "filesystem"));
+    }
+
+    #[test]
+    fn python_scan_flags_risky_constructs() {
+        let findings: Vec<SafetyFinding> = scan_python_code(
+            r#"
+import subprocess
+from ctypes import CDLL
+COLLECT_URL = "https://evil.example.org/collect"
+result = eval(request_text)
+pattern = re.compile("[a-z]+")  # not the compile builtin
+home = os.environ.get("HOME")
+port = os.environ.get("WEB_SERVER_PORT", "8080")
+shutil.rmtree("/srv")
+api_key = "sk-live-1234"
+client.get("https://api.example.com/rates")
+"#,
+            &["api.example.com".to_string()],
+        );
+
+        let summary: Vec<(usize, &str)> = findings
+            .iter()
+            .map(|finding| (finding.line, finding.category))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (2, "process"),
+                (3, "unsafe"),
+                (4, "network"),
+                (5, "process"),
+                (9, "filesystem"),
+                (10, "secret"),
+                (7, "environment"),
+                (10, "secret"),
+            ]
+        );
+    }
+
+    #[test]
+    fn python_scan_ignores_comments_and_allowed_hosts() {
+        let findings: Vec<SafetyFinding> = scan_python_code(
+            r#"
+# subprocess.run(["rm", "-rf", "/"]) is never called
+BASE_URL = "https://api.example.com/v1"  # eval(
+response = httpx.get(f"{BASE_URL}/latest", proxies=None)
+"#,
+            &["api.example.com".to_string()],
+        );
+
+        assert!(findings.is_empty(), "{:?}", findings);
+    }
+}
@@ -0,0 +1,653 @@
+use crate::helpers::general::env_setting;
+use crate::helpers::socket_bridge::NetworkBridge;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::sync::OnceLock;
+use std::time::Duration;
+use tokio::time;
+
+/// Environment variable selecting the sandbox backend: `bwrap`, `rlimits` or `none`.
+const SANDBOX_MODE_ENV: &str = "SANDBOX_MODE";
+/// Environment variable overriding the address space limit of the web server, in megabytes.
+const SANDBOX_MEMORY_MB_ENV: &str = "SANDBOX_MEMORY_MB";
+/// Environment variable overriding the CPU time limit of the web server, in seconds.
+const SANDBOX_CPU_SECONDS_ENV: &str = "SANDBOX_CPU_SECONDS";
+/// Environment variable overriding the wall time limit of a build, in seconds.
+const SANDBOX_BUILD_SECONDS_ENV: &str = "SANDBOX_BUILD_SECONDS";
+/// Test harness argument running the integration tests of a generated project one at a time, as they
+/// change the process working directory.
+pub const TEST_THREADS_ARG: &str = "--test-threads=1";
+/// System directories and files every sandboxed command may read: the shared libraries, programs,
+/// dynamic linker configuration, CA certificates and name lookup files. Everything else of the host,
+/// including home directories and the operator's `.env`, stays hidden.
+const SYSTEM_PATHS: &[&str] = &[
+    "/usr",
+    "/bin",
+    "/sbin",
+    "/lib",
+    "/lib32",
+    "/lib64",
+    "/etc/alternatives",
+    "/etc/ld.so.cache",
+    "/etc/ld.so.conf",
+    "/etc/ld.so.conf.d",
+    "/etc/ssl",
+    "/etc/ca-certificates",
+    "/etc/pki",
+    "/etc/hosts",
+    "/etc/nsswitch.conf",
+    "/etc/passwd",
+    "/etc/group",
+    "/etc/localtime",
+];
+/// The virtualenv directory of a generated Python project.
+const PYTHON_VENV_DIR: &str = ".venv";
+/// The cargo home of sandboxed builds, inside the generated project's `target` directory.
+const SANDBOX_CARGO_HOME_DIR: &str = "target/sandbox-cargo-home";
+/// Entries of the developer's cargo home a sandboxed build reads: the crate registry, git checkouts and the
+/// cargo config. The toolchain proxies in `bin` and the installed programs are not among them.
+const SHARED_CARGO_HOME_ENTRIES: &[&str] = &["registry", "git", "config.toml", "config"];
+/// The linter installed next to the requirements of a Python project, pinned so its findings stay stable.
+const RUFF_REQUIREMENT: &str = "ruff==0.8.4";
+/// Ruff rules failing a Python check: syntax errors, invalid comparisons, misplaced statements and
+/// undefined names. Style findings are left to the model.
+const RUFF_SELECT: &str = "E9,F63,F7,F82";
+
+/// How AI-generated code is isolated from the developer machine.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SandboxBackend {
+    /// Bubblewrap namespaces: read-only filesystem, private `/tmp`, optional network isolation, plus rlimits.
+    Bubblewrap,
+    /// Resource limits only: CPU time and address space.
+    Rlimits,
+    /// No isolation at all.
+    Unsandboxed,
+}
+
+impl SandboxBackend {
+    /// Returns the backend selected by `SANDBOX_MODE`, or the strongest one available.
+    pub fn detect() -> Self {
+        static BACKEND: OnceLock<SandboxBackend> = OnceLock::new();
+        *BACKEND.get_or_init(|| {
+            match env_setting(SANDBOX_MODE_ENV, String::new()).as_str() {
+                "bwrap" => return SandboxBackend::Bubblewrap,
+                "rlimits" => return SandboxBackend::Rlimits,
+                "none" => return SandboxBackend::Unsandboxed,
+                _ => {}
+            }
+
+            if !cfg!(target_os = "linux") {
+                return SandboxBackend::Unsandboxed;
+            }
+            let bwrap_works: bool = Command::new("bwrap")
+                .args(["--ro-bind", "/", "/", "--unshare-all", "true"])
+                .output()
+                .is_ok_and(|output| output.status.success());
+            if bwrap_works {
+                SandboxBackend::Bubblewrap
+            } else {
+                SandboxBackend::Rlimits
+            }
+        })
+    }
+
+    /// A short description shown to the user before code is built or run.
+    pub fn describe(&self) -> &'static str {
+        match self {
+            SandboxBackend::Bubblewrap => {
+                "bubblewrap (only system libraries, the toolchain and the project visible, writes to the \
+                 scratch dir only, loopback-only network with the egress proxy bridged in, resource limits)"
+            }
+            SandboxBackend::Rlimits => {
+                "resource limits only: code can read your files and reach the network directly, the egress proxy \
+                 only covers clients honouring the proxy env vars (install bubblewrap for isolation)"
+            }
+            SandboxBackend::Unsandboxed => "none, code runs with your full privileges",
+        }
+    }
+}
+
+/// Restrictions applied to a command building or running AI-generated code.
+///
+/// Under bubblewrap the command only sees the system directories in [`SYSTEM_PATHS`], `readonly_paths`,
+/// a private `/tmp` and the writable directories, and it always gets a network namespace of its own with
+/// nothing but loopback.
+///
+/// # Fields
+/// - `scratch_dir`: The only directory the command may write to; also its working directory.
+/// - `writable_dirs`: Additional directories the command may write to, e.g. the bridge sockets.
+/// - `readonly_paths`: Directories the command may read besides the system ones, e.g. the project and the toolchain.
+/// - `network_bridge`: Forwards the web server's port and the egress proxy between the host and the sandbox
+///   loopback. Without it the command has no network at all.
+/// - `proxy_url`: Egress proxy enforcing the host allowlist, passed through the proxy env vars.
+/// - `cargo_home`: The cargo home passed through `CARGO_HOME`, if the command must not use the developer's.
+/// - `cpu_seconds`: CPU time limit.
+/// - `memory_bytes`: Address space limit, if any.
+#[derive(Debug, Clone)]
+pub struct SandboxPolicy {
+    pub scratch_dir: PathBuf,
+    pub writable_dirs: Vec<PathBuf>,
+    pub readonly_paths: Vec<PathBuf>,
+    pub network_bridge: Option<NetworkBridge>,
+    pub proxy_url: Option<String>,
+    pub cargo_home: Option<PathBuf>,
+    pub cpu_seconds: u64,
+    pub memory_bytes: Option<u64>,
+}
+
+impl SandboxPolicy {
+    /// Policy for compiling the generated project: writes only to the project, no network (dependencies are
+    /// fetched beforehand).
+    ///
+    /// Cargo runs with a cargo home of the project's own, see [`link_sandbox_cargo_home`], so build scripts and
+    /// proc-macros cannot put programs into `~/.cargo/bin` or change the cached crates.
+    ///
+    /// # Arguments
+    /// - `project_dir`: The generated project directory.
+    pub fn for_build(project_dir: &Path) -> Self {
+        let shared_cargo_home: PathBuf = cargo_home();
+        let mut readonly_paths: Vec<PathBuf> = toolchain_paths(project_dir);
+        readonly_paths.push(shared_cargo_home.join("bin"));
+        readonly_paths.extend(
+            SHARED_CARGO_HOME_ENTRIES
+                .iter()
+                .map(|entry| shared_cargo_home.join(entry)),
+        );
+        Self {
+            scratch_dir: project_dir.to_path_buf(),
+            writable_dirs: vec![],
+            readonly_paths,
+            network_bridge: None,
+            proxy_url: None,
+            // Other platforms are not sandboxed and keep the developer's cargo home
+            cargo_home: cfg!(unix).then(|| project_dir.join(SANDBOX_CARGO_HOME_DIR)),
+            cpu_seconds: 1800,
+            memory_bytes: None,
+        }
+    }
+
+    /// Policy for running the tests of the generated project: the build policy with the memory limit of the
+    /// web server, as the tests run the generated handlers.
+    ///
+    /// # Arguments
+    /// - `project_dir`: The generated project directory.
+    pub fn for_test(project_dir: &Path) -> Self {
+        Self {
+            memory_bytes: Some(server_memory_bytes()),
+            ..Self::for_build(project_dir)
+        }
+    }
+
+    /// Policy for running the generated web server: reads only the project, writes only to its data
+    /// directory, and reaches external hosts only through the allowlisting egress proxy.
+    /// Its wall time is bounded by the readiness and endpoint test timeouts of `TestServerGuard`.
+    ///
+    /// # Arguments
+    /// - `project_dir`: The generated project directory, holding the server program.
+    /// - `data_dir`: The directory the web server keeps its data in.
+    /// - `proxy_url`: The egress proxy address, see `EgressProxy`.
+    /// - `network_bridge`: The bridge the server is reached through under bubblewrap, see `HostBridge`.
+    pub fn for_run(
+        project_dir: &Path,
+        data_dir: &Path,
+        proxy_url: String,
+        network_bridge: Option<NetworkBridge>,
+    ) -> Self {
+        let mut readonly_paths: Vec<PathBuf> = toolchain_paths(project_dir);
+        readonly_paths.push(project_dir.to_path_buf());
+        Self {
+            scratch_dir: data_dir.to_path_buf(),
+            writable_dirs: network_bridge
+                .iter()
+                .map(|bridge| bridge.socket_dir.clone())
+                .collect(),
+            readonly_paths,
+            network_bridge,
+            proxy_url: Some(proxy_url),
+            cargo_home: None,
+            cpu_seconds: env_setting(SANDBOX_CPU_SECONDS_ENV, 300),
+            memory_bytes: Some(server_memory_bytes()),
+        }
+    }
+
+    /// Builds a command running `program` with `args` inside the sandbox.
+    ///
+    /// With a network bridge, bubblewrap runs this program's own `sandbox-bridge` command in front of
+    /// `program` to connect the sandbox loopback to the host.
+    ///
+    /// # Arguments
+    /// - `program`: The program to run, e.g. `cargo` or the web server binary.
+    /// - `args`: Arguments passed to the program.
+    ///
+    /// # Returns
+    /// A `Command` ready to be spawned, using the backend from [`SandboxBackend::detect`].
+    pub fn command(&self, program: &str, args: &[&str]) -> Command {
+        let mut command: Command = match SandboxBackend::detect() {
+            SandboxBackend::Bubblewrap => {
+                let mut bwrap: Command = Command::new("bwrap");
+                bwrap.args(["--unshare-all", "--die-with-parent", "--new-session"]);
+                bwrap.args(["--dev", "/dev", "--proc", "/proc", "--tmpfs", "/tmp"]);
+                for system_path in SYSTEM_PATHS.iter().map(Path::new) {
+                    add_readonly_path(&mut bwrap, system_path);
+                }
+                for readonly_path in &self.readonly_paths {
+                    add_readonly_path(&mut bwrap, readonly_path);
+                }
+
+                let bridge_program: Option<(PathBuf, Vec<String>)> =
+                    self.network_bridge.as_ref().map(|bridge| {
+                        let bridge_exe: PathBuf = std::env::current_exe()
+                            .expect("Failed to locate the sandbox bridge program");
+                        (bridge_exe, bridge.command_args(program, args))
+                    });
+                if let Some((bridge_exe, _)) = &bridge_program {
+                    add_readonly_path(&mut bwrap, bridge_exe);
+                }
+                for dir in std::iter::once(&self.scratch_dir).chain(&self.writable_dirs) {
+                    bwrap.arg("--bind").arg(dir).arg(dir);
+                }
+                bwrap.arg("--chdir").arg(&self.scratch_dir);
+                match bridge_program {
+                    Some((bridge_exe, bridge_args)) => {
+                        bwrap.arg("--").arg(bridge_exe).args(bridge_args);
+                    }
+                    None => {
+                        bwrap.arg("--").arg(program).args(args);
+                    }
+                }
+                bwrap
+            }
+            SandboxBackend::Rlimits | SandboxBackend::Unsandboxed => {
+                let mut direct: Command = Command::new(program);
+                direct.args(args);
+                direct
+            }
+        };
+        command.current_dir(&self.scratch_dir);
+
+        if let Some(proxy_url) = &self.proxy_url {
+            for proxy_env in ["HTTP_PROXY", "HTTPS_PROXY", "ALL_PROXY"] {
+                command.env(proxy_env, proxy_url);
+                command.env(proxy_env.to_lowercase(), proxy_url);
+            }
+            command.env("NO_PROXY", "127.0.0.1,localhost");
+            command.env("no_proxy", "127.0.0.1,localhost");
+        }
+        if let Some(cargo_home) = &self.cargo_home {
+            command.env("CARGO_HOME", cargo_home);
+        }
+
+        if SandboxBackend::detect() != SandboxBackend::Unsandboxed {
+            apply_resource_limits(&mut command, self.cpu_seconds, self.memory_bytes);
+        }
+        command
+    }
+}
+
+/// The address space limit of the web server and of the generated tests, from `SANDBOX_MEMORY_MB`.
+fn server_memory_bytes() -> u64 {
+    env_setting(SANDBOX_MEMORY_MB_ENV, 1024) * 1024 * 1024
+}
+
+/// Adds a read-only bind of `path` to a bubblewrap command, recreating it as a symlink if it is one,
+/// e.g. `/lib -> usr/lib`. Paths that do not exist are skipped.
+fn add_readonly_path(bwrap: &mut Command, path: &Path) {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return;
+    };
+    match fs::read_link(path) {
+        Ok(target) if metadata.file_type().is_symlink() => {
+            bwrap.arg("--symlink").arg(target).arg(path);
+        }
+        _ => {
+            bwrap.arg("--ro-bind").arg(path).arg(path);
+        }
+    }
+}
+
+/// Directories outside the system ones that builds and the server need to read: the rustup toolchains
+/// and the Python installation the project's virtualenv was created from.
+///
+/// # Arguments
+/// - `project_dir`: The generated project directory.
+fn toolchain_paths(project_dir: &Path) -> Vec<PathBuf> {
+    let rustup_home: PathBuf = std::env::var_os("RUSTUP_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".rustup")))
+        .unwrap_or_else(|| PathBuf::from(".rustup"));
+    let mut paths: Vec<PathBuf> = vec![rustup_home];
+    if let Some(python_prefix) = python_base_prefix(project_dir) {
+        paths.push(python_prefix);
+    }
+    paths
+}
+
+/// Reads the installation prefix of the interpreter the project's `.venv` was created from, e.g.
+/// `/usr` or `~/.pyenv/versions/3.11.9`, from its `pyvenv.cfg`.
+fn python_base_prefix(project_dir: &Path) -> Option<PathBuf> {
+    let venv_config: String =
+        fs::read_to_string(project_dir.join(PYTHON_VENV_DIR).join("pyvenv.cfg")).ok()?;
+    let python_home: &str = venv_config.lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        (key.trim() == "home").then(|| value.trim())
+    })?;
+    Path::new(python_home).parent().map(Path::to_path_buf)
+}
+
+/// How long a build of the generated project may take before it is killed.
+pub fn build_time_limit() -> Duration {
+    Duration::from_secs(env_setting(SANDBOX_BUILD_SECONDS_ENV, 900))
+}
+
+/// Fetches the dependencies of a generated project, outside the sandbox because builds have no network.
+///
+/// # Arguments
+/// - `project_dir`: The generated project directory.
+/// - `fetch_args`: Extra arguments for `cargo fetch`, e.g. `--target`.
+///
+/// # Returns
+/// Whether the fetch succeeded. On failure the build can still use the local cargo cache.
+pub fn fetch_project_dependencies(project_dir: &Path, fetch_args: &[&str]) -> bool {
+    Command::new("cargo")
+        .arg("fetch")
+        .args(fetch_args)
+        .current_dir(project_dir)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+/// Builds a generated project offline inside the sandbox, bounded by [`build_time_limit`].
+///
+/// # Arguments
+/// - `project_dir`: The generated project directory.
+/// - `build_args`: Extra arguments for `cargo build`, e.g. `--target`.
+///
+/// # Returns
+/// The compiler errors if the build failed or timed out.
+pub async fn build_project_in_sandbox(
+    project_dir: &Path,
+    build_args: &[&str],
+) -> Result<(), String> {
+    let mut cargo_args: Vec<&str> = vec!["build", "--offline"];
+    cargo_args.extend_from_slice(build_args);
+
+    let build_policy: SandboxPolicy = SandboxPolicy::for_build(project_dir);
+    let build_output: Output = run_build_in_sandbox(&build_policy, "cargo", &cargo_args).await?;
+    if build_output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&build_output.stderr).to_string())
+    }
+}
+
+/// Runs the tests of a generated project offline inside the sandbox, bounded by [`build_time_limit`].
+///
+/// The tests are compiled with the build policy and run with the memory limit of [`SandboxPolicy::for_test`].
+/// They run one at a time: the tests template points the process working directory, shared by every
+/// test, at a temporary directory so the handlers save `database.json` there, see [`TEST_THREADS_ARG`].
+///
+/// # Arguments
+/// - `project_dir`: The generated project directory.
+///
+/// # Returns
+/// The compiler errors and test output if a test failed or the run timed out.
+pub async fn test_project_in_sandbox(project_dir: &Path) -> Result<(), String> {
+    let build_policy: SandboxPolicy = SandboxPolicy::for_build(project_dir);
+    let build_output: Output =
+        run_build_in_sandbox(&build_policy, "cargo", &["test", "--offline", "--no-run"]).await?;
+    if !build_output.status.success() {
+        return Err(String::from_utf8_lossy(&build_output.stderr).to_string());
+    }
+
+    let test_policy: SandboxPolicy = SandboxPolicy::for_test(project_dir);
+    let test_output: Output = run_build_in_sandbox(
+        &test_policy,
+        "cargo",
+        &["test", "--offline", "--", TEST_THREADS_ARG],
+    )
+    .await?;
+    if test_output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "{}\n{}",
+            String::from_utf8_lossy(&test_output.stderr),
+            String::from_utf8_lossy(&test_output.stdout)
+        ))
+    }
+}
+
+/// Installs the requirements of a generated Python project and the linter into its `.venv`, outside
+/// the sandbox because checks have no network.
+///
+/// # Arguments
+/// - `project_dir`: The generated project directory.
+///
+/// # Returns
+/// Whether the install succeeded. On failure the check can still use a virtualenv an earlier run set up.
+pub fn install_python_requirements(project_dir: &Path) -> bool {
+    let venv_created: bool = project_dir.join(PYTHON_VENV_DIR).exists()
+        || Command::new("python3")
+            .args(["-m", "venv", PYTHON_VENV_DIR])
+            .current_dir(project_dir)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()
+            .is_ok_and(|output| output.status.success());
+    venv_created
+        && Command::new(project_dir.join(PYTHON_VENV_DIR).join("bin").join("pip"))
+            .args(["install", "--quiet", "-r", "requirements.txt"])
+            .arg(RUFF_REQUIREMENT)
+            .current_dir(project_dir)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()
+            .is_ok_and(|output| output.status.success())
+}
+
+/// Checks a generated Python project inside the sandbox, bounded by [`build_time_limit`]: `main.py`
+/// must compile, then pass ruff's error rules when ruff is installed in the project's `.venv`.
+///
+/// # Arguments
+/// - `project_dir`: The generated project directory.
+///
+/// # Returns
+/// The compiler or linter errors if the check failed or timed out.
+pub async fn check_python_project_in_sandbox(project_dir: &Path) -> Result<(), String> {
+    let python_path: PathBuf = project_dir.join(PYTHON_VENV_DIR).join("bin").join("python");
+    if !python_path.exists() {
+        return Err(format!(
+            "No Python virtualenv at {}, the requirements could not be installed",
+            python_path.display()
+        ));
+    }
+
+    let check_policy: SandboxPolicy = SandboxPolicy::for_build(project_dir);
+    let compile_output: Output = run_build_in_sandbox(
+        &check_policy,
+        python_path.to_str().unwrap(),
+        &["-m", "py_compile", "main.py"],
+    )
+    .await?;
+    if !compile_output.status.success() {
+        return Err(String::from_utf8_lossy(&compile_output.stderr).to_string());
+    }
+
+    let ruff_path: PathBuf = project_dir.join(PYTHON_VENV_DIR).join("bin").join("ruff");
+    if !ruff_path.exists() {
+        return Ok(());
+    }
+    let lint_output: Output = run_build_in_sandbox(
+        &check_policy,
+        ruff_path.to_str().unwrap(),
+        &["check", "--no-cache", "--select", RUFF_SELECT, "main.py"],
+    )
+    .await?;
+    if lint_output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&lint_output.stdout).to_string())
+    }
+}
+
+/// Runs a build, check or test program with a policy of the project, killing it after [`build_time_limit`].
+///
+/// # Returns
+/// The output of the program, or why it could not be run or did not finish in time.
+async fn run_build_in_sandbox(
+    build_policy: &SandboxPolicy,
+    program: &str,
+    args: &[&str],
+) -> Result<Output, String> {
+    let program_name: &str = Path::new(program)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(program);
+    if let Some(sandbox_cargo_home) = &build_policy.cargo_home {
+        link_sandbox_cargo_home(sandbox_cargo_home).map_err(|e| {
+            format!(
+                "Failed to set up the cargo home {} for {}: {}",
+                sandbox_cargo_home.display(),
+                program_name,
+                e
+            )
+        })?;
+    }
+
+    let mut build_command: tokio::process::Command = build_policy.command(program, args).into();
+    build_command
+        // Backtraces would bury the failures fed back to the fix loop
+        .env("RUST_BACKTRACE", "0")
+        .env("PYTHONDONTWRITEBYTECODE", "1")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    let build_limit: Duration = build_time_limit();
+    match time::timeout(build_limit, build_command.output()).await {
+        Ok(build_output) => {
+            build_output.map_err(|e| format!("Failed to run {}: {}", program_name, e))
+        }
+        Err(_) => Err(format!(
+            "{} {} did not finish within {} seconds",
+            program_name,
+            args.join(" "),
+            build_limit.as_secs()
+        )),
+    }
+}
+
+/// The developer's cargo home directory.
+fn cargo_home() -> PathBuf {
+    std::env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cargo")))
+        .unwrap_or_else(|| PathBuf::from(".cargo"))
+}
+
+/// Creates the cargo home of sandboxed builds, linking the [`SHARED_CARGO_HOME_ENTRIES`] of the developer's
+/// cargo home into it. The sandbox binds those read-only, so the build writes only its package cache lock.
+///
+/// # Arguments
+/// - `sandbox_cargo_home`: The cargo home of the sandboxed build, see [`SandboxPolicy::for_build`].
+#[cfg(unix)]
+fn link_sandbox_cargo_home(sandbox_cargo_home: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(sandbox_cargo_home)?;
+    let shared_cargo_home: PathBuf = cargo_home();
+    for entry in SHARED_CARGO_HOME_ENTRIES {
+        let shared_entry: PathBuf = shared_cargo_home.join(entry);
+        let link: PathBuf = sandbox_cargo_home.join(entry);
+        if shared_entry.exists() && fs::symlink_metadata(&link).is_err() {
+            std::os::unix::fs::symlink(&shared_entry, &link)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn link_sandbox_cargo_home(_sandbox_cargo_home: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+/// Sets CPU time and address space limits on the spawned process.
+#[cfg(unix)]
+fn apply_resource_limits(command: &mut Command, cpu_seconds: u64, memory_bytes: Option<u64>) {
+    use std::os::unix::process::CommandExt;
+
+    let set_limit = |resource, value: u64| {
+        let limit = libc::rlimit {
+            rlim_cur: value as libc::rlim_t,
+            rlim_max: value as libc::rlim_t,
+        };
+        // SAFETY: `setrlimit` only reads the provided struct.
+        if unsafe { libc::setrlimit(resource, &limit) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(())
+    };
+
+    // SAFETY: the closure only calls `setrlimit`, which is async-signal-safe.
+    unsafe {
+        command.pre_exec(move || {
+            set_limit(libc::RLIMIT_CPU, cpu_seconds)?;
+            if let Some(memory_bytes) = memory_bytes {
+                set_limit(libc::RLIMIT_AS, memory_bytes)?;
+            }
+            Ok(())
+        });
+    }
+}
+
+#[cfg(not(unix))]
+fn apply_resource_limits(_command: &mut Command, _cpu_seconds: u64, _memory_bytes: Option<u64>) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn builds_cannot_write_to_the_developer_cargo_home() {
+        let project_dir: PathBuf = std::env::temp_dir().join("sandbox_policy_project");
+        let build_policy: SandboxPolicy = SandboxPolicy::for_build(&project_dir);
+
+        assert!(build_policy.writable_dirs.is_empty());
+        assert!(build_policy
+            .readonly_paths
+            .contains(&cargo_home().join("registry")));
+        assert_eq!(
+            build_policy.cargo_home,
+            Some(project_dir.join(SANDBOX_CARGO_HOME_DIR))
+        );
+        assert_eq!(build_policy.memory_bytes, None);
+        assert_eq!(
+            SandboxPolicy::for_test(&project_dir).memory_bytes,
+            Some(server_memory_bytes())
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn links_the_registry_into_the_sandbox_cargo_home() {
+        let sandbox_cargo_home: PathBuf =
+            std::env::temp_dir().join(format!("sandbox_cargo_home_{}", std::process::id()));
+        link_sandbox_cargo_home(&sandbox_cargo_home).unwrap();
+        // Linking again keeps the existing links
+        link_sandbox_cargo_home(&sandbox_cargo_home).unwrap();
+
+        for entry in SHARED_CARGO_HOME_ENTRIES {
+            let shared_entry: PathBuf = cargo_home().join(entry);
+            if shared_entry.exists() {
+                assert_eq!(
+                    fs::read_link(sandbox_cargo_home.join(entry)).unwrap(),
+                    shared_entry
+                );
+            }
+        }
+        assert!(!sandbox_cargo_home.join("bin").exists());
+        fs::remove_dir_all(&sandbox_cargo_home).unwrap();
+    }
+}
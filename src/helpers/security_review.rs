@@ -0,0 +1,413 @@
+use crate::helpers::route_extraction::{collect_routes, RegisteredRoute};
+use crate::helpers::target_framework::TargetFramework;
+use quote::ToTokens;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+use syn::{Expr, ExprMethodCall, ExprPath, Fields, FnArg, ItemFn, ItemStruct, Macro};
+
+/// Route or handler name fragments of routes that must stay reachable without logging in.
+const PUBLIC_ROUTE_HINTS: &[&str] = &["login", "logout", "register", "signup", "sign_up", "signin"];
+
+/// Tokens showing a handler looks at the caller's identity.
+const AUTH_HINTS: &[&str] = &[
+    "httprequest",
+    "headermap",
+    "typedheader",
+    "authorization",
+    "bearer",
+    "session",
+    "claims",
+    "token",
+    "identity",
+];
+
+/// Tokens showing passwords are hashed.
+const HASHING_HINTS: &[&str] = &[
+    "argon2",
+    "bcrypt",
+    "hash_password",
+    "verify_password",
+    "passwordhasher",
+];
+
+/// Tokens showing request bodies are size limited.
+const SIZE_LIMIT_HINTS: &[&str] = &[
+    "JsonConfig",
+    "PayloadConfig",
+    "FormConfig",
+    "DefaultBodyLimit",
+    "RequestBodyLimitLayer",
+];
+
+/// Extractors reading the request body.
+const BODY_EXTRACTORS: &[&str] = &["Json", "Form", "Bytes", "Payload", "Multipart"];
+
+/// CORS constructors allowing every origin, as `Type::constructor` pairs.
+const PERMISSIVE_CORS: &[(&str, &str)] = &[
+    ("Cors", "permissive"),
+    ("CorsLayer", "permissive"),
+    ("CorsLayer", "very_permissive"),
+];
+
+/// Variable names usually holding errors.
+const ERROR_NAMES: &[&str] = &["e", "err", "error"];
+
+/// An item of the security checklist generated code is reviewed against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SecurityCheck {
+    PasswordHashing,
+    MutatingRouteAuthz,
+    InputSizeLimits,
+    CorsOrigins,
+    ErrorLeakage,
+}
+
+impl SecurityCheck {
+    /// Every item of the checklist, in review order.
+    pub const ALL: [SecurityCheck; 5] = [
+        SecurityCheck::PasswordHashing,
+        SecurityCheck::MutatingRouteAuthz,
+        SecurityCheck::InputSizeLimits,
+        SecurityCheck::CorsOrigins,
+        SecurityCheck::ErrorLeakage,
+    ];
+
+    /// The fix requested for a finding of this check, in terms of the framework the code is written with.
+    pub fn requested_fix(&self, framework: TargetFramework) -> &'static str {
+        match (self, framework) {
+            (SecurityCheck::PasswordHashing, _) => {
+                "Hash passwords with argon2 (`Argon2::default().hash_password` with a `SaltString`) before storing them, check them with `verify_password` and never return a password in a response"
+            }
+            (SecurityCheck::MutatingRouteAuthz, _) => {
+                "Issue a random session token (rand) at login, return it as JSON `{\"token\": \"<token>\"}`, require it as `Authorization: Bearer <token>` on this route and return 401 without a valid one"
+            }
+            (SecurityCheck::InputSizeLimits, TargetFramework::ActixWeb) => {
+                "Register `web::JsonConfig::default().limit(65_536)` and `web::PayloadConfig::new(65_536)` as app data and reject empty or oversized fields with 400"
+            }
+            (SecurityCheck::InputSizeLimits, TargetFramework::Axum) => {
+                "Add `.layer(DefaultBodyLimit::max(65_536))` from `axum::extract` to the router and reject empty or oversized fields with 400"
+            }
+            (SecurityCheck::InputSizeLimits, TargetFramework::FastApi) => {
+                "Reject requests whose `Content-Length` exceeds 65536 bytes with 413 in an `@app.middleware(\"http\")` and give string fields `Field(min_length=1, max_length=...)`"
+            }
+            (SecurityCheck::CorsOrigins, TargetFramework::ActixWeb) => {
+                "Replace the permissive CORS setup with `Cors::default()` allowing only the frontend origins, e.g. `http://localhost:3000`, and only the methods and headers the routes use"
+            }
+            (SecurityCheck::CorsOrigins, TargetFramework::Axum) => {
+                "Replace the permissive CORS setup with `CorsLayer::new()` allowing only the frontend origins, e.g. `http://localhost:3000`, and only the methods and headers the routes use"
+            }
+            (SecurityCheck::CorsOrigins, TargetFramework::FastApi) => {
+                "Replace the permissive `CORSMiddleware` setup with `allow_origins` listing only the frontend origins, e.g. `http://localhost:3000`, and only the methods and headers the routes use"
+            }
+            (SecurityCheck::ErrorLeakage, _) => {
+                "Log the error on the server with `eprintln!` and return a generic message without error details"
+            }
+        }
+    }
+}
+
+impl fmt::Display for SecurityCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SecurityCheck::PasswordHashing => write!(f, "password hashing"),
+            SecurityCheck::MutatingRouteAuthz => write!(f, "authz on mutating routes"),
+            SecurityCheck::InputSizeLimits => write!(f, "input size limits"),
+            SecurityCheck::CorsOrigins => write!(f, "CORS origins"),
+            SecurityCheck::ErrorLeakage => write!(f, "error leakage"),
+        }
+    }
+}
+
+/// A violation of the security checklist, with the fix the reviewer requests.
+///
+/// # Fields
+/// - `check`: The checklist item that is violated.
+/// - `line`: The line of the generated code the finding starts on, `0` if it concerns the whole file.
+/// - `message`: What was found.
+/// - `requested_fix`: How the code must be changed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SecurityFinding {
+    pub check: SecurityCheck,
+    #[serde(default)]
+    pub line: usize,
+    pub message: String,
+    pub requested_fix: String,
+}
+
+impl SecurityFinding {
+    fn new(check: SecurityCheck, line: usize, message: String, framework: TargetFramework) -> Self {
+        Self {
+            check,
+            line,
+            message,
+            requested_fix: check.requested_fix(framework).to_string(),
+        }
+    }
+}
+
+/// Audits generated web server code against the security checklist.
+///
+/// Checks that passwords are hashed, that mutating routes look at the caller's identity when
+/// users log in, that request bodies are size limited, that CORS does not allow every origin
+/// and that error details are not sent to clients.
+///
+/// # Arguments
+/// - `code`: The generated Rust source.
+/// - `requires_login`: Whether users log in, from `ProjectScope.is_user_login_and_logout`.
+/// - `framework`: The web framework the code is written with, which the requested fixes refer to.
+///
+/// # Returns
+/// The findings ordered by check and line, or the parse error if the code is not valid Rust.
+pub fn audit_generated_code(
+    code: &str,
+    requires_login: bool,
+    framework: TargetFramework,
+) -> Result<Vec<SecurityFinding>, syn::Error> {
+    let syntax_tree: syn::File = syn::parse_file(code)?;
+
+    let mut auditor: SecurityAuditor = SecurityAuditor {
+        framework,
+        ..SecurityAuditor::default()
+    };
+    auditor.visit_file(&syntax_tree);
+
+    let code_lower: String = code.to_lowercase();
+    let mut findings: Vec<SecurityFinding> = auditor.findings;
+
+    if !HASHING_HINTS.iter().any(|hint| code_lower.contains(hint)) {
+        if let Some(line) = auditor.password_field_line {
+            findings.push(SecurityFinding::new(
+                SecurityCheck::PasswordHashing,
+                line,
+                "Passwords are stored and compared in plaintext".to_string(),
+                framework,
+            ));
+        }
+    }
+
+    if requires_login {
+        let routes: Vec<RegisteredRoute> = collect_routes(&syntax_tree);
+        for route in routes.iter().filter(|route| route.method.is_mutating()) {
+            let route_name: String = format!("{} {}", route.path, route.handler).to_lowercase();
+            if PUBLIC_ROUTE_HINTS
+                .iter()
+                .any(|hint| route_name.contains(hint))
+            {
+                continue;
+            }
+            let Some((line, handler_tokens)) = auditor.handlers.get(&route.handler) else {
+                continue;
+            };
+            if !AUTH_HINTS.iter().any(|hint| handler_tokens.contains(hint)) {
+                findings.push(SecurityFinding::new(
+                    SecurityCheck::MutatingRouteAuthz,
+                    *line,
+                    format!(
+                        "{} {} is handled by `{}` without checking who is calling",
+                        route.method, route.path, route.handler
+                    ),
+                    framework,
+                ));
+            }
+        }
+    }
+
+    if !SIZE_LIMIT_HINTS.iter().any(|hint| code.contains(hint)) {
+        if let Some(line) = auditor.body_extractor_line {
+            findings.push(SecurityFinding::new(
+                SecurityCheck::InputSizeLimits,
+                line,
+                "Request bodies are read without a size limit".to_string(),
+                framework,
+            ));
+        }
+    }
+
+    findings.sort_by_key(|finding| (finding.check, finding.line));
+    Ok(findings)
+}
+
+/// Formats security findings for the console and the fix prompt.
+///
+/// # Arguments
+/// - `findings`: The findings of the review.
+pub fn format_security_findings(findings: &[SecurityFinding]) -> String {
+    findings
+        .iter()
+        .map(|finding| {
+            let location: String = match finding.line {
+                0 => String::new(),
+                line => format!(" (line {})", line),
+            };
+            format!(
+                "- [{}]{} {}\n  Fix: {}",
+                finding.check, location, finding.message, finding.requested_fix
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Collects handlers and checklist violations visible in a single pass over the code.
+#[derive(Default)]
+struct SecurityAuditor {
+    framework: TargetFramework,
+    findings: Vec<SecurityFinding>,
+    handlers: HashMap<String, (usize, String)>,
+    password_field_line: Option<usize>,
+    body_extractor_line: Option<usize>,
+}
+
+impl<'ast> Visit<'ast> for SecurityAuditor {
+    fn visit_item_fn(&mut self, item_fn: &'ast ItemFn) {
+        self.handlers.insert(
+            item_fn.sig.ident.to_string(),
+            (
+                item_fn.span().start().line,
+                item_fn.to_token_stream().to_string().to_lowercase(),
+            ),
+        );
+
+        if self.body_extractor_line.is_none() {
+            let reads_body: bool = item_fn.sig.inputs.iter().any(|input| match input {
+                FnArg::Typed(typed) => {
+                    let type_tokens: String = typed.ty.to_token_stream().to_string();
+                    BODY_EXTRACTORS.iter().any(|extractor| {
+                        type_tokens
+                            .split_whitespace()
+                            .any(|token| token == *extractor)
+                    })
+                }
+                FnArg::Receiver(_) => false,
+            });
+            if reads_body {
+                self.body_extractor_line = Some(item_fn.span().start().line);
+            }
+        }
+        visit::visit_item_fn(self, item_fn);
+    }
+
+    fn visit_item_struct(&mut self, item_struct: &'ast ItemStruct) {
+        if let Fields::Named(fields) = &item_struct.fields {
+            for field in &fields.named {
+                let is_password: bool = field
+                    .ident
+                    .as_ref()
+                    .is_some_and(|ident| ident.to_string().to_lowercase().contains("password"));
+                if is_password && self.password_field_line.is_none() {
+                    self.password_field_line = Some(field.span().start().line);
+                }
+            }
+        }
+        visit::visit_item_struct(self, item_struct);
+    }
+
+    fn visit_expr_method_call(&mut self, method_call: &'ast ExprMethodCall) {
+        let line: usize = method_call.method.span().start().line;
+        match method_call.method.to_string().as_str() {
+            "allow_any_origin" | "send_wildcard" => self.findings.push(SecurityFinding::new(
+                SecurityCheck::CorsOrigins,
+                line,
+                format!("CORS is configured with `{}`", method_call.method),
+                self.framework,
+            )),
+            // tower-http's `CorsLayer::new().allow_origin(Any)`
+            "allow_origin" if method_call.args.iter().any(is_any_origin) => {
+                self.findings.push(SecurityFinding::new(
+                    SecurityCheck::CorsOrigins,
+                    line,
+                    "CORS is configured with `allow_origin(Any)`, which allows every origin"
+                        .to_string(),
+                    self.framework,
+                ))
+            }
+            "body" | "json" => {
+                let mut leak_detector: ErrorLeakDetector = ErrorLeakDetector::default();
+                for arg in &method_call.args {
+                    leak_detector.visit_expr(arg);
+                }
+                if leak_detector.leaks {
+                    self.findings.push(SecurityFinding::new(
+                        SecurityCheck::ErrorLeakage,
+                        line,
+                        "Error details are sent to the client in a response body".to_string(),
+                        self.framework,
+                    ));
+                }
+            }
+            _ => {}
+        }
+        visit::visit_expr_method_call(self, method_call);
+    }
+
+    fn visit_expr_path(&mut self, expr_path: &'ast ExprPath) {
+        let segments: Vec<String> = expr_path
+            .path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect();
+        if let [.., owner, constructor] = segments.as_slice() {
+            if PERMISSIVE_CORS.contains(&(owner.as_str(), constructor.as_str())) {
+                self.findings.push(SecurityFinding::new(
+                    SecurityCheck::CorsOrigins,
+                    expr_path.span().start().line,
+                    format!(
+                        "CORS is configured with `{}::{}()`, which allows every origin",
+                        owner, constructor
+                    ),
+                    self.framework,
+                ));
+            }
+        }
+        visit::visit_expr_path(self, expr_path);
+    }
+}
+
+/// Checks whether an `allow_origin(...)` argument is tower-http's `Any`.
+fn is_any_origin(arg: &Expr) -> bool {
+    match arg {
+        Expr::Path(ExprPath { path, .. }) => path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Any"),
+        _ => false,
+    }
+}
+
+/// Detects error values and debug formatting inside a response body expression.
+#[derive(Default)]
+struct ErrorLeakDetector {
+    leaks: bool,
+}
+
+impl<'ast> Visit<'ast> for ErrorLeakDetector {
+    fn visit_expr_path(&mut self, expr_path: &'ast ExprPath) {
+        if expr_path
+            .path
+            .get_ident()
+            .is_some_and(|ident| ERROR_NAMES.contains(&ident.to_string().as_str()))
+        {
+            self.leaks = true;
+        }
+        visit::visit_expr_path(self, expr_path);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        let tokens: String = mac.tokens.to_string();
+        let has_error_arg: bool = mac.tokens.clone().into_iter().any(|token| {
+            matches!(token, proc_macro2::TokenTree::Ident(ident) if ERROR_NAMES.contains(&ident.to_string().as_str()))
+        });
+        let has_inline_error: bool = ERROR_NAMES.iter().any(|name| {
+            tokens.contains(&format!("{{{}}}", name)) || tokens.contains(&format!("{{{}:", name))
+        });
+        if tokens.contains(":?") || has_error_arg || has_inline_error {
+            self.leaks = true;
+        }
+        visit::visit_macro(self, mac);
+    }
+}
@@ -0,0 +1,528 @@
+use crate::helpers::egress_proxy::{hosts_from_urls, EgressProxy};
+use crate::helpers::general::{
+    get_openapi_document_path, get_web_server_data_path, get_web_server_program_path,
+    get_web_server_project_path, get_web_server_static_path,
+};
+use crate::helpers::sandbox::{SandboxBackend, SandboxPolicy};
+use crate::helpers::socket_bridge::{HostBridge, NetworkBridge};
+use crate::helpers::target_framework::TargetFramework;
+use serde::Serialize;
+use std::io::{BufRead, BufReader, Read};
+use std::net::{TcpListener, TcpStream};
+use std::path::Path;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::{Arc, Mutex, Weak};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use tokio::time;
+
+/// Environment variable the generated web server reads its port from.
+pub const WEB_SERVER_PORT_ENV: &str = "WEB_SERVER_PORT";
+
+/// Environment variable the generated web server reads the address it binds to from.
+pub const WEB_SERVER_HOST_ENV: &str = "WEB_SERVER_HOST";
+
+/// Environment variable the generated web server reads its static files directory from.
+pub const WEB_SERVER_STATIC_DIR_ENV: &str = "WEB_SERVER_STATIC_DIR";
+
+/// Environment variable the generated web server reads the path of its OpenAPI document from.
+pub const WEB_SERVER_OPENAPI_PATH_ENV: &str = "WEB_SERVER_OPENAPI_PATH";
+
+/// How long to wait for a freshly spawned web server to accept connections.
+pub const SERVER_READY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long the endpoint tests may run against a web server before it is stopped.
+pub const ENDPOINT_TESTS_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// How many lines of server output are attached to bug reports.
+pub const SERVER_LOG_TAIL_LINES: usize = 40;
+
+/// How long to wait for the output pipes of a stopped web server to close before its logs are reported.
+const LOG_READERS_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Web servers that are currently running, so they can be stopped on Ctrl-C.
+static RUNNING_SERVERS: Mutex<Vec<Weak<Mutex<Child>>>> = Mutex::new(Vec::new());
+
+/// Asks the OS for a free port on the loopback interface.
+///
+/// # Returns
+/// A port number that was free at the time of the call.
+pub fn allocate_free_port() -> std::io::Result<u16> {
+    let listener: TcpListener = TcpListener::bind(("127.0.0.1", 0))?;
+    Ok(listener.local_addr()?.port())
+}
+
+/// Reasons a spawned web server never became ready.
+#[derive(Debug)]
+pub enum ServerStartError {
+    /// The process exited before it accepted any connection.
+    Exited(ExitStatus),
+    /// The port did not accept connections before the timeout expired.
+    Timeout(Duration),
+    /// The process status could not be queried.
+    Io(std::io::Error),
+}
+
+impl std::fmt::Display for ServerStartError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ServerStartError::Exited(status) => {
+                write!(
+                    f,
+                    "Web server exited before accepting connections ({})",
+                    status
+                )
+            }
+            ServerStartError::Timeout(timeout) => write!(
+                f,
+                "Web server did not accept connections within {} seconds",
+                timeout.as_secs()
+            ),
+            ServerStartError::Io(e) => write!(f, "Unable to query web server process: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ServerStartError {}
+
+/// Reasons a sandboxed web server could not be started for a test run.
+#[derive(Debug)]
+pub enum ServerLaunchError {
+    /// The web server or its egress proxy could not be spawned, a problem with the host rather than the code.
+    Spawn(std::io::Error),
+    /// The web server was spawned but never became ready, it has been stopped and its logs are in the report.
+    NotReady(ServerStartError, ServerRunReport),
+}
+
+impl std::fmt::Display for ServerLaunchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ServerLaunchError::Spawn(e) => write!(f, "Unable to start the web server: {}", e),
+            ServerLaunchError::NotReady(start_error, _) => write!(f, "{}", start_error),
+        }
+    }
+}
+
+impl std::error::Error for ServerLaunchError {}
+
+/// What happened to a test server during one run, attached to the run report.
+///
+/// # Fields
+/// - `port`: The port the web server was asked to bind to.
+/// - `outcome`: A short description of how the run ended.
+/// - `exit_status`: How the process exited on its own, if it did before being stopped.
+/// - `stdout`: Everything the web server printed to stdout.
+/// - `stderr`: Everything the web server printed to stderr.
+#[derive(Debug, Serialize, Clone)]
+pub struct ServerRunReport {
+    pub port: u16,
+    pub outcome: String,
+    pub exit_status: Option<String>,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl ServerRunReport {
+    /// Returns the last lines of the combined server output.
+    pub fn log_tail(&self) -> String {
+        tail_lines(
+            &format!("{}{}", self.stdout, self.stderr),
+            SERVER_LOG_TAIL_LINES,
+        )
+    }
+}
+
+/// Owns a spawned web server and guarantees it is killed and reaped.
+///
+/// The process is stopped when the guard is dropped (including while unwinding from a panic)
+/// and when the user presses Ctrl-C, see [`install_ctrl_c_handler`].
+/// A server in a sandbox with a network of its own is reached through its [`HostBridge`], owned by the guard.
+#[derive(Debug)]
+pub struct TestServerGuard {
+    child: Arc<Mutex<Child>>,
+    logs: Option<ServerLogs>,
+    port: u16,
+    bridge: Option<HostBridge>,
+}
+
+impl TestServerGuard {
+    /// Spawns the web server with its stdout and stderr captured.
+    ///
+    /// The server leads a process group of its own, so the wrappers, workers and reloaders it starts are
+    /// stopped with it.
+    ///
+    /// # Arguments
+    /// - `command`: The command starting the web server.
+    /// - `port`: The port passed to the web server through `WEB_SERVER_PORT`.
+    pub fn spawn(command: &mut Command, port: u16) -> std::io::Result<Self> {
+        #[cfg(unix)]
+        std::os::unix::process::CommandExt::process_group(command, 0);
+
+        let mut child: Child = command
+            .env(WEB_SERVER_HOST_ENV, "127.0.0.1")
+            .env(WEB_SERVER_PORT_ENV, port.to_string())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let logs: ServerLogs = ServerLogs::capture(&mut child);
+
+        let child: Arc<Mutex<Child>> = Arc::new(Mutex::new(child));
+        let mut running_servers = RUNNING_SERVERS.lock().unwrap();
+        running_servers.retain(|server| server.strong_count() > 0);
+        running_servers.push(Arc::downgrade(&child));
+
+        Ok(Self {
+            child,
+            logs: Some(logs),
+            port,
+            bridge: None,
+        })
+    }
+
+    /// Attaches the bridge the web server is reached through, keeping it open while the server runs.
+    ///
+    /// # Arguments
+    /// - `bridge`: The host side of the sandbox network bridge.
+    pub fn with_bridge(mut self, bridge: Option<HostBridge>) -> Self {
+        self.bridge = bridge;
+        self
+    }
+
+    /// The port the web server was asked to bind to.
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Returns the exit status if the web server has already exited on its own.
+    pub fn exit_status(&self) -> Option<ExitStatus> {
+        self.child.lock().unwrap().try_wait().ok().flatten()
+    }
+
+    /// Polls until the server accepts connections, the process exits or the timeout expires.
+    ///
+    /// # Arguments
+    /// - `timeout`: How long to keep polling.
+    pub async fn wait_until_ready(&self, timeout: Duration) -> Result<(), ServerStartError> {
+        let started: Instant = Instant::now();
+        loop {
+            let status: Option<ExitStatus> = self
+                .child
+                .lock()
+                .unwrap()
+                .try_wait()
+                .map_err(ServerStartError::Io)?;
+            if let Some(status) = status {
+                return Err(ServerStartError::Exited(status));
+            }
+
+            let ready: bool = match &self.bridge {
+                Some(bridge) => bridge.is_server_ready(),
+                None => TcpStream::connect(("127.0.0.1", self.port)).is_ok(),
+            };
+            if ready {
+                return Ok(());
+            }
+
+            if started.elapsed() >= timeout {
+                return Err(ServerStartError::Timeout(timeout));
+            }
+
+            time::sleep(Duration::from_millis(200)).await;
+        }
+    }
+
+    /// Kills and reaps the web server and returns what it printed.
+    ///
+    /// # Arguments
+    /// - `outcome`: A short description of how the run ended, stored in the report.
+    pub fn stop(mut self, outcome: &str) -> ServerRunReport {
+        let exit_status: Option<ExitStatus> = self.exit_status();
+        kill_and_reap(&self.child);
+
+        let (stdout, stderr) = self
+            .logs
+            .take()
+            .map(ServerLogs::collect)
+            .unwrap_or_default();
+
+        ServerRunReport {
+            port: self.port,
+            outcome: outcome.to_string(),
+            exit_status: exit_status.map(|status| status.to_string()),
+            stdout,
+            stderr,
+        }
+    }
+}
+
+impl Drop for TestServerGuard {
+    fn drop(&mut self) {
+        kill_and_reap(&self.child);
+    }
+}
+
+/// Starts the built web server inside the run sandbox on a free port.
+///
+/// Outgoing traffic goes through an egress proxy allowing only the hosts of `external_urls`. Under bubblewrap
+/// the server has a loopback-only network, the proxy and its own port being forwarded by a [`HostBridge`].
+///
+/// # Arguments
+/// - `framework`: The framework the web server is written with, picking the program and its arguments.
+/// - `external_urls`: The verified external urls the web server may call.
+///
+/// # Returns
+/// The guard owning the web server and the egress proxy, which must outlive the server.
+pub async fn spawn_sandboxed_web_server(
+    framework: TargetFramework,
+    external_urls: &[String],
+) -> std::io::Result<(TestServerGuard, EgressProxy)> {
+    let egress_proxy: EgressProxy = EgressProxy::start(hosts_from_urls(external_urls)).await?;
+    let port: u16 = allocate_free_port()?;
+    let host_bridge: Option<HostBridge> = match SandboxBackend::detect() {
+        SandboxBackend::Bubblewrap => Some(HostBridge::start(port, egress_proxy.port()).await?),
+        SandboxBackend::Rlimits | SandboxBackend::Unsandboxed => None,
+    };
+    let network_bridge: Option<NetworkBridge> =
+        host_bridge.as_ref().map(|bridge| bridge.bridge().clone());
+    let run_policy: SandboxPolicy = SandboxPolicy::for_run(
+        Path::new(&get_web_server_project_path()),
+        Path::new(&get_web_server_data_path()),
+        egress_proxy.url(),
+        network_bridge,
+    );
+
+    let run_args: Vec<String> = framework.run_args(&get_web_server_project_path(), port);
+    let run_args: Vec<&str> = run_args.iter().map(String::as_str).collect();
+    let mut command: Command =
+        run_policy.command(&get_web_server_program_path(framework), &run_args);
+    // The project is read-only inside the sandbox, Python must not try to cache bytecode there
+    command.env("PYTHONDONTWRITEBYTECODE", "1");
+    command.env(WEB_SERVER_STATIC_DIR_ENV, get_web_server_static_path());
+    command.env(WEB_SERVER_OPENAPI_PATH_ENV, get_openapi_document_path());
+    let test_server: TestServerGuard =
+        TestServerGuard::spawn(&mut command, port)?.with_bridge(host_bridge);
+
+    Ok((test_server, egress_proxy))
+}
+
+/// Starts the built web server inside the run sandbox, see [`spawn_sandboxed_web_server`], and waits until it
+/// accepts connections.
+///
+/// # Arguments
+/// - `framework`: The framework the web server is written with, picking the program and its arguments.
+/// - `external_urls`: The verified external urls the web server may call.
+///
+/// # Returns
+/// The guard owning the ready web server and the egress proxy, which must outlive the server, or why it could
+/// not be started. A server that never became ready is stopped before returning.
+pub async fn start_sandboxed_web_server(
+    framework: TargetFramework,
+    external_urls: &[String],
+) -> Result<(TestServerGuard, EgressProxy), ServerLaunchError> {
+    let (test_server, egress_proxy): (TestServerGuard, EgressProxy) =
+        spawn_sandboxed_web_server(framework, external_urls)
+            .await
+            .map_err(ServerLaunchError::Spawn)?;
+    if let Err(start_error) = test_server.wait_until_ready(SERVER_READY_TIMEOUT).await {
+        let server_report: ServerRunReport = test_server.stop(start_error.to_string().as_str());
+        return Err(ServerLaunchError::NotReady(start_error, server_report));
+    }
+    Ok((test_server, egress_proxy))
+}
+
+/// Kills the process group of the process, then waits for the process so no zombie is left behind.
+///
+/// The group is killed even when the process already exited, as processes it started may still hold its
+/// output pipes open.
+fn kill_and_reap(child: &Mutex<Child>) {
+    let mut child = match child.lock() {
+        Ok(child) => child,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    kill_process_group(&child);
+    if let Ok(None) = child.try_wait() {
+        let _ = child.kill();
+    }
+    let _ = child.wait();
+}
+
+/// Sends `SIGKILL` to the process group led by the process, see [`TestServerGuard::spawn`].
+#[cfg(unix)]
+fn kill_process_group(child: &Child) {
+    // SAFETY: `kill` has no memory safety requirements; a group that is already gone is reported as an error.
+    unsafe {
+        libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL);
+    }
+}
+
+#[cfg(not(unix))]
+fn kill_process_group(_child: &Child) {}
+
+/// Stops every running web server and exits when the user presses Ctrl-C.
+///
+/// Must be called from within the Tokio runtime, before any web server is spawned.
+pub fn install_ctrl_c_handler() {
+    tokio::spawn(async {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            let running_servers = match RUNNING_SERVERS.lock() {
+                Ok(running_servers) => running_servers,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            for server in running_servers.iter().filter_map(Weak::upgrade) {
+                kill_and_reap(&server);
+            }
+            std::process::exit(130);
+        }
+    });
+}
+
+/// Collects the stdout and stderr of a spawned process on background threads,
+/// so the pipes never fill up and the output can be reported later.
+#[derive(Debug)]
+pub struct ServerLogs {
+    stdout: Arc<Mutex<String>>,
+    stderr: Arc<Mutex<String>>,
+    readers: Vec<JoinHandle<()>>,
+}
+
+impl ServerLogs {
+    /// Starts collecting the piped stdout and stderr of `child`.
+    ///
+    /// The process must have been spawned with `Stdio::piped()` for both streams.
+    pub fn capture(child: &mut Child) -> Self {
+        let stdout: Arc<Mutex<String>> = Arc::new(Mutex::new(String::new()));
+        let stderr: Arc<Mutex<String>> = Arc::new(Mutex::new(String::new()));
+        let mut readers: Vec<JoinHandle<()>> = vec![];
+
+        if let Some(pipe) = child.stdout.take() {
+            readers.push(spawn_reader(pipe, Arc::clone(&stdout)));
+        }
+        if let Some(pipe) = child.stderr.take() {
+            readers.push(spawn_reader(pipe, Arc::clone(&stderr)));
+        }
+
+        Self {
+            stdout,
+            stderr,
+            readers,
+        }
+    }
+
+    /// Waits for both streams to close and returns everything the process printed.
+    ///
+    /// Call this only after the process has exited or been killed. A stream a process outside its group
+    /// still holds open is given up after [`LOG_READERS_TIMEOUT`], returning what was printed so far.
+    ///
+    /// # Returns
+    /// The captured stdout and stderr.
+    pub fn collect(self) -> (String, String) {
+        let deadline: Instant = Instant::now() + LOG_READERS_TIMEOUT;
+        for reader in self.readers {
+            while !reader.is_finished() && Instant::now() < deadline {
+                std::thread::sleep(Duration::from_millis(20));
+            }
+            if reader.is_finished() {
+                let _ = reader.join();
+            }
+        }
+        let stdout: String = self.stdout.lock().unwrap().clone();
+        let stderr: String = self.stderr.lock().unwrap().clone();
+        (stdout, stderr)
+    }
+}
+
+fn spawn_reader<R: Read + Send + 'static>(pipe: R, buffer: Arc<Mutex<String>>) -> JoinHandle<()> {
+    std::thread::spawn(move || {
+        for line in BufReader::new(pipe).lines().map_while(Result::ok) {
+            let mut buffer = buffer.lock().unwrap();
+            buffer.push_str(&line);
+            buffer.push('\n');
+        }
+    })
+}
+
+/// Returns the last `lines` lines of `text`.
+pub fn tail_lines(text: &str, lines: usize) -> String {
+    let all_lines: Vec<&str> = text.lines().collect();
+    let start: usize = all_lines.len().saturating_sub(lines);
+    all_lines[start..].join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_the_last_lines_of_the_server_output() {
+        assert_eq!(tail_lines("a\nb\nc\n", 2), "b\nc");
+        assert_eq!(tail_lines("a\nb", 5), "a\nb");
+        assert_eq!(tail_lines("", 5), "");
+
+        let report: ServerRunReport = ServerRunReport {
+            port: 8080,
+            outcome: "Crashed during endpoint tests".to_string(),
+            exit_status: Some("exit status: 101".to_string()),
+            stdout: (1..=SERVER_LOG_TAIL_LINES)
+                .map(|line| format!("request {}\n", line))
+                .collect(),
+            stderr: "thread 'actix-rt' panicked\n".to_string(),
+        };
+        let log_tail: String = report.log_tail();
+        assert_eq!(log_tail.lines().count(), SERVER_LOG_TAIL_LINES);
+        assert!(!log_tail.contains("request 1\n"));
+        assert!(log_tail.ends_with("request 40\nthread 'actix-rt' panicked"));
+    }
+
+    fn spawn_shell(script: &str) -> TestServerGuard {
+        TestServerGuard::spawn(Command::new("sh").args(["-c", script]), 0).unwrap()
+    }
+
+    #[test]
+    fn stops_the_server_and_reports_its_output() {
+        let test_server: TestServerGuard =
+            spawn_shell("echo \"listening on $WEB_SERVER_HOST\"; echo warming up >&2; sleep 30");
+        std::thread::sleep(Duration::from_millis(300));
+        assert!(test_server.exit_status().is_none());
+
+        let report: ServerRunReport = test_server.stop("Stopped after endpoint tests");
+        assert_eq!(report.outcome, "Stopped after endpoint tests");
+        assert_eq!(report.exit_status, None);
+        assert_eq!(report.stdout, "listening on 127.0.0.1\n");
+        assert_eq!(report.stderr, "warming up\n");
+    }
+
+    #[test]
+    fn reports_how_a_crashed_server_exited() {
+        let test_server: TestServerGuard = spawn_shell("echo panicked >&2; exit 101");
+        std::thread::sleep(Duration::from_millis(300));
+
+        let report: ServerRunReport = test_server.stop("Crashed during endpoint tests");
+        assert!(report.exit_status.unwrap().contains("101"));
+        assert_eq!(report.stderr, "panicked\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn stops_the_processes_the_server_started() {
+        // The background `sleep` inherits stdout and would keep it open after the shell is killed
+        let test_server: TestServerGuard = spawn_shell("sleep 30 & echo started; wait");
+        std::thread::sleep(Duration::from_millis(300));
+
+        let started: Instant = Instant::now();
+        let report: ServerRunReport = test_server.stop("Stopped after endpoint tests");
+        assert!(started.elapsed() < LOG_READERS_TIMEOUT);
+        assert_eq!(report.stdout, "started\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn gives_up_on_pipes_held_outside_the_process_group() {
+        let test_server: TestServerGuard = spawn_shell("setsid sleep 10 & echo started; wait");
+        std::thread::sleep(Duration::from_millis(300));
+
+        let started: Instant = Instant::now();
+        let report: ServerRunReport = test_server.stop("Stopped after endpoint tests");
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(report.stdout, "started\n");
+    }
+}
@@ -0,0 +1,290 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use tokio::task::JoinHandle;
+#[cfg(unix)]
+use {
+    std::process::ExitStatus,
+    std::time::Duration,
+    tokio::io::copy_bidirectional,
+    tokio::net::{TcpListener, TcpStream, UnixListener, UnixStream},
+    tokio::process::{Child, Command},
+    tokio::time,
+};
+
+/// The hidden command the sandbox runs to connect the web server's private network to the host.
+pub const SANDBOX_BRIDGE_COMMAND: &str = "sandbox-bridge";
+/// Unix socket the host connects to, forwarded to the web server's port inside the sandbox.
+const SERVER_SOCKET: &str = "server.sock";
+/// Unix socket the sandbox connects to, forwarded to the egress proxy on the host.
+const PROXY_SOCKET: &str = "proxy.sock";
+
+/// The addresses a sandboxed web server is reached at and reaches the egress proxy at.
+///
+/// Inside the sandbox only loopback exists, so both ports are forwarded through Unix sockets in `socket_dir`,
+/// the only way in or out of the sandbox's network.
+///
+/// # Fields
+/// - `socket_dir`: The directory holding the Unix sockets, bound into the sandbox.
+/// - `server_port`: The port the web server listens on, on the host and inside the sandbox.
+/// - `proxy_port`: The port of the egress proxy, on the host and inside the sandbox.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkBridge {
+    pub socket_dir: PathBuf,
+    pub server_port: u16,
+    pub proxy_port: u16,
+}
+
+impl NetworkBridge {
+    /// Returns the arguments running `program` with `args` behind the bridge, after the bridge command itself.
+    ///
+    /// # Arguments
+    /// - `program`: The web server program.
+    /// - `args`: Arguments passed to the program.
+    pub fn command_args(&self, program: &str, args: &[&str]) -> Vec<String> {
+        let mut command_args: Vec<String> = vec![
+            SANDBOX_BRIDGE_COMMAND.to_string(),
+            self.socket_dir.to_string_lossy().to_string(),
+            self.server_port.to_string(),
+            self.proxy_port.to_string(),
+            program.to_string(),
+        ];
+        command_args.extend(args.iter().map(|arg| arg.to_string()));
+        command_args
+    }
+
+    fn server_socket(&self) -> PathBuf {
+        self.socket_dir.join(SERVER_SOCKET)
+    }
+
+    fn proxy_socket(&self) -> PathBuf {
+        self.socket_dir.join(PROXY_SOCKET)
+    }
+}
+
+/// The host side of a [`NetworkBridge`]: accepts connections to the web server's port on the host loopback
+/// and serves the egress proxy to the sandbox.
+///
+/// The forwarding stops and the sockets are removed when it is dropped.
+#[derive(Debug)]
+pub struct HostBridge {
+    bridge: NetworkBridge,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+#[cfg(unix)]
+impl HostBridge {
+    /// Starts forwarding the web server's port and the egress proxy through a fresh socket directory.
+    ///
+    /// # Arguments
+    /// - `server_port`: The free port the web server is reached at from the host.
+    /// - `proxy_port`: The port of the egress proxy on the host.
+    pub async fn start(server_port: u16, proxy_port: u16) -> io::Result<Self> {
+        let socket_dir: PathBuf = std::env::temp_dir().join(format!(
+            "web_server_bridge_{}_{}",
+            std::process::id(),
+            server_port
+        ));
+        let _ = fs::remove_dir_all(&socket_dir);
+        fs::create_dir_all(&socket_dir)?;
+        let bridge: NetworkBridge = NetworkBridge {
+            socket_dir,
+            server_port,
+            proxy_port,
+        };
+
+        let server_listener: TcpListener = TcpListener::bind(("127.0.0.1", server_port)).await?;
+        let proxy_listener: UnixListener = UnixListener::bind(bridge.proxy_socket())?;
+        let tasks: Vec<JoinHandle<()>> = vec![
+            tokio::spawn(forward_tcp_to_unix(server_listener, bridge.server_socket())),
+            tokio::spawn(forward_unix_to_tcp(proxy_listener, proxy_port)),
+        ];
+
+        Ok(Self { bridge, tasks })
+    }
+
+    /// The addresses the sandbox side of the bridge is started with.
+    pub fn bridge(&self) -> &NetworkBridge {
+        &self.bridge
+    }
+
+    /// Whether the web server accepts connections: the sandbox side only opens the server socket once it does.
+    pub fn is_server_ready(&self) -> bool {
+        std::os::unix::net::UnixStream::connect(self.bridge.server_socket()).is_ok()
+    }
+}
+
+impl Drop for HostBridge {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+        let _ = fs::remove_dir_all(&self.bridge.socket_dir);
+    }
+}
+
+/// Bubblewrap only exists on Linux, so there is no sandbox to bridge into elsewhere.
+#[cfg(not(unix))]
+impl HostBridge {
+    pub async fn start(_server_port: u16, _proxy_port: u16) -> io::Result<Self> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "Unix sockets are not available",
+        ))
+    }
+
+    pub fn bridge(&self) -> &NetworkBridge {
+        &self.bridge
+    }
+
+    pub fn is_server_ready(&self) -> bool {
+        false
+    }
+}
+
+/// Runs the sandbox side of a [`NetworkBridge`], started by the sandbox through [`SANDBOX_BRIDGE_COMMAND`].
+///
+/// Serves the egress proxy on its port on the sandbox loopback, starts the web server and, once the server
+/// accepts connections, opens the server socket for the host.
+///
+/// # Arguments
+/// - `args`: The arguments after the bridge command: socket dir, server port, proxy port, program and its arguments.
+///
+/// # Returns
+/// The exit code of the web server, passed on as the exit code of the sandbox.
+#[cfg(unix)]
+pub async fn run_sandbox_bridge(args: &[String]) -> i32 {
+    let (bridge, program, program_args) = match args {
+        [socket_dir, server_port, proxy_port, program, program_args @ ..] => {
+            match (server_port.parse::<u16>(), proxy_port.parse::<u16>()) {
+                (Ok(server_port), Ok(proxy_port)) => (
+                    NetworkBridge {
+                        socket_dir: PathBuf::from(socket_dir),
+                        server_port,
+                        proxy_port,
+                    },
+                    program,
+                    program_args,
+                ),
+                _ => return bridge_usage_error(),
+            }
+        }
+        _ => return bridge_usage_error(),
+    };
+
+    match TcpListener::bind(("127.0.0.1", bridge.proxy_port)).await {
+        Ok(proxy_listener) => {
+            tokio::spawn(forward_tcp_to_unix(proxy_listener, bridge.proxy_socket()));
+        }
+        Err(e) => eprintln!("Sandbox bridge: unable to serve the egress proxy: {}", e),
+    }
+
+    let mut server: Child = match Command::new(program)
+        .args(program_args)
+        .kill_on_drop(true)
+        .spawn()
+    {
+        Ok(server) => server,
+        Err(e) => {
+            eprintln!("Sandbox bridge: unable to start {}: {}", program, e);
+            return 127;
+        }
+    };
+    tokio::spawn(open_server_socket(bridge));
+
+    match server.wait().await {
+        Ok(status) => exit_code(status),
+        Err(_) => 1,
+    }
+}
+
+#[cfg(not(unix))]
+pub async fn run_sandbox_bridge(_args: &[String]) -> i32 {
+    bridge_usage_error()
+}
+
+fn bridge_usage_error() -> i32 {
+    eprintln!(
+        "Usage: {} <socket dir> <server port> <proxy port> <program> [args...]",
+        SANDBOX_BRIDGE_COMMAND
+    );
+    2
+}
+
+/// Waits until the web server accepts connections, then forwards the server socket to it.
+#[cfg(unix)]
+async fn open_server_socket(bridge: NetworkBridge) {
+    while TcpStream::connect(("127.0.0.1", bridge.server_port))
+        .await
+        .is_err()
+    {
+        time::sleep(Duration::from_millis(100)).await;
+    }
+    match UnixListener::bind(bridge.server_socket()) {
+        Ok(server_listener) => forward_unix_to_tcp(server_listener, bridge.server_port).await,
+        Err(e) => eprintln!("Sandbox bridge: unable to open the server socket: {}", e),
+    }
+}
+
+/// Forwards every connection accepted on a TCP port to a Unix socket.
+#[cfg(unix)]
+async fn forward_tcp_to_unix(listener: TcpListener, socket_path: PathBuf) {
+    while let Ok((mut client, _)) = listener.accept().await {
+        let socket_path: PathBuf = socket_path.clone();
+        tokio::spawn(async move {
+            if let Ok(mut upstream) = UnixStream::connect(&socket_path).await {
+                let _ = copy_bidirectional(&mut client, &mut upstream).await;
+            }
+        });
+    }
+}
+
+/// Forwards every connection accepted on a Unix socket to a port on the loopback interface.
+#[cfg(unix)]
+async fn forward_unix_to_tcp(listener: UnixListener, port: u16) {
+    while let Ok((mut client, _)) = listener.accept().await {
+        tokio::spawn(async move {
+            if let Ok(mut upstream) = TcpStream::connect(("127.0.0.1", port)).await {
+                let _ = copy_bidirectional(&mut client, &mut upstream).await;
+            }
+        });
+    }
+}
+
+/// Converts an exit status to a shell style exit code, `128 + signal` for a killed process.
+#[cfg(unix)]
+fn exit_code(status: ExitStatus) -> i32 {
+    use std::os::unix::process::ExitStatusExt;
+
+    status
+        .code()
+        .or_else(|| status.signal().map(|signal| 128 + signal))
+        .unwrap_or(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn command_args_put_the_bridge_before_the_program() {
+        let bridge: NetworkBridge = NetworkBridge {
+            socket_dir: PathBuf::from("/tmp/bridge"),
+            server_port: 8080,
+            proxy_port: 3128,
+        };
+
+        assert_eq!(
+            bridge.command_args("python", &["-m", "uvicorn"]),
+            vec![
+                SANDBOX_BRIDGE_COMMAND,
+                "/tmp/bridge",
+                "8080",
+                "3128",
+                "python",
+                "-m",
+                "uvicorn"
+            ]
+        );
+    }
+}
@@ -0,0 +1,268 @@
+use crate::models::agents::agent_traits::{
+    DomainModel, EntitySchema, FieldSchema, FieldType, FieldValidation, RelationKind,
+};
+use serde::{Deserialize, Serialize};
+
+/// The file name of the migration creating the tables of the domain model.
+const INITIAL_MIGRATION_FILE: &str = "0001_create_tables.sql";
+
+/// A SQL migration of the generated web server, applied once on startup in file name order.
+///
+/// # Fields
+/// - `file_name`: The file name inside the migrations directory, e.g. `0001_create_tables.sql`.
+/// - `sql`: The SQL statements of the migration.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MigrationFile {
+    pub file_name: String,
+    pub sql: String,
+}
+
+/// Renders the SQLite migrations creating one table per entity of the domain model.
+///
+/// Columns keep the field names, required fields are `NOT NULL`, validation rules and unique keys
+/// become constraints and `BelongsTo` relations become indexed foreign keys.
+///
+/// # Arguments
+/// - `domain_model`: The domain model the tables are created from.
+///
+/// # Returns
+/// The migrations in the order they are applied.
+pub fn render_migrations(domain_model: &DomainModel) -> Vec<MigrationFile> {
+    let mut sql: String =
+        "-- Generated from the domain model. Add new migrations instead of editing applied ones.\n"
+            .to_string();
+    for entity in &domain_model.entities {
+        sql.push('\n');
+        sql.push_str(&render_table(entity, domain_model));
+    }
+    vec![MigrationFile {
+        file_name: INITIAL_MIGRATION_FILE.to_string(),
+        sql,
+    }]
+}
+
+/// Returns the table name of an entity, its name in snake case, e.g. `task_list` for `TaskList`.
+pub fn table_name(entity_name: &str) -> String {
+    let mut name: String = String::new();
+    let mut previous_lowercase: bool = false;
+    for c in entity_name.chars() {
+        if c.is_ascii_uppercase() {
+            if previous_lowercase {
+                name.push('_');
+            }
+            name.push(c.to_ascii_lowercase());
+            previous_lowercase = false;
+        } else if c.is_ascii_alphanumeric() {
+            name.push(c);
+            previous_lowercase = true;
+        } else {
+            if !name.ends_with('_') && !name.is_empty() {
+                name.push('_');
+            }
+            previous_lowercase = false;
+        }
+    }
+    name.trim_end_matches('_').to_string()
+}
+
+/// Renders the `CREATE TABLE` statement of an entity and the indexes of its foreign keys.
+fn render_table(entity: &EntitySchema, domain_model: &DomainModel) -> String {
+    let table: String = table_name(&entity.name);
+    let mut definitions: Vec<String> = entity
+        .fields
+        .iter()
+        .map(|field| render_column(field, field.name == entity.primary_key))
+        .collect();
+
+    for unique_key in &entity.unique_keys {
+        if unique_key.is_empty() || unique_key == std::slice::from_ref(&entity.primary_key) {
+            continue;
+        }
+        let columns: Vec<String> = unique_key
+            .iter()
+            .map(|name| quote_identifier(name))
+            .collect();
+        definitions.push(format!("UNIQUE ({})", columns.join(", ")));
+    }
+
+    let foreign_keys: Vec<(&str, &EntitySchema)> = entity
+        .relations
+        .iter()
+        .filter(|relation| relation.kind == RelationKind::BelongsTo)
+        .filter_map(|relation| {
+            domain_model
+                .entities
+                .iter()
+                .find(|target| target.name == relation.target)
+                .map(|target| (relation.field.as_str(), target))
+        })
+        .collect();
+    for (field, target) in &foreign_keys {
+        definitions.push(format!(
+            "FOREIGN KEY ({}) REFERENCES {} ({}) ON DELETE CASCADE",
+            quote_identifier(field),
+            quote_identifier(&table_name(&target.name)),
+            quote_identifier(&target.primary_key)
+        ));
+    }
+
+    let mut sql: String = format!(
+        "CREATE TABLE {} (\n    {}\n);\n",
+        quote_identifier(&table),
+        definitions.join(",\n    ")
+    );
+    for (field, _) in &foreign_keys {
+        sql.push_str(&format!(
+            "CREATE INDEX {} ON {} ({});\n",
+            quote_identifier(&format!("idx_{}_{}", table, field)),
+            quote_identifier(&table),
+            quote_identifier(field)
+        ));
+    }
+    sql
+}
+
+/// Renders a column definition with the constraints of the field's validation rules.
+///
+/// An integer primary key is the SQLite rowid, so the database assigns it when an insert leaves it out.
+fn render_column(field: &FieldSchema, is_primary_key: bool) -> String {
+    let column: String = quote_identifier(&field.name);
+    let sql_type: &str = match field.field_type {
+        FieldType::Integer | FieldType::Bool => "INTEGER",
+        FieldType::Float => "REAL",
+        // Date times are stored as RFC 3339 text, which sorts chronologically
+        FieldType::String | FieldType::DateTime => "TEXT",
+    };
+    let mut definition: String = format!("{} {}", column, sql_type);
+    if is_primary_key {
+        definition.push_str(" PRIMARY KEY");
+        // SQLite allows null in other primary keys
+        if field.field_type != FieldType::Integer {
+            definition.push_str(" NOT NULL");
+        }
+        return definition;
+    }
+    if field.required {
+        definition.push_str(" NOT NULL");
+    }
+
+    let mut checks: Vec<String> = validation_checks(&column, &field.validation);
+    if field.field_type == FieldType::Bool {
+        checks.push(format!("{} IN (0, 1)", column));
+    }
+    if !checks.is_empty() {
+        definition.push_str(&format!(" CHECK ({})", checks.join(" AND ")));
+    }
+    definition
+}
+
+/// Turns the validation rules of a field into SQL conditions on its column.
+fn validation_checks(column: &str, validation: &FieldValidation) -> Vec<String> {
+    let mut checks: Vec<String> = vec![];
+    if let Some(min_length) = validation.min_length {
+        checks.push(format!("length({}) >= {}", column, min_length));
+    }
+    if let Some(max_length) = validation.max_length {
+        checks.push(format!("length({}) <= {}", column, max_length));
+    }
+    if let Some(minimum) = validation.minimum {
+        checks.push(format!("{} >= {}", column, minimum));
+    }
+    if let Some(maximum) = validation.maximum {
+        checks.push(format!("{} <= {}", column, maximum));
+    }
+    if !validation.allowed_values.is_empty() {
+        let values: Vec<String> = validation
+            .allowed_values
+            .iter()
+            .map(|value| format!("'{}'", value.replace('\'', "''")))
+            .collect();
+        checks.push(format!("{} IN ({})", column, values.join(", ")));
+    }
+    checks
+}
+
+/// Quotes an identifier so names like `order` or `group` can be used as columns.
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board_model() -> DomainModel {
+        serde_json::from_value(serde_json::json!({
+            "entities": [
+                {
+                    "name": "Board",
+                    "resource": "boards",
+                    "primary_key": "slug",
+                    "fields": [
+                        {"name": "slug", "type": "string"},
+                        {"name": "order", "type": "integer", "required": false}
+                    ]
+                },
+                {
+                    "name": "TaskCard",
+                    "resource": "cards",
+                    "fields": [
+                        {"name": "id", "type": "integer"},
+                        {"name": "board_slug", "type": "string"},
+                        {"name": "title", "type": "string", "validation": {"min_length": 1, "max_length": 80}},
+                        {"name": "status", "type": "string", "validation": {"allowed_values": ["open", "won't do"]}},
+                        {"name": "points", "type": "float", "validation": {"minimum": 0.5}},
+                        {"name": "done", "type": "bool"},
+                        {"name": "due", "type": "datetime", "required": false}
+                    ],
+                    "unique_keys": [["id"], ["board_slug", "title"]],
+                    "relations": [
+                        {"field": "board_slug", "target": "Board", "kind": "belongs_to"},
+                        {"field": "comments", "target": "Comment", "kind": "has_many"}
+                    ]
+                }
+            ]
+        }))
+        .expect("valid domain model")
+    }
+
+    #[test]
+    fn renders_one_table_per_entity() {
+        let migrations: Vec<MigrationFile> = render_migrations(&board_model());
+
+        assert_eq!(migrations.len(), 1);
+        assert_eq!(migrations[0].file_name, "0001_create_tables.sql");
+        assert_eq!(
+            migrations[0].sql,
+            r#"-- Generated from the domain model. Add new migrations instead of editing applied ones.
+
+CREATE TABLE "board" (
+    "slug" TEXT PRIMARY KEY NOT NULL,
+    "order" INTEGER
+);
+
+CREATE TABLE "task_card" (
+    "id" INTEGER PRIMARY KEY,
+    "board_slug" TEXT NOT NULL,
+    "title" TEXT NOT NULL CHECK (length("title") >= 1 AND length("title") <= 80),
+    "status" TEXT NOT NULL CHECK ("status" IN ('open', 'won''t do')),
+    "points" REAL NOT NULL CHECK ("points" >= 0.5),
+    "done" INTEGER NOT NULL CHECK ("done" IN (0, 1)),
+    "due" TEXT,
+    UNIQUE ("board_slug", "title"),
+    FOREIGN KEY ("board_slug") REFERENCES "board" ("slug") ON DELETE CASCADE
+);
+CREATE INDEX "idx_task_card_board_slug" ON "task_card" ("board_slug");
+"#
+        );
+    }
+
+    #[test]
+    fn names_tables_in_snake_case() {
+        assert_eq!(table_name("TaskList"), "task_list");
+        assert_eq!(table_name("HTTPRequest"), "httprequest");
+        assert_eq!(table_name("order item"), "order_item");
+        assert_eq!(table_name("Line-Item_"), "line_item");
+        assert_eq!(quote_identifier("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
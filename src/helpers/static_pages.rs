@@ -0,0 +1,396 @@
+use crate::helpers::endpoint_testing::resource_key;
+use crate::helpers::general::send_endpoint_request;
+use crate::models::agents::agent_traits::{BodySchema, FieldType, HttpMethod, RouteObject};
+use reqwest::{Client, Method};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// The url prefix the generated web server serves its static files directory under.
+pub const STATIC_URL_PREFIX: &str = "/static";
+
+/// A file of the static frontend.
+///
+/// # Fields
+/// - `file_name`: The file name inside the static files directory.
+/// - `contents`: The file contents.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaticPage {
+    pub file_name: String,
+    pub contents: String,
+}
+
+/// A field shown in tables and forms, with the type deciding its input element.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct FieldConfig {
+    name: String,
+    #[serde(rename = "type")]
+    field_type: String,
+}
+
+/// The routes a resource page calls; dynamic routes keep their `{param}` placeholder.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+struct ResourceRoutes {
+    list: Option<String>,
+    detail: Option<String>,
+    create: Option<String>,
+    update: Option<String>,
+    delete: Option<String>,
+}
+
+/// The description of a resource embedded into each of its pages and read by `app.js`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct ResourceConfig {
+    resource: String,
+    id_field: String,
+    fields: Vec<FieldConfig>,
+    routes: ResourceRoutes,
+}
+
+/// Renders the static frontend for the routes of the API endpoint schema.
+///
+/// Every resource (first route segment) gets a list page, a detail page when items can be read and a
+/// create/edit form when they can be written. Delete buttons are added when the resource has a delete route.
+/// An `index.html` links all resources, and the pages share `app.js` and `style.css`.
+///
+/// # Arguments
+/// - `routes`: The API endpoint schema.
+/// - `app_js`: The contents of the shared script.
+/// - `style_css`: The contents of the shared stylesheet.
+///
+/// # Returns
+/// All files of the static frontend, including the shared assets.
+pub fn render_static_pages(
+    routes: &[RouteObject],
+    app_js: &str,
+    style_css: &str,
+) -> Vec<StaticPage> {
+    let resources: Vec<ResourceConfig> = group_resources(routes);
+
+    let mut pages: Vec<StaticPage> = vec![
+        StaticPage {
+            file_name: "app.js".to_string(),
+            contents: app_js.to_string(),
+        },
+        StaticPage {
+            file_name: "style.css".to_string(),
+            contents: style_css.to_string(),
+        },
+        StaticPage {
+            file_name: "index.html".to_string(),
+            contents: render_index(&resources),
+        },
+    ];
+
+    for resource in &resources {
+        let routes: &ResourceRoutes = &resource.routes;
+        pages.push(render_resource_page(resource, "list", ""));
+        if routes.detail.is_some() || routes.list.is_some() {
+            pages.push(render_resource_page(resource, "detail", "_detail"));
+        }
+        if routes.create.is_some() || routes.update.is_some() {
+            pages.push(render_resource_page(resource, "form", "_form"));
+        }
+    }
+    pages
+}
+
+/// Groups routes by resource and picks the fields shown for each resource.
+fn group_resources(routes: &[RouteObject]) -> Vec<ResourceConfig> {
+    let mut grouped: BTreeMap<String, Vec<&RouteObject>> = BTreeMap::new();
+    for route_object in routes {
+        let resource: String = resource_key(&route_object.route);
+        if !resource.is_empty() && !resource.starts_with('{') {
+            grouped.entry(resource).or_default().push(route_object);
+        }
+    }
+
+    grouped
+        .into_iter()
+        .map(|(resource, resource_routes)| {
+            let mut routes: ResourceRoutes = ResourceRoutes::default();
+            let mut field_shapes: Vec<&BodySchema> = vec![];
+            for route_object in &resource_routes {
+                let route: Option<String> = Some(route_object.route.clone());
+                match (route_object.method, route_object.is_route_dynamic) {
+                    (HttpMethod::Get, false) => {
+                        routes.list = routes.list.take().or(route);
+                        if let BodySchema::Array(item) = &route_object.response {
+                            field_shapes.push(item);
+                        }
+                    }
+                    (HttpMethod::Get, true) => {
+                        routes.detail = routes.detail.take().or(route);
+                        field_shapes.push(&route_object.response);
+                    }
+                    (HttpMethod::Post, false) => {
+                        routes.create = routes.create.take().or(route);
+                        field_shapes.insert(0, &route_object.request_body);
+                    }
+                    (HttpMethod::Put | HttpMethod::Patch, _) => {
+                        routes.update = routes.update.take().or(route);
+                        field_shapes.push(&route_object.request_body);
+                    }
+                    (HttpMethod::Delete, true) => routes.delete = routes.delete.take().or(route),
+                    _ => {}
+                }
+            }
+
+            let fields: Vec<FieldConfig> = field_shapes
+                .into_iter()
+                .find_map(|shape| shape.fields().filter(|fields| !fields.is_empty()))
+                .map(|fields| {
+                    fields
+                        .iter()
+                        .map(|(name, shape)| FieldConfig {
+                            name: name.clone(),
+                            field_type: field_type(shape),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            let id_field: String = fields
+                .iter()
+                .map(|field| field.name.clone())
+                .find(|name| name == "id")
+                .or_else(|| {
+                    fields
+                        .iter()
+                        .map(|field| field.name.clone())
+                        .find(|name| name.ends_with("_id"))
+                })
+                .unwrap_or_else(|| "id".to_string());
+
+            ResourceConfig {
+                resource,
+                id_field,
+                fields,
+                routes,
+            }
+        })
+        .collect()
+}
+
+/// Maps a field schema from `RouteObject` to the input type used by `app.js`.
+fn field_type(shape: &BodySchema) -> String {
+    match shape {
+        BodySchema::Field(FieldType::Integer | FieldType::Float) => "number",
+        BodySchema::Field(FieldType::Bool) => "bool",
+        BodySchema::Object(_) | BodySchema::Array(_) => "json",
+        _ => "string",
+    }
+    .to_string()
+}
+
+fn render_index(resources: &[ResourceConfig]) -> String {
+    let links: String = resources
+        .iter()
+        .map(|resource| {
+            format!(
+                "        <li><a href=\"{0}.html\">{0}</a></li>\n",
+                escape_html(&resource.resource)
+            )
+        })
+        .collect();
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n    <meta charset=\"utf-8\" />\n    <title>Home</title>\n    <link rel=\"stylesheet\" href=\"style.css\" />\n</head>\n<body>\n    <h1>Resources</h1>\n    <ul>\n{}    </ul>\n</body>\n</html>\n",
+        links
+    )
+}
+
+fn render_resource_page(resource: &ResourceConfig, page: &str, file_suffix: &str) -> StaticPage {
+    // `</` is escaped so the JSON can never close the surrounding script element.
+    let config_json: String = serde_json::to_string_pretty(resource)
+        .expect("Failed to serialize resource config")
+        .replace("</", "<\\/");
+    let title: String = escape_html(&format!("{} {}", resource.resource, page));
+
+    StaticPage {
+        file_name: format!("{}{}.html", resource.resource, file_suffix),
+        contents: format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n    <meta charset=\"utf-8\" />\n    <title>{title}</title>\n    <link rel=\"stylesheet\" href=\"style.css\" />\n</head>\n<body data-page=\"{page}\">\n    <nav><a href=\"index.html\">Home</a><a href=\"{resource}.html\">{resource}</a></nav>\n    <h1>{title}</h1>\n    <div id=\"content\"></div>\n    <script id=\"resource-config\" type=\"application/json\">{config_json}</script>\n    <script src=\"app.js\"></script>\n</body>\n</html>\n",
+            title = title,
+            page = page,
+            resource = escape_html(&resource.resource),
+            config_json = config_json,
+        ),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// The outcome of fetching one static page from the running web server.
+///
+/// # Fields
+/// - `file_name`: The page that was fetched.
+/// - `url`: The full url the page was fetched from.
+/// - `error`: Why the page was considered broken, if it was.
+#[derive(Debug, Clone, Serialize)]
+pub struct PageTestResult {
+    pub file_name: String,
+    pub url: String,
+    pub error: Option<String>,
+}
+
+/// Fetches every static page from the running web server and checks it is served unchanged.
+///
+/// # Arguments
+/// - `client`: The `reqwest::Client` used to make the requests.
+/// - `base_url`: The web server address, e.g. `http://127.0.0.1:8080`.
+/// - `pages`: The pages written to the static files directory.
+///
+/// # Returns
+/// One result per page.
+pub async fn run_page_smoke_tests(
+    client: &Client,
+    base_url: &str,
+    pages: &[StaticPage],
+) -> Vec<PageTestResult> {
+    let mut results: Vec<PageTestResult> = vec![];
+    for page in pages {
+        let url: String = format!("{}{}/{}", base_url, STATIC_URL_PREFIX, page.file_name);
+        let error: Option<String> =
+            match send_endpoint_request(client, Method::GET, &url, None, None).await {
+                Ok((200, body)) if body == page.contents => None,
+                Ok((200, _)) => Some("Served contents differ from the generated page".to_string()),
+                Ok((status, _)) => Some(format!("Expected status 200, got {}", status)),
+                Err(e) => Some(format!("Request failed: {}", e)),
+            };
+        results.push(PageTestResult {
+            file_name: page.file_name.clone(),
+            url,
+            error,
+        });
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn todo_routes() -> Vec<RouteObject> {
+        let todo = |fields: &[(&str, FieldType)]| {
+            BodySchema::Object(
+                fields
+                    .iter()
+                    .map(|(name, field_type)| (name.to_string(), BodySchema::Field(*field_type)))
+                    .collect(),
+            )
+        };
+        let stored_todo: BodySchema = todo(&[
+            ("id", FieldType::Integer),
+            ("title", FieldType::String),
+            ("done", FieldType::Bool),
+        ]);
+        vec![
+            RouteObject::new(
+                HttpMethod::Get,
+                "/todos".to_string(),
+                BodySchema::None,
+                BodySchema::Array(Box::new(stored_todo.clone())),
+            ),
+            RouteObject::new(
+                HttpMethod::Get,
+                "/todos/{id}".to_string(),
+                BodySchema::None,
+                stored_todo,
+            ),
+            RouteObject::new(
+                HttpMethod::Post,
+                "/todos".to_string(),
+                todo(&[("title", FieldType::String), ("done", FieldType::Bool)]),
+                BodySchema::Unknown,
+            ),
+            RouteObject::new(
+                HttpMethod::Delete,
+                "/todos/{id}".to_string(),
+                BodySchema::None,
+                BodySchema::None,
+            ),
+            RouteObject::new(
+                HttpMethod::Get,
+                "/{id}".to_string(),
+                BodySchema::None,
+                BodySchema::None,
+            ),
+        ]
+    }
+
+    #[test]
+    fn renders_the_pages_of_each_resource() {
+        let pages: Vec<StaticPage> = render_static_pages(&todo_routes(), "// app", "/* style */");
+
+        let file_names: Vec<&str> = pages.iter().map(|page| page.file_name.as_str()).collect();
+        assert_eq!(
+            file_names,
+            vec![
+                "app.js",
+                "style.css",
+                "index.html",
+                "todos.html",
+                "todos_detail.html",
+                "todos_form.html",
+            ]
+        );
+        assert_eq!(pages[0].contents, "// app");
+        assert!(pages[2]
+            .contents
+            .contains("<a href=\"todos.html\">todos</a>"));
+        assert!(pages[5].contents.contains("data-page=\"form\""));
+    }
+
+    #[test]
+    fn groups_routes_and_prefers_the_create_body_fields() {
+        let resources: Vec<ResourceConfig> = group_resources(&todo_routes());
+
+        assert_eq!(resources.len(), 1);
+        let todos: &ResourceConfig = &resources[0];
+        assert_eq!(
+            todos.routes,
+            ResourceRoutes {
+                list: Some("/todos".to_string()),
+                detail: Some("/todos/{id}".to_string()),
+                create: Some("/todos".to_string()),
+                update: None,
+                delete: Some("/todos/{id}".to_string()),
+            }
+        );
+        let fields: Vec<(&str, &str)> = todos
+            .fields
+            .iter()
+            .map(|field| (field.name.as_str(), field.field_type.as_str()))
+            .collect();
+        assert_eq!(fields, vec![("done", "bool"), ("title", "string")]);
+        assert_eq!(todos.id_field, "id");
+    }
+
+    #[test]
+    fn escapes_names_and_the_embedded_config() {
+        let resource: ResourceConfig = ResourceConfig {
+            resource: "notes".to_string(),
+            id_field: "id".to_string(),
+            fields: vec![FieldConfig {
+                name: "</script><script>alert(1)</script>".to_string(),
+                field_type: "string".to_string(),
+            }],
+            routes: ResourceRoutes::default(),
+        };
+
+        let page: StaticPage = render_resource_page(&resource, "list", "");
+
+        assert_eq!(page.file_name, "notes.html");
+        assert_eq!(page.contents.matches("</script>").count(), 2);
+        assert!(page
+            .contents
+            .contains("<\\/script><script>alert(1)<\\/script>"));
+        assert_eq!(
+            escape_html("<a href=\"x\">&</a>"),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;"
+        );
+    }
+}
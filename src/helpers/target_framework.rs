@@ -0,0 +1,208 @@
+use crate::helpers::general::env_setting;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Environment variable choosing the web framework of the generated backend, `actix-web`, `axum` or `fastapi`.
+const BACKEND_FRAMEWORK_ENV: &str = "BACKEND_FRAMEWORK";
+
+/// The language the generated backend is written in, which decides how it is built, checked and run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetLanguage {
+    /// A cargo project compiled in the sandbox and run as a binary.
+    Rust,
+    /// A `requirements.txt` project installed into a virtualenv, checked with `py_compile` and ruff.
+    Python,
+}
+
+impl TargetLanguage {
+    /// The line comment marker of the language, e.g. for the template headers sent to the model.
+    pub fn line_comment(&self) -> &'static str {
+        match self {
+            TargetLanguage::Rust => "//",
+            TargetLanguage::Python => "#",
+        }
+    }
+}
+
+/// The web framework the backend is generated with.
+///
+/// It is a setting of the run rather than part of the `FactSheet`, so the same fact sheet can produce
+/// any framework. It switches the code templates, the prompt wording and the tests harness, and through
+/// its [`TargetLanguage`] how the project is built, run and read for routes.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TargetFramework {
+    #[default]
+    ActixWeb,
+    Axum,
+    #[serde(rename = "fastapi")]
+    FastApi,
+}
+
+impl TargetFramework {
+    /// Reads the framework from `BACKEND_FRAMEWORK`, defaulting to actix-web.
+    pub fn from_env() -> Self {
+        env_setting(BACKEND_FRAMEWORK_ENV, TargetFramework::default())
+    }
+
+    /// The language servers of this framework are written in.
+    pub fn language(&self) -> TargetLanguage {
+        match self {
+            TargetFramework::ActixWeb | TargetFramework::Axum => TargetLanguage::Rust,
+            TargetFramework::FastApi => TargetLanguage::Python,
+        }
+    }
+
+    /// The package every server of this framework depends on, as named in `Cargo.toml` or `requirements.txt`.
+    pub fn package_name(&self) -> &'static str {
+        match self {
+            TargetFramework::ActixWeb => "actix-web",
+            TargetFramework::Axum => "axum",
+            TargetFramework::FastApi => "fastapi",
+        }
+    }
+
+    /// The arguments the program from `get_web_server_program_path` is started with.
+    ///
+    /// Rust servers read their port from `WEB_SERVER_PORT`; FastAPI apps are served by uvicorn, which is
+    /// given the port and the project directory `main.py` is imported from.
+    ///
+    /// # Arguments
+    /// - `project_dir`: The generated project directory.
+    /// - `port`: The port allocated for the server.
+    pub fn run_args(&self, project_dir: &str, port: u16) -> Vec<String> {
+        match self {
+            TargetFramework::ActixWeb | TargetFramework::Axum => vec![],
+            TargetFramework::FastApi => [
+                "-m",
+                "uvicorn",
+                "main:app",
+                "--app-dir",
+                project_dir,
+                "--host",
+                "127.0.0.1",
+                "--port",
+                &port.to_string(),
+            ]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect(),
+        }
+    }
+
+    /// How the generated project is started by hand, shown in its README.
+    pub fn run_instructions(&self) -> &'static str {
+        match self {
+            TargetFramework::ActixWeb | TargetFramework::Axum => "cargo run --release",
+            TargetFramework::FastApi => {
+                "python -m venv .venv\n.venv/bin/pip install -r requirements.txt\n.venv/bin/python main.py"
+            }
+        }
+    }
+
+    /// The libraries the generated project has installed before any template adds its own.
+    pub fn installed_libraries(&self) -> &'static str {
+        match self {
+            TargetFramework::ActixWeb => {
+                "reqwest, serde, serde_json, tokio, async-trait, actix-web, actix_cors, actix_files"
+            }
+            TargetFramework::Axum => {
+                "reqwest, serde, serde_json, tokio, async-trait, axum (with the ws and multipart features) and tower-http (with the cors and fs features)"
+            }
+            TargetFramework::FastApi => {
+                "fastapi, uvicorn, pydantic 2, python-multipart, websockets and PyJWT"
+            }
+        }
+    }
+
+    /// How a server of this framework is structured, sent to the backend prompts as `FRAMEWORK_CONVENTIONS`.
+    pub fn backend_conventions(&self) -> Vec<String> {
+        let mut conventions: Vec<String> = vec![format!(
+            "The server is written with {}; the installed libraries are {}",
+            self,
+            self.installed_libraries()
+        )];
+        let framework_conventions: &[&str] = match self {
+            TargetFramework::ActixWeb => &[
+                "Register every route inside `fn configure_routes(cfg: &mut web::ServiceConfig)` and add it with `.configure(configure_routes)`, exactly as the CODE_TEMPLATE does",
+                "Share the application state as `web::Data<AppState>` registered with `.app_data(...)`",
+                "Write path parameters as `{id}` and read them with `web::Path<T>`",
+                "Serve \"/static\" with `actix_files::Files` and allow the frontend origins with `actix_cors::Cors`",
+            ],
+            TargetFramework::Axum => &[
+                "Build every route inside `fn configure_routes() -> Router<Arc<AppState>>` with `.route(path, get(handler).post(handler))` and give it the state in `main` with `.with_state(...)`, exactly as the CODE_TEMPLATE does",
+                "Handlers take extractors such as `State(app_state): State<Arc<AppState>>`, `Path(id): Path<u64>`, `Query(...)` and `Json(body): Json<T>`, with the body extractor last, and return `Json(...)`, a `StatusCode` or `(StatusCode, Json(...))`",
+                "Write path parameters as `{id}`, never as `:id`, which axum 0.8 rejects",
+                "Serve \"/static\" with `tower_http::services::ServeDir` through `.nest_service(...)` and allow the frontend origins with `tower_http::cors::CorsLayer`",
+                "Start the server with `tokio::net::TcpListener::bind` and `axum::serve`",
+            ],
+            TargetFramework::FastApi => &[
+                "Write Python 3.11 in a single `main.py` defining `app = FastAPI(openapi_url=None)`, which is served with `uvicorn main:app`, exactly as the CODE_TEMPLATE does",
+                "Register routes with `@app.get(path)` style decorators, or on an `APIRouter` added with `app.include_router(...)`, and set `response_model=` and `status_code=` on the decorator",
+                "Declare every request and response body as a pydantic `BaseModel` with type annotations and take the request body as a parameter typed with its model",
+                "Write path parameters as `{id}`, take them as typed handler parameters and raise `HTTPException` for missing items and invalid requests",
+                "Serve \"/static\" with `StaticFiles` mounted from `WEB_SERVER_STATIC_DIR` and allow the frontend origins with `CORSMiddleware`",
+                "Keep the `if __name__ == \"__main__\":` block running uvicorn on `WEB_SERVER_HOST` and `WEB_SERVER_PORT`",
+            ],
+        };
+        conventions.extend(
+            framework_conventions
+                .iter()
+                .map(|convention| convention.to_string()),
+        );
+        conventions
+    }
+
+    /// How the integration tests build and call the app, sent to the test writer as `TEST_CONVENTIONS`.
+    pub fn test_conventions(&self) -> Vec<String> {
+        let conventions: &[&str] = match self {
+            TargetFramework::ActixWeb => &[
+                "Build the app in-process with `actix_web::test::init_service(App::new().app_data(...).configure(configure_routes))`, exactly as the TEST_TEMPLATE does, with a fresh state per test",
+                "Refer to actix test helpers through `actix_test::`",
+                "Only use actix-web, serde and serde_json",
+            ],
+            TargetFramework::Axum => &[
+                "Build the router in-process with `configure_routes().with_state(...)`, exactly as the TEST_TEMPLATE does, with a fresh state per test",
+                "Send every request with `tower::ServiceExt::oneshot` on a clone of the router and read response bodies with `http_body_util::BodyExt::collect`",
+                "Write paths of the `axum::http` types in full, e.g. `axum::http::StatusCode::CREATED`",
+                "Only use axum, tower, http-body-util, serde and serde_json",
+            ],
+            // There is no tests template for FastAPI servers, so the test writer skips them
+            TargetFramework::FastApi => &[],
+        };
+        conventions
+            .iter()
+            .map(|convention| convention.to_string())
+            .collect()
+    }
+}
+
+impl FromStr for TargetFramework {
+    type Err = String;
+
+    /// Parses `actix-web`, `actix`, `axum` or `fastapi`, ignoring case, dashes and underscores.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let normalized: String = value
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_lowercase();
+        match normalized.as_str() {
+            "actixweb" | "actix" => Ok(TargetFramework::ActixWeb),
+            "axum" => Ok(TargetFramework::Axum),
+            "fastapi" => Ok(TargetFramework::FastApi),
+            _ => Err(format!("Unknown backend framework: {}", value)),
+        }
+    }
+}
+
+impl fmt::Display for TargetFramework {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TargetFramework::ActixWeb => write!(f, "actix-web 4"),
+            TargetFramework::Axum => write!(f, "axum 0.8 and tower-http 0.6"),
+            TargetFramework::FastApi => write!(f, "FastAPI 0.115 on Python"),
+        }
+    }
+}
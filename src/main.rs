@@ -12,15 +12,89 @@ pub mod helpers;
 pub mod models;
 
 use crate::helpers::command_lines::get_user_response;
+use crate::helpers::general::get_web_server_project_path;
+use crate::helpers::project_history::{
+    list_project_stages, resolve_project_stage, roll_back_project, stage_numbers, StageCommit,
+};
+use crate::helpers::server_process::install_ctrl_c_handler;
+use crate::helpers::socket_bridge::{run_sandbox_bridge, SANDBOX_BRIDGE_COMMAND};
 use models::agents_manager::managing_agents::ManagingAgent;
+use std::path::Path;
+
+/// Prints the committed stages of the generated project, numbered for `rollback`. The commits of
+/// earlier rollbacks are listed without a number.
+fn print_project_history() {
+    let project_path: String = get_web_server_project_path();
+    let stages: Vec<StageCommit> =
+        list_project_stages(Path::new(&project_path)).expect("Error reading project history");
+    if stages.is_empty() {
+        println!("No stages committed in {} yet", project_path);
+    }
+    for (number, stage) in stage_numbers(&stages).into_iter().zip(&stages) {
+        let number: String = number.map_or("-".to_string(), |number| number.to_string());
+        println!("{:>3}  {}  {}", number, stage.hash, stage.subject);
+    }
+}
 
 #[tokio::main]
 async fn main() {
-    let usr_req: String = get_user_response("What website are we building today?");
+    // Inside the run sandbox this program connects the web server's private network to the host
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some(SANDBOX_BRIDGE_COMMAND) {
+        std::process::exit(run_sandbox_bridge(&args[2..]).await);
+    }
+
+    install_ctrl_c_handler();
+
+    // `cargo run -- revise` applies a change request to the project an earlier run generated,
+    // `history` and `rollback <stage>` list and restore the stages committed during the runs
+    match args.get(1).map(String::as_str) {
+        Some("history") => {
+            print_project_history();
+            return;
+        }
+        Some("rollback") => {
+            let Some(stage) = args.get(2).map(String::as_str) else {
+                eprintln!("Usage: rollback <stage number or commit hash>");
+                std::process::exit(1);
+            };
+            let project_path: String = get_web_server_project_path();
+            let target: StageCommit = match resolve_project_stage(Path::new(&project_path), stage) {
+                Ok(target) => target,
+                Err(e) => {
+                    eprintln!("Error rolling back project: {}", e);
+                    std::process::exit(1);
+                }
+            };
+            println!("Rolling back to {}: {}", target.hash, target.subject);
+            match roll_back_project(Path::new(&project_path), &target) {
+                Ok(()) => println!("Rolled back to {}", target.hash),
+                Err(e) => {
+                    eprintln!("Error rolling back project: {}", e);
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        _ => {}
+    }
+    let is_revise: bool = args.get(1).map(String::as_str) == Some("revise");
 
-    let mut manage_agent: ManagingAgent = ManagingAgent::new(usr_req)
-        .await
-        .expect("Error creating agent");
+    let mut manage_agent: ManagingAgent = if is_revise {
+        let change_request: String =
+            get_user_response("What should change in the generated project?");
+        ManagingAgent::revise(change_request)
+            .await
+            .expect("Error revising project")
+    } else {
+        let usr_req: String = get_user_response("What website are we building today?");
+        ManagingAgent::new(usr_req)
+            .await
+            .expect("Error creating agent")
+    };
 
-    manage_agent.execute_project().await;
+    if let Err(e) = manage_agent.execute_project().await {
+        eprintln!("Run stopped: {}", e);
+        std::process::exit(1);
+    }
 }
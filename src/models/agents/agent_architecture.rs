@@ -17,6 +17,12 @@ pub struct AgentSolutionArchitect {
     attributes: BasicAgent,
 }
 
+impl Default for AgentSolutionArchitect {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl AgentSolutionArchitect {
     /// Creates a new instance of `AgentSolutionArchitect` with default attributes
     pub fn new() -> Self {
@@ -38,7 +44,7 @@ impl AgentSolutionArchitect {
     /// # Returns
     /// - A ProjectScope
     async fn call_project_scope(&mut self, fact_sheet: &mut FactSheet) -> ProjectScope {
-        let project_description: String = format!("{}", fact_sheet.project_description);
+        let project_description: String = fact_sheet.project_context();
 
         let decoded_project_scope: ProjectScope = ai_task_request_decoded::<ProjectScope>(
             project_description,
@@ -46,9 +52,10 @@ impl AgentSolutionArchitect {
             get_function_string!(print_project_scope),
             print_project_scope,
         )
-        .await;
+        .await
+        .reconciled();
 
-        fact_sheet.project_scope = Some(decoded_project_scope.clone());
+        fact_sheet.project_scope = Some(decoded_project_scope);
         self.attributes.update_state(AgentState::Finished);
         decoded_project_scope
     }
@@ -96,11 +103,8 @@ impl SpecialFunctions for AgentSolutionArchitect {
                 AgentState::Discovery => {
                     let project_scope: ProjectScope = self.call_project_scope(fact_sheet).await;
                     if project_scope.is_external_urls_required {
-                        self.call_determine_external_urls(
-                            fact_sheet,
-                            fact_sheet.project_description.clone(),
-                        )
-                        .await;
+                        self.call_determine_external_urls(fact_sheet, fact_sheet.project_context())
+                            .await;
                         self.attributes.state = AgentState::UnitTesting;
                     }
                 }
@@ -136,13 +140,13 @@ impl SpecialFunctions for AgentSolutionArchitect {
                         }
                     }
 
-                    if excluded_external_urls.len() > 0 {
+                    if !excluded_external_urls.is_empty() {
                         let confirmed_external_urls: Vec<String> = fact_sheet
                             .external_urls
                             .as_ref()
                             .unwrap()
                             .iter()
-                            .filter(|url| !excluded_external_urls.contains(&url))
+                            .filter(|url| !excluded_external_urls.contains(url))
                             .cloned()
                             .collect();
                         fact_sheet.external_urls = Some(confirmed_external_urls);
@@ -167,9 +171,14 @@ impl SpecialFunctions for AgentSolutionArchitect {
 //
 //         let mut factsheet: FactSheet = FactSheet {
 //             project_description: "Build a full stack website with user login and logout that shows latest Forex prices".to_string(),
+//             requirements: None,
 //             project_scope: None,
+//             domain_model: None,
 //             external_urls: None,
 //             backend_code: None,
+//             frontend_code: None,
+//             api_tests_code: None,
+//             env_vars: None,
 //             api_endpoint_schema: None,
 //         };
 //
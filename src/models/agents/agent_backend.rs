@@ -1,31 +1,156 @@
 use crate::ai_functions::ai_func_backend::{
     print_backend_webserver_code, print_fixed_code, print_improved_webserver_code,
-    print_rest_api_endpoints,
+    print_rest_api_endpoints, print_revised_webserver_code,
+};
+use crate::helpers::code_templates::{
+    compose_code_template, required_features, select_templates, CodeTemplate, TemplateFeature,
 };
-use crate::helpers::general::{check_status_code, get_web_server_project_path, read_code_template_contents, read_exec_main_contents, save_api_endpoints, save_backend_code};
-use std::path::Path;
-
 use crate::helpers::command_lines::{confirm_safe_code, PrintCommand};
-use crate::helpers::general::ai_task_request;
+use crate::helpers::dependency_check::{
+    check_dependencies, format_dependency_feedback, CrateAllowlist, DependencyCheck,
+};
+use crate::helpers::egress_proxy::{hosts_from_urls, EgressProxy};
+use crate::helpers::endpoint_testing::{run_endpoint_tests, EndpointTestResult, RuntimeBugReport};
+use crate::helpers::fastapi_route_extraction::extract_fastapi_route_objects;
+use crate::helpers::general::{
+    ai_task_request, env_setting, get_web_server_binary_name, get_web_server_manifest_path,
+    get_web_server_project_path, read_code_templates, read_exec_main_contents, read_openapi_schema,
+    save_api_endpoints, save_backend_code, save_migrations, save_openapi_document, save_run_report,
+    save_template_dependencies,
+};
+use crate::helpers::openapi::{
+    build_openapi_document, check_openapi_served, validate_openapi_document, OPENAPI_ROUTE,
+};
+use crate::helpers::project_history::{record_project_stage, ProjectStage};
+use crate::helpers::route_extraction::extract_route_objects;
+use crate::helpers::safety_scanner::{
+    format_safety_report, scan_generated_code, scan_python_code, SafetyFinding,
+};
+use crate::helpers::sandbox::{
+    build_project_in_sandbox, check_python_project_in_sandbox, fetch_project_dependencies,
+    install_python_requirements, SandboxBackend,
+};
+use crate::helpers::server_process::{
+    start_sandboxed_web_server, ServerLaunchError, ServerRunReport, TestServerGuard,
+    ENDPOINT_TESTS_TIMEOUT,
+};
+use crate::helpers::sql_migrations::{render_migrations, MigrationFile};
+use crate::helpers::target_framework::{TargetFramework, TargetLanguage};
 use crate::models::agent_basic::basic_agent::{AgentState, BasicAgent};
-use crate::models::agents::agent_traits::{FactSheet, RouteObject, SpecialFunctions};
+use crate::models::agents::agent_security::AgentSecurityReviewer;
+use crate::models::agents::agent_test_writer::AgentTestWriter;
+use crate::models::agents::agent_traits::{
+    DomainModel, FactSheet, RouteObject, SpecialFunctions, StorageBackend,
+};
 
 use async_trait::async_trait;
 use reqwest::Client;
-use std::process::{Command, Stdio};
+use serde_json::Value;
+use std::path::Path;
+use std::process::ExitStatus;
 use std::time::Duration;
 use tokio::time;
 
+/// Lists the backend instructions derived from the architect's project scope as a JSON array.
+///
+/// # Parameters
+/// - `fact_sheet`: A reference to the fact sheet containing project information
+/// - `framework`: The web framework the backend is written with
+fn scope_requirements(fact_sheet: &FactSheet, framework: TargetFramework) -> String {
+    let requirements: Vec<String> = fact_sheet
+        .project_scope
+        .map(|scope| scope.backend_requirements(framework))
+        .unwrap_or_default();
+    serde_json::to_string(&requirements).expect("Failed to serialize scope requirements")
+}
+
+/// Lists how a server of the framework is structured as a JSON array.
+///
+/// # Parameters
+/// - `framework`: The web framework the backend is written with
+fn framework_conventions(framework: TargetFramework) -> String {
+    serde_json::to_string(&framework.backend_conventions())
+        .expect("Failed to serialize framework conventions")
+}
+
+/// Writes the migrations of a SQLite backend, rendered from the domain model, to the generated project.
+///
+/// # Parameters
+/// - `fact_sheet`: A reference to the fact sheet containing project information
+///
+/// # Returns
+/// The MIGRATIONS part of the prompt, empty if the backend does not store its data in SQLite
+fn write_sqlite_migrations(fact_sheet: &FactSheet) -> String {
+    let uses_sqlite: bool = fact_sheet
+        .project_scope
+        .is_some_and(|scope| scope.storage_backend == StorageBackend::Sqlite);
+    if !uses_sqlite {
+        return String::new();
+    }
+
+    // The schema comes from the domain model rather than from the model's own SQL
+    let migrations: Vec<MigrationFile> = fact_sheet
+        .domain_model
+        .as_ref()
+        .map(render_migrations)
+        .unwrap_or_default();
+    save_migrations(&migrations);
+    format!(
+        " MIGRATIONS: {} \n",
+        serde_json::to_string(&migrations).expect("Failed to serialize migrations")
+    )
+}
+
+/// Where in the test loop a bug was found; each kind has its own fix budget.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BugKind {
+    /// Dependency, compiler or linter errors, counted until the next successful build.
+    Build,
+    /// The server failed to start, crashed or answered an endpoint test wrongly.
+    Runtime,
+    /// The generated integration tests failed.
+    IntegrationTest,
+}
+
 /// Struct representing a backend developer agent
 /// Attributes:
 /// - `attributes`: Basic agent properties (objective, position, etc.)
 /// - `bug_errors`: Optional string describing encountered errors
-/// - `bug_count`: Counter for the number of bugs encountered
+/// - `bug_count`: Counter for the build errors since the last successful build
+/// - `runtime_bug_count`: Counter for the server start and endpoint test failures
+/// - `test_bug_count`: Counter for the integration test failures
+/// - `fix_count`: Counter for the fix iterations of the run, numbering them in the project history
+/// - `framework`: The web framework the code is generated with, read from `BACKEND_FRAMEWORK`
+/// - `max_bug_fixes`: How many bugs of one kind are sent back for fixing before the agent gives up
+/// - `previous_routes`: The routes the project served before a revise run, checked for regressions
+/// - `run_reports`: What happened to each test server that was started
+/// - `security_reviewer`: Reviews the code and applies security fixes once the endpoint tests pass
+/// - `test_writer`: Writes and runs the integration tests once the endpoint tests pass
 #[derive(Debug)]
 pub struct AgentBackendDeveloper {
     attributes: BasicAgent,
     bug_errors: Option<String>,
     bug_count: u8,
+    runtime_bug_count: u8,
+    test_bug_count: u8,
+    fix_count: u8,
+    framework: TargetFramework,
+    max_bug_fixes: u8,
+    previous_routes: Vec<RouteObject>,
+    run_reports: Vec<ServerRunReport>,
+    security_reviewer: AgentSecurityReviewer,
+    test_writer: AgentTestWriter,
+}
+
+/// Environment variable overriding how many fix iterations the backend agent attempts.
+const MAX_BUG_FIXES_ENV: &str = "BACKEND_MAX_BUG_FIXES";
+/// Default number of fix iterations the backend agent attempts.
+const DEFAULT_MAX_BUG_FIXES: u8 = 2;
+
+impl Default for AgentBackendDeveloper {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl AgentBackendDeveloper {
@@ -42,20 +167,49 @@ impl AgentBackendDeveloper {
             attributes,
             bug_errors: None,
             bug_count: 0,
+            runtime_bug_count: 0,
+            test_bug_count: 0,
+            fix_count: 0,
+            framework: TargetFramework::from_env(),
+            max_bug_fixes: env_setting(MAX_BUG_FIXES_ENV, DEFAULT_MAX_BUG_FIXES),
+            previous_routes: vec![],
+            run_reports: vec![],
+            security_reviewer: AgentSecurityReviewer::new(),
+            test_writer: AgentTestWriter::new(),
         }
     }
 
-    /// Generates initial backend code based on a code template and project description
+    /// Generates initial backend code based on the code templates matching the project scope and the project description
     ///
     /// # Parameters
     /// - `fact_sheet`: A mutable reference to the fact sheet containing project information
     async fn call_initial_backend_code(&mut self, fact_sheet: &mut FactSheet) {
-        let code_template_str: String = read_code_template_contents();
-
-        let msg_context: String = format!(
-            "CODE TEMPLATE: {} \n PROJECT_DESCRIPTION: {} \n",
-            code_template_str, fact_sheet.project_description
+        let templates: Vec<CodeTemplate> = read_code_templates(self.framework);
+        let features: Vec<TemplateFeature> = required_features(fact_sheet.project_scope.as_ref());
+        let selected_templates: Vec<&CodeTemplate> = select_templates(&templates, &features);
+        let template_names: Vec<&str> = selected_templates
+            .iter()
+            .map(|template| template.name.as_str())
+            .collect();
+        PrintCommand::AICall.print_agent_message(
+            &self.attributes.position,
+            format!(
+                "Using {} code templates: {}",
+                self.framework.package_name(),
+                template_names.join(", ")
+            )
+            .as_str(),
         );
+        let code_template_str: String = compose_code_template(&selected_templates);
+
+        let mut msg_context: String = format!(
+            "CODE TEMPLATE: {} \n PROJECT_DESCRIPTION: {} \n SCOPE_REQUIREMENTS: {} \n FRAMEWORK_CONVENTIONS: {} \n",
+            code_template_str,
+            fact_sheet.project_context(),
+            scope_requirements(fact_sheet, self.framework),
+            framework_conventions(self.framework)
+        );
+        msg_context.push_str(&write_sqlite_migrations(fact_sheet));
 
         let ai_response: String = ai_task_request(
             msg_context,
@@ -63,7 +217,41 @@ impl AgentBackendDeveloper {
             get_function_string!(print_backend_webserver_code),
             print_backend_webserver_code,
         )
-            .await;
+        .await;
+        save_backend_code(&ai_response);
+        save_template_dependencies(&selected_templates, self.framework);
+        fact_sheet.backend_code = Some(ai_response);
+    }
+
+    /// Applies the fact sheet's change request to the backend code of an earlier run
+    ///
+    /// # Parameters
+    /// - `fact_sheet`: A mutable reference to the fact sheet containing project information
+    async fn call_revise_backend_code(&mut self, fact_sheet: &mut FactSheet) {
+        self.previous_routes = fact_sheet.api_endpoint_schema.clone().unwrap_or_default();
+
+        let mut msg_context: String = format!(
+            "EXISTING_CODE: {:?} \n CHANGE_REQUEST: {:?} \n SCOPE_REQUIREMENTS: {} \n FRAMEWORK_CONVENTIONS: {} \n",
+            fact_sheet.backend_code,
+            fact_sheet.change_request,
+            scope_requirements(fact_sheet, self.framework),
+            framework_conventions(self.framework)
+        );
+        if let Some(domain_model) = &fact_sheet.domain_model {
+            msg_context.push_str(&format!(
+                " DATA_MODEL: {} \n",
+                serde_json::to_string(domain_model).expect("Failed to serialize domain model")
+            ));
+        }
+        msg_context.push_str(&write_sqlite_migrations(fact_sheet));
+
+        let ai_response: String = ai_task_request(
+            msg_context,
+            &self.attributes.position,
+            get_function_string!(print_revised_webserver_code),
+            print_revised_webserver_code,
+        )
+        .await;
         save_backend_code(&ai_response);
         fact_sheet.backend_code = Some(ai_response);
     }
@@ -74,8 +262,11 @@ impl AgentBackendDeveloper {
     /// - `fact_sheet`: A mutable reference to the fact sheet containing project information
     async fn call_improved_backend_code(&mut self, fact_sheet: &mut FactSheet) {
         let msg_context: String = format!(
-            "CODE TEMPLATE: {:?} \n PROJECT_DESCRIPTION: {:?} \n",
-            fact_sheet.backend_code, fact_sheet
+            "CODE TEMPLATE: {:?} \n PROJECT_DESCRIPTION: {:?} \n SCOPE_REQUIREMENTS: {} \n FRAMEWORK_CONVENTIONS: {} \n",
+            fact_sheet.backend_code,
+            fact_sheet,
+            scope_requirements(fact_sheet, self.framework),
+            framework_conventions(self.framework)
         );
 
         let ai_response: String = ai_task_request(
@@ -84,7 +275,7 @@ impl AgentBackendDeveloper {
             get_function_string!(print_improved_webserver_code),
             print_improved_webserver_code,
         )
-            .await;
+        .await;
         save_backend_code(&ai_response);
         fact_sheet.backend_code = Some(ai_response);
     }
@@ -95,9 +286,11 @@ impl AgentBackendDeveloper {
     /// - `fact_sheet`: A mutable reference to the fact sheet containing project information
     async fn call_fix_code_bugs(&mut self, fact_sheet: &mut FactSheet) {
         let msg_context: String = format!(
-            "BROKEN_CODE: {:?} \n ERROR_BUGS: {:?} \n
+            "BROKEN_CODE: {:?} \n ERROR_BUGS: {:?} \n FRAMEWORK_CONVENTIONS: {} \n
       THIS FUNCTION ONLY OUTPUTS CODE. JUST OUTPUT THE CODE.",
-            fact_sheet.backend_code, self.bug_errors
+            fact_sheet.backend_code,
+            self.bug_errors,
+            framework_conventions(self.framework)
         );
 
         let ai_response: String = ai_task_request(
@@ -106,7 +299,7 @@ impl AgentBackendDeveloper {
             get_function_string!(print_fixed_code),
             print_fixed_code,
         )
-            .await;
+        .await;
 
         save_backend_code(&ai_response);
         fact_sheet.backend_code = Some(ai_response);
@@ -114,10 +307,28 @@ impl AgentBackendDeveloper {
 
     /// Extracts REST API endpoints from the backend code
     ///
+    /// Routes are read from the syntax tree of Rust code, or from the decorators of FastAPI code.
+    /// The model is only asked if the code cannot be parsed or no routes are found in it.
+    ///
     /// # Returns
-    /// - A string containing the extracted API endpoints
-    async fn call_extract_rest_api_endpoints(&self) -> String {
+    /// - The extracted API endpoints, or an error if the model's answer is not a list of endpoints
+    async fn call_extract_rest_api_endpoints(&self) -> Result<Vec<RouteObject>, String> {
         let backend_code: String = read_exec_main_contents();
+        let extracted: Result<Vec<RouteObject>, String> = match self.framework.language() {
+            TargetLanguage::Rust => extract_route_objects(&backend_code).map_err(|e| e.to_string()),
+            TargetLanguage::Python => Ok(extract_fastapi_route_objects(&backend_code)),
+        };
+        let fallback_message: String = match extracted {
+            Ok(api_endpoints) if !api_endpoints.is_empty() => return Ok(api_endpoints),
+            Ok(_) => "Backend Code Unit Testing: No routes found in the code, asking for the API endpoints".to_string(),
+            Err(e) => format!(
+                "Backend Code Unit Testing: Unable to parse the code ({}), asking for the API endpoints",
+                e
+            ),
+        };
+        PrintCommand::Issue
+            .print_agent_message(self.attributes.position.as_str(), fallback_message.as_str());
+
         let msg_context: String = format!("CODE_INPUT: {}", backend_code);
         let ai_response: String = ai_task_request(
             msg_context,
@@ -125,9 +336,247 @@ impl AgentBackendDeveloper {
             get_function_string!(print_rest_api_endpoints),
             print_rest_api_endpoints,
         )
-            .await;
+        .await;
+
+        serde_json::from_str(ai_response.as_str()).map_err(|e| {
+            format!(
+                "Unable to read the API endpoints of the code, make sure the routes are registered where they can be found ({}):\n{}",
+                e, ai_response
+            )
+        })
+    }
+
+    /// Checks the crates used by the generated code against the dependency allowlist
+    ///
+    /// Allowlisted crates missing from the generated `Cargo.toml` are added when auto-add is enabled.
+    ///
+    /// # Returns
+    /// - Feedback for the fix loop naming every crate that is not allowed or not declared
+    fn call_check_dependencies(&self) -> Result<(), String> {
+        let allowlist: CrateAllowlist = CrateAllowlist::from_env();
+        let dependency_check: DependencyCheck = match check_dependencies(
+            &read_exec_main_contents(),
+            Path::new(&get_web_server_manifest_path()),
+            &allowlist,
+        ) {
+            Ok(dependency_check) => dependency_check,
+            // Unparsable code is reported by the compiler during the build
+            Err(_) => return Ok(()),
+        };
+
+        for added_crate in &dependency_check.added_crates {
+            PrintCommand::UnitTest.print_agent_message(
+                self.attributes.position.as_str(),
+                format!(
+                    "Backend Code Unit Testing: Added {} to Cargo.toml",
+                    added_crate
+                )
+                .as_str(),
+            );
+        }
 
-        ai_response
+        if dependency_check.issues.is_empty() {
+            Ok(())
+        } else {
+            Err(format_dependency_feedback(
+                &dependency_check.issues,
+                &allowlist,
+            ))
+        }
+    }
+
+    /// Builds the generated web server project inside the sandbox
+    ///
+    /// Dependencies are fetched first, outside the sandbox, because the build itself has no network.
+    /// Python projects get their requirements installed into a virtualenv and are checked with
+    /// `py_compile` and ruff instead of being built.
+    ///
+    /// # Returns
+    /// - The compiler or linter errors if the build failed or timed out
+    async fn call_build_web_server(&self) -> Result<(), String> {
+        let project_path: String = get_web_server_project_path();
+
+        match self.framework.language() {
+            TargetLanguage::Rust => {
+                if !fetch_project_dependencies(Path::new(&project_path), &[]) {
+                    PrintCommand::Issue.print_agent_message(
+                        self.attributes.position.as_str(),
+                        "Backend Code Unit Testing: Unable to fetch dependencies, building from local cache",
+                    );
+                }
+
+                build_project_in_sandbox(Path::new(&project_path), &[]).await
+            }
+            TargetLanguage::Python => {
+                if !install_python_requirements(Path::new(&project_path)) {
+                    PrintCommand::Issue.print_agent_message(
+                        self.attributes.position.as_str(),
+                        "Backend Code Unit Testing: Unable to install requirements, checking with the existing virtualenv",
+                    );
+                }
+
+                check_python_project_in_sandbox(Path::new(&project_path)).await
+            }
+        }
+    }
+
+    /// Records a bug for the fix loop and sends the agent back to `Working`
+    ///
+    /// # Parameters
+    /// - `kind`: Where in the test loop the bug was found
+    /// - `error`: A description of the bug, e.g. compiler output or server logs
+    ///
+    /// # Returns
+    /// - An error once more bugs of the kind were found than `max_bug_fixes` allows
+    fn record_bug(&mut self, kind: BugKind, error: String) -> Result<(), String> {
+        let bug_count: &mut u8 = match kind {
+            BugKind::Build => &mut self.bug_count,
+            BugKind::Runtime => &mut self.runtime_bug_count,
+            BugKind::IntegrationTest => &mut self.test_bug_count,
+        };
+        *bug_count += 1;
+        let is_over_budget: bool = *bug_count > self.max_bug_fixes;
+        self.bug_errors = Some(error);
+
+        if is_over_budget {
+            PrintCommand::Issue.print_agent_message(
+                self.attributes.position.as_str(),
+                "Backend Code Unit Testing: Too many bugs found in code",
+            );
+            return Err(format!(
+                "Too many {:?} bugs left after {} fixes:\n{}",
+                kind,
+                self.max_bug_fixes,
+                self.bug_errors.as_deref().unwrap_or_default()
+            ));
+        }
+
+        self.attributes.state = AgentState::Working;
+        Ok(())
+    }
+
+    /// Warns about every route the project served before a revise run that the revised code no longer serves
+    ///
+    /// # Parameters
+    /// - `api_endpoints`: The routes extracted from the revised backend code
+    fn report_removed_routes(&self, api_endpoints: &[RouteObject]) {
+        for previous_route in &self.previous_routes {
+            let is_served: bool = api_endpoints.iter().any(|route_object| {
+                route_object.route == previous_route.route
+                    && route_object.method == previous_route.method
+            });
+            if !is_served {
+                let removed_message: String = format!(
+                    "Backend Code Unit Testing: {} {} is no longer served after the revision",
+                    previous_route.method, previous_route.route
+                );
+                PrintCommand::Issue.print_agent_message(
+                    self.attributes.position.as_str(),
+                    removed_message.as_str(),
+                );
+            }
+        }
+    }
+
+    /// Adds the report of a stopped test server to the run report and saves it
+    ///
+    /// # Parameters
+    /// - `server_report`: What happened to the test server, including its logs
+    fn add_run_report(&mut self, server_report: ServerRunReport) {
+        self.run_reports.push(server_report);
+        let run_report: String = serde_json::to_string_pretty(&self.run_reports)
+            .expect("Failed to serialize run report");
+        save_run_report(&run_report);
+    }
+
+    /// Builds the OpenAPI document of the extracted routes and saves it to the generated project
+    ///
+    /// The document is validated against the OpenAPI 3.1 schema; problems are reported but do not stop the run.
+    ///
+    /// # Parameters
+    /// - `fact_sheet`: The fact sheet containing the project description and domain model
+    /// - `api_endpoints`: The routes extracted from the backend code
+    ///
+    /// # Returns
+    /// - The OpenAPI document the web server must serve under `/openapi.json`
+    fn call_write_openapi_document(
+        &self,
+        fact_sheet: &FactSheet,
+        api_endpoints: &[RouteObject],
+    ) -> Value {
+        let openapi_document: Value = build_openapi_document(
+            &get_web_server_binary_name(),
+            &fact_sheet.project_description,
+            api_endpoints,
+            fact_sheet.domain_model.as_ref(),
+        );
+        for issue in validate_openapi_document(&openapi_document, &read_openapi_schema()) {
+            PrintCommand::Issue.print_agent_message(
+                self.attributes.position.as_str(),
+                format!(
+                    "Backend Code Unit Testing: Invalid OpenAPI document: {}",
+                    issue
+                )
+                .as_str(),
+            );
+        }
+        save_openapi_document(
+            &serde_json::to_string_pretty(&openapi_document)
+                .expect("Failed to serialize OpenAPI document"),
+        );
+        openapi_document
+    }
+
+    /// Tests every extracted route against the running web server
+    ///
+    /// Resources are created via POST with payloads synthesized from the route schema,
+    /// then dynamic GET, PUT and DELETE routes are called with the created ids.
+    /// Finally the OpenAPI document is fetched to check the server serves it.
+    ///
+    /// # Parameters
+    /// - `api_endpoints`: The routes extracted from the backend code
+    /// - `domain_model`: The domain model the payloads and responses are checked against, if designed
+    /// - `openapi_document`: The OpenAPI document the server must serve
+    /// - `port`: The port the web server is listening on
+    ///
+    /// # Returns
+    /// - The result of each endpoint call
+    async fn call_test_endpoints(
+        &self,
+        api_endpoints: &[RouteObject],
+        domain_model: Option<&DomainModel>,
+        openapi_document: &Value,
+        port: u16,
+    ) -> Vec<EndpointTestResult> {
+        let client: Client = Client::builder()
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap();
+
+        let base_url: String = format!("http://127.0.0.1:{}", port);
+        let mut test_results: Vec<EndpointTestResult> =
+            run_endpoint_tests(&client, &base_url, api_endpoints, domain_model).await;
+        test_results.push(check_openapi_served(&client, &base_url, openapi_document).await);
+
+        for test_result in &test_results {
+            let test_message: String = format!(
+                "Testing endpoint: '{} {}'...",
+                test_result.method, test_result.route
+            );
+            PrintCommand::UnitTest
+                .print_agent_message(self.attributes.position.as_str(), test_message.as_str());
+
+            if let Some(error) = &test_result.error {
+                let error_msg: String = format!(
+                    "WARNING: Failed to call backend endpoint {} {}: {}",
+                    test_result.method, test_result.url, error
+                );
+                PrintCommand::Issue
+                    .print_agent_message(self.attributes.position.as_str(), error_msg.as_str());
+            }
+        }
+
+        test_results
     }
 }
 
@@ -153,162 +602,283 @@ impl SpecialFunctions for AgentBackendDeveloper {
         while self.attributes.state != AgentState::Finished {
             match &self.attributes.state {
                 AgentState::Discovery => {
-                    self.call_initial_backend_code(fact_sheet).await;
-                    self.attributes.state = AgentState::Working;
+                    // A revision edits working code, so it goes straight to testing
+                    if let (Some(change_request), Some(_)) =
+                        (fact_sheet.change_request.clone(), &fact_sheet.backend_code)
+                    {
+                        self.call_revise_backend_code(fact_sheet).await;
+                        record_project_stage(
+                            &ProjectStage::Revised { change_request },
+                            fact_sheet,
+                            &self.attributes.position,
+                        );
+                        self.attributes.state = AgentState::UnitTesting;
+                    } else {
+                        self.call_initial_backend_code(fact_sheet).await;
+                        record_project_stage(
+                            &ProjectStage::Initial,
+                            fact_sheet,
+                            &self.attributes.position,
+                        );
+                        self.attributes.state = AgentState::Working;
+                    }
                     continue;
                 }
 
                 AgentState::Working => {
-                    if self.bug_count == 0 {
+                    if self.bug_errors.is_none() {
                         self.call_improved_backend_code(fact_sheet).await;
+                        record_project_stage(
+                            &ProjectStage::Improved,
+                            fact_sheet,
+                            &self.attributes.position,
+                        );
                     } else {
                         self.call_fix_code_bugs(fact_sheet).await;
+                        self.fix_count += 1;
+                        let fix_stage: ProjectStage = ProjectStage::Fix {
+                            iteration: self.fix_count,
+                            errors: self.bug_errors.clone().unwrap_or_default(),
+                        };
+                        record_project_stage(&fix_stage, fact_sheet, &self.attributes.position);
                     }
                     self.attributes.state = AgentState::UnitTesting;
                     continue;
                 }
 
                 AgentState::UnitTesting => {
+                    // The crate allowlist only applies to Rust, requirements come from the templates
+                    if self.framework.language() == TargetLanguage::Rust {
+                        PrintCommand::UnitTest.print_agent_message(
+                            self.attributes.position.as_str(),
+                            "Backend Code Unit Testing: Checking dependencies",
+                        );
+
+                        if let Err(dependency_errors) = self.call_check_dependencies() {
+                            PrintCommand::Issue.print_agent_message(
+                                self.attributes.position.as_str(),
+                                dependency_errors.as_str(),
+                            );
+                            self.record_bug(BugKind::Build, dependency_errors)?;
+                            continue;
+                        }
+                    }
+
                     PrintCommand::UnitTest.print_agent_message(
-                        &self.attributes.position.as_str(),
+                        self.attributes.position.as_str(),
                         "Backend Code Unit Testing: Ensuring Safe Code",
                     );
 
-                    let user_confirmation: bool = confirm_safe_code();
+                    let sandbox_message: String = format!(
+                        "Backend Code Unit Testing: Code will be built and run with sandbox: {}",
+                        SandboxBackend::detect().describe()
+                    );
+                    PrintCommand::UnitTest.print_agent_message(
+                        self.attributes.position.as_str(),
+                        sandbox_message.as_str(),
+                    );
+
+                    let allowed_hosts: Vec<String> =
+                        hosts_from_urls(&fact_sheet.external_urls.clone().unwrap_or_default());
+                    let safety_scan: Result<Vec<SafetyFinding>, syn::Error> =
+                        match self.framework.language() {
+                            TargetLanguage::Rust => {
+                                scan_generated_code(&read_exec_main_contents(), &allowed_hosts)
+                            }
+                            TargetLanguage::Python => {
+                                Ok(scan_python_code(&read_exec_main_contents(), &allowed_hosts))
+                            }
+                        };
+                    let user_confirmation: bool =
+                        confirm_safe_code(&format_safety_report(&safety_scan));
 
                     if !user_confirmation {
-                        panic!("Better go work on some AI alignment instead...");
+                        return Err("Better go work on some AI alignment instead...".into());
                     }
 
                     PrintCommand::UnitTest.print_agent_message(
-                        &self.attributes.position.as_str(),
+                        self.attributes.position.as_str(),
                         "Backend Code Unit Testing: Building web server...",
                     );
 
-                    let build_backend_server = Command::new("cargo")
-                        .args(&["build", "--bin", "main2"])
-                        .current_dir(get_web_server_project_path())
-                        .stdout(Stdio::piped())
-                        .stderr(Stdio::piped())
-                        .output()
-                        .expect("Failed to build backend application");
-
-                    if build_backend_server.status.success() {
-                        self.bug_count = 0;
-                        PrintCommand::UnitTest.print_agent_message(
-                            &self.attributes.position.as_str(),
-                            "Backend Code Unit Testing: Test server build successful...",
-                        );
-                    } else {
-                        let error_arr: Vec<u8> = build_backend_server.stderr;
-                        let error_str = String::from_utf8(error_arr).unwrap();
-                        self.bug_count += 1;
-                        self.bug_errors = Some(error_str);
-
-                        if self.bug_count > 2 {
-                            PrintCommand::Issue.print_agent_message(
+                    match self.call_build_web_server().await {
+                        Ok(()) => {
+                            self.bug_count = 0;
+                            PrintCommand::UnitTest.print_agent_message(
                                 self.attributes.position.as_str(),
-                                "Backend Code Unit Testing: Too many bugs found in code",
+                                "Backend Code Unit Testing: Test server build successful...",
                             );
-                            panic!("Error: Too many bugs")
                         }
-
-                        self.attributes.state = AgentState::Working;
-                        continue;
+                        Err(build_errors) => {
+                            self.record_bug(BugKind::Build, build_errors)?;
+                            continue;
+                        }
                     }
 
-                    let api_endpoints_str: String = self.call_extract_rest_api_endpoints().await;
-
-                    let api_endpoints: Vec<RouteObject> =
-                        serde_json::from_str(api_endpoints_str.as_str())
-                            .expect("Failed to parse API endpoints");
-
-                    let check_endpoints: Vec<RouteObject> = api_endpoints
-                        .iter()
-                        .filter(|&route_object| {
-                            route_object.method == "get" && route_object.is_route_dynamic == "false"
-                        })
-                        .cloned()
-                        .collect();
+                    let mut api_endpoints: Vec<RouteObject> =
+                        match self.call_extract_rest_api_endpoints().await {
+                            Ok(api_endpoints) => api_endpoints,
+                            Err(extraction_error) => {
+                                self.record_bug(BugKind::Runtime, extraction_error)?;
+                                continue;
+                            }
+                        };
+                    api_endpoints.retain(|route_object| route_object.route != OPENAPI_ROUTE);
+                    self.report_removed_routes(&api_endpoints);
 
-                    fact_sheet.api_endpoint_schema = Some(check_endpoints.clone());
+                    fact_sheet.api_endpoint_schema = Some(api_endpoints.clone());
+                    let openapi_document: Value =
+                        self.call_write_openapi_document(fact_sheet, &api_endpoints);
 
                     PrintCommand::UnitTest.print_agent_message(
-                        &self.attributes.position.as_str(),
+                        self.attributes.position.as_str(),
                         "Backend Code Unit Testing: Starting Web server...",
                     );
 
+                    let external_urls: Vec<String> =
+                        fact_sheet.external_urls.clone().unwrap_or_default();
+                    let (test_server, _egress_proxy): (TestServerGuard, EgressProxy) =
+                        match start_sandboxed_web_server(self.framework, &external_urls).await {
+                            Ok(started) => started,
+                            Err(ServerLaunchError::NotReady(start_error, server_report)) => {
+                                let bug_error: String = format!(
+                                    "{}\nSERVER STDOUT:\n{}\nSERVER STDERR:\n{}",
+                                    start_error, server_report.stdout, server_report.stderr
+                                );
+                                self.add_run_report(server_report);
+                                self.record_bug(BugKind::Runtime, bug_error)?;
+                                continue;
+                            }
+                            Err(launch_error) => return Err(launch_error.into()),
+                        };
+                    let port: u16 = test_server.port();
 
-                    let binary_name = "main2";
-                    let binary_path = Path::new("target/debug").join(binary_name);
-                    if binary_path.exists() {
-                        println!("Running binary: {:?}", binary_path);
-                    }
-
-                    let mut run_backend_server: std::process::Child = Command::new(binary_path)
-                        .stdout(Stdio::inherit())
-                        .stderr(Stdio::inherit())
-                        .spawn()
-                        .expect("Failed to run backend application");
-
+                    let ready_message: String = format!(
+                        "Backend Code Unit Testing: Server is ready on port {}...",
+                        port
+                    );
                     PrintCommand::UnitTest.print_agent_message(
-                        &self.attributes.position.as_str(),
-                        "Backend Code Unit Testing: Launching test on server in 5 sec...",
+                        self.attributes.position.as_str(),
+                        ready_message.as_str(),
                     );
 
-                    let seconds_sleep: Duration = Duration::from_secs(5);
-                    time::sleep(seconds_sleep).await;
+                    let test_results: Option<Vec<EndpointTestResult>> = time::timeout(
+                        ENDPOINT_TESTS_TIMEOUT,
+                        self.call_test_endpoints(
+                            &api_endpoints,
+                            fact_sheet.domain_model.as_ref(),
+                            &openapi_document,
+                            port,
+                        ),
+                    )
+                    .await
+                    .ok();
+                    let server_exit: Option<ExitStatus> = test_server.exit_status();
+
+                    let outcome: &str = match (&test_results, server_exit) {
+                        (None, _) => "Endpoint tests timed out",
+                        (Some(_), Some(_)) => "Crashed during endpoint tests",
+                        (Some(_), None) => "Stopped after endpoint tests",
+                    };
+                    let server_report: ServerRunReport = test_server.stop(outcome);
+                    let server_log_tail: String = server_report.log_tail();
+                    self.add_run_report(server_report);
+
+                    let Some(test_results) = test_results else {
+                        self.record_bug(
+                            BugKind::Runtime,
+                            format!(
+                                "Endpoint tests did not finish within {} seconds, a handler may never respond\nSERVER LOG TAIL:\n{}",
+                                ENDPOINT_TESTS_TIMEOUT.as_secs(),
+                                server_log_tail
+                            ),
+                        )?;
+                        continue;
+                    };
+
+                    let bug_reports: Vec<RuntimeBugReport> = test_results
+                        .iter()
+                        .filter_map(|test_result| test_result.to_bug_report(&server_log_tail))
+                        .collect();
 
-                    for endpoint in check_endpoints {
-                        let test_message: String =
-                            format!("Testing endpoint: '{}...'", endpoint.route);
+                    if server_exit.is_some() || !bug_reports.is_empty() {
+                        let crash_note: String = match server_exit {
+                            Some(status) => format!(
+                                "Web server crashed during endpoint tests ({})\nSERVER LOG TAIL:\n{}\n",
+                                status, server_log_tail
+                            ),
+                            None => String::new(),
+                        };
+                        self.record_bug(
+                            BugKind::Runtime,
+                            format!(
+                                "{}RUNTIME TEST FAILURES:\n{}",
+                                crash_note,
+                                serde_json::to_string_pretty(&bug_reports)
+                                    .expect("Failed to serialize bug reports")
+                            ),
+                        )?;
+                        continue;
+                    }
+
+                    let reviewed_code: Option<String> = fact_sheet.backend_code.clone();
+                    self.security_reviewer.execute(fact_sheet).await?;
+                    if fact_sheet.backend_code != reviewed_code {
+                        record_project_stage(
+                            &ProjectStage::SecurityFixes,
+                            fact_sheet,
+                            &self.attributes.position,
+                        );
                         PrintCommand::UnitTest.print_agent_message(
-                            &self.attributes.position.as_str(),
-                            test_message.as_str(),
+                            self.attributes.position.as_str(),
+                            "Backend Code Unit Testing: Re-testing code with security fixes",
                         );
+                        continue;
+                    }
 
-                        let client: Client = Client::builder()
-                            .timeout(Duration::from_secs(5))
-                            .build()
-                            .unwrap();
-
-                        let url: String = format!("http://127.0.0.1:8080{}", endpoint.route);
-                        match check_status_code(&client, &url).await {
-                            Ok(status_code) => {
-                                if status_code != 200 {
-                                    let error_msg: String = format!(
-                                        "WARNING: Failed to call backend endpoint {}",
-                                        endpoint.route
-                                    );
-                                    PrintCommand::Issue.print_agent_message(
-                                        &self.attributes.position.as_str(),
-                                        error_msg.as_str(),
-                                    );
-                                }
-                            }
-                            Err(error_msg) => {
-                                run_backend_server
-                                    .kill()
-                                    .expect("Unable to stop backend application");
-                                let error_msg: String =
-                                    format!("ERROR: While checking backend {}", error_msg);
-                                PrintCommand::Issue.print_agent_message(
-                                    &self.attributes.position.as_str(),
-                                    error_msg.as_str(),
-                                );
-                            }
-                        }
+                    if let Err(test_failures) = self.test_writer.execute(fact_sheet).await {
+                        self.record_bug(BugKind::IntegrationTest, test_failures.to_string())?;
+                        continue;
                     }
 
+                    let api_endpoints_str: String = serde_json::to_string_pretty(&api_endpoints)
+                        .expect("Failed to serialize API endpoints");
                     save_api_endpoints(&api_endpoints_str);
-                    PrintCommand::Success.print_agent_message(
-                        &self.attributes.position.as_str(),
-                        "Backend Testing completed...",
+                    let skipped_checks: Vec<String> = [
+                        (
+                            self.security_reviewer.covers_framework(),
+                            "the security review",
+                        ),
+                        (self.test_writer.covers_framework(), "the integration tests"),
+                    ]
+                    .into_iter()
+                    .filter(|(is_run, _)| !is_run)
+                    .map(|(_, check)| check.to_string())
+                    .collect();
+                    record_project_stage(
+                        &ProjectStage::TestsPassing {
+                            skipped_checks: skipped_checks.clone(),
+                        },
+                        fact_sheet,
+                        &self.attributes.position,
                     );
+                    if skipped_checks.is_empty() {
+                        PrintCommand::Success.print_agent_message(
+                            self.attributes.position.as_str(),
+                            "Backend Testing completed...",
+                        );
+                    } else {
+                        let skipped_message: String = format!(
+                            "Backend Testing completed WITHOUT {}, which are not available for {}: check the server manually",
+                            skipped_checks.join(" and "),
+                            self.framework
+                        );
+                        PrintCommand::Issue.print_agent_message(
+                            self.attributes.position.as_str(),
+                            skipped_message.as_str(),
+                        );
+                    }
 
-                    run_backend_server
-                        .kill()
-                        .expect("failed to Kill server on completion");
                     self.attributes.state = AgentState::Finished;
                 }
                 _ => {}
@@ -318,7 +888,6 @@ impl SpecialFunctions for AgentBackendDeveloper {
     }
 }
 
-
 // #[cfg(test)]
 // mod tests {
 //     use super::*;
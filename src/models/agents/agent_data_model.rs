@@ -0,0 +1,199 @@
+use crate::ai_functions::ai_func_data_model::{
+    print_domain_model, print_fixed_domain_model, print_revised_domain_model,
+};
+use crate::helpers::command_lines::PrintCommand;
+use crate::helpers::domain_model::validate_domain_model;
+use crate::helpers::general::ai_task_request_decoded;
+use crate::models::agent_basic::basic_agent::{AgentState, BasicAgent};
+use crate::models::agents::agent_traits::{DomainModel, FactSheet, SpecialFunctions};
+
+use async_trait::async_trait;
+
+/// Struct representing a data-model designer agent
+/// Attributes:
+/// - `attributes`: Basic agent properties (objective, position, etc.)
+/// - `model_issues`: The problems found in the last designed model
+/// - `fix_count`: Counter for the number of times the model was sent back for fixing
+#[derive(Debug)]
+pub struct AgentDataModelDesigner {
+    attributes: BasicAgent,
+    model_issues: Vec<String>,
+    fix_count: u8,
+}
+
+/// How many times an inconsistent model is sent back for fixing before it is used as is.
+const MAX_MODEL_FIXES: u8 = 2;
+
+impl Default for AgentDataModelDesigner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AgentDataModelDesigner {
+    /// Creates a new instance of `AgentDataModelDesigner` with default attributes
+    pub fn new() -> Self {
+        let attributes: BasicAgent = BasicAgent {
+            objective: "Designs the entities, fields and relations the website stores".to_string(),
+            position: "Data Modeller".to_string(),
+            state: AgentState::Discovery,
+            memory: vec![],
+        };
+
+        Self {
+            attributes,
+            model_issues: vec![],
+            fix_count: 0,
+        }
+    }
+
+    /// Designs the domain model from the project context and scope
+    ///
+    /// # Parameters
+    /// - `fact_sheet`: A mutable reference to the fact sheet containing project information
+    async fn call_design_domain_model(&mut self, fact_sheet: &mut FactSheet) {
+        let msg_context: String = format!(
+            "PROJECT_DESCRIPTION: {} \n PROJECT_SCOPE: {:?} \n",
+            fact_sheet.project_context(),
+            fact_sheet.project_scope
+        );
+
+        let domain_model: DomainModel = ai_task_request_decoded::<DomainModel>(
+            msg_context,
+            &self.attributes.position,
+            get_function_string!(print_domain_model),
+            print_domain_model,
+        )
+        .await;
+        fact_sheet.domain_model = Some(domain_model);
+    }
+
+    /// Applies the fact sheet's change request to the domain model of an earlier run
+    ///
+    /// # Parameters
+    /// - `fact_sheet`: A mutable reference to the fact sheet containing project information
+    async fn call_revise_domain_model(&mut self, fact_sheet: &mut FactSheet) {
+        let msg_context: String = format!(
+            "DATA_MODEL: {} \n CHANGE_REQUEST: {:?} \n",
+            serde_json::to_string(&fact_sheet.domain_model)
+                .expect("Failed to serialize domain model"),
+            fact_sheet.change_request
+        );
+
+        let domain_model: DomainModel = ai_task_request_decoded::<DomainModel>(
+            msg_context,
+            &self.attributes.position,
+            get_function_string!(print_revised_domain_model),
+            print_revised_domain_model,
+        )
+        .await;
+        fact_sheet.domain_model = Some(domain_model);
+    }
+
+    /// Fixes the problems found in the domain model
+    ///
+    /// # Parameters
+    /// - `fact_sheet`: A mutable reference to the fact sheet containing project information
+    async fn call_fix_domain_model(&mut self, fact_sheet: &mut FactSheet) {
+        let msg_context: String = format!(
+            "DATA_MODEL: {} \n ISSUES: {:?} \n",
+            serde_json::to_string(&fact_sheet.domain_model)
+                .expect("Failed to serialize domain model"),
+            self.model_issues
+        );
+
+        let domain_model: DomainModel = ai_task_request_decoded::<DomainModel>(
+            msg_context,
+            &self.attributes.position,
+            get_function_string!(print_fixed_domain_model),
+            print_fixed_domain_model,
+        )
+        .await;
+        fact_sheet.domain_model = Some(domain_model);
+    }
+}
+
+/// Implementation of special functions for `AgentDataModelDesigner`
+#[async_trait]
+impl SpecialFunctions for AgentDataModelDesigner {
+    /// Retrieves the attributes of the agent
+    fn get_attributes_from_agent(&self) -> &BasicAgent {
+        &self.attributes
+    }
+
+    /// Executes the agent's workflow based on its state
+    ///
+    /// # Parameters
+    /// - `fact_sheet`: A mutable reference to the fact sheet containing project information
+    ///
+    /// # Returns
+    /// - A result indicating success or an error
+    async fn execute(
+        &mut self,
+        fact_sheet: &mut FactSheet,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        while self.attributes.state != AgentState::Finished {
+            match &self.attributes.state {
+                AgentState::Discovery => {
+                    if fact_sheet.change_request.is_some() && fact_sheet.domain_model.is_some() {
+                        self.call_revise_domain_model(fact_sheet).await;
+                    } else {
+                        self.call_design_domain_model(fact_sheet).await;
+                    }
+                    self.attributes.state = AgentState::UnitTesting;
+                }
+
+                AgentState::Working => {
+                    self.call_fix_domain_model(fact_sheet).await;
+                    self.attributes.state = AgentState::UnitTesting;
+                }
+
+                AgentState::UnitTesting => {
+                    let domain_model: &DomainModel = fact_sheet
+                        .domain_model
+                        .as_ref()
+                        .expect("Missing domain model");
+                    self.model_issues = validate_domain_model(domain_model);
+
+                    if self.model_issues.is_empty() {
+                        let model_message: String = format!(
+                            "Data Model: Designed {} entities\n{}",
+                            domain_model.entities.len(),
+                            serde_json::to_string_pretty(domain_model)
+                                .expect("Failed to serialize domain model")
+                        );
+                        PrintCommand::Success.print_agent_message(
+                            self.attributes.position.as_str(),
+                            model_message.as_str(),
+                        );
+                        self.attributes.state = AgentState::Finished;
+                        continue;
+                    }
+
+                    for issue in &self.model_issues {
+                        let issue_message: String = format!("Data Model: {}", issue);
+                        PrintCommand::Issue.print_agent_message(
+                            self.attributes.position.as_str(),
+                            issue_message.as_str(),
+                        );
+                    }
+
+                    if self.fix_count >= MAX_MODEL_FIXES {
+                        PrintCommand::Issue.print_agent_message(
+                            self.attributes.position.as_str(),
+                            "Data Model: Issues remain after fixing, using the model as is",
+                        );
+                        self.attributes.state = AgentState::Finished;
+                        continue;
+                    }
+
+                    self.fix_count += 1;
+                    self.attributes.state = AgentState::Working;
+                }
+
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
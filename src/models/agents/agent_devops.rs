@@ -0,0 +1,167 @@
+use crate::helpers::command_lines::PrintCommand;
+use crate::helpers::devops::{render_devops_files, validate_devops_files, DevOpsFile};
+use crate::helpers::general::{
+    get_web_server_binary_name, read_devops_templates, save_devops_files,
+};
+use crate::helpers::safety_scanner::find_env_vars;
+use crate::helpers::server_process::{
+    WEB_SERVER_HOST_ENV, WEB_SERVER_OPENAPI_PATH_ENV, WEB_SERVER_PORT_ENV,
+    WEB_SERVER_STATIC_DIR_ENV,
+};
+use crate::helpers::target_framework::{TargetFramework, TargetLanguage};
+use crate::models::agent_basic::basic_agent::{AgentState, BasicAgent};
+use crate::models::agents::agent_traits::{FactSheet, SpecialFunctions};
+
+use async_trait::async_trait;
+
+/// Struct representing a DevOps agent
+/// Attributes:
+/// - `attributes`: Basic agent properties (objective, position, etc.)
+/// - `files`: The deployment files written into the web server project
+#[derive(Debug)]
+pub struct AgentDevOps {
+    attributes: BasicAgent,
+    files: Vec<DevOpsFile>,
+}
+
+impl Default for AgentDevOps {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AgentDevOps {
+    /// Creates a new instance of `AgentDevOps` with default attributes
+    pub fn new() -> Self {
+        let attributes: BasicAgent = BasicAgent {
+            objective: "Packages the webserver as a container and sets up CI".to_string(),
+            position: "DevOps Engineer".to_string(),
+            state: AgentState::Discovery,
+            memory: vec![],
+        };
+
+        Self {
+            attributes,
+            files: vec![],
+        }
+    }
+
+    /// Finds the environment variables the backend reads
+    ///
+    /// Falls back to the variables of the code template if the backend code cannot be parsed.
+    ///
+    /// # Parameters
+    /// - `backend_code`: The generated backend code
+    ///
+    /// # Returns
+    /// - The sorted variable names
+    fn call_find_env_vars(&self, backend_code: &str) -> Vec<String> {
+        find_env_vars(backend_code).unwrap_or_else(|e| {
+            let parse_message: String = format!(
+                "DevOps: Unable to parse backend code ({}), using the template environment variables",
+                e
+            );
+            PrintCommand::Issue.print_agent_message(
+                self.attributes.position.as_str(),
+                parse_message.as_str(),
+            );
+            vec![
+                WEB_SERVER_HOST_ENV.to_string(),
+                WEB_SERVER_PORT_ENV.to_string(),
+                WEB_SERVER_STATIC_DIR_ENV.to_string(),
+                WEB_SERVER_OPENAPI_PATH_ENV.to_string(),
+            ]
+        })
+    }
+}
+
+/// Implementation of special functions for `AgentDevOps`
+#[async_trait]
+impl SpecialFunctions for AgentDevOps {
+    /// Retrieves the attributes of the agent
+    fn get_attributes_from_agent(&self) -> &BasicAgent {
+        &self.attributes
+    }
+
+    /// Executes the agent's workflow based on its state
+    ///
+    /// # Parameters
+    /// - `fact_sheet`: A mutable reference to the fact sheet containing project information
+    ///
+    /// # Returns
+    /// - A result indicating success or an error
+    async fn execute(
+        &mut self,
+        fact_sheet: &mut FactSheet,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        while self.attributes.state != AgentState::Finished {
+            match &self.attributes.state {
+                AgentState::Discovery => {
+                    let Some(backend_code) = fact_sheet.backend_code.clone() else {
+                        PrintCommand::Issue.print_agent_message(
+                            self.attributes.position.as_str(),
+                            "DevOps: No backend code to package, skipping",
+                        );
+                        self.attributes.state = AgentState::Finished;
+                        continue;
+                    };
+                    if TargetFramework::from_env().language() != TargetLanguage::Rust {
+                        PrintCommand::Issue.print_agent_message(
+                            self.attributes.position.as_str(),
+                            "DevOps: Deployment files are only templated for Rust servers, skipping",
+                        );
+                        self.attributes.state = AgentState::Finished;
+                        continue;
+                    }
+
+                    let env_vars: Vec<String> = self.call_find_env_vars(&backend_code);
+                    self.files = render_devops_files(
+                        &get_web_server_binary_name(),
+                        &env_vars,
+                        &read_devops_templates(),
+                    );
+                    save_devops_files(&self.files);
+                    fact_sheet.env_vars = Some(env_vars);
+
+                    let files_message: String = format!(
+                        "DevOps: Wrote {} to the web server project",
+                        self.files
+                            .iter()
+                            .map(|file| file.path.as_str())
+                            .collect::<Vec<&str>>()
+                            .join(", ")
+                    );
+                    PrintCommand::UnitTest.print_agent_message(
+                        self.attributes.position.as_str(),
+                        files_message.as_str(),
+                    );
+                    self.attributes.state = AgentState::UnitTesting;
+                }
+
+                AgentState::UnitTesting => {
+                    self.attributes.state = AgentState::Finished;
+
+                    let issues: Vec<String> = validate_devops_files(&self.files);
+                    if issues.is_empty() {
+                        PrintCommand::Success.print_agent_message(
+                            self.attributes.position.as_str(),
+                            "DevOps Testing completed, Dockerfile, compose file and CI workflow passed validation...",
+                        );
+                        continue;
+                    }
+
+                    for issue in &issues {
+                        let issue_message: String = format!("DevOps Unit Testing: {}", issue);
+                        PrintCommand::Issue.print_agent_message(
+                            self.attributes.position.as_str(),
+                            issue_message.as_str(),
+                        );
+                    }
+                }
+
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,222 @@
+use crate::helpers::command_lines::PrintCommand;
+use crate::helpers::egress_proxy::EgressProxy;
+use crate::helpers::endpoint_testing::{run_endpoint_tests, EndpointTestResult};
+use crate::helpers::general::{
+    get_web_server_binary_name, get_web_server_program_path, save_project_readme,
+    web_server_project_file_exists,
+};
+use crate::helpers::project_docs::{examples_from_results, render_project_readme, EndpointExample};
+use crate::helpers::server_process::{
+    start_sandboxed_web_server, ServerLaunchError, TestServerGuard, ENDPOINT_TESTS_TIMEOUT,
+};
+use crate::helpers::target_framework::TargetFramework;
+use crate::models::agent_basic::basic_agent::{AgentState, BasicAgent};
+use crate::models::agents::agent_traits::{FactSheet, RouteObject, SpecialFunctions};
+
+use async_trait::async_trait;
+use reqwest::Client;
+use std::path::Path;
+use std::time::Duration;
+use tokio::time;
+
+/// Struct representing a documentation agent
+/// Attributes:
+/// - `attributes`: Basic agent properties (objective, position, etc.)
+/// - `examples`: The endpoint calls verified against the live test server
+#[derive(Debug)]
+pub struct AgentDocumentation {
+    attributes: BasicAgent,
+    examples: Vec<EndpointExample>,
+}
+
+impl Default for AgentDocumentation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AgentDocumentation {
+    /// Creates a new instance of `AgentDocumentation` with default attributes
+    pub fn new() -> Self {
+        let attributes: BasicAgent = BasicAgent {
+            objective: "Documents how to run and call the webserver".to_string(),
+            position: "Technical Writer".to_string(),
+            state: AgentState::Discovery,
+            memory: vec![],
+        };
+
+        Self {
+            attributes,
+            examples: vec![],
+        }
+    }
+
+    /// Calls every route on the running test server and keeps the passing calls as examples
+    ///
+    /// # Parameters
+    /// - `routes`: The API endpoint schema
+    /// - `fact_sheet`: The fact sheet containing the domain model
+    /// - `port`: The port the test server listens on
+    ///
+    /// # Returns
+    /// - The calls that failed and are left out of the documentation
+    async fn call_verify_examples(
+        &mut self,
+        routes: &[RouteObject],
+        fact_sheet: &FactSheet,
+        port: u16,
+    ) -> Vec<EndpointTestResult> {
+        let client: Client = Client::builder()
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap();
+        let base_url: String = format!("http://127.0.0.1:{}", port);
+
+        let test_results: Vec<EndpointTestResult> =
+            run_endpoint_tests(&client, &base_url, routes, fact_sheet.domain_model.as_ref()).await;
+        self.examples = examples_from_results(&test_results, &base_url);
+        test_results
+            .into_iter()
+            .filter(|test_result| test_result.error.is_some())
+            .collect()
+    }
+
+    /// Runs the examples against a sandboxed test server
+    ///
+    /// # Parameters
+    /// - `routes`: The API endpoint schema
+    /// - `fact_sheet`: The fact sheet containing the external urls and domain model
+    ///
+    /// # Returns
+    /// - An error if the web server could not be spawned at all
+    async fn call_run_examples(
+        &mut self,
+        routes: &[RouteObject],
+        fact_sheet: &FactSheet,
+    ) -> Result<(), ServerLaunchError> {
+        let framework: TargetFramework = TargetFramework::from_env();
+        if !Path::new(&get_web_server_program_path(framework)).exists() {
+            PrintCommand::Issue.print_agent_message(
+                self.attributes.position.as_str(),
+                "Documentation: Web server is not built, writing the API reference without examples",
+            );
+            return Ok(());
+        }
+
+        let external_urls: Vec<String> = fact_sheet.external_urls.clone().unwrap_or_default();
+        let (test_server, _egress_proxy): (TestServerGuard, EgressProxy) =
+            match start_sandboxed_web_server(framework, &external_urls).await {
+                Ok(started) => started,
+                Err(ServerLaunchError::NotReady(start_error, server_report)) => {
+                    let start_message: String = format!(
+                        "Documentation: {}, writing the API reference without examples\n{}",
+                        start_error,
+                        server_report.log_tail()
+                    );
+                    PrintCommand::Issue.print_agent_message(
+                        self.attributes.position.as_str(),
+                        start_message.as_str(),
+                    );
+                    return Ok(());
+                }
+                Err(launch_error) => return Err(launch_error),
+            };
+        let port: u16 = test_server.port();
+
+        let failures: Option<Vec<EndpointTestResult>> = time::timeout(
+            ENDPOINT_TESTS_TIMEOUT,
+            self.call_verify_examples(routes, fact_sheet, port),
+        )
+        .await
+        .ok();
+        test_server.stop("Stopped after documentation examples");
+
+        let Some(failures) = failures else {
+            PrintCommand::Issue.print_agent_message(
+                self.attributes.position.as_str(),
+                "Documentation: Examples timed out, writing the API reference without examples",
+            );
+            self.examples.clear();
+            return Ok(());
+        };
+        for failure in &failures {
+            let failure_message: String = format!(
+                "Documentation: Left out the example for {} {}: {}",
+                failure.method,
+                failure.route,
+                failure.error.clone().unwrap_or_default()
+            );
+            PrintCommand::Issue
+                .print_agent_message(self.attributes.position.as_str(), failure_message.as_str());
+        }
+        Ok(())
+    }
+}
+
+/// Implementation of special functions for `AgentDocumentation`
+#[async_trait]
+impl SpecialFunctions for AgentDocumentation {
+    /// Retrieves the attributes of the agent
+    fn get_attributes_from_agent(&self) -> &BasicAgent {
+        &self.attributes
+    }
+
+    /// Executes the agent's workflow based on its state
+    ///
+    /// # Parameters
+    /// - `fact_sheet`: A mutable reference to the fact sheet containing project information
+    ///
+    /// # Returns
+    /// - A result indicating success or an error
+    async fn execute(
+        &mut self,
+        fact_sheet: &mut FactSheet,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        while self.attributes.state != AgentState::Finished {
+            match &self.attributes.state {
+                AgentState::Discovery => {
+                    if fact_sheet.backend_code.is_none() {
+                        PrintCommand::Issue.print_agent_message(
+                            self.attributes.position.as_str(),
+                            "Documentation: No backend code to document, skipping",
+                        );
+                        self.attributes.state = AgentState::Finished;
+                        continue;
+                    }
+                    self.attributes.state = AgentState::UnitTesting;
+                }
+
+                AgentState::UnitTesting => {
+                    let routes: Vec<RouteObject> =
+                        fact_sheet.api_endpoint_schema.clone().unwrap_or_default();
+                    if !routes.is_empty() {
+                        self.call_run_examples(&routes, fact_sheet).await?;
+                    }
+
+                    let readme: String = render_project_readme(
+                        &get_web_server_binary_name(),
+                        TargetFramework::from_env(),
+                        fact_sheet,
+                        &self.examples,
+                        web_server_project_file_exists("Dockerfile"),
+                    );
+                    save_project_readme(&readme);
+
+                    let readme_message: String = format!(
+                        "Documentation completed, README.md documents {} routes with {} verified examples...",
+                        routes.len(),
+                        self.examples.len()
+                    );
+                    PrintCommand::Success.print_agent_message(
+                        self.attributes.position.as_str(),
+                        readme_message.as_str(),
+                    );
+                    self.attributes.state = AgentState::Finished;
+                }
+
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
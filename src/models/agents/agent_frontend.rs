@@ -0,0 +1,256 @@
+use crate::ai_functions::ai_func_frontend::{print_fixed_frontend_code, print_yew_frontend_code};
+use crate::helpers::command_lines::PrintCommand;
+use crate::helpers::general::{
+    ai_task_request, env_setting, get_web_frontend_project_path,
+    read_frontend_code_template_contents, save_frontend_code,
+};
+use crate::helpers::sandbox::{build_project_in_sandbox, fetch_project_dependencies};
+use crate::models::agent_basic::basic_agent::{AgentState, BasicAgent};
+use crate::models::agents::agent_traits::{FactSheet, SpecialFunctions};
+
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Struct representing a frontend developer agent
+/// Attributes:
+/// - `attributes`: Basic agent properties (objective, position, etc.)
+/// - `bug_errors`: Optional string describing encountered errors
+/// - `bug_count`: Counter for the number of bugs encountered
+/// - `max_bug_fixes`: How many bugs are sent back for fixing before the agent gives up
+#[derive(Debug)]
+pub struct AgentFrontendDeveloper {
+    attributes: BasicAgent,
+    bug_errors: Option<String>,
+    bug_count: u8,
+    max_bug_fixes: u8,
+}
+
+/// Environment variable overriding how many fix iterations the frontend agent attempts.
+const MAX_BUG_FIXES_ENV: &str = "FRONTEND_MAX_BUG_FIXES";
+/// Default number of fix iterations the frontend agent attempts.
+const DEFAULT_MAX_BUG_FIXES: u8 = 2;
+/// The target Yew apps are compiled for.
+const WASM_TARGET: &str = "wasm32-unknown-unknown";
+
+impl Default for AgentFrontendDeveloper {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AgentFrontendDeveloper {
+    /// Creates a new instance of `AgentFrontendDeveloper` with default attributes
+    pub fn new() -> Self {
+        let attributes: BasicAgent = BasicAgent {
+            objective: "Develops a Yew frontend for the backend api".to_string(),
+            position: "Frontend Developer".to_string(),
+            state: AgentState::Discovery,
+            memory: vec![],
+        };
+
+        Self {
+            attributes,
+            bug_errors: None,
+            bug_count: 0,
+            max_bug_fixes: env_setting(MAX_BUG_FIXES_ENV, DEFAULT_MAX_BUG_FIXES),
+        }
+    }
+
+    /// Generates the Yew app from the code template, project description and api endpoint schema
+    ///
+    /// # Parameters
+    /// - `fact_sheet`: A mutable reference to the fact sheet containing project information
+    async fn call_initial_frontend_code(&mut self, fact_sheet: &mut FactSheet) {
+        let code_template_str: String = read_frontend_code_template_contents();
+        let api_endpoint_schema: String =
+            serde_json::to_string_pretty(&fact_sheet.api_endpoint_schema)
+                .expect("Failed to serialize API endpoints");
+
+        let msg_context: String = format!(
+            "CODE TEMPLATE: {} \n PROJECT_DESCRIPTION: {} \n API_ENDPOINT_SCHEMA: {} \n",
+            code_template_str,
+            fact_sheet.project_context(),
+            api_endpoint_schema
+        );
+
+        let ai_response: String = ai_task_request(
+            msg_context,
+            &self.attributes.position,
+            get_function_string!(print_yew_frontend_code),
+            print_yew_frontend_code,
+        )
+        .await;
+        save_frontend_code(&ai_response);
+        fact_sheet.frontend_code = Some(ai_response);
+    }
+
+    /// Fixes frontend bugs based on compiler errors and broken code
+    ///
+    /// # Parameters
+    /// - `fact_sheet`: A mutable reference to the fact sheet containing project information
+    async fn call_fix_code_bugs(&mut self, fact_sheet: &mut FactSheet) {
+        let msg_context: String = format!(
+            "BROKEN_CODE: {:?} \n ERROR_BUGS: {:?} \n
+      THIS FUNCTION ONLY OUTPUTS CODE. JUST OUTPUT THE CODE.",
+            fact_sheet.frontend_code, self.bug_errors
+        );
+
+        let ai_response: String = ai_task_request(
+            msg_context,
+            &self.attributes.position,
+            get_function_string!(print_fixed_frontend_code),
+            print_fixed_frontend_code,
+        )
+        .await;
+
+        save_frontend_code(&ai_response);
+        fact_sheet.frontend_code = Some(ai_response);
+    }
+
+    /// Builds the generated frontend project for wasm inside the sandbox
+    ///
+    /// # Returns
+    /// - The compiler errors if the build failed or timed out
+    async fn call_build_frontend(&self) -> Result<(), String> {
+        let project_path: String = get_web_frontend_project_path();
+        let target_args: [&str; 2] = ["--target", WASM_TARGET];
+
+        if !fetch_project_dependencies(Path::new(&project_path), &target_args) {
+            PrintCommand::Issue.print_agent_message(
+                self.attributes.position.as_str(),
+                "Frontend Code Unit Testing: Unable to fetch dependencies, building from local cache",
+            );
+        }
+
+        build_project_in_sandbox(Path::new(&project_path), &target_args).await
+    }
+
+    /// Records a bug for the fix loop and sends the agent back to `Working`
+    ///
+    /// # Parameters
+    /// - `error`: A description of the bug, e.g. compiler output
+    ///
+    /// # Returns
+    /// - An error once more bugs were found than `max_bug_fixes` allows
+    fn record_bug(&mut self, error: String) -> Result<(), String> {
+        self.bug_count += 1;
+        self.bug_errors = Some(error);
+
+        if self.bug_count > self.max_bug_fixes {
+            PrintCommand::Issue.print_agent_message(
+                self.attributes.position.as_str(),
+                "Frontend Code Unit Testing: Too many bugs found in code",
+            );
+            return Err(format!(
+                "Too many frontend build errors left after {} fixes:\n{}",
+                self.max_bug_fixes,
+                self.bug_errors.as_deref().unwrap_or_default()
+            ));
+        }
+
+        self.attributes.state = AgentState::Working;
+        Ok(())
+    }
+}
+
+/// Checks whether the standard library for the wasm target is installed in the active toolchain
+fn is_wasm_target_installed() -> bool {
+    Command::new("rustc")
+        .args(["--print", "sysroot"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| {
+            PathBuf::from(String::from_utf8_lossy(&output.stdout).trim())
+                .join("lib")
+                .join("rustlib")
+                .join(WASM_TARGET)
+                .exists()
+        })
+        .unwrap_or(false)
+}
+
+/// Implementation of special functions for `AgentFrontendDeveloper`
+#[async_trait]
+impl SpecialFunctions for AgentFrontendDeveloper {
+    /// Retrieves the attributes of the agent
+    fn get_attributes_from_agent(&self) -> &BasicAgent {
+        &self.attributes
+    }
+
+    /// Executes the agent's workflow based on its state
+    ///
+    /// # Parameters
+    /// - `fact_sheet`: A mutable reference to the fact sheet containing project information
+    ///
+    /// # Returns
+    /// - A result indicating success or an error
+    async fn execute(
+        &mut self,
+        fact_sheet: &mut FactSheet,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        while self.attributes.state != AgentState::Finished {
+            match &self.attributes.state {
+                AgentState::Discovery => {
+                    let has_endpoints: bool = fact_sheet
+                        .api_endpoint_schema
+                        .as_ref()
+                        .is_some_and(|routes| !routes.is_empty());
+                    if !has_endpoints {
+                        PrintCommand::Issue.print_agent_message(
+                            self.attributes.position.as_str(),
+                            "Frontend Code: No API endpoints to build a frontend for, skipping",
+                        );
+                        self.attributes.state = AgentState::Finished;
+                        continue;
+                    }
+
+                    self.call_initial_frontend_code(fact_sheet).await;
+                    self.attributes.state = AgentState::UnitTesting;
+                    continue;
+                }
+
+                AgentState::Working => {
+                    self.call_fix_code_bugs(fact_sheet).await;
+                    self.attributes.state = AgentState::UnitTesting;
+                    continue;
+                }
+
+                AgentState::UnitTesting => {
+                    if !is_wasm_target_installed() {
+                        let skip_message: String = format!(
+                            "Frontend Code Unit Testing: {} target is not installed, skipping build (rustup target add {})",
+                            WASM_TARGET, WASM_TARGET
+                        );
+                        PrintCommand::Issue.print_agent_message(
+                            self.attributes.position.as_str(),
+                            skip_message.as_str(),
+                        );
+                        self.attributes.state = AgentState::Finished;
+                        continue;
+                    }
+
+                    PrintCommand::UnitTest.print_agent_message(
+                        self.attributes.position.as_str(),
+                        "Frontend Code Unit Testing: Building frontend for wasm...",
+                    );
+
+                    match self.call_build_frontend().await {
+                        Ok(()) => {
+                            PrintCommand::UnitTest.print_agent_message(
+                                self.attributes.position.as_str(),
+                                "Frontend Code Unit Testing: Frontend build successful...",
+                            );
+                            self.attributes.state = AgentState::Finished;
+                        }
+                        Err(build_errors) => self.record_bug(build_errors)?,
+                    }
+                }
+
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
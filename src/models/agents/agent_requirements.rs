@@ -0,0 +1,166 @@
+use crate::ai_functions::ai_func_requirements::{
+    print_follow_up_questions, print_requirements_document,
+};
+use crate::helpers::command_lines::{get_user_response, PrintCommand};
+use crate::helpers::general::{ai_task_request_decoded, env_setting};
+use crate::models::agent_basic::basic_agent::{AgentState, BasicAgent};
+use crate::models::agents::agent_traits::{FactSheet, RequirementsDocument, SpecialFunctions};
+
+use async_trait::async_trait;
+
+/// Struct representing a requirements interviewer agent
+/// Attributes:
+/// - `attributes`: Basic agent properties (objective, position, etc.)
+/// - `interview`: The follow-up questions asked so far, paired with the client's answers
+/// - `max_rounds`: How many rounds of follow-up questions are asked before writing the requirements
+#[derive(Debug)]
+pub struct AgentRequirementsInterviewer {
+    attributes: BasicAgent,
+    interview: Vec<(String, String)>,
+    max_rounds: u8,
+}
+
+/// Environment variable overriding how many rounds of follow-up questions are asked; `0` skips the interview.
+const INTERVIEW_ROUNDS_ENV: &str = "REQUIREMENTS_INTERVIEW_ROUNDS";
+/// Default number of rounds of follow-up questions.
+const DEFAULT_INTERVIEW_ROUNDS: u8 = 2;
+
+impl Default for AgentRequirementsInterviewer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AgentRequirementsInterviewer {
+    /// Creates a new instance of `AgentRequirementsInterviewer` with default attributes
+    pub fn new() -> Self {
+        let attributes: BasicAgent = BasicAgent {
+            objective: "Gathers the client's requirements before the website is designed"
+                .to_string(),
+            position: "Requirements Analyst".to_string(),
+            state: AgentState::Discovery,
+            memory: vec![],
+        };
+
+        Self {
+            attributes,
+            interview: vec![],
+            max_rounds: env_setting(INTERVIEW_ROUNDS_ENV, DEFAULT_INTERVIEW_ROUNDS),
+        }
+    }
+
+    /// Formats the project description and the interview so far for the AI functions
+    ///
+    /// # Parameters
+    /// - `fact_sheet`: A reference to the fact sheet containing project information
+    fn interview_context(&self, fact_sheet: &FactSheet) -> String {
+        let interview: String = self
+            .interview
+            .iter()
+            .map(|(question, answer)| format!("Q: {} \n A: {} \n", question, answer))
+            .collect();
+        format!(
+            "PROJECT_DESCRIPTION: {} \n INTERVIEW: \n {}",
+            fact_sheet.project_description, interview
+        )
+    }
+
+    /// Asks the client the follow-up questions that are still open
+    ///
+    /// # Parameters
+    /// - `fact_sheet`: A reference to the fact sheet containing project information
+    ///
+    /// # Returns
+    /// - `false` once there are no questions left to ask
+    async fn call_ask_follow_up_questions(&mut self, fact_sheet: &FactSheet) -> bool {
+        let questions: Vec<String> = ai_task_request_decoded::<Vec<String>>(
+            self.interview_context(fact_sheet),
+            &self.attributes.position,
+            get_function_string!(print_follow_up_questions),
+            print_follow_up_questions,
+        )
+        .await;
+
+        for question in &questions {
+            let answer: String = get_user_response(question);
+            self.interview.push((question.clone(), answer));
+        }
+        !questions.is_empty()
+    }
+
+    /// Writes the requirements document from the project description and the interview
+    ///
+    /// # Parameters
+    /// - `fact_sheet`: A mutable reference to the fact sheet containing project information
+    async fn call_write_requirements(&mut self, fact_sheet: &mut FactSheet) {
+        let requirements: RequirementsDocument = ai_task_request_decoded::<RequirementsDocument>(
+            self.interview_context(fact_sheet),
+            &self.attributes.position,
+            get_function_string!(print_requirements_document),
+            print_requirements_document,
+        )
+        .await;
+        fact_sheet.requirements = Some(requirements);
+    }
+}
+
+/// Implementation of special functions for `AgentRequirementsInterviewer`
+#[async_trait]
+impl SpecialFunctions for AgentRequirementsInterviewer {
+    /// Retrieves the attributes of the agent
+    fn get_attributes_from_agent(&self) -> &BasicAgent {
+        &self.attributes
+    }
+
+    /// Executes the agent's workflow based on its state
+    ///
+    /// # Parameters
+    /// - `fact_sheet`: A mutable reference to the fact sheet containing project information
+    ///
+    /// # Returns
+    /// - A result indicating success or an error
+    async fn execute(
+        &mut self,
+        fact_sheet: &mut FactSheet,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        while self.attributes.state != AgentState::Finished {
+            match &self.attributes.state {
+                AgentState::Discovery => {
+                    if self.max_rounds == 0 {
+                        PrintCommand::Issue.print_agent_message(
+                            self.attributes.position.as_str(),
+                            "Requirements: Interview disabled, designing from the project description only",
+                        );
+                        self.attributes.state = AgentState::Finished;
+                        continue;
+                    }
+
+                    for _ in 0..self.max_rounds {
+                        if !self.call_ask_follow_up_questions(fact_sheet).await {
+                            break;
+                        }
+                    }
+                    self.attributes.state = AgentState::Working;
+                }
+
+                AgentState::Working => {
+                    self.call_write_requirements(fact_sheet).await;
+
+                    let requirements_json: String =
+                        serde_json::to_string_pretty(&fact_sheet.requirements)
+                            .expect("Failed to serialize requirements");
+                    let requirements_message: String =
+                        format!("Requirements: Recorded requirements\n{}", requirements_json);
+                    PrintCommand::Success.print_agent_message(
+                        self.attributes.position.as_str(),
+                        requirements_message.as_str(),
+                    );
+                    self.attributes.state = AgentState::Finished;
+                }
+
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
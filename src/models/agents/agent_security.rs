@@ -0,0 +1,233 @@
+use crate::ai_functions::ai_func_security::{print_security_fixed_code, print_security_review};
+use crate::helpers::command_lines::PrintCommand;
+use crate::helpers::dependency_check::{check_dependencies, CrateAllowlist};
+use crate::helpers::general::{
+    ai_task_request, ai_task_request_decoded, env_setting, get_web_server_manifest_path,
+    read_exec_main_contents, save_backend_code,
+};
+use crate::helpers::security_review::{
+    audit_generated_code, format_security_findings, SecurityCheck, SecurityFinding,
+};
+use crate::helpers::target_framework::{TargetFramework, TargetLanguage};
+use crate::models::agent_basic::basic_agent::{AgentState, BasicAgent};
+use crate::models::agents::agent_traits::{FactSheet, SpecialFunctions};
+
+use async_trait::async_trait;
+use std::path::Path;
+
+/// Struct representing a security reviewer agent
+///
+/// It is run by the backend developer once the endpoint tests pass. When it applies fixes it
+/// returns early with the new code in `FactSheet.backend_code`, so the backend builds and tests
+/// the fixed code before calling the reviewer again.
+///
+/// Attributes:
+/// - `attributes`: Basic agent properties (objective, position, etc.)
+/// - `findings`: The findings of the last review
+/// - `framework`: The web framework of the backend, which the requested fixes refer to
+/// - `review_count`: Counter for the number of times fixes were applied
+/// - `max_reviews`: How many rounds of fixes are applied before remaining findings are only reported
+#[derive(Debug)]
+pub struct AgentSecurityReviewer {
+    attributes: BasicAgent,
+    findings: Vec<SecurityFinding>,
+    framework: TargetFramework,
+    review_count: u8,
+    max_reviews: u8,
+}
+
+/// Environment variable overriding how many rounds of security fixes are applied.
+const MAX_REVIEWS_ENV: &str = "SECURITY_MAX_REVIEWS";
+/// Default number of rounds of security fixes.
+const DEFAULT_MAX_REVIEWS: u8 = 2;
+
+impl Default for AgentSecurityReviewer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AgentSecurityReviewer {
+    /// Creates a new instance of `AgentSecurityReviewer` with default attributes
+    pub fn new() -> Self {
+        let attributes: BasicAgent = BasicAgent {
+            objective: "Reviews the webserver for auth and input handling flaws".to_string(),
+            position: "Security Reviewer".to_string(),
+            state: AgentState::Discovery,
+            memory: vec![],
+        };
+
+        Self {
+            attributes,
+            findings: vec![],
+            framework: TargetFramework::from_env(),
+            review_count: 0,
+            max_reviews: env_setting(MAX_REVIEWS_ENV, DEFAULT_MAX_REVIEWS),
+        }
+    }
+
+    /// Whether the audit covers servers of the configured framework; it reads the syntax tree of Rust code
+    pub fn covers_framework(&self) -> bool {
+        self.framework.language() == TargetLanguage::Rust
+    }
+
+    /// Audits the backend code against the checklist, statically and with the AI reviewer
+    ///
+    /// # Parameters
+    /// - `fact_sheet`: A reference to the fact sheet containing project information
+    async fn call_review_code(&mut self, fact_sheet: &FactSheet) {
+        let backend_code: String = read_exec_main_contents();
+        let requires_login: bool = fact_sheet
+            .project_scope
+            .is_some_and(|scope| scope.is_user_login_and_logout);
+        // Unparsable code is reported by the compiler during the build
+        let static_findings: Vec<SecurityFinding> =
+            audit_generated_code(&backend_code, requires_login, self.framework).unwrap_or_default();
+
+        let checklist: Vec<SecurityCheck> = SecurityCheck::ALL.to_vec();
+        let msg_context: String = format!(
+            "BACKEND_CODE: {} \n PROJECT_SCOPE: {:?} \n CHECKLIST: {} \n STATIC_FINDINGS: {} \n",
+            backend_code,
+            fact_sheet.project_scope,
+            serde_json::to_string(&checklist).expect("Failed to serialize checklist"),
+            serde_json::to_string(&static_findings).expect("Failed to serialize findings")
+        );
+        let review_findings: Vec<SecurityFinding> =
+            ai_task_request_decoded::<Vec<SecurityFinding>>(
+                msg_context,
+                &self.attributes.position,
+                get_function_string!(print_security_review),
+                print_security_review,
+            )
+            .await;
+
+        self.findings = static_findings;
+        for finding in review_findings {
+            if !self.findings.contains(&finding) {
+                self.findings.push(finding);
+            }
+        }
+    }
+
+    /// Applies the requested fixes to the backend code
+    ///
+    /// Allowlisted crates the fixes use, e.g. `argon2`, are added to the generated `Cargo.toml`.
+    ///
+    /// # Parameters
+    /// - `fact_sheet`: A mutable reference to the fact sheet containing project information
+    async fn call_apply_fixes(&mut self, fact_sheet: &mut FactSheet) {
+        let msg_context: String = format!(
+            "BACKEND_CODE: {} \n FINDINGS: \n{} \n INSTALLED_LIBRARIES: {} \n
+      THIS FUNCTION ONLY OUTPUTS CODE. JUST OUTPUT THE CODE.",
+            read_exec_main_contents(),
+            format_security_findings(&self.findings),
+            self.framework.installed_libraries()
+        );
+
+        let ai_response: String = ai_task_request(
+            msg_context,
+            &self.attributes.position,
+            get_function_string!(print_security_fixed_code),
+            print_security_fixed_code,
+        )
+        .await;
+        save_backend_code(&ai_response);
+
+        // Remaining dependency issues are reported by the backend's dependency check
+        let _ = check_dependencies(
+            &ai_response,
+            Path::new(&get_web_server_manifest_path()),
+            &CrateAllowlist::from_env().with_auto_add(),
+        );
+        fact_sheet.backend_code = Some(ai_response);
+    }
+}
+
+/// Implementation of special functions for `AgentSecurityReviewer`
+#[async_trait]
+impl SpecialFunctions for AgentSecurityReviewer {
+    /// Retrieves the attributes of the agent
+    fn get_attributes_from_agent(&self) -> &BasicAgent {
+        &self.attributes
+    }
+
+    /// Executes the agent's workflow based on its state
+    ///
+    /// # Parameters
+    /// - `fact_sheet`: A mutable reference to the fact sheet containing project information
+    ///
+    /// # Returns
+    /// - A result indicating success or an error. Returns before `Finished` after applying fixes.
+    async fn execute(
+        &mut self,
+        fact_sheet: &mut FactSheet,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        while self.attributes.state != AgentState::Finished {
+            match &self.attributes.state {
+                AgentState::Discovery => {
+                    if !self.covers_framework() {
+                        let skip_message: String = format!(
+                            "Security Review: NOT RUN, the audit only covers Rust servers, review the {} code manually",
+                            self.framework
+                        );
+                        PrintCommand::Issue.print_agent_message(
+                            self.attributes.position.as_str(),
+                            skip_message.as_str(),
+                        );
+                        self.attributes.state = AgentState::Finished;
+                        continue;
+                    }
+
+                    PrintCommand::UnitTest.print_agent_message(
+                        self.attributes.position.as_str(),
+                        "Security Review: Auditing password hashing, authz, input limits, CORS and error leakage...",
+                    );
+                    self.call_review_code(fact_sheet).await;
+
+                    if self.findings.is_empty() {
+                        PrintCommand::Success.print_agent_message(
+                            self.attributes.position.as_str(),
+                            "Security Review: No findings",
+                        );
+                        self.attributes.state = AgentState::Finished;
+                        continue;
+                    }
+
+                    let findings_message: String = format!(
+                        "Security Review: {} findings\n{}",
+                        self.findings.len(),
+                        format_security_findings(&self.findings)
+                    );
+                    PrintCommand::Issue.print_agent_message(
+                        self.attributes.position.as_str(),
+                        findings_message.as_str(),
+                    );
+
+                    if self.review_count >= self.max_reviews {
+                        PrintCommand::Issue.print_agent_message(
+                            self.attributes.position.as_str(),
+                            "Security Review: Findings remain after the last round of fixes, review them manually",
+                        );
+                        self.attributes.state = AgentState::Finished;
+                        continue;
+                    }
+                    self.attributes.state = AgentState::Working;
+                }
+
+                AgentState::Working => {
+                    self.call_apply_fixes(fact_sheet).await;
+                    self.review_count += 1;
+                    PrintCommand::UnitTest.print_agent_message(
+                        self.attributes.position.as_str(),
+                        "Security Review: Fixes applied, re-testing before the next review",
+                    );
+                    self.attributes.state = AgentState::Discovery;
+                    return Ok(());
+                }
+
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
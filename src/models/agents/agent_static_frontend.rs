@@ -0,0 +1,212 @@
+use crate::helpers::command_lines::PrintCommand;
+use crate::helpers::egress_proxy::EgressProxy;
+use crate::helpers::general::{
+    get_web_server_program_path, read_static_frontend_asset, save_static_pages,
+};
+use crate::helpers::server_process::{
+    start_sandboxed_web_server, ServerLaunchError, TestServerGuard, ENDPOINT_TESTS_TIMEOUT,
+};
+use crate::helpers::static_pages::{
+    render_static_pages, run_page_smoke_tests, PageTestResult, StaticPage,
+};
+use crate::helpers::target_framework::TargetFramework;
+use crate::models::agent_basic::basic_agent::{AgentState, BasicAgent};
+use crate::models::agents::agent_traits::{FactSheet, RouteObject, SpecialFunctions};
+
+use async_trait::async_trait;
+use reqwest::Client;
+use std::path::Path;
+use std::time::Duration;
+use tokio::time;
+
+/// Struct representing a static frontend developer agent
+/// Attributes:
+/// - `attributes`: Basic agent properties (objective, position, etc.)
+/// - `pages`: The static pages written to the web server's static files directory
+#[derive(Debug)]
+pub struct AgentStaticFrontendDeveloper {
+    attributes: BasicAgent,
+    pages: Vec<StaticPage>,
+}
+
+impl Default for AgentStaticFrontendDeveloper {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AgentStaticFrontendDeveloper {
+    /// Creates a new instance of `AgentStaticFrontendDeveloper` with default attributes
+    pub fn new() -> Self {
+        let attributes: BasicAgent = BasicAgent {
+            objective: "Develops plain HTML, CSS and JS pages served by the webserver".to_string(),
+            position: "Static Frontend Developer".to_string(),
+            state: AgentState::Discovery,
+            memory: vec![],
+        };
+
+        Self {
+            attributes,
+            pages: vec![],
+        }
+    }
+
+    /// Renders the pages for every resource of the API and saves them to the static files directory
+    ///
+    /// # Parameters
+    /// - `routes`: The API endpoint schema the pages call
+    fn call_render_pages(&mut self, routes: &[RouteObject]) {
+        self.pages = render_static_pages(
+            routes,
+            &read_static_frontend_asset("app.js"),
+            &read_static_frontend_asset("style.css"),
+        );
+        save_static_pages(&self.pages);
+    }
+
+    /// Fetches every page from the running test server
+    ///
+    /// # Parameters
+    /// - `port`: The port the test server listens on
+    ///
+    /// # Returns
+    /// - One result per page
+    async fn call_smoke_test_pages(&self, port: u16) -> Vec<PageTestResult> {
+        let client: Client = Client::builder()
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap();
+        let base_url: String = format!("http://127.0.0.1:{}", port);
+        run_page_smoke_tests(&client, &base_url, &self.pages).await
+    }
+}
+
+/// Implementation of special functions for `AgentStaticFrontendDeveloper`
+#[async_trait]
+impl SpecialFunctions for AgentStaticFrontendDeveloper {
+    /// Retrieves the attributes of the agent
+    fn get_attributes_from_agent(&self) -> &BasicAgent {
+        &self.attributes
+    }
+
+    /// Executes the agent's workflow based on its state
+    ///
+    /// # Parameters
+    /// - `fact_sheet`: A mutable reference to the fact sheet containing project information
+    ///
+    /// # Returns
+    /// - A result indicating success or an error
+    async fn execute(
+        &mut self,
+        fact_sheet: &mut FactSheet,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        while self.attributes.state != AgentState::Finished {
+            match &self.attributes.state {
+                AgentState::Discovery => {
+                    let routes: Vec<RouteObject> =
+                        fact_sheet.api_endpoint_schema.clone().unwrap_or_default();
+                    if routes.is_empty() {
+                        PrintCommand::Issue.print_agent_message(
+                            self.attributes.position.as_str(),
+                            "Static Frontend: No API endpoints to build pages for, skipping",
+                        );
+                        self.attributes.state = AgentState::Finished;
+                        continue;
+                    }
+
+                    self.call_render_pages(&routes);
+                    let pages_message: String = format!(
+                        "Static Frontend: Wrote {} files to the web server static directory",
+                        self.pages.len()
+                    );
+                    PrintCommand::UnitTest.print_agent_message(
+                        self.attributes.position.as_str(),
+                        pages_message.as_str(),
+                    );
+                    self.attributes.state = AgentState::UnitTesting;
+                }
+
+                AgentState::UnitTesting => {
+                    self.attributes.state = AgentState::Finished;
+
+                    let framework: TargetFramework = TargetFramework::from_env();
+                    if !Path::new(&get_web_server_program_path(framework)).exists() {
+                        PrintCommand::Issue.print_agent_message(
+                            self.attributes.position.as_str(),
+                            "Static Frontend Unit Testing: Web server is not built, skipping smoke tests",
+                        );
+                        continue;
+                    }
+
+                    let external_urls: Vec<String> =
+                        fact_sheet.external_urls.clone().unwrap_or_default();
+                    let (test_server, _egress_proxy): (TestServerGuard, EgressProxy) =
+                        match start_sandboxed_web_server(framework, &external_urls).await {
+                            Ok(started) => started,
+                            Err(ServerLaunchError::NotReady(start_error, server_report)) => {
+                                let start_message: String = format!(
+                                    "Static Frontend Unit Testing: {}\n{}",
+                                    start_error,
+                                    server_report.log_tail()
+                                );
+                                PrintCommand::Issue.print_agent_message(
+                                    self.attributes.position.as_str(),
+                                    start_message.as_str(),
+                                );
+                                continue;
+                            }
+                            Err(launch_error) => return Err(launch_error.into()),
+                        };
+                    let port: u16 = test_server.port();
+
+                    let test_results: Option<Vec<PageTestResult>> =
+                        time::timeout(ENDPOINT_TESTS_TIMEOUT, self.call_smoke_test_pages(port))
+                            .await
+                            .ok();
+                    test_server.stop("Stopped after page smoke tests");
+
+                    let failures: Vec<PageTestResult> = match test_results {
+                        Some(test_results) => test_results
+                            .into_iter()
+                            .filter(|test_result| test_result.error.is_some())
+                            .collect(),
+                        None => {
+                            PrintCommand::Issue.print_agent_message(
+                                self.attributes.position.as_str(),
+                                "Static Frontend Unit Testing: Page smoke tests timed out",
+                            );
+                            continue;
+                        }
+                    };
+
+                    if failures.is_empty() {
+                        PrintCommand::Success.print_agent_message(
+                            self.attributes.position.as_str(),
+                            "Static Frontend Testing completed, pages are served under /static/...",
+                        );
+                        continue;
+                    }
+
+                    for failure in &failures {
+                        let failure_message: String = format!(
+                            "Static Frontend Unit Testing: {} failed: {}",
+                            failure.url,
+                            failure.error.clone().unwrap_or_default()
+                        );
+                        PrintCommand::Issue.print_agent_message(
+                            self.attributes.position.as_str(),
+                            failure_message.as_str(),
+                        );
+                    }
+                    PrintCommand::Issue.print_agent_message(
+                        self.attributes.position.as_str(),
+                        "Static Frontend Unit Testing: Make sure the web server keeps the /static files service",
+                    );
+                }
+
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
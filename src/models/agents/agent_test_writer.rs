@@ -0,0 +1,245 @@
+use crate::ai_functions::ai_func_test_writer::{
+    print_api_integration_tests, print_fixed_api_integration_tests,
+};
+use crate::helpers::code_templates::{required_features, select_templates, CodeTemplate};
+use crate::helpers::command_lines::PrintCommand;
+use crate::helpers::general::{
+    ai_task_request, env_setting, get_tests_template_path, get_web_server_project_path,
+    read_code_templates, read_exec_main_contents, read_tests_template_contents, save_api_tests,
+};
+use crate::helpers::sandbox::test_project_in_sandbox;
+use crate::helpers::target_framework::TargetFramework;
+use crate::models::agent_basic::basic_agent::{AgentState, BasicAgent};
+use crate::models::agents::agent_traits::{FactSheet, SpecialFunctions};
+
+use async_trait::async_trait;
+use std::path::Path;
+
+/// Struct representing a test writer agent
+///
+/// It is run by the backend developer once the endpoint tests pass. Errors returned from `execute`
+/// are failing tests of the backend and go into the backend fix loop; compile errors in the tests
+/// themselves are fixed by this agent.
+///
+/// Attributes:
+/// - `attributes`: Basic agent properties (objective, position, etc.)
+/// - `test_errors`: Optional string describing compile errors in the tests
+/// - `fix_count`: Counter for the number of times the tests were fixed
+/// - `framework`: The web framework of the backend, which decides how the tests build the app
+/// - `max_test_fixes`: How many times the tests are fixed before errors are handed to the backend
+#[derive(Debug)]
+pub struct AgentTestWriter {
+    attributes: BasicAgent,
+    test_errors: Option<String>,
+    fix_count: u8,
+    framework: TargetFramework,
+    max_test_fixes: u8,
+}
+
+/// Environment variable overriding how many fix iterations the test writer attempts on its own tests.
+const MAX_TEST_FIXES_ENV: &str = "TEST_WRITER_MAX_FIXES";
+/// Default number of fix iterations the test writer attempts on its own tests.
+const DEFAULT_MAX_TEST_FIXES: u8 = 2;
+/// How compiler errors located in the generated tests file start.
+const TESTS_FILE_ERROR_MARKER: &str = "--> tests/api.rs";
+
+impl Default for AgentTestWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AgentTestWriter {
+    /// Creates a new instance of `AgentTestWriter` with default attributes
+    pub fn new() -> Self {
+        let attributes: BasicAgent = BasicAgent {
+            objective: "Writes and runs integration tests for the webserver".to_string(),
+            position: "Test Engineer".to_string(),
+            state: AgentState::Discovery,
+            memory: vec![],
+        };
+
+        Self {
+            attributes,
+            test_errors: None,
+            fix_count: 0,
+            framework: TargetFramework::from_env(),
+            max_test_fixes: env_setting(MAX_TEST_FIXES_ENV, DEFAULT_MAX_TEST_FIXES),
+        }
+    }
+
+    /// Whether integration tests can be written for servers of the configured framework, which needs a tests template
+    pub fn covers_framework(&self) -> bool {
+        get_tests_template_path(self.framework).is_some()
+    }
+
+    /// Writes `tests/api.rs` from the tests template, the backend code and the extracted routes
+    ///
+    /// The tests template of the code templates picked for the project scope is used when one has it,
+    /// e.g. tests against a temporary database file for SQLite servers.
+    ///
+    /// # Parameters
+    /// - `fact_sheet`: A mutable reference to the fact sheet containing project information
+    async fn call_write_tests(&mut self, fact_sheet: &mut FactSheet) {
+        let templates: Vec<CodeTemplate> = read_code_templates(self.framework);
+        let tests_template: String = select_templates(
+            &templates,
+            &required_features(fact_sheet.project_scope.as_ref()),
+        )
+        .iter()
+        .find_map(|template| template.tests_code.clone())
+        .or_else(|| read_tests_template_contents(self.framework))
+        .expect("The framework has no tests template");
+        let api_endpoint_schema: String =
+            serde_json::to_string_pretty(&fact_sheet.api_endpoint_schema)
+                .expect("Failed to serialize API endpoints");
+        let test_conventions: String = serde_json::to_string(&self.framework.test_conventions())
+            .expect("Failed to serialize test conventions");
+        let msg_context: String = format!(
+            "TEST_TEMPLATE: {} \n TEST_CONVENTIONS: {} \n BACKEND_CODE: {} \n API_ENDPOINT_SCHEMA: {} \n DATA_MODEL: {:?} \n",
+            tests_template,
+            test_conventions,
+            read_exec_main_contents(),
+            api_endpoint_schema,
+            fact_sheet.domain_model
+        );
+
+        let ai_response: String = ai_task_request(
+            msg_context,
+            &self.attributes.position,
+            get_function_string!(print_api_integration_tests),
+            print_api_integration_tests,
+        )
+        .await;
+        save_api_tests(&ai_response);
+        fact_sheet.api_tests_code = Some(ai_response);
+    }
+
+    /// Fixes compile errors in the generated tests
+    ///
+    /// # Parameters
+    /// - `fact_sheet`: A mutable reference to the fact sheet containing project information
+    async fn call_fix_tests(&mut self, fact_sheet: &mut FactSheet) {
+        let msg_context: String = format!(
+            "BROKEN_TESTS: {:?} \n BACKEND_CODE: {} \n ERROR_BUGS: {:?} \n
+      THIS FUNCTION ONLY OUTPUTS CODE. JUST OUTPUT THE CODE.",
+            fact_sheet.api_tests_code,
+            read_exec_main_contents(),
+            self.test_errors
+        );
+
+        let ai_response: String = ai_task_request(
+            msg_context,
+            &self.attributes.position,
+            get_function_string!(print_fixed_api_integration_tests),
+            print_fixed_api_integration_tests,
+        )
+        .await;
+        save_api_tests(&ai_response);
+        fact_sheet.api_tests_code = Some(ai_response);
+    }
+}
+
+/// Implementation of special functions for `AgentTestWriter`
+#[async_trait]
+impl SpecialFunctions for AgentTestWriter {
+    /// Retrieves the attributes of the agent
+    fn get_attributes_from_agent(&self) -> &BasicAgent {
+        &self.attributes
+    }
+
+    /// Executes the agent's workflow based on its state
+    ///
+    /// # Parameters
+    /// - `fact_sheet`: A mutable reference to the fact sheet containing project information
+    ///
+    /// # Returns
+    /// - The output of the failing tests if the backend has to be fixed. The tests are kept
+    ///   and run again on the next call.
+    async fn execute(
+        &mut self,
+        fact_sheet: &mut FactSheet,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        while self.attributes.state != AgentState::Finished {
+            match &self.attributes.state {
+                AgentState::Discovery => {
+                    let has_endpoints: bool = fact_sheet
+                        .api_endpoint_schema
+                        .as_ref()
+                        .is_some_and(|routes| !routes.is_empty());
+                    if !has_endpoints {
+                        PrintCommand::Issue.print_agent_message(
+                            self.attributes.position.as_str(),
+                            "Integration Tests: No API endpoints to test, skipping",
+                        );
+                        self.attributes.state = AgentState::Finished;
+                        continue;
+                    }
+                    if !self.covers_framework() {
+                        let skip_message: String = format!(
+                            "Integration Tests: NOT RUN, there is no tests template for {}, test the server manually",
+                            self.framework
+                        );
+                        PrintCommand::Issue.print_agent_message(
+                            self.attributes.position.as_str(),
+                            skip_message.as_str(),
+                        );
+                        self.attributes.state = AgentState::Finished;
+                        continue;
+                    }
+
+                    self.call_write_tests(fact_sheet).await;
+                    self.attributes.state = AgentState::UnitTesting;
+                }
+
+                AgentState::Working => {
+                    self.call_fix_tests(fact_sheet).await;
+                    self.attributes.state = AgentState::UnitTesting;
+                }
+
+                AgentState::UnitTesting => {
+                    PrintCommand::UnitTest.print_agent_message(
+                        self.attributes.position.as_str(),
+                        "Integration Tests: Running cargo test...",
+                    );
+
+                    let project_path: String = get_web_server_project_path();
+                    let test_output: String =
+                        match test_project_in_sandbox(Path::new(&project_path)).await {
+                            Ok(()) => {
+                                PrintCommand::Success.print_agent_message(
+                                    self.attributes.position.as_str(),
+                                    "Integration Tests: All tests in tests/api.rs pass",
+                                );
+                                self.attributes.state = AgentState::Finished;
+                                continue;
+                            }
+                            Err(test_output) => test_output,
+                        };
+
+                    if test_output.contains(TESTS_FILE_ERROR_MARKER)
+                        && self.fix_count < self.max_test_fixes
+                    {
+                        PrintCommand::Issue.print_agent_message(
+                            self.attributes.position.as_str(),
+                            "Integration Tests: Tests do not compile, fixing tests",
+                        );
+                        self.fix_count += 1;
+                        self.test_errors = Some(test_output);
+                        self.attributes.state = AgentState::Working;
+                        continue;
+                    }
+
+                    PrintCommand::Issue.print_agent_message(
+                        self.attributes.position.as_str(),
+                        "Integration Tests: Tests failed, sending failures to the backend",
+                    );
+                    return Err(format!("INTEGRATION TEST FAILURES:\n{}", test_output).into());
+                }
+
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,54 +1,820 @@
+use crate::helpers::target_framework::{TargetFramework, TargetLanguage};
 use crate::models::agent_basic::basic_agent::BasicAgent;
 use async_trait::async_trait;
-use serde::{Deserialize, Serialize};
-use std::fmt::Debug;
+use serde::{de, Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt::{self, Debug};
+use std::str::FromStr;
+
+/// An HTTP method of a route.
+///
+/// Serialized lower case, e.g. `"get"`; any case is accepted when reading. Displayed upper case, e.g. `GET`.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum HttpMethod {
+    Get,
+    Post,
+    Put,
+    Patch,
+    Delete,
+    Head,
+    Options,
+}
+
+impl HttpMethod {
+    /// Every supported method.
+    pub const ALL: [HttpMethod; 7] = [
+        HttpMethod::Get,
+        HttpMethod::Post,
+        HttpMethod::Put,
+        HttpMethod::Patch,
+        HttpMethod::Delete,
+        HttpMethod::Head,
+        HttpMethod::Options,
+    ];
+
+    /// Returns the lower case name, e.g. `get`, as used by `web::get()` and `#[get("/path")]`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HttpMethod::Get => "get",
+            HttpMethod::Post => "post",
+            HttpMethod::Put => "put",
+            HttpMethod::Patch => "patch",
+            HttpMethod::Delete => "delete",
+            HttpMethod::Head => "head",
+            HttpMethod::Options => "options",
+        }
+    }
+
+    /// Whether requests with this method change data.
+    pub fn is_mutating(&self) -> bool {
+        matches!(
+            self,
+            HttpMethod::Post | HttpMethod::Put | HttpMethod::Patch | HttpMethod::Delete
+        )
+    }
+}
+
+impl fmt::Display for HttpMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str().to_uppercase())
+    }
+}
+
+impl FromStr for HttpMethod {
+    type Err = String;
+
+    fn from_str(method: &str) -> Result<Self, Self::Err> {
+        HttpMethod::ALL
+            .into_iter()
+            .find(|candidate| candidate.as_str().eq_ignore_ascii_case(method.trim()))
+            .ok_or_else(|| format!("Unsupported HTTP method '{}'", method))
+    }
+}
+
+impl<'de> Deserialize<'de> for HttpMethod {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let method: String = String::deserialize(deserializer)?;
+        method.parse().map_err(de::Error::custom)
+    }
+}
+
+/// The shape of a request body or response: a tree of objects and arrays with `FieldType` leaves.
+///
+/// Serialized compactly, e.g. `{"id": "integer", "tags": ["string"]}`; a missing body is `null` and an
+/// unknown one `"unknown"`. The legacy format written by the endpoint extraction prompt is accepted
+/// when reading: `"number"` becomes `Float`, and `"None"` and `"not_provided"` become `None` and `Unknown`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(from = "Value", into = "Value")]
+pub enum BodySchema {
+    /// The route sends or returns no JSON body.
+    #[default]
+    None,
+    /// The route sends or returns JSON of an unknown shape.
+    Unknown,
+    Field(FieldType),
+    Object(BTreeMap<String, BodySchema>),
+    Array(Box<BodySchema>),
+}
+
+impl BodySchema {
+    /// Whether the schema describes an actual payload with fields or items.
+    pub fn is_described(&self) -> bool {
+        match self {
+            BodySchema::Object(fields) => !fields.is_empty(),
+            BodySchema::Array(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns the fields of an object schema.
+    pub fn fields(&self) -> Option<&BTreeMap<String, BodySchema>> {
+        match self {
+            BodySchema::Object(fields) => Some(fields),
+            _ => None,
+        }
+    }
+}
+
+impl From<Value> for BodySchema {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Null => BodySchema::None,
+            Value::Object(fields) => BodySchema::Object(
+                fields
+                    .into_iter()
+                    .map(|(name, field)| (name, BodySchema::from(field)))
+                    .collect(),
+            ),
+            Value::Array(items) => BodySchema::Array(Box::new(
+                items
+                    .into_iter()
+                    .next()
+                    .map_or(BodySchema::Unknown, BodySchema::from),
+            )),
+            // Example values instead of type names
+            Value::Bool(_) => BodySchema::Field(FieldType::Bool),
+            Value::Number(number) if number.is_f64() => BodySchema::Field(FieldType::Float),
+            Value::Number(_) => BodySchema::Field(FieldType::Integer),
+            Value::String(type_name) => match type_name.trim().to_lowercase().as_str() {
+                "" | "none" | "null" => BodySchema::None,
+                "string" | "str" | "text" => BodySchema::Field(FieldType::String),
+                "integer" | "int" => BodySchema::Field(FieldType::Integer),
+                "number" | "float" => BodySchema::Field(FieldType::Float),
+                "bool" | "boolean" => BodySchema::Field(FieldType::Bool),
+                "datetime" | "date-time" => BodySchema::Field(FieldType::DateTime),
+                _ => BodySchema::Unknown,
+            },
+        }
+    }
+}
+
+impl From<BodySchema> for Value {
+    fn from(schema: BodySchema) -> Self {
+        match schema {
+            BodySchema::None => Value::Null,
+            BodySchema::Unknown => Value::String("unknown".to_string()),
+            BodySchema::Field(field_type) => {
+                serde_json::to_value(field_type).expect("Failed to serialize field type")
+            }
+            BodySchema::Object(fields) => Value::Object(
+                fields
+                    .into_iter()
+                    .map(|(name, field)| (name, Value::from(field)))
+                    .collect(),
+            ),
+            BodySchema::Array(item) => Value::Array(vec![Value::from(*item)]),
+        }
+    }
+}
+
+impl fmt::Display for BodySchema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Value::from(self.clone()))
+    }
+}
 
 /// Represents an object describing a route in the API, including its method,
 /// whether it is dynamic, request/response body schemas, and the route itself.
 ///
+/// Routes written in the legacy format, with `"true"`/`"false"` strings for `is_route_dynamic` and no
+/// `path_params`, are still read; the path parameters are then parsed from the route.
+///
 /// # Fields
 /// - `is_route_dynamic`: Indicates if the route is dynamic (e.g., contains path parameters).
 /// - `method`: The HTTP method associated with the route (e.g., GET, POST).
-/// - `request_body`: A JSON schema for the request body.
-/// - `response`: A JSON schema for the response body.
+/// - `path_params`: The names of the path parameters, e.g. `["id"]` for `/api/users/{id}`.
+/// - `request_body`: The schema of the request body.
+/// - `response`: The schema of the response body.
 /// - `route`: The route string, e.g., `/api/users/{id}`.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(from = "RouteObjectRecord")]
 pub struct RouteObject {
-    pub is_route_dynamic: String,
-    pub method: String,
-    pub request_body: serde_json::Value,
-    pub response: serde_json::Value,
+    pub is_route_dynamic: bool,
+    pub method: HttpMethod,
+    pub path_params: Vec<String>,
+    pub request_body: BodySchema,
+    pub response: BodySchema,
     pub route: String,
 }
 
+impl RouteObject {
+    /// Creates a route, parsing its path parameters from the route string.
+    pub fn new(
+        method: HttpMethod,
+        route: String,
+        request_body: BodySchema,
+        response: BodySchema,
+    ) -> Self {
+        let path_params: Vec<String> = parse_path_params(&route);
+        Self {
+            is_route_dynamic: !path_params.is_empty(),
+            method,
+            path_params,
+            request_body,
+            response,
+            route,
+        }
+    }
+}
+
+/// A `RouteObject` as read from JSON, in the current or the legacy format.
+#[derive(Deserialize)]
+struct RouteObjectRecord {
+    #[serde(default, deserialize_with = "deserialize_flag")]
+    is_route_dynamic: bool,
+    method: HttpMethod,
+    #[serde(default)]
+    path_params: Vec<String>,
+    #[serde(default)]
+    request_body: BodySchema,
+    #[serde(default)]
+    response: BodySchema,
+    route: String,
+}
+
+impl From<RouteObjectRecord> for RouteObject {
+    fn from(record: RouteObjectRecord) -> Self {
+        let path_params: Vec<String> = if record.path_params.is_empty() {
+            parse_path_params(&record.route)
+        } else {
+            record.path_params
+        };
+        Self {
+            is_route_dynamic: record.is_route_dynamic || !path_params.is_empty(),
+            method: record.method,
+            path_params,
+            request_body: record.request_body,
+            response: record.response,
+            route: record.route,
+        }
+    }
+}
+
+/// Returns the names of the `{param}` segments of a route; regex constraints like `{id:\d+}` are dropped.
+pub fn parse_path_params(route: &str) -> Vec<String> {
+    route
+        .split('/')
+        .filter_map(|segment| segment.strip_prefix('{')?.split_once('}'))
+        .map(|(param, _)| param.split(':').next().unwrap_or_default().to_string())
+        .filter(|param| !param.is_empty())
+        .collect()
+}
+
+/// Reads a flag written either as a bool or as a string like `"true"`, as models sometimes do; `null` is `false`.
+fn deserialize_flag<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    match Value::deserialize(deserializer)? {
+        Value::Bool(flag) => Ok(flag),
+        Value::Null => Ok(false),
+        Value::String(flag) => match flag.trim().to_lowercase().as_str() {
+            "true" | "yes" | "1" => Ok(true),
+            "false" | "no" | "0" | "" => Ok(false),
+            _ => Err(de::Error::custom(format!("Invalid flag '{}'", flag))),
+        },
+        other => Err(de::Error::custom(format!("Invalid flag {}", other))),
+    }
+}
+
+/// Reads one of the named choices of a `ProjectScope` field, e.g. `"SQLite"` or `"in-memory"`.
+///
+/// A missing value (`null` or `""`) is the default choice.
+fn deserialize_choice<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr<Err = String> + Default,
+{
+    match Option::<String>::deserialize(deserializer)? {
+        Some(choice) if !choice.trim().is_empty() => choice.parse().map_err(de::Error::custom),
+        _ => Ok(T::default()),
+    }
+}
+
+/// Finds the choice whose name matches, ignoring case, spaces, `_` and `-`.
+///
+/// # Arguments
+/// - `choices`: Every choice of the field with its accepted names, the serialized name first.
+/// - `kind`: What is being chosen, used in the error message.
+/// - `value`: The name to look up.
+fn parse_choice<T: Copy>(choices: &[(T, &[&str])], kind: &str, value: &str) -> Result<T, String> {
+    let normalize = |name: &str| -> String {
+        name.chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_lowercase()
+    };
+    let wanted: String = normalize(value);
+    choices
+        .iter()
+        .find(|(_, names)| names.iter().any(|name| normalize(name) == wanted))
+        .map(|(choice, _)| *choice)
+        .ok_or_else(|| format!("Unsupported {} '{}'", kind, value))
+}
+
+/// Where the generated server keeps its data.
+#[derive(Debug, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageBackend {
+    /// The whole database is rewritten to `database.json`, as the code template does.
+    #[default]
+    JsonFile,
+    /// Tables in a SQLite database file.
+    Sqlite,
+    /// Data lives in memory only and is lost on restart.
+    InMemory,
+}
+
+impl StorageBackend {
+    /// Every storage backend with the names accepted when reading it.
+    const CHOICES: &'static [(StorageBackend, &'static [&'static str])] = &[
+        (StorageBackend::JsonFile, &["json_file", "json", "file"]),
+        (StorageBackend::Sqlite, &["sqlite", "sqlite3", "sql"]),
+        (StorageBackend::InMemory, &["in_memory", "memory"]),
+    ];
+}
+
+impl FromStr for StorageBackend {
+    type Err = String;
+
+    fn from_str(storage: &str) -> Result<Self, Self::Err> {
+        parse_choice(StorageBackend::CHOICES, "storage backend", storage)
+    }
+}
+
+impl<'de> Deserialize<'de> for StorageBackend {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_choice(deserializer)
+    }
+}
+
+/// How users or clients authenticate against the generated server.
+#[derive(Debug, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthStyle {
+    /// Every route is public.
+    #[default]
+    None,
+    /// Users log in and get an opaque session token the server keeps.
+    Session,
+    /// Users log in and get a signed JSON Web Token.
+    Jwt,
+    /// Clients send a shared API key with every request.
+    ApiKey,
+}
+
+impl AuthStyle {
+    /// Every auth style with the names accepted when reading it.
+    const CHOICES: &'static [(AuthStyle, &'static [&'static str])] = &[
+        (AuthStyle::None, &["none", "public"]),
+        (AuthStyle::Session, &["session", "sessions", "cookie"]),
+        (AuthStyle::Jwt, &["jwt", "json_web_token", "bearer"]),
+        (AuthStyle::ApiKey, &["api_key", "api_keys", "key"]),
+    ];
+}
+
+impl FromStr for AuthStyle {
+    type Err = String;
+
+    fn from_str(auth_style: &str) -> Result<Self, Self::Err> {
+        parse_choice(AuthStyle::CHOICES, "auth style", auth_style)
+    }
+}
+
+impl<'de> Deserialize<'de> for AuthStyle {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_choice(deserializer)
+    }
+}
+
+/// How the generated server pushes updates to clients.
+#[derive(Debug, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RealtimeTransport {
+    /// Clients poll, nothing is pushed.
+    #[default]
+    None,
+    /// A websocket clients keep open.
+    Websocket,
+    /// A stream of server-sent events.
+    Sse,
+}
+
+impl RealtimeTransport {
+    /// Every realtime transport with the names accepted when reading it.
+    const CHOICES: &'static [(RealtimeTransport, &'static [&'static str])] = &[
+        (RealtimeTransport::None, &["none", "polling"]),
+        (
+            RealtimeTransport::Websocket,
+            &["websocket", "websockets", "ws"],
+        ),
+        (
+            RealtimeTransport::Sse,
+            &["sse", "server_sent_events", "event_source"],
+        ),
+    ];
+}
+
+impl FromStr for RealtimeTransport {
+    type Err = String;
+
+    fn from_str(realtime: &str) -> Result<Self, Self::Err> {
+        parse_choice(RealtimeTransport::CHOICES, "realtime transport", realtime)
+    }
+}
+
+impl<'de> Deserialize<'de> for RealtimeTransport {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_choice(deserializer)
+    }
+}
+
 /// Represents the scope of the project, specifying features and requirements.
 ///
+/// Fields added after the first three default when missing, so older fact sheets still load.
+///
 /// # Fields
 /// - `is_crud_required`: Indicates if CRUD operations are required for the project.
 /// - `is_user_login_and_logout`: Indicates if user authentication is needed.
 /// - `is_external_urls_required`: Indicates if external URLs are part of the project requirements.
+/// - `storage_backend`: Where the server keeps its data.
+/// - `auth_style`: How users or clients authenticate.
+/// - `is_pagination_required`: Indicates if list routes return their items page by page.
+/// - `is_filtering_required`: Indicates if list routes filter their items by field values.
+/// - `realtime`: How updates are pushed to clients, if at all.
+/// - `is_background_jobs_required`: Indicates if the server runs periodic work next to the requests.
+/// - `is_file_upload_required`: Indicates if clients upload files.
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
 pub struct ProjectScope {
+    #[serde(deserialize_with = "deserialize_flag")]
     pub is_crud_required: bool,
+    #[serde(deserialize_with = "deserialize_flag")]
     pub is_user_login_and_logout: bool,
+    #[serde(deserialize_with = "deserialize_flag")]
     pub is_external_urls_required: bool,
+    #[serde(default)]
+    pub storage_backend: StorageBackend,
+    #[serde(default)]
+    pub auth_style: AuthStyle,
+    #[serde(default, deserialize_with = "deserialize_flag")]
+    pub is_pagination_required: bool,
+    #[serde(default, deserialize_with = "deserialize_flag")]
+    pub is_filtering_required: bool,
+    #[serde(default)]
+    pub realtime: RealtimeTransport,
+    #[serde(default, deserialize_with = "deserialize_flag")]
+    pub is_background_jobs_required: bool,
+    #[serde(default, deserialize_with = "deserialize_flag")]
+    pub is_file_upload_required: bool,
+}
+
+impl ProjectScope {
+    /// Makes the auth decisions agree: users who log in need an auth style, sessions unless stated otherwise.
+    pub fn reconciled(mut self) -> Self {
+        if self.auth_style == AuthStyle::Session || self.auth_style == AuthStyle::Jwt {
+            self.is_user_login_and_logout = true;
+        } else if self.is_user_login_and_logout && self.auth_style == AuthStyle::None {
+            self.auth_style = AuthStyle::Session;
+        }
+        self
+    }
+
+    /// Turns the scope decisions into instructions for the backend code prompts.
+    ///
+    /// # Arguments
+    /// - `framework`: The web framework the backend is written with, which decides the libraries named,
+    ///   e.g. the websocket and upload crates or their Python counterparts.
+    ///
+    /// # Returns
+    /// One instruction per decision that changes the code, e.g. the storage backend or pagination.
+    pub fn backend_requirements(&self, framework: TargetFramework) -> Vec<String> {
+        let mut requirements: Vec<String> = vec![];
+        let language: TargetLanguage = framework.language();
+        let password_hashing: &str = match language {
+            TargetLanguage::Rust => "argon2",
+            TargetLanguage::Python => "`hashlib.scrypt` and a random salt per user",
+        };
+
+        match self.storage_backend {
+            StorageBackend::JsonFile => requirements.push(
+                "Persist the data in database.json, loading it on startup and saving it after every change, as the CODE_TEMPLATE does".to_string(),
+            ),
+            StorageBackend::Sqlite => requirements.push(format!(
+                "Persist the data with {} in the SQLite file read from WEB_SERVER_DATABASE_PATH (default \"database.sqlite\"), applying the MIGRATIONS when the database is opened as the CODE_TEMPLATE does, and never write database.json",
+                match language {
+                    TargetLanguage::Rust => "the rusqlite crate",
+                    TargetLanguage::Python => "the sqlite3 module",
+                }
+            )),
+            StorageBackend::InMemory => requirements.push(
+                "Keep the data in memory only, in the shared application state, and never read or write any file".to_string(),
+            ),
+        }
+
+        match self.auth_style {
+            AuthStyle::None => requirements.push(
+                "Do not add login or authentication, every route is public".to_string(),
+            ),
+            AuthStyle::Session => requirements.push(format!(
+                "Add register, login and logout routes; hash passwords with {}, return a random session token from login that the server keeps, and require it as `Authorization: Bearer <token>` on the protected routes",
+                password_hashing
+            )),
+            AuthStyle::Jwt => requirements.push(format!(
+                "Add register and login routes; hash passwords with {}, return a JWT signed with {} using the secret read from WEB_SERVER_JWT_SECRET, and require it as `Authorization: Bearer <token>` on the protected routes",
+                password_hashing,
+                match language {
+                    TargetLanguage::Rust => "the jsonwebtoken crate",
+                    TargetLanguage::Python => "PyJWT",
+                }
+            )),
+            AuthStyle::ApiKey => requirements.push(
+                "Require the key read from WEB_SERVER_API_KEY in the `X-API-Key` header on every route except \"/static\" and \"/openapi.json\", answering 401 when it is missing or wrong".to_string(),
+            ),
+        }
+
+        if self.is_pagination_required {
+            requirements.push(
+                "List routes accept the `page` and `per_page` query parameters, defaulting to 1 and 20 and capping per_page at 100, and return only that page".to_string(),
+            );
+        }
+        if self.is_filtering_required {
+            requirements.push(
+                "List routes accept optional query parameters named after the entity fields and return only the items whose fields equal them".to_string(),
+            );
+        }
+
+        match self.realtime {
+            RealtimeTransport::None => {}
+            RealtimeTransport::Websocket => requirements.push(format!(
+                "Serve a websocket at \"/ws\" with {} and send every created, updated or deleted item to the connected clients as JSON",
+                match framework {
+                    TargetFramework::ActixWeb => "the actix-ws crate",
+                    TargetFramework::Axum => "axum's `WebSocketUpgrade` extractor",
+                    TargetFramework::FastApi => "an `@app.websocket` route",
+                }
+            )),
+            RealtimeTransport::Sse => requirements.push(format!(
+                "Serve server-sent events at \"/events\" as `text/event-stream`, fed by {}, and send every created, updated or deleted item as a JSON event",
+                match language {
+                    TargetLanguage::Rust => "a tokio broadcast channel through tokio-stream",
+                    TargetLanguage::Python => "an asyncio queue per client through a `StreamingResponse`",
+                }
+            )),
+        }
+
+        if self.is_background_jobs_required {
+            requirements.push(format!(
+                "Run the periodic work in {}, sharing the application state with the routes",
+                match language {
+                    TargetLanguage::Rust => "a task started with `tokio::spawn` and `tokio::time::interval` before the server starts",
+                    TargetLanguage::Python => "an asyncio task started in the app's lifespan handler that sleeps between runs",
+                }
+            ));
+        }
+        if self.is_file_upload_required {
+            requirements.push(format!(
+                "Accept file uploads as multipart/form-data with {}, limit each file to 10 MB, and save it under the directory read from WEB_SERVER_UPLOAD_DIR (default \"uploads\") with a server generated name",
+                match framework {
+                    TargetFramework::ActixWeb => "the actix-multipart crate",
+                    TargetFramework::Axum => "axum's `Multipart` extractor and a raised `DefaultBodyLimit` on the upload route",
+                    TargetFramework::FastApi => "an `UploadFile` parameter",
+                }
+            ));
+        }
+
+        requirements
+    }
+}
+
+/// An entity the client described during the requirements interview.
+///
+/// # Fields
+/// - `name`: The entity name, e.g. `Task`.
+/// - `description`: What the entity represents.
+/// - `fields`: The fields the client mentioned, e.g. `title`, `due date`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EntityRequirement {
+    pub name: String,
+    pub description: String,
+    pub fields: Vec<String>,
+}
+
+/// Structured requirements gathered by interviewing the client before the architect runs.
+///
+/// # Fields
+/// - `summary`: A short summary of what is being built.
+/// - `entities`: The entities the application manages.
+/// - `auth`: Authentication and authorization needs, e.g. who can log in and what they may do.
+/// - `external_data_sources`: Third party data the application needs.
+/// - `persistence`: How and how long data must be stored.
+/// - `non_functional`: Performance, security, scale and other non-functional needs.
+/// - `open_questions`: Anything the client left undecided.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RequirementsDocument {
+    pub summary: String,
+    pub entities: Vec<EntityRequirement>,
+    pub auth: String,
+    pub external_data_sources: Vec<String>,
+    pub persistence: String,
+    pub non_functional: Vec<String>,
+    pub open_questions: Vec<String>,
+}
+
+/// The type of a field of the domain model.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldType {
+    String,
+    Integer,
+    Float,
+    Bool,
+    DateTime,
+}
+
+/// Validation rules a field value must satisfy; every rule is optional.
+///
+/// # Fields
+/// - `min_length`: The minimum number of characters of a string.
+/// - `max_length`: The maximum number of characters of a string.
+/// - `minimum`: The smallest allowed number.
+/// - `maximum`: The largest allowed number.
+/// - `allowed_values`: The only values a string may take, e.g. `["open", "done"]`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct FieldValidation {
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+    pub minimum: Option<f64>,
+    pub maximum: Option<f64>,
+    pub allowed_values: Vec<String>,
+}
+
+/// A field of an entity of the domain model.
+///
+/// # Fields
+/// - `name`: The field name, used verbatim in structs and JSON, e.g. `due_date`.
+/// - `field_type`: The type of the field.
+/// - `required`: Whether the field must be present in requests and responses.
+/// - `validation`: The rules the field value must satisfy.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FieldSchema {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub field_type: FieldType,
+    #[serde(default = "default_required")]
+    pub required: bool,
+    #[serde(default)]
+    pub validation: FieldValidation,
+}
+
+fn default_required() -> bool {
+    true
+}
+
+/// How an entity relates to another entity.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RelationKind {
+    BelongsTo,
+    HasMany,
+}
+
+/// A relation between two entities of the domain model.
+///
+/// # Fields
+/// - `field`: The field holding the related id, e.g. `project_id` for `BelongsTo`.
+/// - `target`: The name of the related entity.
+/// - `kind`: How the entities relate.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EntityRelation {
+    pub field: String,
+    pub target: String,
+    pub kind: RelationKind,
+}
+
+/// An entity of the domain model, stored and served by the web server.
+///
+/// # Fields
+/// - `name`: The entity and struct name, e.g. `Task`.
+/// - `resource`: The first route segment the entity is served under, e.g. `task` for `/task/{id}`.
+/// - `primary_key`: The field identifying an item, usually `id`.
+/// - `fields`: The fields of the entity, including the primary key.
+/// - `unique_keys`: Sets of fields whose combined values must be unique across items.
+/// - `relations`: The relations to other entities.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EntitySchema {
+    pub name: String,
+    pub resource: String,
+    #[serde(default = "default_primary_key")]
+    pub primary_key: String,
+    pub fields: Vec<FieldSchema>,
+    #[serde(default)]
+    pub unique_keys: Vec<Vec<String>>,
+    #[serde(default)]
+    pub relations: Vec<EntityRelation>,
+}
+
+fn default_primary_key() -> String {
+    "id".to_string()
+}
+
+/// The typed domain model all generated code and tests agree on.
+///
+/// # Fields
+/// - `entities`: The entities of the application.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DomainModel {
+    pub entities: Vec<EntitySchema>,
 }
 
 /// Contains details about the project, including its description, scope, and other metadata.
 ///
 /// # Fields
 /// - `project_description`: A textual description of the project's requirements and goals.
+/// - `requirements`: Optional structured requirements from the client interview.
 /// - `project_scope`: Optional information about the project's scope.
+/// - `domain_model`: Optional typed entity schema shared by code generation and endpoint testing.
 /// - `external_urls`: Optional list of external URLs related to the project.
 /// - `backend_code`: Optional string containing generated backend code.
+/// - `frontend_code`: Optional string containing generated frontend code.
+/// - `api_tests_code`: Optional string containing the generated integration tests of the backend.
+/// - `env_vars`: Optional names of the environment variables the backend reads.
 /// - `api_endpoint_schema`: Optional schema describing API endpoints as a list of `RouteObject`s.
+/// - `change_request`: The change being applied by a revise run, `None` while generating from scratch.
+/// - `revisions`: The change requests applied to the generated project so far, oldest first.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct FactSheet {
     pub project_description: String,
+    pub requirements: Option<RequirementsDocument>,
     pub project_scope: Option<ProjectScope>,
+    pub domain_model: Option<DomainModel>,
     pub external_urls: Option<Vec<String>>,
     pub backend_code: Option<String>,
+    pub frontend_code: Option<String>,
+    pub api_tests_code: Option<String>,
+    pub env_vars: Option<Vec<String>>,
     pub api_endpoint_schema: Option<Vec<RouteObject>>,
+    #[serde(default)]
+    pub change_request: Option<String>,
+    #[serde(default)]
+    pub revisions: Vec<String>,
+}
+
+impl FactSheet {
+    /// Returns the project description together with the structured requirements, domain model and
+    /// applied revisions, if present.
+    ///
+    /// Used as the project context in prompts so every agent builds from the same requirements and field names.
+    pub fn project_context(&self) -> String {
+        let mut context: String = self.project_description.clone();
+        if !self.revisions.is_empty() {
+            context.push_str(&format!(
+                " \n REVISIONS: {}",
+                serde_json::to_string(&self.revisions).expect("Failed to serialize revisions")
+            ));
+        }
+        if let Some(requirements) = &self.requirements {
+            context.push_str(&format!(
+                " \n REQUIREMENTS: {}",
+                serde_json::to_string(requirements).expect("Failed to serialize requirements")
+            ));
+        }
+        if let Some(domain_model) = &self.domain_model {
+            context.push_str(&format!(
+                " \n DATA_MODEL: {}",
+                serde_json::to_string(domain_model).expect("Failed to serialize domain model")
+            ));
+        }
+        context
+    }
+}
+
+/// The parts of a generated project a change request touches, decided before a revise run.
+///
+/// # Fields
+/// - `is_data_model_changed`: Indicates if entities, fields or relations of the domain model change.
+/// - `is_backend_changed`: Indicates if the server code changes, e.g. new routes, fields or filters.
+/// - `is_frontend_changed`: Indicates if the UI changes beyond showing new routes and fields.
+/// - `summary`: One sentence describing the planned change, shown before the agents run.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ChangePlan {
+    #[serde(deserialize_with = "deserialize_flag")]
+    pub is_data_model_changed: bool,
+    #[serde(deserialize_with = "deserialize_flag")]
+    pub is_backend_changed: bool,
+    #[serde(deserialize_with = "deserialize_flag")]
+    pub is_frontend_changed: bool,
+    #[serde(default)]
+    pub summary: String,
+}
+
+impl ChangePlan {
+    /// Makes the plan consistent: the server follows the domain model, so changing it changes the backend too.
+    pub fn reconciled(mut self) -> Self {
+        if self.is_data_model_changed {
+            self.is_backend_changed = true;
+        }
+        self
+    }
 }
 
 /// Defines the interface for agents to implement special functions.
@@ -64,3 +830,239 @@ pub trait SpecialFunctions: Debug {
         fact_sheet: &mut FactSheet,
     ) -> Result<(), Box<dyn std::error::Error>>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_legacy_route_objects() {
+        let route_object: RouteObject = serde_json::from_str(
+            r#"{
+                "route": "/item/{id}",
+                "is_route_dynamic": "true",
+                "method": "GET",
+                "request_body": "None",
+                "response": {"id": "number", "name": "string", "completed": "bool"}
+            }"#,
+        )
+        .unwrap();
+
+        assert!(route_object.is_route_dynamic);
+        assert_eq!(route_object.method, HttpMethod::Get);
+        assert_eq!(route_object.path_params, vec!["id".to_string()]);
+        assert_eq!(route_object.request_body, BodySchema::None);
+        let fields: &BTreeMap<String, BodySchema> = route_object.response.fields().unwrap();
+        assert_eq!(fields["id"], BodySchema::Field(FieldType::Float));
+        assert_eq!(fields["name"], BodySchema::Field(FieldType::String));
+        assert_eq!(fields["completed"], BodySchema::Field(FieldType::Bool));
+    }
+
+    #[test]
+    fn derives_dynamic_flag_from_path_params() {
+        let route_object: RouteObject = serde_json::from_str(
+            r#"{"route": "/user/{user_id}/post/{id:\\d+}", "is_route_dynamic": "false", "method": "delete"}"#,
+        )
+        .unwrap();
+
+        assert!(route_object.is_route_dynamic);
+        assert_eq!(route_object.path_params, vec!["user_id", "id"]);
+        assert_eq!(route_object.response, BodySchema::None);
+    }
+
+    #[test]
+    fn rejects_unknown_methods_and_flags() {
+        assert!(
+            serde_json::from_str::<RouteObject>(r#"{"route": "/", "method": "fetch"}"#).is_err()
+        );
+        assert!(serde_json::from_str::<RouteObject>(
+            r#"{"route": "/", "method": "get", "is_route_dynamic": "maybe"}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn reads_legacy_body_schemas() {
+        assert_eq!(
+            BodySchema::from(Value::String("not_provided".to_string())),
+            BodySchema::Unknown
+        );
+        assert_eq!(
+            BodySchema::from(Value::String("None".to_string())),
+            BodySchema::None
+        );
+        assert_eq!(
+            BodySchema::from(serde_json::json!([{"tag": "string"}])),
+            BodySchema::Array(Box::new(BodySchema::Object(BTreeMap::from([(
+                "tag".to_string(),
+                BodySchema::Field(FieldType::String)
+            )]))))
+        );
+        // Example values instead of type names
+        assert_eq!(
+            BodySchema::from(serde_json::json!({"count": 3, "ratio": 0.5, "done": false})),
+            BodySchema::Object(BTreeMap::from([
+                ("count".to_string(), BodySchema::Field(FieldType::Integer)),
+                ("done".to_string(), BodySchema::Field(FieldType::Bool)),
+                ("ratio".to_string(), BodySchema::Field(FieldType::Float)),
+            ]))
+        );
+    }
+
+    #[test]
+    fn round_trips_route_objects() {
+        let route_object: RouteObject = RouteObject::new(
+            HttpMethod::Post,
+            "/task".to_string(),
+            BodySchema::from(serde_json::json!({"title": "string", "tags": ["string"]})),
+            BodySchema::Unknown,
+        );
+
+        let serialized: String = serde_json::to_string(&route_object).unwrap();
+        assert!(serialized.contains(r#""method":"post""#));
+        assert!(serialized.contains(r#""response":"unknown""#));
+        assert_eq!(
+            serde_json::from_str::<RouteObject>(&serialized).unwrap(),
+            route_object
+        );
+    }
+
+    #[test]
+    fn reads_project_scopes_from_model_answers() {
+        let legacy_scope: ProjectScope = serde_json::from_str(
+            r#"{"is_crud_required": "true", "is_user_login_and_logout": null, "is_external_urls_required": false}"#,
+        )
+        .unwrap();
+        assert!(legacy_scope.is_crud_required);
+        assert!(!legacy_scope.is_user_login_and_logout);
+        assert_eq!(legacy_scope.storage_backend, StorageBackend::JsonFile);
+        assert_eq!(legacy_scope.auth_style, AuthStyle::None);
+        assert_eq!(legacy_scope.realtime, RealtimeTransport::None);
+        assert!(!legacy_scope.is_file_upload_required);
+
+        let scope: ProjectScope = serde_json::from_str(
+            r#"{
+                "is_crud_required": true,
+                "is_user_login_and_logout": "yes",
+                "is_external_urls_required": "0",
+                "storage_backend": "SQLite3",
+                "auth_style": "JSON Web Token",
+                "is_pagination_required": "TRUE",
+                "realtime": "server-sent events",
+                "is_file_upload_required": true
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(scope.storage_backend, StorageBackend::Sqlite);
+        assert_eq!(scope.auth_style, AuthStyle::Jwt);
+        assert_eq!(scope.realtime, RealtimeTransport::Sse);
+        assert!(scope.is_pagination_required);
+        assert!(!scope.is_filtering_required);
+        assert!(!scope.is_background_jobs_required);
+
+        let blank_choices: ProjectScope = serde_json::from_str(
+            r#"{"is_crud_required": true, "is_user_login_and_logout": false, "is_external_urls_required": false,
+                "storage_backend": "", "auth_style": null, "realtime": " "}"#,
+        )
+        .unwrap();
+        assert_eq!(blank_choices.storage_backend, StorageBackend::JsonFile);
+        assert_eq!(blank_choices.auth_style, AuthStyle::None);
+        assert_eq!(blank_choices.realtime, RealtimeTransport::None);
+    }
+
+    #[test]
+    fn rejects_unknown_scope_choices() {
+        let error: String = serde_json::from_str::<ProjectScope>(
+            r#"{"is_crud_required": true, "is_user_login_and_logout": false, "is_external_urls_required": false,
+                "storage_backend": "mongodb"}"#,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(
+            error.contains("Unsupported storage backend 'mongodb'"),
+            "{}",
+            error
+        );
+        assert_eq!(
+            "carrier pigeon".parse::<RealtimeTransport>(),
+            Err("Unsupported realtime transport 'carrier pigeon'".to_string())
+        );
+        assert_eq!("api-key".parse::<AuthStyle>(), Ok(AuthStyle::ApiKey));
+    }
+
+    #[test]
+    fn reconciles_scope_auth_decisions() {
+        let scope = |is_user_login_and_logout: bool, auth_style: AuthStyle| -> ProjectScope {
+            ProjectScope {
+                is_crud_required: true,
+                is_user_login_and_logout,
+                is_external_urls_required: false,
+                storage_backend: StorageBackend::JsonFile,
+                auth_style,
+                is_pagination_required: false,
+                is_filtering_required: false,
+                realtime: RealtimeTransport::None,
+                is_background_jobs_required: false,
+                is_file_upload_required: false,
+            }
+            .reconciled()
+        };
+
+        let jwt: ProjectScope = scope(false, AuthStyle::Jwt);
+        assert!(jwt.is_user_login_and_logout);
+        assert_eq!(jwt.auth_style, AuthStyle::Jwt);
+
+        let login: ProjectScope = scope(true, AuthStyle::None);
+        assert_eq!(login.auth_style, AuthStyle::Session);
+
+        let api_key: ProjectScope = scope(false, AuthStyle::ApiKey);
+        assert!(!api_key.is_user_login_and_logout);
+        assert_eq!(api_key.auth_style, AuthStyle::ApiKey);
+
+        let public: ProjectScope = scope(false, AuthStyle::None);
+        assert_eq!(public, scope(false, AuthStyle::None).reconciled());
+        assert_eq!(public.auth_style, AuthStyle::None);
+    }
+
+    #[test]
+    fn reads_change_plans_from_model_answers() {
+        let plan: ChangePlan = serde_json::from_str(
+            r#"{
+                "is_data_model_changed": "yes",
+                "is_backend_changed": null,
+                "is_frontend_changed": false
+            }"#,
+        )
+        .unwrap();
+        assert!(plan.is_data_model_changed);
+        assert!(!plan.is_backend_changed);
+        assert!(!plan.is_frontend_changed);
+        assert_eq!(plan.summary, "");
+
+        assert!(serde_json::from_str::<ChangePlan>(
+            r#"{"is_data_model_changed": "maybe", "is_backend_changed": true, "is_frontend_changed": true}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn reconciles_change_plans() {
+        let plan = |is_data_model_changed: bool, is_backend_changed: bool| -> ChangePlan {
+            ChangePlan {
+                is_data_model_changed,
+                is_backend_changed,
+                is_frontend_changed: false,
+                summary: "Add due dates to tasks".to_string(),
+            }
+        };
+
+        let data_model_change: ChangePlan = plan(true, false).reconciled();
+        assert!(data_model_change.is_backend_changed);
+        assert!(data_model_change.is_data_model_changed);
+        assert!(!data_model_change.is_frontend_changed);
+
+        assert_eq!(plan(false, true).reconciled(), plan(false, true));
+        assert_eq!(plan(false, false).reconciled(), plan(false, false));
+        assert_eq!(plan(true, true).reconciled(), plan(true, true));
+    }
+}
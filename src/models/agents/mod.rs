@@ -1,3 +1,11 @@
 pub mod agent_architecture;
 pub mod agent_backend;
+pub mod agent_data_model;
+pub mod agent_devops;
+pub mod agent_documentation;
+pub mod agent_frontend;
+pub mod agent_requirements;
+pub mod agent_security;
+pub mod agent_static_frontend;
+pub mod agent_test_writer;
 pub mod agent_traits;
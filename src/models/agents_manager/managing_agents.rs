@@ -1,9 +1,24 @@
-use crate::ai_functions::ai_func_manager::convert_user_input_to_goal;
-use crate::helpers::general::ai_task_request;
+use crate::ai_functions::ai_func_manager::{convert_user_input_to_goal, print_change_plan};
+use crate::helpers::command_lines::PrintCommand;
+use crate::helpers::general::{
+    ai_task_request, ai_task_request_decoded, env_setting, get_exec_main_path,
+    read_exec_main_contents, read_fact_sheet, save_fact_sheet,
+};
+use crate::helpers::project_history::{record_project_stage, ProjectStage};
 use crate::models::agent_basic::basic_agent::{AgentState, BasicAgent};
 use crate::models::agents::agent_architecture::AgentSolutionArchitect;
 use crate::models::agents::agent_backend::AgentBackendDeveloper;
-use crate::models::agents::agent_traits::{FactSheet, SpecialFunctions};
+use crate::models::agents::agent_data_model::AgentDataModelDesigner;
+use crate::models::agents::agent_devops::AgentDevOps;
+use crate::models::agents::agent_documentation::AgentDocumentation;
+use crate::models::agents::agent_frontend::AgentFrontendDeveloper;
+use crate::models::agents::agent_requirements::AgentRequirementsInterviewer;
+use crate::models::agents::agent_static_frontend::AgentStaticFrontendDeveloper;
+use crate::models::agents::agent_traits::{ChangePlan, FactSheet, SpecialFunctions};
+use std::path::Path;
+
+/// Environment variable selecting the frontend agent: `static`, `yew` or `none`.
+const FRONTEND_AGENT_ENV: &str = "FRONTEND_AGENT";
 
 /// Represents a managing agent responsible for overseeing the workflow of other agents and managing the fact sheet.
 #[derive(Debug)]
@@ -45,10 +60,17 @@ impl ManagingAgent {
 
         let fact_sheet: FactSheet = FactSheet {
             project_description,
+            requirements: None,
             project_scope: None,
+            domain_model: None,
             external_urls: None,
             backend_code: None,
+            frontend_code: None,
+            api_tests_code: None,
+            env_vars: None,
             api_endpoint_schema: None,
+            change_request: None,
+            revisions: vec![],
         };
         Ok(Self {
             _attributes: attributes,
@@ -56,6 +78,38 @@ impl ManagingAgent {
             fact_sheet,
         })
     }
+
+    /// Creates a `ManagingAgent` that applies a change request to the project an earlier run generated.
+    ///
+    /// The fact sheet is loaded from the generated project and the backend code is read back from disk,
+    /// so edits made to it since the run are kept.
+    ///
+    /// # Parameters
+    /// - `change_request`: A string describing what should change in the generated project.
+    ///
+    /// # Returns
+    /// - A `ManagingAgent` instance wrapped in a `Result`, an error if no earlier run saved a fact sheet.
+    pub async fn revise(change_request: String) -> Result<Self, Box<dyn std::error::Error>> {
+        let attributes = BasicAgent {
+            objective: "Manage agents who change a generated website".to_string(),
+            position: "Project Manager".to_string(),
+            state: AgentState::Discovery,
+            memory: vec![],
+        };
+
+        let mut fact_sheet: FactSheet = read_fact_sheet()
+            .ok_or("No fact sheet found, generate the project before revising it")?;
+        if Path::new(&get_exec_main_path()).exists() {
+            fact_sheet.backend_code = Some(read_exec_main_contents());
+        }
+        fact_sheet.change_request = Some(change_request);
+
+        Ok(Self {
+            _attributes: attributes,
+            agents: vec![],
+            fact_sheet,
+        })
+    }
     /// Adds a new agent to the `ManagingAgent`.
     ///
     /// # Parameters
@@ -66,16 +120,138 @@ impl ManagingAgent {
 
     /// Creates and initializes agents to handle specific tasks related to the project.
     fn create_agents(&mut self) {
+        self.add_agent(Box::new(AgentRequirementsInterviewer::new()));
         self.add_agent(Box::new(AgentSolutionArchitect::new()));
+        self.add_agent(Box::new(AgentDataModelDesigner::new()));
         self.add_agent(Box::new(AgentBackendDeveloper::new()));
+        match env_setting(FRONTEND_AGENT_ENV, "static".to_string()).as_str() {
+            "yew" => self.add_agent(Box::new(AgentFrontendDeveloper::new())),
+            "none" => {}
+            _ => self.add_agent(Box::new(AgentStaticFrontendDeveloper::new())),
+        }
+        self.add_agent(Box::new(AgentDevOps::new()));
+        self.add_agent(Box::new(AgentDocumentation::new()));
+    }
+
+    /// Decides which parts of the generated project the change request touches.
+    ///
+    /// # Parameters
+    /// - `change_request`: A string describing what should change in the generated project.
+    ///
+    /// # Returns
+    /// - The reconciled `ChangePlan`.
+    async fn plan_change(&self, change_request: &str) -> ChangePlan {
+        let msg_context: String = format!(
+            "CHANGE_REQUEST: {} \n PROJECT_DESCRIPTION: {} \n DATA_MODEL: {} \n API_ENDPOINT_SCHEMA: {} \n",
+            change_request,
+            self.fact_sheet.project_description,
+            serde_json::to_string(&self.fact_sheet.domain_model)
+                .expect("Failed to serialize domain model"),
+            serde_json::to_string(&self.fact_sheet.api_endpoint_schema)
+                .expect("Failed to serialize API endpoints")
+        );
+
+        let change_plan: ChangePlan = ai_task_request_decoded::<ChangePlan>(
+            msg_context,
+            &self._attributes.position,
+            get_function_string!(print_change_plan),
+            print_change_plan,
+        )
+        .await;
+        change_plan.reconciled()
+    }
+
+    /// Creates only the agents whose part of the project the change plan touches.
+    ///
+    /// The static frontend renders the API, so it reruns whenever the backend changes.
+    ///
+    /// # Parameters
+    /// - `change_plan`: The parts of the project the change request touches.
+    fn create_revision_agents(&mut self, change_plan: &ChangePlan) {
+        if change_plan.is_data_model_changed {
+            self.add_agent(Box::new(AgentDataModelDesigner::new()));
+        }
+        if change_plan.is_backend_changed {
+            self.add_agent(Box::new(AgentBackendDeveloper::new()));
+        }
+        match env_setting(FRONTEND_AGENT_ENV, "static".to_string()).as_str() {
+            "yew" if change_plan.is_frontend_changed => {
+                self.add_agent(Box::new(AgentFrontendDeveloper::new()))
+            }
+            "yew" | "none" => {}
+            _ if change_plan.is_frontend_changed || change_plan.is_backend_changed => {
+                self.add_agent(Box::new(AgentStaticFrontendDeveloper::new()))
+            }
+            _ => {}
+        }
+        if change_plan.is_backend_changed {
+            self.add_agent(Box::new(AgentDevOps::new()));
+            self.add_agent(Box::new(AgentDocumentation::new()));
+        }
     }
 
     /// Executes the project workflow by iterating through all agents and invoking their `execute` methods.
-    pub async fn execute_project(&mut self) {
-        self.create_agents();
+    ///
+    /// A revise run only executes the agents the change request affects. The run stops at the first agent
+    /// that fails. Either way the fact sheet is saved to the generated project afterwards, so it can be
+    /// revised later, and the project is committed to its history.
+    ///
+    /// # Returns
+    /// - The error of the agent that stopped the run, if one did.
+    pub async fn execute_project(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        match self.fact_sheet.change_request.clone() {
+            Some(change_request) => {
+                let change_plan: ChangePlan = self.plan_change(&change_request).await;
+                let plan_message: String = format!("Revising project: {}", change_plan.summary);
+                PrintCommand::AICall
+                    .print_agent_message(&self._attributes.position, plan_message.as_str());
+                self.create_revision_agents(&change_plan);
+            }
+            None => self.create_agents(),
+        }
+
+        let mut run_error: Option<String> = None;
         for agent in &mut self.agents {
-            let _: Result<(), Box<dyn std::error::Error>> =
-                agent.execute(&mut self.fact_sheet).await;
+            if let Err(e) = agent.execute(&mut self.fact_sheet).await {
+                let agent_position: &str = agent.get_attributes_from_agent().position.as_str();
+                PrintCommand::Issue.print_agent_message(
+                    agent_position,
+                    format!("Stopping the run: {}", e).as_str(),
+                );
+                run_error = Some(format!("{}: {}", agent_position, e));
+                break;
+            }
+        }
+
+        // Only a change request that went through every agent counts as applied
+        if let Some(change_request) = self.fact_sheet.change_request.take() {
+            if run_error.is_none() {
+                self.fact_sheet.revisions.push(change_request);
+            }
+        }
+        let fact_sheet: String =
+            serde_json::to_string_pretty(&self.fact_sheet).expect("Failed to serialize fact sheet");
+        save_fact_sheet(&fact_sheet);
+
+        match run_error {
+            Some(error) => {
+                record_project_stage(
+                    &ProjectStage::Stopped {
+                        error: error.clone(),
+                    },
+                    &self.fact_sheet,
+                    &self._attributes.position,
+                );
+                Err(error.into())
+            }
+            None => {
+                record_project_stage(
+                    &ProjectStage::Completed,
+                    &self.fact_sheet,
+                    &self._attributes.position,
+                );
+                Ok(())
+            }
         }
     }
 }